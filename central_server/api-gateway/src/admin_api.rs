@@ -0,0 +1,305 @@
+// 租户管理 API（运维使用，独立的管理 Token 鉴权）
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::{delete, get, post},
+    Router,
+};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use serde_json::json;
+use crate::tenant::{ApiKeyRecord, Tenant};
+use crate::AppState;
+
+pub fn create_admin_router() -> Router<AppState> {
+    Router::new()
+        .route("/v1/tenants", post(handle_create_tenant).get(handle_list_tenants))
+        .route("/v1/tenants/:tenant_id", get(handle_get_tenant).patch(handle_update_tenant))
+        .route("/v1/tenants/:tenant_id/keys", post(handle_issue_key))
+        .route("/v1/tenants/:tenant_id/keys/rotate", post(handle_rotate_key))
+        .route("/v1/tenants/:tenant_id/keys/:key_id", delete(handle_revoke_key))
+        .route("/v1/tenants/:tenant_id/usage", get(handle_tenant_usage))
+        .route("/v1/usage/export", get(handle_usage_export))
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateTenantRequest {
+    name: String,
+    max_requests_per_second: Option<usize>,
+    max_concurrent_sessions: Option<usize>,
+    request_burst: Option<usize>,
+    max_audio_seconds_per_minute: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateTenantRequest {
+    name: Option<String>,
+    max_requests_per_second: Option<usize>,
+    max_concurrent_sessions: Option<usize>,
+    request_burst: Option<usize>,
+    max_audio_seconds_per_minute: Option<u64>,
+    enabled: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct IssueKeyRequest {
+    /// 有效期（秒），不填表示不过期
+    expires_in_secs: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RotateKeyRequest {
+    /// 旧 key 的宽限期（秒），默认 24 小时
+    grace_secs: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct UsageExportQuery {
+    /// 不填表示全部租户
+    tenant_id: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    granularity: Option<String>,
+    /// "csv"（默认）| "jsonl"
+    format: Option<String>,
+}
+
+async fn handle_create_tenant(
+    State(state): State<AppState>,
+    Json(req): Json<CreateTenantRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    if req.name.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let (tenant, issued) = state
+        .tenant_manager
+        .onboard_tenant(
+            req.name,
+            req.max_requests_per_second
+                .unwrap_or(state.config.rate_limit.default_max_rps),
+            req.max_concurrent_sessions
+                .unwrap_or(state.config.rate_limit.default_max_sessions),
+            req.request_burst,
+            req.max_audio_seconds_per_minute,
+        )
+        .await
+        .map_err(|e| e.status_code())?;
+
+    tracing::info!(tenant_id = %tenant.tenant_id, name = %tenant.name, "管理 API：已创建租户");
+    Ok(Json(json!({
+        "tenant": tenant_json(&tenant),
+        "api_key": issued.api_key,
+        "key_id": issued.record.key_id,
+    })))
+}
+
+async fn handle_list_tenants(
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let mut tenants = state
+        .tenant_manager
+        .list_tenants()
+        .await
+        .map_err(|e| e.status_code())?;
+    tenants.sort_by_key(|t| t.created_at);
+    Ok(Json(json!({
+        "tenants": tenants.iter().map(tenant_json).collect::<Vec<_>>(),
+    })))
+}
+
+async fn handle_get_tenant(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let tenant = state
+        .tenant_manager
+        .get_tenant(&tenant_id)
+        .await
+        .map_err(|e| e.status_code())?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(tenant_json(&tenant)))
+}
+
+async fn handle_update_tenant(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<String>,
+    Json(req): Json<UpdateTenantRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let tenant = state
+        .tenant_manager
+        .update(&tenant_id, |t| {
+            if let Some(name) = req.name.clone() {
+                t.name = name;
+            }
+            if let Some(rps) = req.max_requests_per_second {
+                t.max_requests_per_second = rps;
+            }
+            if let Some(sessions) = req.max_concurrent_sessions {
+                t.max_concurrent_sessions = sessions;
+            }
+            if let Some(burst) = req.request_burst {
+                t.request_burst = Some(burst);
+            }
+            if let Some(audio) = req.max_audio_seconds_per_minute {
+                t.max_audio_seconds_per_minute = Some(audio);
+            }
+            if let Some(enabled) = req.enabled {
+                t.enabled = enabled;
+            }
+        })
+        .await
+        .map_err(|e| e.status_code())?;
+    tracing::info!(
+        tenant_id = %tenant.tenant_id,
+        max_rps = tenant.max_requests_per_second,
+        max_sessions = tenant.max_concurrent_sessions,
+        enabled = tenant.enabled,
+        "管理 API：已更新租户"
+    );
+    Ok(Json(tenant_json(&tenant)))
+}
+
+async fn handle_issue_key(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<String>,
+    body: Option<Json<IssueKeyRequest>>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let req = body.map(|Json(r)| r).unwrap_or_default();
+    let expires_at = req
+        .expires_in_secs
+        .map(|secs| Utc::now() + Duration::seconds(secs));
+    let issued = state
+        .tenant_manager
+        .issue_api_key(&tenant_id, expires_at)
+        .await
+        .map_err(|e| e.status_code())?;
+    Ok(Json(json!({
+        "api_key": issued.api_key,
+        "key": key_json(&issued.record),
+    })))
+}
+
+async fn handle_rotate_key(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<String>,
+    body: Option<Json<RotateKeyRequest>>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let req = body.map(|Json(r)| r).unwrap_or_default();
+    let grace = Duration::seconds(req.grace_secs.unwrap_or(24 * 3600).max(0));
+    let issued = state
+        .tenant_manager
+        .rotate_api_key(&tenant_id, grace)
+        .await
+        .map_err(|e| e.status_code())?;
+    Ok(Json(json!({
+        "api_key": issued.api_key,
+        "key": key_json(&issued.record),
+    })))
+}
+
+async fn handle_revoke_key(
+    State(state): State<AppState>,
+    Path((tenant_id, key_id)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let tenant = state
+        .tenant_manager
+        .revoke_api_key(&tenant_id, &key_id)
+        .await
+        .map_err(|e| e.status_code())?;
+    tracing::info!(tenant_id = %tenant_id, key_id = %key_id, "管理 API：已撤销 API Key");
+    Ok(Json(tenant_json(&tenant)))
+}
+
+async fn handle_tenant_usage(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let tenant = state
+        .tenant_manager
+        .get_tenant(&tenant_id)
+        .await
+        .map_err(|e| e.status_code())?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let usage = state.usage.get(&tenant_id).await;
+    // 计量用量（近 24 小时汇总）来自 Scheduler 的共享计量存储，与处理请求的网关副本无关
+    let metered = match state
+        .scheduler_client
+        .get_usage(&[("tenant_id", tenant_id.clone())])
+        .await
+    {
+        Ok(v) => v.get("totals").cloned(),
+        Err(e) => {
+            tracing::warn!(tenant_id = %tenant_id, error = %e, "管理 API：查询计量用量失败");
+            None
+        }
+    };
+    Ok(Json(json!({
+        "tenant_id": tenant.tenant_id,
+        "active_sessions": state.scheduler_client.active_sessions(&tenant_id),
+        "max_concurrent_sessions": tenant.max_concurrent_sessions,
+        "max_requests_per_second": tenant.max_requests_per_second,
+        "total_requests": usage.total_requests,
+        "rejected_requests": usage.rejected_requests,
+        "last_request_at": usage.last_request_at,
+        "metered_last_24h": metered,
+    })))
+}
+
+/// 计费导出：转发 Scheduler 的小时/天汇总（CSV 或 JSONL）
+async fn handle_usage_export(
+    State(state): State<AppState>,
+    Query(q): Query<UsageExportQuery>,
+) -> Result<Response, StatusCode> {
+    let query: Vec<(&str, String)> = [
+        ("tenant_id", q.tenant_id),
+        ("from", q.from),
+        ("to", q.to),
+        ("granularity", q.granularity),
+        ("format", q.format),
+    ]
+    .into_iter()
+    .filter_map(|(k, v)| v.map(|v| (k, v)))
+    .collect();
+
+    let export = state.scheduler_client.export_usage(&query).await.map_err(|e| {
+        tracing::warn!(error = %e, "管理 API：导出用量失败");
+        e.status_code()
+    })?;
+    let mut response = export.body.into_response();
+    let headers = response.headers_mut();
+    if let Ok(v) = export.content_type.parse() {
+        headers.insert(header::CONTENT_TYPE, v);
+    }
+    if let Some(v) = export.content_disposition.and_then(|v| v.parse().ok()) {
+        headers.insert(header::CONTENT_DISPOSITION, v);
+    }
+    Ok(response)
+}
+
+/// 对外展示的租户信息（不含 key 哈希）
+fn tenant_json(tenant: &Tenant) -> serde_json::Value {
+    json!({
+        "tenant_id": tenant.tenant_id,
+        "name": tenant.name,
+        "enabled": tenant.enabled,
+        "max_requests_per_second": tenant.max_requests_per_second,
+        "max_concurrent_sessions": tenant.max_concurrent_sessions,
+        "request_burst": tenant.request_burst,
+        "max_audio_seconds_per_minute": tenant.max_audio_seconds_per_minute,
+        "created_at": tenant.created_at,
+        "api_keys": tenant.api_keys.iter().map(key_json).collect::<Vec<_>>(),
+    })
+}
+
+fn key_json(key: &ApiKeyRecord) -> serde_json::Value {
+    json!({
+        "key_id": key.key_id,
+        "created_at": key.created_at,
+        "expires_at": key.expires_at,
+        "revoked": key.revoked,
+        "active": key.is_active(Utc::now()),
+    })
+}
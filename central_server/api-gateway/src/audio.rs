@@ -0,0 +1,3 @@
+// 音频时长估算（限流/计量使用，不解码音频；与 Scheduler 计量共用同一实现）
+
+pub use lingua_audio_estimate::estimate_audio_seconds;
@@ -1,10 +1,11 @@
 use axum::{
     extract::{Request, State},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
 
 use crate::AppState;
+use crate::tenant::constant_time_eq;
 
 pub async fn auth_middleware(
     State(state): State<AppState>,
@@ -20,27 +21,56 @@ pub async fn auth_middleware(
 
     let api_key = api_key.ok_or(axum::http::StatusCode::UNAUTHORIZED)?;
 
-    let tenant_id = state
+    let tenant = state
         .tenant_manager
         .validate_api_key(&api_key)
         .await
+        .map_err(|e| {
+            tracing::error!("租户存储不可用: {}", e);
+            axum::http::StatusCode::SERVICE_UNAVAILABLE
+        })?
         .ok_or(axum::http::StatusCode::UNAUTHORIZED)?;
+    let tenant_id = tenant.tenant_id.clone();
 
-    // 限流（按租户）
-    let max_rps = state
-        .tenant_manager
-        .get_tenant(&tenant_id)
-        .await
-        .map(|t| t.max_requests_per_second)
-        .unwrap_or(state.config.rate_limit.default_max_rps);
+    // 限流（按租户的请求令牌桶）
+    let decision = state.rate_limiter.check_requests(&tenant).await;
+    if let Some(d) = decision.as_ref().filter(|d| !d.allowed) {
+        state.usage.record_rejected(&tenant_id).await;
+        return Ok(d.clone().into_response());
+    }
+    state.usage.record_request(&tenant_id).await;
 
-    state
-        .rate_limiter
-        .check_rate_limit(&tenant_id, max_rps)
-        .map_err(|_| axum::http::StatusCode::TOO_MANY_REQUESTS)?;
-
-    // 将 tenant_id 放入请求扩展中，供 handler 使用
+    // 将 tenant_id 与租户记录放入请求扩展中，供 handler 使用（会话配额、音频限流等）
     req.extensions_mut().insert(tenant_id);
-    Ok(next.run(req).await)
+    req.extensions_mut().insert(tenant);
+    let mut response = next.run(req).await;
+    if let Some(d) = decision {
+        d.apply_headers(response.headers_mut());
+    }
+    Ok(response)
 }
 
+
+/// 管理 API 鉴权：`Authorization: Bearer <admin token>`
+pub async fn admin_auth_middleware(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, axum::http::StatusCode> {
+    let expected = state
+        .config
+        .admin
+        .resolved_token()
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+
+    let provided = req.headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| s.strip_prefix("Bearer "))
+        .ok_or(axum::http::StatusCode::UNAUTHORIZED)?;
+
+    if !constant_time_eq(provided.as_bytes(), expected.as_bytes()) {
+        return Err(axum::http::StatusCode::UNAUTHORIZED);
+    }
+    Ok(next.run(req).await)
+}
@@ -6,6 +6,17 @@ pub struct Config {
     pub scheduler: SchedulerConfig,
     pub rate_limit: RateLimitConfig,
     pub tenant: TenantConfig,
+    /// Redis 连接（tenant.store = "redis" 等场景使用）
+    #[serde(default)]
+    pub redis: RedisConfig,
+    #[serde(default)]
+    pub admin: AdminConfig,
+    #[serde(default)]
+    pub file_translation: FileTranslationConfig,
+    #[serde(default)]
+    pub subtitles: SubtitleConfig,
+    #[serde(default)]
+    pub webhooks: WebhookConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,20 +25,375 @@ pub struct ServerConfig {
     pub host: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 调度服务器连接配置（Debug 手写实现，不输出 admin_token）
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SchedulerConfig {
     pub url: String,
+    /// 调度服务器 HTTP 地址（用量查询等内部 API）；为空时由 url 推导（ws→http，去掉路径）
+    #[serde(default)]
+    pub http_url: Option<String>,
+    /// 建立调度服务器 WebSocket 连接（含 session_init 握手）的超时（毫秒）
+    #[serde(default = "default_connect_timeout_ms")]
+    pub connect_timeout_ms: u64,
+    /// 等待单个 utterance 翻译结果的超时（毫秒）
+    #[serde(default = "default_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+    /// 连接断开后的最大重连次数（每次请求触发）
+    #[serde(default = "default_reconnect_max_attempts")]
+    pub reconnect_max_attempts: u32,
+    /// 重连退避初始间隔（毫秒），每次失败翻倍
+    #[serde(default = "default_reconnect_backoff_initial_ms")]
+    pub reconnect_backoff_initial_ms: u64,
+    /// 重连退避最大间隔（毫秒）
+    #[serde(default = "default_reconnect_backoff_max_ms")]
+    pub reconnect_backoff_max_ms: u64,
+    /// 调度服务器 node_auth.admin_token（转写等管理 API 需要）；为空时读取环境变量 LINGUA_SCHEDULER_ADMIN_TOKEN
+    #[serde(default)]
+    pub admin_token: Option<String>,
+}
+
+impl SchedulerConfig {
+    /// 调度服务器 HTTP API 根地址：优先 `http_url`，否则由 `url` 推导（ws→http、wss→https，去掉路径）
+    pub fn http_base_url(&self) -> String {
+        if let Some(url) = self.http_url.as_ref().filter(|u| !u.is_empty()) {
+            return url.trim_end_matches('/').to_string();
+        }
+        let (scheme, rest) = match self.url.split_once("://") {
+            Some(("wss", rest)) => ("https", rest),
+            Some((_, rest)) => ("http", rest),
+            None => ("http", self.url.as_str()),
+        };
+        let host = rest.split('/').next().unwrap_or(rest);
+        format!("{}://{}", scheme, host)
+    }
+
+    pub fn resolved_admin_token(&self) -> Option<String> {
+        self.admin_token
+            .clone()
+            .filter(|t| !t.is_empty())
+            .or_else(|| std::env::var("LINGUA_SCHEDULER_ADMIN_TOKEN").ok())
+            .filter(|t| !t.is_empty())
+    }
+}
+
+impl std::fmt::Debug for SchedulerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SchedulerConfig")
+            .field("url", &self.url)
+            .field("http_url", &self.http_url)
+            .field("connect_timeout_ms", &self.connect_timeout_ms)
+            .field("request_timeout_ms", &self.request_timeout_ms)
+            .field("reconnect_max_attempts", &self.reconnect_max_attempts)
+            .field("reconnect_backoff_initial_ms", &self.reconnect_backoff_initial_ms)
+            .field("reconnect_backoff_max_ms", &self.reconnect_backoff_max_ms)
+            .field("admin_token", &redact(&self.admin_token))
+            .finish()
+    }
+}
+
+/// 已配置的密钥只输出占位符
+fn redact(secret: &Option<String>) -> Option<&'static str> {
+    secret.as_ref().map(|_| "<redacted>")
+}
+
+fn default_connect_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_request_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_reconnect_max_attempts() -> u32 {
+    3
+}
+
+fn default_reconnect_backoff_initial_ms() -> u64 {
+    200
+}
+
+fn default_reconnect_backoff_max_ms() -> u64 {
+    5000
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimitConfig {
     pub default_max_rps: usize,
     pub default_max_sessions: usize,
+    /// 请求令牌桶默认突发容量（0 表示等于租户 max_requests_per_second）
+    #[serde(default)]
+    pub default_burst: usize,
+    /// 默认每分钟音频秒数上限（0 表示不限制）
+    #[serde(default)]
+    pub default_max_audio_seconds_per_minute: u64,
+    /// 令牌桶与请求计数存储："memory"（单副本）| "redis"（多副本共享，使用 [redis] 连接）
+    #[serde(default = "default_rate_limit_backend")]
+    pub backend: String,
+}
+
+fn default_rate_limit_backend() -> String {
+    "memory".to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TenantConfig {
-    // 租户配置可以通过数据库管理
+    /// 租户存储后端："memory" | "file" | "redis"
+    #[serde(default = "default_tenant_store")]
+    pub store: String,
+    /// store = "file" 时的 JSON 文件路径
+    #[serde(default = "default_tenant_file_path")]
+    pub file_path: String,
+}
+
+fn default_tenant_store() -> String {
+    "memory".to_string()
+}
+
+fn default_tenant_file_path() -> String {
+    "data/tenants.json".to_string()
+}
+
+/// 管理 API 配置（Debug 手写实现，不输出 token）
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct AdminConfig {
+    /// 管理 API Bearer Token；为空时读取环境变量 LINGUA_ADMIN_TOKEN，仍为空则不启用管理 API
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+impl AdminConfig {
+    pub fn resolved_token(&self) -> Option<String> {
+        self.token
+            .clone()
+            .filter(|t| !t.is_empty())
+            .or_else(|| std::env::var("LINGUA_ADMIN_TOKEN").ok())
+            .filter(|t| !t.is_empty())
+    }
+}
+
+impl std::fmt::Debug for AdminConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdminConfig")
+            .field("token", &redact(&self.token))
+            .finish()
+    }
+}
+
+/// 长音频文件翻译（异步任务）配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileTranslationConfig {
+    /// 上传文件大小上限（字节）
+    #[serde(default = "default_file_max_bytes")]
+    pub max_file_bytes: usize,
+    /// 音频时长上限（秒）
+    #[serde(default = "default_file_max_duration_secs")]
+    pub max_duration_secs: u64,
+    /// 分段最短时长（毫秒）：短于该值时遇到静音也不切分
+    #[serde(default = "default_file_min_segment_ms")]
+    pub min_segment_ms: u64,
+    /// 分段最长时长（毫秒）：超过后强制切分；Opus 按该时长定长切分
+    #[serde(default = "default_file_max_segment_ms")]
+    pub max_segment_ms: u64,
+    /// 触发切分的最短静音时长（毫秒）
+    #[serde(default = "default_file_min_silence_ms")]
+    pub min_silence_ms: u64,
+    /// 静音判定阈值（20ms 帧 RMS，16bit 幅度）
+    #[serde(default = "default_file_silence_rms_threshold")]
+    pub silence_rms_threshold: u32,
+    /// 每个租户同时进行中的文件任务上限（0 表示不限制）
+    #[serde(default = "default_file_max_jobs_per_tenant")]
+    pub max_jobs_per_tenant: usize,
+    /// 任务结束后结果保留时长（秒）
+    #[serde(default = "default_file_result_ttl_secs")]
+    pub result_ttl_secs: u64,
+    /// 任务快照存储："memory"（只能在提交任务的副本上查询）| "redis"（任意副本可查询，使用 [redis] 连接）
+    #[serde(default = "default_file_store")]
+    pub store: String,
+}
+
+fn default_file_max_bytes() -> usize {
+    200 * 1024 * 1024
+}
+
+fn default_file_max_duration_secs() -> u64 {
+    2 * 3600
+}
+
+fn default_file_min_segment_ms() -> u64 {
+    2000
+}
+
+fn default_file_max_segment_ms() -> u64 {
+    15_000
+}
+
+fn default_file_min_silence_ms() -> u64 {
+    400
+}
+
+fn default_file_silence_rms_threshold() -> u32 {
+    300
+}
+
+fn default_file_max_jobs_per_tenant() -> usize {
+    2
+}
+
+fn default_file_result_ttl_secs() -> u64 {
+    3600
+}
+
+fn default_file_store() -> String {
+    "memory".to_string()
+}
+
+impl Default for FileTranslationConfig {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: default_file_max_bytes(),
+            max_duration_secs: default_file_max_duration_secs(),
+            min_segment_ms: default_file_min_segment_ms(),
+            max_segment_ms: default_file_max_segment_ms(),
+            min_silence_ms: default_file_min_silence_ms(),
+            silence_rms_threshold: default_file_silence_rms_threshold(),
+            max_jobs_per_tenant: default_file_max_jobs_per_tenant(),
+            result_ttl_secs: default_file_result_ttl_secs(),
+            store: default_file_store(),
+        }
+    }
+}
+
+/// 会话字幕配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubtitleConfig {
+    /// 会话结束后字幕保留时长（秒），期间可下载
+    #[serde(default = "default_subtitle_retention_secs")]
+    pub retention_secs: u64,
+}
+
+fn default_subtitle_retention_secs() -> u64 {
+    3600
+}
+
+impl Default for SubtitleConfig {
+    fn default() -> Self {
+        Self {
+            retention_secs: default_subtitle_retention_secs(),
+        }
+    }
+}
+
+/// 会话事件 Webhook 投递配置（订阅由租户通过 /v1/webhooks 管理）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    #[serde(default = "default_webhook_enabled")]
+    pub enabled: bool,
+    /// 单次投递（含首次）的最大尝试次数，用尽后进入死信列表
+    #[serde(default = "default_webhook_max_attempts")]
+    pub max_attempts: u32,
+    /// 重试退避初始间隔（毫秒），每次失败翻倍
+    #[serde(default = "default_webhook_backoff_initial_ms")]
+    pub backoff_initial_ms: u64,
+    /// 重试退避最大间隔（毫秒）
+    #[serde(default = "default_webhook_backoff_max_ms")]
+    pub backoff_max_ms: u64,
+    /// 单次 HTTP 请求超时（毫秒）
+    #[serde(default = "default_webhook_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+    /// 同时进行中的投递数上限
+    #[serde(default = "default_webhook_max_concurrent_deliveries")]
+    pub max_concurrent_deliveries: usize,
+    /// 每个租户保留的死信条数（超出丢弃最早的）
+    #[serde(default = "default_webhook_dead_letter_capacity")]
+    pub dead_letter_capacity: usize,
+    /// 每个租户的订阅数上限
+    #[serde(default = "default_webhook_max_subscriptions_per_tenant")]
+    pub max_subscriptions_per_tenant: usize,
+    /// 只允许 https 目标（生产环境保持开启）
+    #[serde(default = "default_webhook_require_https")]
+    pub require_https: bool,
+    /// 跳过 https 与内网地址限制的主机名（内网接收端、本地联调）
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+}
+
+fn default_webhook_enabled() -> bool {
+    true
+}
+
+fn default_webhook_max_attempts() -> u32 {
+    6
+}
+
+fn default_webhook_backoff_initial_ms() -> u64 {
+    1000
+}
+
+fn default_webhook_backoff_max_ms() -> u64 {
+    300_000
+}
+
+fn default_webhook_request_timeout_ms() -> u64 {
+    10_000
+}
+
+fn default_webhook_max_concurrent_deliveries() -> usize {
+    64
+}
+
+fn default_webhook_dead_letter_capacity() -> usize {
+    1000
+}
+
+fn default_webhook_max_subscriptions_per_tenant() -> usize {
+    10
+}
+
+fn default_webhook_require_https() -> bool {
+    true
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_webhook_enabled(),
+            max_attempts: default_webhook_max_attempts(),
+            backoff_initial_ms: default_webhook_backoff_initial_ms(),
+            backoff_max_ms: default_webhook_backoff_max_ms(),
+            request_timeout_ms: default_webhook_request_timeout_ms(),
+            max_concurrent_deliveries: default_webhook_max_concurrent_deliveries(),
+            dead_letter_capacity: default_webhook_dead_letter_capacity(),
+            max_subscriptions_per_tenant: default_webhook_max_subscriptions_per_tenant(),
+            require_https: default_webhook_require_https(),
+            allowed_hosts: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisConfig {
+    #[serde(default = "default_redis_url")]
+    pub url: String,
+    /// key 前缀（便于多环境隔离）
+    #[serde(default = "default_redis_key_prefix")]
+    pub key_prefix: String,
+}
+
+fn default_redis_url() -> String {
+    "redis://127.0.0.1:6379".to_string()
+}
+
+fn default_redis_key_prefix() -> String {
+    "lingua:gw".to_string()
+}
+
+impl Default for RedisConfig {
+    fn default() -> Self {
+        Self {
+            url: default_redis_url(),
+            key_prefix: default_redis_key_prefix(),
+        }
+    }
 }
 
 impl Config {
@@ -53,12 +419,30 @@ impl Default for Config {
             },
             scheduler: SchedulerConfig {
                 url: "ws://localhost:5010/ws/session".to_string(),
+                http_url: None,
+                connect_timeout_ms: default_connect_timeout_ms(),
+                request_timeout_ms: default_request_timeout_ms(),
+                reconnect_max_attempts: default_reconnect_max_attempts(),
+                reconnect_backoff_initial_ms: default_reconnect_backoff_initial_ms(),
+                reconnect_backoff_max_ms: default_reconnect_backoff_max_ms(),
+                admin_token: None,
             },
             rate_limit: RateLimitConfig {
                 default_max_rps: 100,
                 default_max_sessions: 10,
+                default_burst: 0,
+                default_max_audio_seconds_per_minute: 0,
+                backend: default_rate_limit_backend(),
+            },
+            tenant: TenantConfig {
+                store: default_tenant_store(),
+                file_path: default_tenant_file_path(),
             },
-            tenant: TenantConfig {},
+            redis: RedisConfig::default(),
+            admin: AdminConfig::default(),
+            file_translation: FileTranslationConfig::default(),
+            subtitles: SubtitleConfig::default(),
+            webhooks: WebhookConfig::default(),
         }
     }
 }
@@ -0,0 +1,566 @@
+// 长音频文件翻译（异步任务）
+//
+// 上传后在服务端切分为多个 utterance，通过一条调度会话按顺序提交，
+// 结果（逐段时间戳、原文、译文、拼接后的 TTS）保存在本进程内，供轮询查询。
+// file_translation.store = "redis" 时任务快照同步写入 Redis，任意网关副本都能查询状态与结果；
+// 任务本身仍由提交时的副本执行。
+
+mod segmenter;
+
+pub use segmenter::{segment_audio, SegmentError, SegmentedAudio};
+
+use crate::config::{Config, FileTranslationConfig};
+use crate::redis_client::GatewayRedis;
+use crate::scheduler_client::{SchedulerClient, SchedulerClientError, UtteranceRequest};
+use crate::subtitles::SubtitleCue;
+use crate::tenant::Tenant;
+use axum::http::StatusCode;
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::time::interval;
+
+#[derive(Debug, Error)]
+pub enum FileJobError {
+    #[error(transparent)]
+    Segment(#[from] SegmentError),
+    #[error("Tenant {tenant_id} already has {max_jobs} file translation jobs in progress")]
+    TooManyJobs { tenant_id: String, max_jobs: usize },
+}
+
+impl FileJobError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            FileJobError::Segment(SegmentError::TooLong { .. }) => StatusCode::PAYLOAD_TOO_LARGE,
+            FileJobError::Segment(SegmentError::UnsupportedFormat(_)) => {
+                StatusCode::UNSUPPORTED_MEDIA_TYPE
+            }
+            FileJobError::Segment(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            FileJobError::TooManyJobs { .. } => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileJobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl FileJobStatus {
+    fn is_finished(&self) -> bool {
+        matches!(self, FileJobStatus::Completed | FileJobStatus::Failed)
+    }
+}
+
+/// 单段结果（时间为相对文件开头的毫秒数）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentResult {
+    pub index: u64,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text_asr: String,
+    pub text_translated: String,
+}
+
+/// 提交参数
+#[derive(Debug, Clone)]
+pub struct FileJobRequest {
+    pub src_lang: String,
+    pub tgt_lang: String,
+    pub dialect: Option<String>,
+    pub features: Option<serde_json::Value>,
+    /// 是否拼接各段 TTS 音频
+    pub include_tts: bool,
+}
+
+/// 任务快照（Redis 中为 JSON，TTS 音频单独存放）
+#[derive(Serialize, Deserialize)]
+struct FileJob {
+    tenant_id: String,
+    status: FileJobStatus,
+    src_lang: String,
+    tgt_lang: String,
+    duration_ms: u64,
+    segments_total: usize,
+    segments: Vec<SegmentResult>,
+    include_tts: bool,
+    /// 拼接后的 TTS（pcm16 直接拼接；opus 为 Plan A 包序列，同样可直接拼接）
+    #[serde(skip)]
+    tts_audio: Vec<u8>,
+    /// TTS 音频是否非空（快照中不含音频本身时据此生成 tts_url）
+    #[serde(default)]
+    has_tts: bool,
+    tts_format: Option<String>,
+    error: Option<(String, String)>,
+    created_at: DateTime<Utc>,
+    finished_at: Option<DateTime<Utc>>,
+}
+
+impl FileJob {
+    fn to_json(&self, job_id: &str) -> serde_json::Value {
+        let mut body = json!({
+            "job_id": job_id,
+            "status": self.status,
+            "src_lang": self.src_lang,
+            "tgt_lang": self.tgt_lang,
+            "duration_ms": self.duration_ms,
+            "progress": {
+                "segments_total": self.segments_total,
+                "segments_done": self.segments.len(),
+            },
+            "created_at": self.created_at,
+            "finished_at": self.finished_at,
+        });
+        if self.status == FileJobStatus::Completed {
+            let join = |f: fn(&SegmentResult) -> &str| {
+                self.segments
+                    .iter()
+                    .map(f)
+                    .filter(|t| !t.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            body["result"] = json!({
+                "transcript": join(|s| &s.text_asr),
+                "translation": join(|s| &s.text_translated),
+                "segments": self.segments,
+                "tts_format": self.tts_format,
+                "tts_url": self.has_tts
+                    .then(|| format!("{}/{}/audio", STATUS_PATH, job_id)),
+                "subtitles_url": format!("{}/{}/subtitles", STATUS_PATH, job_id),
+            });
+        }
+        if let Some((code, message)) = &self.error {
+            body["error"] = json!({ "code": code, "message": message });
+        }
+        body
+    }
+
+    fn cues(&self) -> Option<Vec<SubtitleCue>> {
+        if self.status != FileJobStatus::Completed {
+            return None;
+        }
+        Some(
+            self.segments
+                .iter()
+                .map(|s| SubtitleCue {
+                    utterance_index: s.index,
+                    start_ms: s.start_ms,
+                    end_ms: s.end_ms,
+                    source: s.text_asr.clone(),
+                    target: s.text_translated.clone(),
+                })
+                .collect(),
+        )
+    }
+}
+
+/// 任务快照的 Redis 存储（状态 JSON 与拼接后的 TTS 分两个 key，TTL 为结果保留时长）
+#[derive(Clone)]
+struct FileJobRedis {
+    redis: GatewayRedis,
+    ttl_secs: u64,
+}
+
+impl FileJobRedis {
+    fn job_key(&self, job_id: &str) -> String {
+        self.redis.key(&format!("file_job:{{{}}}", job_id))
+    }
+
+    fn tts_key(&self, job_id: &str) -> String {
+        self.redis.key(&format!("file_job:{{{}}}:tts", job_id))
+    }
+
+    /// 写入快照（tts 只在任务完成时提供）；TTS 与状态在同一事务中写入，读到 completed 时音频已可用
+    async fn save(&self, job_id: &str, json: String, tts: Option<Vec<u8>>) -> redis::RedisResult<()> {
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        if let Some(tts) = tts {
+            pipe.cmd("SET")
+                .arg(self.tts_key(job_id))
+                .arg(tts)
+                .arg("EX")
+                .arg(self.ttl_secs.max(1))
+                .ignore();
+        }
+        pipe.cmd("SET")
+            .arg(self.job_key(job_id))
+            .arg(json)
+            .arg("EX")
+            .arg(self.ttl_secs.max(1))
+            .ignore();
+        self.redis.query_pipe(pipe).await
+    }
+
+    async fn load(&self, job_id: &str) -> Option<FileJob> {
+        let mut cmd = redis::cmd("GET");
+        cmd.arg(self.job_key(job_id));
+        let raw: Option<String> = self
+            .redis
+            .query(cmd)
+            .await
+            .map_err(|e| tracing::warn!(job_id = %job_id, error = %e, "读取文件任务快照失败"))
+            .ok()?;
+        serde_json::from_str(&raw?).ok()
+    }
+
+    async fn load_tts(&self, job_id: &str) -> Option<Vec<u8>> {
+        let mut cmd = redis::cmd("GET");
+        cmd.arg(self.tts_key(job_id));
+        self.redis.query::<Option<Vec<u8>>>(cmd).await.ok().flatten()
+    }
+}
+
+/// 状态查询路径前缀（与 rest_api 路由一致）
+pub const STATUS_PATH: &str = "/v1/speech/translate/file";
+
+pub struct FileTranslationManager {
+    config: FileTranslationConfig,
+    scheduler_client: Arc<SchedulerClient>,
+    jobs: Arc<DashMap<String, FileJob>>,
+    store: Option<FileJobRedis>,
+}
+
+impl FileTranslationManager {
+    pub fn new(config: FileTranslationConfig, scheduler_client: Arc<SchedulerClient>) -> Self {
+        let manager = Self {
+            config,
+            scheduler_client,
+            jobs: Arc::new(DashMap::new()),
+            store: None,
+        };
+        manager.start_cleanup_task();
+        manager
+    }
+
+    /// 按配置创建；store = "redis" 时任务快照写入 Redis
+    pub async fn from_config(config: &Config, scheduler_client: Arc<SchedulerClient>) -> anyhow::Result<Self> {
+        let mut manager = Self::new(config.file_translation.clone(), scheduler_client);
+        match config.file_translation.store.as_str() {
+            "memory" => {}
+            "redis" => {
+                manager.store = Some(FileJobRedis {
+                    redis: GatewayRedis::connect(&config.redis).await?,
+                    ttl_secs: config.file_translation.result_ttl_secs,
+                });
+                tracing::info!("文件翻译任务快照使用 Redis 存储");
+            }
+            other => anyhow::bail!("Unknown file translation store: {}", other),
+        }
+        Ok(manager)
+    }
+
+    pub fn config(&self) -> &FileTranslationConfig {
+        &self.config
+    }
+
+    /// 创建任务并在后台执行，返回 job_id
+    pub fn submit(
+        &self,
+        tenant: &Tenant,
+        request: FileJobRequest,
+        audio: SegmentedAudio,
+    ) -> Result<String, FileJobError> {
+        let max_jobs = self.config.max_jobs_per_tenant;
+        if max_jobs > 0 {
+            let in_progress = self
+                .jobs
+                .iter()
+                .filter(|j| j.tenant_id == tenant.tenant_id && !j.status.is_finished())
+                .count();
+            if in_progress >= max_jobs {
+                return Err(FileJobError::TooManyJobs {
+                    tenant_id: tenant.tenant_id.clone(),
+                    max_jobs,
+                });
+            }
+        }
+
+        let job_id = format!("file-{}", uuid::Uuid::new_v4());
+        self.jobs.insert(
+            job_id.clone(),
+            FileJob {
+                tenant_id: tenant.tenant_id.clone(),
+                status: FileJobStatus::Queued,
+                src_lang: request.src_lang.clone(),
+                tgt_lang: request.tgt_lang.clone(),
+                duration_ms: audio.duration_ms,
+                segments_total: audio.segments.len(),
+                segments: Vec::new(),
+                include_tts: request.include_tts,
+                tts_audio: Vec::new(),
+                has_tts: false,
+                tts_format: None,
+                error: None,
+                created_at: Utc::now(),
+                finished_at: None,
+            },
+        );
+
+        let worker = JobWorker {
+            job_id: job_id.clone(),
+            jobs: self.jobs.clone(),
+            scheduler_client: self.scheduler_client.clone(),
+            store: self.store.clone(),
+        };
+        let tenant = tenant.clone();
+        tokio::spawn(async move {
+            worker.run(tenant, request, audio).await;
+        });
+        Ok(job_id)
+    }
+
+    /// 读取任务（本进程优先，其次 Redis 快照；只返回属于该租户的任务）
+    async fn with_job<R>(&self, tenant_id: &str, job_id: &str, f: impl FnOnce(&FileJob) -> R) -> Option<R> {
+        if let Some(job) = self.jobs.get(job_id) {
+            return (job.tenant_id == tenant_id).then(|| f(&job));
+        }
+        let job = self.store.as_ref()?.load(job_id).await?;
+        (job.tenant_id == tenant_id).then(|| f(&job))
+    }
+
+    /// 任务状态
+    pub async fn status(&self, tenant_id: &str, job_id: &str) -> Option<serde_json::Value> {
+        self.with_job(tenant_id, job_id, |j| j.to_json(job_id)).await
+    }
+
+    /// 已完成任务的逐段结果，转为字幕
+    pub async fn cues(&self, tenant_id: &str, job_id: &str) -> Option<Vec<SubtitleCue>> {
+        self.with_job(tenant_id, job_id, FileJob::cues).await?
+    }
+
+    /// 拼接后的 TTS 音频：`(格式, 数据)`
+    pub async fn tts_audio(&self, tenant_id: &str, job_id: &str) -> Option<(String, Vec<u8>)> {
+        let (format, local) = self
+            .with_job(tenant_id, job_id, |job| {
+                if job.status != FileJobStatus::Completed || !job.has_tts {
+                    return None;
+                }
+                let local = (!job.tts_audio.is_empty()).then(|| job.tts_audio.clone());
+                Some((job.tts_format.clone()?, local))
+            })
+            .await??;
+        let audio = match local {
+            Some(audio) => audio,
+            None => self.store.as_ref()?.load_tts(job_id).await?,
+        };
+        Some((format, audio))
+    }
+
+    fn start_cleanup_task(&self) {
+        let jobs = self.jobs.clone();
+        let ttl = chrono::Duration::seconds(self.config.result_ttl_secs as i64);
+        tokio::spawn(async move {
+            let mut interval = interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                let now = Utc::now();
+                jobs.retain(|_, job| job.finished_at.is_none_or(|t| now - t < ttl));
+            }
+        });
+    }
+}
+
+struct JobWorker {
+    job_id: String,
+    jobs: Arc<DashMap<String, FileJob>>,
+    scheduler_client: Arc<SchedulerClient>,
+    store: Option<FileJobRedis>,
+}
+
+impl JobWorker {
+    /// 修改本进程中的任务，并同步快照到 Redis（失败只记日志，本副本仍可查询）
+    async fn update(&self, f: impl FnOnce(&mut FileJob)) {
+        let Some(mut job) = self.jobs.get_mut(&self.job_id) else {
+            return;
+        };
+        f(&mut job);
+        let Some(store) = self.store.as_ref() else {
+            return;
+        };
+        // 序列化后释放分片锁再写 Redis
+        let json = match serde_json::to_string(&*job) {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::warn!(job_id = %self.job_id, error = %e, "文件任务快照序列化失败");
+                return;
+            }
+        };
+        let tts = (job.status == FileJobStatus::Completed && job.has_tts).then(|| job.tts_audio.clone());
+        drop(job);
+        if let Err(e) = store.save(&self.job_id, json, tts).await {
+            tracing::warn!(job_id = %self.job_id, error = %e, "文件任务快照写入 Redis 失败");
+        }
+    }
+
+    async fn fail(&self, code: &str, message: String) {
+        tracing::warn!(job_id = %self.job_id, code = %code, error = %message, "文件翻译任务失败");
+        self.update(|job| {
+            job.status = FileJobStatus::Failed;
+            job.error = Some((code.to_string(), message));
+            job.finished_at = Some(Utc::now());
+        })
+        .await;
+    }
+
+    async fn run(self, tenant: Tenant, request: FileJobRequest, audio: SegmentedAudio) {
+        self.update(|job| job.status = FileJobStatus::Running).await;
+
+        let session_id = match self
+            .scheduler_client
+            .create_session(
+                tenant.tenant_id.clone(),
+                tenant.max_concurrent_sessions,
+                request.src_lang.clone(),
+                request.tgt_lang.clone(),
+                request.dialect.clone(),
+                request.features.clone(),
+            )
+            .await
+        {
+            Ok(id) => id,
+            Err(e) => {
+                self.fail(e.error_code().unwrap_or("SCHEDULER_ERROR"), e.to_string()).await;
+                return;
+            }
+        };
+
+        // 按顺序逐段提交；同一会话上 utterance_index 连续递增
+        for (index, segment) in audio.segments.into_iter().enumerate() {
+            let index = index as u64;
+            let result = self
+                .scheduler_client
+                .send_utterance(
+                    &session_id,
+                    UtteranceRequest {
+                        utterance_index: index,
+                        audio_data: segment.data,
+                        audio_format: audio.format.clone(),
+                        sample_rate: audio.sample_rate,
+                        src_lang: request.src_lang.clone(),
+                        tgt_lang: request.tgt_lang.clone(),
+                        dialect: request.dialect.clone(),
+                        features: request.features.clone(),
+                    },
+                )
+                .await;
+            let (text_asr, text_translated, tts_format, tts) = match result {
+                Ok(r) => (
+                    r.text_asr.trim().to_string(),
+                    r.text_translated.trim().to_string(),
+                    r.frame["tts_format"].as_str().unwrap_or("pcm16").to_string(),
+                    general_purpose::STANDARD.decode(&r.tts_audio).unwrap_or_default(),
+                ),
+                // 静音段调度端不返回结果（ASR_EMPTY 空核销），记为空字幕继续
+                Err(e @ SchedulerClientError::MissingResult { .. }) => {
+                    tracing::debug!(job_id = %self.job_id, segment = index, error = %e, "分段无结果，记为空段");
+                    (String::new(), String::new(), String::new(), Vec::new())
+                }
+                // 超时的段没有结果，不能当作静音段静默补空，整个任务失败
+                Err(e) => {
+                    let code = match &e {
+                        SchedulerClientError::Timeout { .. } => "JOB_TIMEOUT",
+                        e => e.error_code().unwrap_or("SCHEDULER_ERROR"),
+                    };
+                    self.scheduler_client.close_session(&session_id, "file_job_failed");
+                    self.fail(code, format!("segment {}: {}", index, e)).await;
+                    return;
+                }
+            };
+
+            self.update(|job| {
+                job.segments.push(SegmentResult {
+                    index,
+                    start_ms: segment.start_ms,
+                    end_ms: segment.end_ms,
+                    text_asr,
+                    text_translated,
+                });
+                if job.include_tts && !tts.is_empty() {
+                    append_tts(job, &tts_format, &tts);
+                }
+            })
+            .await;
+        }
+
+        self.scheduler_client.close_session(&session_id, "file_job_done");
+        self.update(|job| {
+            job.status = FileJobStatus::Completed;
+            job.has_tts = !job.tts_audio.is_empty();
+            job.finished_at = Some(Utc::now());
+        })
+        .await;
+        tracing::info!(job_id = %self.job_id, tenant_id = %tenant.tenant_id, "文件翻译任务完成");
+    }
+}
+
+/// 只拼接可直接串接的格式，且各段格式需一致
+fn append_tts(job: &mut FileJob, format: &str, data: &[u8]) {
+    if format != "pcm16" && format != "opus" {
+        return;
+    }
+    match job.tts_format.as_deref() {
+        None => job.tts_format = Some(format.to_string()),
+        Some(existing) if existing != format => return,
+        Some(_) => {}
+    }
+    job.tts_audio.extend_from_slice(data);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_omits_audio_but_keeps_tts_url() {
+        let job = FileJob {
+            tenant_id: "t1".to_string(),
+            status: FileJobStatus::Completed,
+            src_lang: "zh".to_string(),
+            tgt_lang: "en".to_string(),
+            duration_ms: 3000,
+            segments_total: 2,
+            segments: vec![
+                SegmentResult {
+                    index: 0,
+                    start_ms: 0,
+                    end_ms: 1500,
+                    text_asr: "你好".to_string(),
+                    text_translated: "hello".to_string(),
+                },
+                // 静音段记为空段
+                SegmentResult {
+                    index: 1,
+                    start_ms: 1500,
+                    end_ms: 3000,
+                    text_asr: String::new(),
+                    text_translated: String::new(),
+                },
+            ],
+            include_tts: true,
+            tts_audio: vec![1, 2, 3, 4],
+            has_tts: true,
+            tts_format: Some("pcm16".to_string()),
+            error: None,
+            created_at: Utc::now(),
+            finished_at: Some(Utc::now()),
+        };
+
+        let raw = serde_json::to_string(&job).unwrap();
+        let restored: FileJob = serde_json::from_str(&raw).unwrap();
+        assert!(restored.tts_audio.is_empty());
+        let body = restored.to_json("file-1");
+        assert_eq!(body["result"]["translation"], "hello");
+        assert_eq!(body["result"]["tts_url"], format!("{}/file-1/audio", STATUS_PATH));
+        assert_eq!(restored.cues().unwrap().len(), 2);
+    }
+}
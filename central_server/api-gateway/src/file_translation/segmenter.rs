@@ -0,0 +1,367 @@
+// 长音频服务端切分
+//
+// - wav / pcm16：按 20ms 帧计算 RMS，在足够长的静音处切分（段长受 min/max 约束），纯静音段丢弃
+// - opus（Plan A：`u16 LE 长度 + 包` 序列，每包 20ms）：不解码，按包定长切分
+
+use crate::config::FileTranslationConfig;
+use thiserror::Error;
+
+/// 分析帧长（毫秒）；Opus 包时长与 Web 端编码一致
+const FRAME_MS: u64 = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SegmentError {
+    #[error("Unsupported audio format: {0}")]
+    UnsupportedFormat(String),
+    #[error("Invalid WAV file: {0}")]
+    InvalidWav(&'static str),
+    #[error("Invalid Opus packet stream at byte {0}")]
+    InvalidOpus(usize),
+    #[error("Audio contains no speech")]
+    NoSpeech,
+    #[error("Audio is {duration_secs}s long, limit is {max_secs}s")]
+    TooLong { duration_secs: u64, max_secs: u64 },
+}
+
+#[derive(Debug, Clone)]
+pub struct AudioSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub data: Vec<u8>,
+}
+
+/// 切分结果；`format` 为发往调度服务器的格式（wav 解析后为 pcm16）
+#[derive(Debug, Clone)]
+pub struct SegmentedAudio {
+    pub format: String,
+    pub sample_rate: u32,
+    pub duration_ms: u64,
+    pub segments: Vec<AudioSegment>,
+}
+
+pub fn segment_audio(
+    audio_format: &str,
+    data: &[u8],
+    sample_rate: u32,
+    config: &FileTranslationConfig,
+) -> Result<SegmentedAudio, SegmentError> {
+    let segmented = match audio_format {
+        "wav" => {
+            let (samples, sample_rate) = decode_wav(data)?;
+            segment_pcm(&samples, sample_rate, config)
+        }
+        "pcm16" | "pcm" => {
+            let samples: Vec<i16> = data
+                .chunks_exact(2)
+                .map(|b| i16::from_le_bytes([b[0], b[1]]))
+                .collect();
+            segment_pcm(&samples, sample_rate.max(1), config)
+        }
+        "opus" => segment_opus(data, sample_rate, config)?,
+        other => return Err(SegmentError::UnsupportedFormat(other.to_string())),
+    };
+
+    let duration_secs = segmented.duration_ms / 1000;
+    if duration_secs > config.max_duration_secs {
+        return Err(SegmentError::TooLong {
+            duration_secs,
+            max_secs: config.max_duration_secs,
+        });
+    }
+    if segmented.segments.is_empty() {
+        return Err(SegmentError::NoSpeech);
+    }
+    Ok(segmented)
+}
+
+/// 解析 16bit PCM WAV，多声道取平均混为单声道
+fn decode_wav(data: &[u8]) -> Result<(Vec<i16>, u32), SegmentError> {
+    if data.len() < 12 || &data[0..4] != b"RIFF" || &data[8..12] != b"WAVE" {
+        return Err(SegmentError::InvalidWav("missing RIFF/WAVE header"));
+    }
+    let u16_at = |o: usize| u16::from_le_bytes([data[o], data[o + 1]]);
+    let u32_at = |o: usize| u32::from_le_bytes([data[o], data[o + 1], data[o + 2], data[o + 3]]);
+
+    let mut fmt: Option<(u16, u16, u32, u16)> = None;
+    let mut offset = 12;
+    while offset + 8 <= data.len() {
+        let chunk_id = &data[offset..offset + 4];
+        let chunk_len = u32_at(offset + 4) as usize;
+        let body = offset + 8;
+        match chunk_id {
+            b"fmt " => {
+                if chunk_len < 16 || body + 16 > data.len() {
+                    return Err(SegmentError::InvalidWav("truncated fmt chunk"));
+                }
+                fmt = Some((u16_at(body), u16_at(body + 2), u32_at(body + 4), u16_at(body + 14)));
+            }
+            b"data" => {
+                let (format_tag, channels, sample_rate, bits) =
+                    fmt.ok_or(SegmentError::InvalidWav("data chunk before fmt chunk"))?;
+                // 1 = PCM，0xFFFE = WAVE_FORMAT_EXTENSIBLE
+                if (format_tag != 1 && format_tag != 0xFFFE) || bits != 16 {
+                    return Err(SegmentError::InvalidWav("only 16-bit PCM is supported"));
+                }
+                if channels == 0 || sample_rate == 0 {
+                    return Err(SegmentError::InvalidWav("invalid channel count or sample rate"));
+                }
+                // 录音工具中断时 data 长度可能大于实际内容，按实际截断
+                let end = body.saturating_add(chunk_len).min(data.len());
+                let channels = channels as usize;
+                let samples = data[body..end]
+                    .chunks_exact(2 * channels)
+                    .map(|frame| {
+                        let sum: i32 = frame
+                            .chunks_exact(2)
+                            .map(|b| i16::from_le_bytes([b[0], b[1]]) as i32)
+                            .sum();
+                        (sum / channels as i32) as i16
+                    })
+                    .collect();
+                return Ok((samples, sample_rate));
+            }
+            _ => {}
+        }
+        // chunk 按偶数字节对齐
+        offset = body.saturating_add(chunk_len + (chunk_len & 1));
+    }
+    Err(SegmentError::InvalidWav("missing data chunk"))
+}
+
+fn segment_pcm(samples: &[i16], sample_rate: u32, config: &FileTranslationConfig) -> SegmentedAudio {
+    let frame_len = ((sample_rate as u64 * FRAME_MS / 1000) as usize).max(1);
+    let threshold = config.silence_rms_threshold as f64;
+    let silent: Vec<bool> = samples
+        .chunks(frame_len)
+        .map(|frame| {
+            let energy: f64 = frame.iter().map(|&s| (s as f64) * (s as f64)).sum();
+            (energy / frame.len() as f64).sqrt() < threshold
+        })
+        .collect();
+
+    let min_frames = (config.min_segment_ms / FRAME_MS).max(1) as usize;
+    let max_frames = (config.max_segment_ms / FRAME_MS).max(1) as usize;
+    let min_silence_frames = (config.min_silence_ms / FRAME_MS).max(1) as usize;
+
+    // 以帧为单位的 [start, end) 区间
+    let mut bounds = Vec::new();
+    let mut start = 0;
+    let mut silence_run = 0;
+    for (i, &is_silent) in silent.iter().enumerate() {
+        silence_run = if is_silent { silence_run + 1 } else { 0 };
+        let len = i + 1 - start;
+        let cut = if len >= max_frames {
+            Some(i + 1)
+        } else if len >= min_frames && silence_run >= min_silence_frames {
+            // 切在静音中点，两侧各留一半静音
+            Some(i + 1 - silence_run / 2)
+        } else {
+            None
+        };
+        if let Some(end) = cut {
+            bounds.push((start, end));
+            start = end;
+            silence_run = 0;
+        }
+    }
+    if start < silent.len() {
+        bounds.push((start, silent.len()));
+    }
+
+    let frame_ms = |frames: usize| (frames * frame_len) as u64 * 1000 / sample_rate as u64;
+    let segments = bounds
+        .into_iter()
+        .filter(|&(s, e)| silent[s..e].iter().any(|&is_silent| !is_silent))
+        .map(|(s, e)| {
+            let first = s * frame_len;
+            let last = (e * frame_len).min(samples.len());
+            AudioSegment {
+                start_ms: frame_ms(s),
+                end_ms: (last as u64 * 1000 / sample_rate as u64).max(frame_ms(s)),
+                data: samples[first..last].iter().flat_map(|s| s.to_le_bytes()).collect(),
+            }
+        })
+        .collect();
+
+    SegmentedAudio {
+        format: "pcm16".to_string(),
+        sample_rate,
+        duration_ms: samples.len() as u64 * 1000 / sample_rate as u64,
+        segments,
+    }
+}
+
+fn segment_opus(
+    data: &[u8],
+    sample_rate: u32,
+    config: &FileTranslationConfig,
+) -> Result<SegmentedAudio, SegmentError> {
+    let packets_per_segment = (config.max_segment_ms / FRAME_MS).max(1) as usize;
+    let mut segments = Vec::new();
+    let mut current = Vec::new();
+    let mut packets_in_current = 0;
+    let mut total_packets = 0u64;
+    let mut offset = 0;
+
+    while offset < data.len() {
+        if offset + 2 > data.len() {
+            return Err(SegmentError::InvalidOpus(offset));
+        }
+        let len = u16::from_le_bytes([data[offset], data[offset + 1]]) as usize;
+        if len == 0 || offset + 2 + len > data.len() {
+            return Err(SegmentError::InvalidOpus(offset));
+        }
+        current.extend_from_slice(&data[offset..offset + 2 + len]);
+        offset += 2 + len;
+        packets_in_current += 1;
+        total_packets += 1;
+
+        if packets_in_current == packets_per_segment {
+            let end_ms = total_packets * FRAME_MS;
+            segments.push(AudioSegment {
+                start_ms: end_ms - packets_in_current as u64 * FRAME_MS,
+                end_ms,
+                data: std::mem::take(&mut current),
+            });
+            packets_in_current = 0;
+        }
+    }
+    if packets_in_current > 0 {
+        let end_ms = total_packets * FRAME_MS;
+        segments.push(AudioSegment {
+            start_ms: end_ms - packets_in_current as u64 * FRAME_MS,
+            end_ms,
+            data: current,
+        });
+    }
+
+    Ok(SegmentedAudio {
+        format: "opus".to_string(),
+        sample_rate,
+        duration_ms: total_packets * FRAME_MS,
+        segments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> FileTranslationConfig {
+        FileTranslationConfig {
+            min_segment_ms: 1000,
+            max_segment_ms: 4000,
+            min_silence_ms: 200,
+            ..Default::default()
+        }
+    }
+
+    /// 16kHz：`speech_ms` 毫秒方波，后接 `silence_ms` 毫秒静音
+    fn pcm(parts: &[(u64, u64)]) -> Vec<i16> {
+        let mut samples = Vec::new();
+        for &(speech_ms, silence_ms) in parts {
+            samples.extend((0..speech_ms * 16).map(|i| if i % 2 == 0 { 3000 } else { -3000 }));
+            samples.extend(std::iter::repeat_n(0, (silence_ms * 16) as usize));
+        }
+        samples
+    }
+
+    fn to_bytes(samples: &[i16]) -> Vec<u8> {
+        samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn pcm_cuts_at_silence_and_drops_silent_tail() {
+        let samples = pcm(&[(1500, 500), (2000, 3000)]);
+        let out = segment_audio("pcm16", &to_bytes(&samples), 16000, &config()).unwrap();
+
+        assert_eq!(out.duration_ms, 7000);
+        assert_eq!(out.segments.len(), 2);
+        assert_eq!(out.segments[0].start_ms, 0);
+        // 静音 200ms 后触发切分，切点在静音中点
+        assert_eq!(out.segments[0].end_ms, 1600);
+        assert_eq!(out.segments[1].start_ms, 1600);
+        assert_eq!(out.segments[1].end_ms, 4100);
+    }
+
+    #[test]
+    fn pcm_forces_cut_at_max_segment() {
+        let samples = pcm(&[(9000, 0)]);
+        let out = segment_audio("pcm16", &to_bytes(&samples), 16000, &config()).unwrap();
+        let bounds: Vec<_> = out.segments.iter().map(|s| (s.start_ms, s.end_ms)).collect();
+        assert_eq!(bounds, vec![(0, 4000), (4000, 8000), (8000, 9000)]);
+        assert_eq!(out.segments[0].data.len(), 4 * 32000);
+    }
+
+    #[test]
+    fn wav_stereo_is_downmixed() {
+        let frames = 16000usize;
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&((36 + frames * 4) as u32).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16000u32.to_le_bytes());
+        wav.extend_from_slice(&(16000u32 * 4).to_le_bytes());
+        wav.extend_from_slice(&4u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&((frames * 4) as u32).to_le_bytes());
+        for i in 0..frames {
+            let v: i16 = if i % 2 == 0 { 2000 } else { -2000 };
+            wav.extend_from_slice(&v.to_le_bytes());
+            wav.extend_from_slice(&v.to_le_bytes());
+        }
+
+        let out = segment_audio("wav", &wav, 0, &config()).unwrap();
+        assert_eq!(out.format, "pcm16");
+        assert_eq!(out.sample_rate, 16000);
+        assert_eq!(out.duration_ms, 1000);
+        assert_eq!(out.segments.len(), 1);
+        assert_eq!(&out.segments[0].data[..4], &[0xD0, 0x07, 0x30, 0xF8]);
+
+        assert_eq!(
+            segment_audio("wav", b"not a wav file", 0, &config()).unwrap_err(),
+            SegmentError::InvalidWav("missing RIFF/WAVE header")
+        );
+    }
+
+    #[test]
+    fn opus_packets_are_grouped_without_decoding() {
+        let mut data = Vec::new();
+        for i in 0..450u16 {
+            data.extend_from_slice(&3u16.to_le_bytes());
+            data.extend_from_slice(&[i as u8, 0, 0]);
+        }
+        let out = segment_audio("opus", &data, 16000, &config()).unwrap();
+        assert_eq!(out.duration_ms, 9000);
+        let bounds: Vec<_> = out.segments.iter().map(|s| (s.start_ms, s.end_ms)).collect();
+        assert_eq!(bounds, vec![(0, 4000), (4000, 8000), (8000, 9000)]);
+        assert_eq!(out.segments[2].data.len(), 50 * 5);
+
+        data.push(0xFF);
+        assert!(matches!(
+            segment_audio("opus", &data, 16000, &config()),
+            Err(SegmentError::InvalidOpus(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_silence_and_overlong_audio() {
+        let silence = vec![0u8; 64000];
+        assert_eq!(
+            segment_audio("pcm16", &silence, 16000, &config()).unwrap_err(),
+            SegmentError::NoSpeech
+        );
+        let limited = FileTranslationConfig {
+            max_duration_secs: 1,
+            ..config()
+        };
+        assert!(matches!(
+            segment_audio("pcm16", &to_bytes(&pcm(&[(3000, 0)])), 16000, &limited),
+            Err(SegmentError::TooLong { duration_secs: 3, max_secs: 1 })
+        ));
+    }
+}
@@ -1,7 +1,6 @@
 use anyhow::Result;
 use axum::{
     extract::{ws::WebSocketUpgrade, State},
-    http::StatusCode,
     response::Response,
     routing::get,
     middleware,
@@ -9,22 +8,33 @@ use axum::{
 };
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tracing::{info, error};
-use tracing_subscriber;
+use tracing::info;
 
+mod audio;
 mod config;
+mod redis_client;
 mod tenant;
 mod auth;
 mod rate_limit;
 mod scheduler_client;
+mod file_translation;
+mod subtitles;
 mod rest_api;
 mod ws_api;
+mod admin_api;
+mod usage;
+mod webhooks;
 
 use config::Config;
 use tenant::TenantManager;
 use rate_limit::RateLimiter;
 use scheduler_client::SchedulerClient;
+use file_translation::FileTranslationManager;
+use subtitles::SubtitleStore;
 use rest_api::create_rest_router;
+use admin_api::create_admin_router;
+use usage::UsageCounters;
+use webhooks::WebhookManager;
 use ws_api::handle_public_websocket;
 use uuid::Uuid;
 
@@ -33,13 +43,13 @@ pub struct AppState {
     pub tenant_manager: Arc<TenantManager>,
     pub rate_limiter: Arc<RateLimiter>,
     pub scheduler_client: Arc<SchedulerClient>,
+    pub usage: Arc<UsageCounters>,
+    pub file_jobs: Arc<FileTranslationManager>,
+    pub subtitles: Arc<SubtitleStore>,
+    pub webhooks: Arc<WebhookManager>,
     pub config: Config,
 }
 
-// 从请求扩展中提取 tenant_id
-#[derive(Clone)]
-struct TenantId(String);
-
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt()
@@ -51,29 +61,47 @@ async fn main() -> Result<()> {
     let config = Config::load()?;
     info!("配置加载成功: {:?}", config);
 
-    let tenant_manager = Arc::new(TenantManager::new());
-    let rate_limiter = Arc::new(RateLimiter::new());
-    let scheduler_client = Arc::new(SchedulerClient::new(config.scheduler.url.clone()));
+    let tenant_manager = Arc::new(TenantManager::from_config(&config).await?);
+    let rate_limiter = Arc::new(RateLimiter::from_config(&config).await?);
+    let scheduler_client = Arc::new(SchedulerClient::new(config.scheduler.clone()));
+    let file_jobs = Arc::new(FileTranslationManager::from_config(&config, scheduler_client.clone()).await?);
+
+    let webhooks = WebhookManager::new(config.webhooks.clone(), tenant_manager.clone());
+    if config.webhooks.enabled {
+        webhooks.start(scheduler_client.subscribe_events());
+    }
 
     let app_state = AppState {
         tenant_manager,
         rate_limiter,
         scheduler_client,
+        usage: Arc::new(UsageCounters::from_config(&config).await?),
+        file_jobs,
+        subtitles: Arc::new(SubtitleStore::new(std::time::Duration::from_secs(
+            config.subtitles.retention_secs,
+        ))),
+        webhooks,
         config: config.clone(),
     };
 
-    // 启动时创建一个默认租户（方便本地快速跑通）
-    // - 优先使用环境变量 LINGUA_API_KEY
-    // - 如果未设置，则自动生成一个随机 key，并在日志中打印出来
-    let default_api_key = std::env::var("LINGUA_API_KEY").unwrap_or_else(|_| Uuid::new_v4().to_string());
-    let default_tenant = app_state
-        .tenant_manager
-        .create_tenant("default".to_string(), default_api_key.clone())
-        .await;
-    info!(
-        "默认租户已创建: tenant_id={}, api_key={} (仅用于开发/测试)",
-        default_tenant.tenant_id, default_api_key
-    );
+    // 开发便利：LINGUA_API_KEY 对应的租户不存在时创建默认租户；
+    // 存储为空且未设置该变量时，自动生成一个随机 key 并在日志中打印
+    let env_api_key = std::env::var("LINGUA_API_KEY").ok();
+    let needs_default = match &env_api_key {
+        Some(key) => app_state.tenant_manager.validate_api_key(key).await?.is_none(),
+        None => app_state.tenant_manager.list_tenants().await?.is_empty(),
+    };
+    if needs_default {
+        let default_api_key = env_api_key.unwrap_or_else(|| Uuid::new_v4().to_string());
+        let default_tenant = app_state
+            .tenant_manager
+            .create_tenant("default".to_string(), default_api_key.clone())
+            .await?;
+        info!(
+            "默认租户已创建: tenant_id={}, api_key={} (仅用于开发/测试)",
+            default_tenant.tenant_id, default_api_key
+        );
+    }
 
     // 需要鉴权的路由（REST + WS）
     let protected = Router::new()
@@ -81,10 +109,18 @@ async fn main() -> Result<()> {
         .merge(create_rest_router())
         .route_layer(middleware::from_fn_with_state(app_state.clone(), auth::auth_middleware));
 
+    // 管理 API（独立的管理 Token）
+    let admin = create_admin_router()
+        .route_layer(middleware::from_fn_with_state(app_state.clone(), auth::admin_auth_middleware));
+    if config.admin.resolved_token().is_none() {
+        info!("未配置管理 Token（admin.token / LINGUA_ADMIN_TOKEN），管理 API 已禁用");
+    }
+
     // 不需要鉴权的路由
     let app = Router::new()
         .route("/health", get(health_check))
         .merge(protected)
+        .nest("/admin", admin)
         .with_state(app_state);
 
     let addr = SocketAddr::from(([0, 0, 0, 0], config.server.port));
@@ -99,9 +135,9 @@ async fn main() -> Result<()> {
 async fn handle_ws(
     ws: WebSocketUpgrade,
     State(state): State<AppState>,
-    axum::extract::Extension(tenant_id): axum::extract::Extension<String>,
+    axum::extract::Extension(tenant): axum::extract::Extension<tenant::Tenant>,
 ) -> Response {
-    ws.on_upgrade(move |socket| handle_public_websocket(socket, tenant_id, state))
+    ws.on_upgrade(move |socket| handle_public_websocket(socket, tenant, state))
 }
 
 async fn health_check() -> &'static str {
@@ -0,0 +1,104 @@
+use std::time::{Duration, Instant};
+
+/// 令牌桶参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketSpec {
+    /// 每秒补充的令牌数
+    pub rate_per_sec: f64,
+    /// 桶容量（允许的突发量）
+    pub capacity: f64,
+}
+
+impl BucketSpec {
+    /// 本次放行需要桶中至少有多少令牌
+    ///
+    /// 单次消耗超过容量时（例如一段很长的音频）只要求桶满即可放行，
+    /// 随后令牌变为负数（欠账），后续请求需等待补回。
+    pub fn required(&self, cost: f64) -> f64 {
+        cost.min(self.capacity)
+    }
+
+    /// 剩余 `tokens` 个令牌（可为负，即欠账）时补满桶所需的时间
+    pub fn time_to_full(&self, tokens: f64) -> Duration {
+        Duration::from_secs_f64(((self.capacity - tokens) / self.rate_per_sec).max(0.0))
+    }
+}
+
+/// 进程内令牌桶
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+    /// 按当前余额（含欠账）补满的时刻；此后删除桶与满桶等价
+    full_at: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(spec: &BucketSpec, now: Instant) -> Self {
+        Self {
+            tokens: spec.capacity,
+            last_refill: now,
+            full_at: now,
+        }
+    }
+
+    /// 补充令牌后尝试消耗 `cost`，返回 `(是否放行, 剩余令牌)`
+    pub fn take(&mut self, spec: &BucketSpec, cost: f64, now: Instant) -> (bool, f64) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * spec.rate_per_sec).min(spec.capacity);
+        self.last_refill = now;
+
+        let allowed = self.tokens >= spec.required(cost);
+        if allowed {
+            self.tokens -= cost;
+        }
+        self.full_at = now + spec.time_to_full(self.tokens);
+        (allowed, self.tokens)
+    }
+
+    pub fn full_at(&self) -> Instant {
+        self.full_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_burst_then_refills_at_rate() {
+        let spec = BucketSpec { rate_per_sec: 2.0, capacity: 4.0 };
+        let t0 = Instant::now();
+        let mut bucket = TokenBucket::new(&spec, t0);
+
+        for _ in 0..4 {
+            assert!(bucket.take(&spec, 1.0, t0).0);
+        }
+        assert!(!bucket.take(&spec, 1.0, t0).0);
+
+        // 0.5 秒补回 1 个令牌
+        let t1 = t0 + Duration::from_millis(500);
+        assert!(bucket.take(&spec, 1.0, t1).0);
+        assert!(!bucket.take(&spec, 1.0, t1).0);
+
+        // 长时间空闲后最多补满到容量
+        let t2 = t1 + Duration::from_secs(60);
+        let (ok, remaining) = bucket.take(&spec, 1.0, t2);
+        assert!(ok);
+        assert_eq!(remaining, 3.0);
+    }
+
+    #[test]
+    fn oversized_cost_runs_into_debt() {
+        let spec = BucketSpec { rate_per_sec: 1.0, capacity: 10.0 };
+        let t0 = Instant::now();
+        let mut bucket = TokenBucket::new(&spec, t0);
+
+        let (ok, remaining) = bucket.take(&spec, 25.0, t0);
+        assert!(ok);
+        assert_eq!(remaining, -15.0);
+        assert_eq!(bucket.full_at(), t0 + Duration::from_secs(25));
+        assert!(!bucket.take(&spec, 1.0, t0 + Duration::from_secs(15)).0);
+        assert!(bucket.take(&spec, 1.0, t0 + Duration::from_secs(16)).0);
+    }
+}
@@ -0,0 +1,269 @@
+// 按租户的令牌桶限流
+//
+// 两个维度：请求数（requests/sec，可配置突发容量）与音频时长（audio-seconds/min）。
+// backend = "redis" 时多个网关副本共享同一个桶；Redis 调用失败时退化为本进程的桶。
+
+mod bucket;
+mod redis_bucket;
+
+pub use bucket::{BucketSpec, TokenBucket};
+
+use crate::config::{Config, RateLimitConfig};
+use crate::redis_client::GatewayRedis;
+use crate::tenant::Tenant;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use dashmap::DashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::time::interval;
+
+/// 限流维度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitDimension {
+    Requests,
+    AudioSeconds,
+}
+
+impl LimitDimension {
+    fn key_suffix(&self) -> &'static str {
+        match self {
+            LimitDimension::Requests => "req",
+            LimitDimension::AudioSeconds => "audio",
+        }
+    }
+
+    /// 响应头前缀：请求维度 `X-RateLimit-*`，音频维度 `X-RateLimit-Audio-*`
+    fn header_prefix(&self) -> &'static str {
+        match self {
+            LimitDimension::Requests => "x-ratelimit",
+            LimitDimension::AudioSeconds => "x-ratelimit-audio",
+        }
+    }
+}
+
+/// 单次限流判定结果
+#[derive(Debug, Clone)]
+pub struct RateLimitDecision {
+    pub dimension: LimitDimension,
+    pub allowed: bool,
+    /// 桶容量（突发上限）
+    pub limit: u64,
+    /// 剩余令牌（向下取整，欠账时为 0）
+    pub remaining: u64,
+    /// 桶补满所需秒数
+    pub reset_after_secs: u64,
+    /// 被拒绝时建议的重试等待秒数
+    pub retry_after_secs: Option<u64>,
+}
+
+impl RateLimitDecision {
+    fn new(dimension: LimitDimension, spec: &BucketSpec, cost: f64, allowed: bool, tokens: f64) -> Self {
+        let secs_until = |target: f64| ((target - tokens).max(0.0) / spec.rate_per_sec).ceil() as u64;
+        Self {
+            dimension,
+            allowed,
+            limit: spec.capacity.floor() as u64,
+            remaining: tokens.max(0.0).floor() as u64,
+            reset_after_secs: secs_until(spec.capacity),
+            retry_after_secs: (!allowed).then(|| secs_until(spec.required(cost)).max(1)),
+        }
+    }
+
+    /// 写入 `X-RateLimit-Limit/Remaining/Reset`（音频维度为 `X-RateLimit-Audio-*`），被拒绝时附带 `Retry-After`
+    pub fn apply_headers(&self, headers: &mut HeaderMap) {
+        let prefix = self.dimension.header_prefix();
+        for (name, value) in [
+            ("limit", self.limit),
+            ("remaining", self.remaining),
+            ("reset", self.reset_after_secs),
+        ] {
+            if let Ok(name) = HeaderName::try_from(format!("{}-{}", prefix, name)) {
+                headers.insert(name, HeaderValue::from(value));
+            }
+        }
+        if let Some(retry_after) = self.retry_after_secs {
+            headers.insert(axum::http::header::RETRY_AFTER, HeaderValue::from(retry_after));
+        }
+    }
+}
+
+/// 被限流时的 429 响应
+impl IntoResponse for RateLimitDecision {
+    fn into_response(self) -> Response {
+        let mut response = StatusCode::TOO_MANY_REQUESTS.into_response();
+        self.apply_headers(response.headers_mut());
+        response
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    /// 进程内桶：`{tenant_id}:{维度}` -> 令牌桶
+    buckets: Arc<DashMap<String, TokenBucket>>,
+    redis: Option<GatewayRedis>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        let limiter = Self {
+            config,
+            buckets: Arc::new(DashMap::new()),
+            redis: None,
+        };
+        limiter.start_cleanup_task();
+        limiter
+    }
+
+    /// 按配置创建；backend = "redis" 时连接 Redis
+    pub async fn from_config(config: &Config) -> anyhow::Result<Self> {
+        let mut limiter = Self::new(config.rate_limit.clone());
+        if config.rate_limit.backend == "redis" {
+            limiter.redis = Some(GatewayRedis::connect(&config.redis).await?);
+            tracing::info!("限流使用 Redis 共享令牌桶");
+        }
+        Ok(limiter)
+    }
+
+    /// 请求维度：每个请求消耗 1 个令牌；租户未设置上限（0）时返回 None
+    pub async fn check_requests(&self, tenant: &Tenant) -> Option<RateLimitDecision> {
+        let spec = self.request_spec(tenant)?;
+        Some(self.check(&tenant.tenant_id, LimitDimension::Requests, spec, 1.0).await)
+    }
+
+    /// 音频维度：按音频秒数消耗令牌；租户未设置上限（0）时返回 None
+    pub async fn check_audio(&self, tenant: &Tenant, audio_seconds: f64) -> Option<RateLimitDecision> {
+        let spec = self.audio_spec(tenant)?;
+        Some(
+            self.check(&tenant.tenant_id, LimitDimension::AudioSeconds, spec, audio_seconds.max(0.0))
+                .await,
+        )
+    }
+
+    fn request_spec(&self, tenant: &Tenant) -> Option<BucketSpec> {
+        let rps = tenant.max_requests_per_second;
+        if rps == 0 {
+            return None;
+        }
+        let burst = tenant.request_burst.unwrap_or(self.config.default_burst);
+        Some(BucketSpec {
+            rate_per_sec: rps as f64,
+            capacity: if burst == 0 { rps } else { burst } as f64,
+        })
+    }
+
+    fn audio_spec(&self, tenant: &Tenant) -> Option<BucketSpec> {
+        let per_minute = tenant
+            .max_audio_seconds_per_minute
+            .unwrap_or(self.config.default_max_audio_seconds_per_minute);
+        if per_minute == 0 {
+            return None;
+        }
+        Some(BucketSpec {
+            rate_per_sec: per_minute as f64 / 60.0,
+            capacity: per_minute as f64,
+        })
+    }
+
+    async fn check(
+        &self,
+        tenant_id: &str,
+        dimension: LimitDimension,
+        spec: BucketSpec,
+        cost: f64,
+    ) -> RateLimitDecision {
+        let bucket_key = format!("{}:{}", tenant_id, dimension.key_suffix());
+
+        if let Some(redis) = self.redis.as_ref() {
+            let key = redis.key(&format!("ratelimit:{{{}}}", bucket_key));
+            match redis_bucket::take(redis, &key, &spec, cost).await {
+                Ok((allowed, tokens)) => {
+                    return RateLimitDecision::new(dimension, &spec, cost, allowed, tokens);
+                }
+                Err(e) => {
+                    tracing::warn!(tenant_id = %tenant_id, error = %e, "Redis 限流不可用，退化为本进程令牌桶");
+                }
+            }
+        }
+
+        let now = Instant::now();
+        let (allowed, tokens) = self
+            .buckets
+            .entry(bucket_key)
+            .or_insert_with(|| TokenBucket::new(&spec, now))
+            .take(&spec, cost, now);
+        RateLimitDecision::new(dimension, &spec, cost, allowed, tokens)
+    }
+
+    fn start_cleanup_task(&self) {
+        let buckets = self.buckets.clone();
+        tokio::spawn(async move {
+            let mut interval = interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                // 已补满（含欠账补回）的桶删除后重建等价
+                let now = Instant::now();
+                buckets.retain(|_, bucket| now < bucket.full_at());
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tenant(rps: usize, burst: Option<usize>, audio_per_minute: Option<u64>) -> Tenant {
+        Tenant {
+            tenant_id: "t1".to_string(),
+            name: "t1".to_string(),
+            max_concurrent_sessions: 0,
+            max_requests_per_second: rps,
+            request_burst: burst,
+            max_audio_seconds_per_minute: audio_per_minute,
+            enabled: true,
+            created_at: chrono::Utc::now(),
+            api_keys: Vec::new(),
+            webhooks: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn request_burst_and_headers() {
+        let limiter = RateLimiter::new(Config::default().rate_limit);
+        let t = tenant(1, Some(3), None);
+
+        for expected_remaining in [2, 1, 0] {
+            let d = limiter.check_requests(&t).await.unwrap();
+            assert!(d.allowed);
+            assert_eq!(d.limit, 3);
+            assert_eq!(d.remaining, expected_remaining);
+        }
+        let rejected = limiter.check_requests(&t).await.unwrap();
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.retry_after_secs, Some(1));
+
+        let response = rejected.into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()["retry-after"], "1");
+        assert_eq!(response.headers()["x-ratelimit-limit"], "3");
+        assert_eq!(response.headers()["x-ratelimit-remaining"], "0");
+        assert_eq!(response.headers()["x-ratelimit-reset"], "3");
+    }
+
+    #[tokio::test]
+    async fn audio_budget_is_per_minute_and_independent_of_requests() {
+        let limiter = RateLimiter::new(Config::default().rate_limit);
+        let t = tenant(100, None, Some(60));
+
+        assert!(limiter.check_audio(&t, 45.0).await.unwrap().allowed);
+        let rejected = limiter.check_audio(&t, 30.0).await.unwrap();
+        assert!(!rejected.allowed);
+        // 还差 15 秒音频额度，按 1 秒/秒补充
+        assert_eq!(rejected.retry_after_secs, Some(15));
+        assert!(limiter.check_requests(&t).await.unwrap().allowed);
+
+        assert!(limiter.check_audio(&tenant(100, None, Some(0)), 1000.0).await.is_none());
+    }
+}
@@ -0,0 +1,52 @@
+use super::bucket::BucketSpec;
+use crate::redis_client::GatewayRedis;
+
+/// 令牌桶（Redis 共享）：HASH { t = 剩余令牌, ts = 上次补充时间 ms }
+///
+/// 补充与消耗在一个 Lua 脚本内完成；时间由网关传入，要求各副本时钟基本同步（NTP 即可）。
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local rate = tonumber(ARGV[1])
+local capacity = tonumber(ARGV[2])
+local cost = tonumber(ARGV[3])
+local now = tonumber(ARGV[4])
+local v = redis.call('HMGET', KEYS[1], 't', 'ts')
+local tokens = tonumber(v[1]) or capacity
+local ts = tonumber(v[2]) or now
+if now > ts then
+  tokens = math.min(capacity, tokens + (now - ts) / 1000 * rate)
+  ts = now
+end
+local allowed = 0
+if tokens >= math.min(cost, capacity) then
+  tokens = tokens - cost
+  allowed = 1
+end
+redis.call('HSET', KEYS[1], 't', tostring(tokens), 'ts', tostring(ts))
+-- 按当前余额（含欠账）补满所需时间 + 余量过期；过期即等价于满桶
+redis.call('PEXPIRE', KEYS[1], math.ceil((capacity - tokens) / rate * 1000) + tonumber(ARGV[5]))
+return {allowed, tostring(tokens)}
+"#;
+
+/// key 过期时间在补满所需时间之外的余量
+const TTL_MARGIN_MS: i64 = 1000;
+
+pub async fn take(
+    redis: &GatewayRedis,
+    key: &str,
+    spec: &BucketSpec,
+    cost: f64,
+) -> redis::RedisResult<(bool, f64)> {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+
+    let mut cmd = redis::cmd("EVAL");
+    cmd.arg(TOKEN_BUCKET_SCRIPT)
+        .arg(1)
+        .arg(key)
+        .arg(spec.rate_per_sec)
+        .arg(spec.capacity)
+        .arg(cost)
+        .arg(now_ms)
+        .arg(TTL_MARGIN_MS);
+    let (allowed, tokens): (i64, String) = redis.query(cmd).await?;
+    Ok((allowed == 1, tokens.parse().unwrap_or(0.0)))
+}
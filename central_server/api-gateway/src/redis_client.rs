@@ -0,0 +1,36 @@
+// 网关共享的 Redis 连接（多路复用，可廉价 clone）
+
+use crate::config::RedisConfig;
+use redis::aio::MultiplexedConnection;
+
+#[derive(Clone)]
+pub struct GatewayRedis {
+    conn: MultiplexedConnection,
+    key_prefix: String,
+}
+
+impl GatewayRedis {
+    pub async fn connect(cfg: &RedisConfig) -> redis::RedisResult<Self> {
+        let client = redis::Client::open(cfg.url.as_str())?;
+        let conn = client.get_multiplexed_tokio_connection().await?;
+        Ok(Self {
+            conn,
+            key_prefix: cfg.key_prefix.clone(),
+        })
+    }
+
+    /// 拼接带前缀的 key
+    pub fn key(&self, suffix: &str) -> String {
+        format!("{}:{}", self.key_prefix, suffix)
+    }
+
+    pub async fn query<T: redis::FromRedisValue>(&self, cmd: redis::Cmd) -> redis::RedisResult<T> {
+        let mut conn = self.conn.clone();
+        cmd.query_async(&mut conn).await
+    }
+
+    pub async fn query_pipe<T: redis::FromRedisValue>(&self, pipe: redis::Pipeline) -> redis::RedisResult<T> {
+        let mut conn = self.conn.clone();
+        pipe.query_async(&mut conn).await
+    }
+}
@@ -1,22 +1,210 @@
 use axum::{
-    extract::{Multipart, State, Extension},
-    response::Json,
-    routing::post,
+    extract::{DefaultBodyLimit, Multipart, Path, Query, State, Extension},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::{delete, get, post},
     Router,
 };
+use serde::Deserialize;
 use serde_json::json;
 use crate::AppState;
+use crate::audio::estimate_audio_seconds;
+use crate::file_translation::{segment_audio, FileJobRequest, STATUS_PATH};
+use crate::subtitles::{header as subtitle_header, render, render_cue, LiveEvent, SubtitleCue, SubtitleFormat, SubtitleMode};
+use futures_util::StreamExt;
+use std::convert::Infallible;
+use tokio::sync::broadcast::error::RecvError;
+use crate::rate_limit::RateLimitDecision;
+use crate::scheduler_client::UtteranceRequest;
+use crate::tenant::Tenant;
+use crate::webhooks::CreateWebhookRequest;
+
+/// REST 错误：普通状态码，或音频限流（429 + Retry-After / X-RateLimit-Audio-*）
+enum RestError {
+    Status(StatusCode),
+    RateLimited(RateLimitDecision),
+}
+
+impl From<StatusCode> for RestError {
+    fn from(status: StatusCode) -> Self {
+        RestError::Status(status)
+    }
+}
+
+impl IntoResponse for RestError {
+    fn into_response(self) -> Response {
+        match self {
+            RestError::Status(status) => status.into_response(),
+            RestError::RateLimited(decision) => decision.into_response(),
+        }
+    }
+}
 
 pub fn create_rest_router() -> Router<AppState> {
     Router::new()
         .route("/v1/speech/translate", post(handle_translate))
+        // 文件大小由 handler 按 [file_translation].max_file_bytes 边读边检查
+        .route(STATUS_PATH, post(handle_translate_file).layer(DefaultBodyLimit::disable()))
+        .route(&format!("{}/:job_id", STATUS_PATH), get(handle_file_job_status))
+        .route(&format!("{}/:job_id/audio", STATUS_PATH), get(handle_file_job_audio))
+        .route(&format!("{}/:job_id/subtitles", STATUS_PATH), get(handle_file_job_subtitles))
+        .route("/v1/sessions/:session_id/subtitles", get(handle_session_subtitles))
+        .route("/v1/sessions/:session_id/subtitles/live", get(handle_session_subtitles_live))
+        .route("/v1/sessions/:session_id/transcript", get(handle_get_transcript).delete(handle_delete_transcript))
+        .route("/v1/usage", get(handle_usage))
+        .route("/v1/webhooks", get(handle_list_webhooks).post(handle_create_webhook))
+        .route("/v1/webhooks/dead-letters", get(handle_list_dead_letters))
+        .route("/v1/webhooks/dead-letters/:event_id/replay", post(handle_replay_dead_letter))
+        .route("/v1/webhooks/:webhook_id", delete(handle_delete_webhook))
+}
+
+#[derive(Debug, Deserialize)]
+struct UsageQuery {
+    /// RFC3339，默认 `to - 24h`
+    from: Option<String>,
+    /// RFC3339，默认当前时间
+    to: Option<String>,
+    /// "hour" | "day"
+    granularity: Option<String>,
+}
+
+/// GET /v1/usage：调用方租户自己的用量（按小时/天汇总，数据来自 Scheduler 计量）
+async fn handle_usage(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Query(q): Query<UsageQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    // tenant_id 固定为调用方，不接受查询参数覆盖
+    let mut query = vec![("tenant_id", tenant.tenant_id.clone())];
+    query.extend(q.from.map(|v| ("from", v)));
+    query.extend(q.to.map(|v| ("to", v)));
+    query.extend(q.granularity.map(|v| ("granularity", v)));
+
+    let usage = state.scheduler_client.get_usage(&query).await.map_err(|e| {
+        tracing::warn!(tenant_id = %tenant.tenant_id, error = %e, "查询用量失败");
+        e.status_code()
+    })?;
+    Ok(Json(usage))
+}
+
+/// 进行中的网关会话换算为当前绑定的调度端 session_id，其余按调度端 session_id 原样查询
+fn transcript_session_id(state: &AppState, session_id: &str) -> String {
+    state
+        .scheduler_client
+        .scheduler_session_id(session_id)
+        .unwrap_or_else(|| session_id.to_string())
+}
+
+/// GET /v1/sessions/:session_id/transcript：调用方租户的会话转写（数据来自 Scheduler）
+async fn handle_get_transcript(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Path(session_id): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    // tenant_id 固定为调用方，Scheduler 对其他租户的转写返回 404
+    let scheduler_session_id = transcript_session_id(&state, &session_id);
+    let transcript = state
+        .scheduler_client
+        .get_transcript(&scheduler_session_id, &tenant.tenant_id)
+        .await
+        .map_err(|e| {
+            tracing::warn!(tenant_id = %tenant.tenant_id, session_id = %session_id, error = %e, "查询会话转写失败");
+            e.status_code()
+        })?;
+    Ok(Json(transcript))
+}
+
+/// DELETE /v1/sessions/:session_id/transcript
+async fn handle_delete_transcript(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Path(session_id): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    let scheduler_session_id = transcript_session_id(&state, &session_id);
+    state
+        .scheduler_client
+        .delete_transcript(&scheduler_session_id, &tenant.tenant_id)
+        .await
+        .map_err(|e| {
+            tracing::warn!(tenant_id = %tenant.tenant_id, session_id = %session_id, error = %e, "删除会话转写失败");
+            e.status_code()
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /v1/webhooks：调用方租户的 Webhook 订阅（不含 secret）
+async fn handle_list_webhooks(Extension(tenant): Extension<Tenant>) -> Json<serde_json::Value> {
+    Json(json!({
+        "webhooks": tenant.webhooks.iter().map(|w| w.to_json()).collect::<Vec<_>>(),
+    }))
+}
+
+/// POST /v1/webhooks：创建订阅；签名密钥只在此处返回一次
+async fn handle_create_webhook(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Json(req): Json<CreateWebhookRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), StatusCode> {
+    let subscription = state
+        .webhooks
+        .create_subscription(&tenant.tenant_id, req)
+        .await
+        .map_err(|e| e.status_code())?;
+    tracing::info!(
+        tenant_id = %tenant.tenant_id,
+        webhook_id = %subscription.webhook_id,
+        url = %subscription.url,
+        "已创建 Webhook 订阅"
+    );
+    let mut body = subscription.to_json();
+    body["secret"] = json!(subscription.secret);
+    Ok((StatusCode::CREATED, Json(body)))
+}
+
+/// DELETE /v1/webhooks/:webhook_id
+async fn handle_delete_webhook(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Path(webhook_id): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    state
+        .webhooks
+        .delete_subscription(&tenant.tenant_id, &webhook_id)
+        .await
+        .map_err(|e| e.status_code())?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /v1/webhooks/dead-letters：重试用尽的投递
+async fn handle_list_dead_letters(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+) -> Json<serde_json::Value> {
+    let letters = state.webhooks.dead_letters(&tenant.tenant_id);
+    Json(json!({
+        "dead_letters": letters.iter().map(|l| l.to_json()).collect::<Vec<_>>(),
+    }))
+}
+
+/// POST /v1/webhooks/dead-letters/:event_id/replay：异步重新投递
+async fn handle_replay_dead_letter(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Path(event_id): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    state
+        .webhooks
+        .replay(&tenant.tenant_id, &event_id)
+        .await
+        .map_err(|e| e.status_code())?;
+    Ok(StatusCode::ACCEPTED)
 }
 
 async fn handle_translate(
     State(state): State<AppState>,
-    Extension(tenant_id): Extension<String>, // 从中间件提取
+    Extension(tenant): Extension<Tenant>, // 从中间件提取
     mut multipart: Multipart,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+) -> Result<(HeaderMap, Json<serde_json::Value>), RestError> {
     let mut audio_data = Vec::new();
     let mut src_lang = None;
     let mut tgt_lang = None;
@@ -72,7 +260,21 @@ async fn handle_translate(
     }
 
     if audio_data.is_empty() {
-        return Err(axum::http::StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    let audio_format = audio_format.unwrap_or_else(|| "pcm16".to_string());
+    let sample_rate = sample_rate.unwrap_or(16000);
+
+    // 音频时长限流（按租户 audio-seconds/min）
+    let mut headers = HeaderMap::new();
+    let audio_seconds = estimate_audio_seconds(&audio_format, audio_data.len(), sample_rate);
+    if let Some(decision) = state.rate_limiter.check_audio(&tenant, audio_seconds).await {
+        if !decision.allowed {
+            state.usage.record_rejected(&tenant.tenant_id).await;
+            return Err(RestError::RateLimited(decision));
+        }
+        decision.apply_headers(&mut headers);
     }
 
     let src_lang = src_lang.unwrap_or_else(|| "zh".to_string());
@@ -81,36 +283,289 @@ async fn handle_translate(
     // 创建会话
     let session_id = state.scheduler_client
         .create_session(
-            tenant_id.clone(),
+            tenant.tenant_id.clone(),
+            tenant.max_concurrent_sessions,
             src_lang.clone(),
             tgt_lang.clone(),
             None,
             None,
         )
         .await
-        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|e| e.status_code())?;
 
-    // 发送 utterance
+    // 发送 utterance（与 session_init 复用同一条连接）
     let result = state.scheduler_client
         .send_utterance(
-            session_id,
-            0,
-            audio_data,
-            src_lang,
-            tgt_lang,
-            None,
-            None,
-            audio_format.unwrap(),
-            sample_rate.unwrap(),
+            &session_id,
+            UtteranceRequest {
+                utterance_index: 0,
+                audio_data,
+                audio_format,
+                sample_rate,
+                src_lang,
+                tgt_lang,
+                dialect: None,
+                features: None,
+            },
         )
-        .await
-        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await;
+    state.scheduler_client.close_session(&session_id, "rest_request_done");
+    let result = result.map_err(|e| e.status_code())?;
 
     // 转换为对外格式
-    Ok(Json(json!({
+    Ok((headers, Json(json!({
         "text": result.text_translated,
         "audio_tts": result.tts_audio,
         "duration_ms": result.processing_time_ms.unwrap_or(0),
-    })))
+    }))))
 }
 
+/// POST /v1/speech/translate/file：长音频异步翻译，返回 202 与状态查询地址
+async fn handle_translate_file(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, HeaderMap, Json<serde_json::Value>), RestError> {
+    let max_bytes = state.file_jobs.config().max_file_bytes;
+    let mut audio_data = Vec::new();
+    let mut fields = std::collections::HashMap::new();
+
+    while let Some(mut field) = multipart.next_field().await
+        .map_err(|_| StatusCode::BAD_REQUEST)? {
+        let name = field.name().unwrap_or("").to_string();
+        if name == "audio" {
+            // 分块读取，超过上限立即拒绝，避免整个请求体先落入内存
+            while let Some(chunk) = field.chunk().await.map_err(|_| StatusCode::BAD_REQUEST)? {
+                if audio_data.len() + chunk.len() > max_bytes {
+                    return Err(StatusCode::PAYLOAD_TOO_LARGE.into());
+                }
+                audio_data.extend_from_slice(&chunk);
+            }
+        } else {
+            let value = field.text().await.map_err(|_| StatusCode::BAD_REQUEST)?;
+            fields.insert(name, value);
+        }
+    }
+
+    if audio_data.is_empty() {
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    let audio_format = fields.remove("audio_format").unwrap_or_else(|| "wav".to_string());
+    let sample_rate = match fields.remove("sample_rate") {
+        Some(v) => v.parse::<u32>().map_err(|_| StatusCode::BAD_REQUEST)?,
+        None => 16000,
+    };
+    let features = match fields.remove("features") {
+        Some(v) => Some(serde_json::from_str(&v).map_err(|_| StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+    let request = FileJobRequest {
+        src_lang: fields.remove("src_lang").unwrap_or_else(|| "zh".to_string()),
+        tgt_lang: fields.remove("tgt_lang").unwrap_or_else(|| "en".to_string()),
+        dialect: fields.remove("dialect"),
+        features,
+        include_tts: fields.remove("include_tts").is_some_and(|v| v == "true" || v == "1"),
+    };
+
+    // 切分为 CPU 密集操作（长文件逐帧计算能量），放到阻塞线程池
+    let config = state.file_jobs.config().clone();
+    let audio = tokio::task::spawn_blocking(move || {
+        segment_audio(&audio_format, &audio_data, sample_rate, &config)
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .map_err(|e| {
+        tracing::info!(tenant_id = %tenant.tenant_id, error = %e, "文件翻译：音频无法切分");
+        crate::file_translation::FileJobError::from(e).status_code()
+    })?;
+
+    // 音频时长限流：按整份文件时长一次性扣减
+    let mut headers = HeaderMap::new();
+    if let Some(decision) = state.rate_limiter.check_audio(&tenant, audio.duration_ms as f64 / 1000.0).await {
+        if !decision.allowed {
+            state.usage.record_rejected(&tenant.tenant_id).await;
+            return Err(RestError::RateLimited(decision));
+        }
+        decision.apply_headers(&mut headers);
+    }
+
+    let duration_ms = audio.duration_ms;
+    let segments_total = audio.segments.len();
+    let job_id = state
+        .file_jobs
+        .submit(&tenant, request, audio)
+        .map_err(|e| e.status_code())?;
+    let status_url = format!("{}/{}", STATUS_PATH, job_id);
+    if let Ok(location) = HeaderValue::from_str(&status_url) {
+        headers.insert(header::LOCATION, location);
+    }
+    tracing::info!(tenant_id = %tenant.tenant_id, job_id = %job_id, segments_total, "文件翻译任务已提交");
+
+    Ok((StatusCode::ACCEPTED, headers, Json(json!({
+        "job_id": job_id,
+        "status": "queued",
+        "status_url": status_url,
+        "duration_ms": duration_ms,
+        "segments_total": segments_total,
+    }))))
+}
+
+/// GET /v1/speech/translate/file/:job_id
+async fn handle_file_job_status(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Path(job_id): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .file_jobs
+        .status(&tenant.tenant_id, &job_id)
+        .await
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// GET /v1/speech/translate/file/:job_id/audio：拼接后的 TTS 音频（原始字节）
+async fn handle_file_job_audio(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Path(job_id): Path<String>,
+) -> Result<Response, StatusCode> {
+    let (format, audio) = state
+        .file_jobs
+        .tts_audio(&tenant.tenant_id, &job_id)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let content_type = match format.as_str() {
+        "pcm16" => "audio/L16",
+        _ => "application/octet-stream",
+    };
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::HeaderName::from_static("x-audio-format"), format),
+        ],
+        audio,
+    )
+        .into_response())
+}
+
+#[derive(Debug, Deserialize)]
+struct SubtitleQuery {
+    /// "srt" | "vtt"，默认 "vtt"
+    format: Option<String>,
+    /// "bilingual" | "target"，默认 "bilingual"
+    mode: Option<String>,
+}
+
+impl SubtitleQuery {
+    fn parse(&self) -> Result<(SubtitleFormat, SubtitleMode), StatusCode> {
+        let format = SubtitleFormat::parse(self.format.as_deref().unwrap_or("vtt"))
+            .ok_or(StatusCode::BAD_REQUEST)?;
+        let mode = SubtitleMode::parse(self.mode.as_deref().unwrap_or("bilingual"))
+            .ok_or(StatusCode::BAD_REQUEST)?;
+        Ok((format, mode))
+    }
+}
+
+fn subtitle_response(name: &str, cues: &[SubtitleCue], format: SubtitleFormat, mode: SubtitleMode) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}.{}\"", name, format.extension()),
+            ),
+        ],
+        render(cues, format, mode),
+    )
+        .into_response()
+}
+
+/// GET /v1/sessions/:session_id/subtitles?format=srt|vtt&mode=bilingual|target
+///
+/// 会话进行中返回当前已生成的部分；会话结束后在保留期内可下载完整字幕。
+async fn handle_session_subtitles(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Path(session_id): Path<String>,
+    Query(q): Query<SubtitleQuery>,
+) -> Result<Response, StatusCode> {
+    let (format, mode) = q.parse()?;
+    let track = state
+        .subtitles
+        .get(&tenant.tenant_id, &session_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(subtitle_response(&session_id, &track.cues(), format, mode))
+}
+
+/// GET /v1/sessions/:session_id/subtitles/live?mode=bilingual|target
+///
+/// 分块传输的 WebVTT：先输出已生成的字幕，之后每条新字幕追加一个块，会话结束时关闭。
+async fn handle_session_subtitles_live(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Path(session_id): Path<String>,
+    Query(q): Query<SubtitleQuery>,
+) -> Result<Response, StatusCode> {
+    let mode = SubtitleMode::parse(q.mode.as_deref().unwrap_or("bilingual"))
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    let track = state
+        .subtitles
+        .get(&tenant.tenant_id, &session_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let (backlog, rx) = track.subscribe();
+
+    let format = SubtitleFormat::WebVtt;
+    let mut initial = String::from(subtitle_header(format));
+    let mut seq = 0;
+    for cue in &backlog {
+        if let Some(block) = render_cue(cue, seq + 1, format, mode) {
+            initial.push_str(&block);
+            seq += 1;
+        }
+    }
+
+    let live = futures_util::stream::unfold((rx, seq), move |(rx, seq)| async move {
+        let mut rx = rx?;
+        loop {
+            match rx.recv().await {
+                Ok(LiveEvent::Cue(cue)) => {
+                    if let Some(block) = render_cue(&cue, seq + 1, format, mode) {
+                        return Some((Ok::<_, Infallible>(block), (Some(rx), seq + 1)));
+                    }
+                }
+                // 读取过慢被跳过的字幕仍可通过下载接口获取
+                Err(RecvError::Lagged(_)) => continue,
+                Ok(LiveEvent::End) | Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+    let body = axum::body::Body::from_stream(
+        futures_util::stream::once(async move { Ok::<_, Infallible>(initial) }).chain(live),
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type()),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// GET /v1/speech/translate/file/:job_id/subtitles?format=srt|vtt&mode=bilingual|target
+async fn handle_file_job_subtitles(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Path(job_id): Path<String>,
+    Query(q): Query<SubtitleQuery>,
+) -> Result<Response, StatusCode> {
+    let (format, mode) = q.parse()?;
+    let cues = state
+        .file_jobs
+        .cues(&tenant.tenant_id, &job_id)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(subtitle_response(&job_id, &cues, format, mode))
+}
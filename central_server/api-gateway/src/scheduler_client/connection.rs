@@ -0,0 +1,568 @@
+// 单个网关会话到调度服务器的长连接
+//
+// 调度服务器的会话与 WebSocket 连接一一绑定（连接断开即清理会话），
+// 因此每个网关会话持有一条专属连接：session_init 与后续 utterance 走同一条 socket，
+// 回传帧按 session_id / utterance_index 路由给对应的等待者。
+
+use super::error::SchedulerClientError;
+use super::{AsrPartial, SessionEvent, SessionParams, TranslationResult, UtteranceRequest};
+use crate::config::SchedulerConfig;
+use base64::{engine::general_purpose, Engine as _};
+use dashmap::DashMap;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::json;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, info, warn};
+
+type ResultWaiter = oneshot::Sender<Result<TranslationResult, SchedulerClientError>>;
+
+/// 当前存活的一条 socket
+struct Link {
+    outbound: mpsc::UnboundedSender<Message>,
+    scheduler_session_id: String,
+    generation: u64,
+    /// 调度端 utterance_index = 网关 utterance_index - index_offset
+    /// 重连后调度端是新会话（从 0 开始），由重连后的第一个 utterance 确定偏移
+    index_offset: Option<u64>,
+}
+
+pub(super) struct SessionConnection {
+    gateway_session_id: String,
+    params: SessionParams,
+    config: SchedulerConfig,
+    link: Mutex<Option<Link>>,
+    /// 串行化连接/重连，避免并发请求各自建立连接
+    connect_lock: tokio::sync::Mutex<()>,
+    generation: AtomicU64,
+    /// 网关 utterance_index -> 等待者
+    pending: DashMap<u64, ResultWaiter>,
+    partials: broadcast::Sender<AsrPartial>,
+    events: broadcast::Sender<SessionEvent>,
+}
+
+impl SessionConnection {
+    pub(super) fn new(
+        gateway_session_id: String,
+        params: SessionParams,
+        config: SchedulerConfig,
+        events: broadcast::Sender<SessionEvent>,
+    ) -> Arc<Self> {
+        let (partials, _) = broadcast::channel(64);
+        Arc::new(Self {
+            gateway_session_id,
+            params,
+            config,
+            link: Mutex::new(None),
+            connect_lock: tokio::sync::Mutex::new(()),
+            generation: AtomicU64::new(0),
+            pending: DashMap::new(),
+            partials,
+            events,
+        })
+    }
+
+    pub(super) fn params(&self) -> &SessionParams {
+        &self.params
+    }
+
+    pub(super) fn scheduler_session_id(&self) -> Option<String> {
+        self.link
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|l| l.scheduler_session_id.clone())
+    }
+
+    pub(super) fn subscribe_partials(&self) -> broadcast::Receiver<AsrPartial> {
+        self.partials.subscribe()
+    }
+
+    /// 发布会话事件（session_id 换为网关侧编号）；无订阅者时直接跳过
+    fn emit(&self, mut frame: serde_json::Value) {
+        if self.events.receiver_count() == 0 {
+            return;
+        }
+        frame["session_id"] = json!(self.gateway_session_id);
+        let _ = self.events.send(SessionEvent {
+            tenant_id: self.params.tenant_id.clone(),
+            session_id: self.gateway_session_id.clone(),
+            frame,
+        });
+    }
+
+    fn is_connected(&self) -> bool {
+        self.link.lock().unwrap().is_some()
+    }
+
+    /// 确保连接可用；断开时按指数退避重连
+    pub(super) async fn ensure_connected(self: &Arc<Self>) -> Result<(), SchedulerClientError> {
+        if self.is_connected() {
+            return Ok(());
+        }
+        let _guard = self.connect_lock.lock().await;
+        if self.is_connected() {
+            return Ok(());
+        }
+
+        let mut delay = Duration::from_millis(self.config.reconnect_backoff_initial_ms);
+        let max_delay = Duration::from_millis(self.config.reconnect_backoff_max_ms);
+        let mut attempt = 0u32;
+        loop {
+            match self.connect_once().await {
+                Ok(link) => {
+                    info!(
+                        gateway_session_id = %self.gateway_session_id,
+                        scheduler_session_id = %link.scheduler_session_id,
+                        generation = link.generation,
+                        "已建立调度服务器会话连接"
+                    );
+                    *self.link.lock().unwrap() = Some(link);
+                    return Ok(());
+                }
+                Err(e) if is_retryable(&e) && attempt < self.config.reconnect_max_attempts => {
+                    attempt += 1;
+                    warn!(
+                        gateway_session_id = %self.gateway_session_id,
+                        attempt = attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %e,
+                        "连接调度服务器失败，退避后重试"
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay = (delay * 2).min(max_delay);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn connect_once(self: &Arc<Self>) -> Result<Link, SchedulerClientError> {
+        let timeout_ms = self.config.connect_timeout_ms;
+        let handshake = async {
+            let (ws_stream, _) = connect_async(&self.config.url)
+                .await
+                .map_err(|e| SchedulerClientError::Connect(e.to_string()))?;
+            let (mut write, mut read) = ws_stream.split();
+
+            let init_msg = json!({
+                "type": "session_init",
+                "tenant_id": self.params.tenant_id,
+                // 0（租户记录未设上限）不下发，由调度服务器按自身配置限制
+                "tenant_max_sessions": (self.params.max_sessions > 0).then_some(self.params.max_sessions),
+                "client_version": "1.0.0",
+                "platform": "api-gateway",
+                "src_lang": self.params.src_lang,
+                "tgt_lang": self.params.tgt_lang,
+                "dialect": self.params.dialect,
+                "features": self.params.features,
+            });
+            write
+                .send(Message::Text(init_msg.to_string()))
+                .await
+                .map_err(|e| SchedulerClientError::Connect(e.to_string()))?;
+
+            while let Some(frame) = read.next().await {
+                let text = match frame {
+                    Ok(Message::Text(text)) => text,
+                    Ok(Message::Close(_)) | Err(_) => break,
+                    Ok(_) => continue,
+                };
+                let ack: serde_json::Value = serde_json::from_str(&text)
+                    .map_err(|e| SchedulerClientError::Protocol(e.to_string()))?;
+                match ack["type"].as_str() {
+                    Some("session_init_ack") => {
+                        let session_id = ack["session_id"]
+                            .as_str()
+                            .ok_or_else(|| {
+                                SchedulerClientError::Protocol(
+                                    "session_init_ack without session_id".to_string(),
+                                )
+                            })?
+                            .to_string();
+                        return Ok((write, read, session_id, ack));
+                    }
+                    Some("error") => return Err(scheduler_error(&ack)),
+                    _ => continue,
+                }
+            }
+            Err(SchedulerClientError::ConnectionClosed)
+        };
+
+        let (mut write, mut read, scheduler_session_id, ack) =
+            tokio::time::timeout(Duration::from_millis(timeout_ms), handshake)
+                .await
+                .map_err(|_| SchedulerClientError::Timeout {
+                    operation: "session_init_ack",
+                    timeout_ms,
+                })??;
+
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        // 重连对外不可见，只在首次连接时发布 session_init_ack
+        if generation == 1 {
+            self.emit(ack);
+        }
+        let (outbound, mut outbound_rx) = mpsc::unbounded_channel::<Message>();
+
+        tokio::spawn(async move {
+            while let Some(msg) = outbound_rx.recv().await {
+                if write.send(msg).await.is_err() {
+                    break;
+                }
+            }
+            let _ = write.close().await;
+        });
+
+        let weak: Weak<Self> = Arc::downgrade(self);
+        tokio::spawn(async move {
+            while let Some(frame) = read.next().await {
+                let Some(conn) = weak.upgrade() else { return };
+                match frame {
+                    Ok(Message::Text(text)) => conn.handle_frame(generation, &text),
+                    Ok(Message::Close(_)) | Err(_) => break,
+                    Ok(_) => {}
+                }
+            }
+            if let Some(conn) = weak.upgrade() {
+                conn.on_disconnected(generation);
+            }
+        });
+
+        Ok(Link {
+            outbound,
+            scheduler_session_id,
+            generation,
+            // 首次连接与网关编号一致；重连后由第一个 utterance 确定
+            index_offset: if generation == 1 { Some(0) } else { None },
+        })
+    }
+
+    pub(super) async fn send_utterance(
+        self: &Arc<Self>,
+        request: UtteranceRequest,
+    ) -> Result<TranslationResult, SchedulerClientError> {
+        self.ensure_connected().await?;
+
+        let index = request.utterance_index;
+        let (waiter_tx, waiter_rx) = oneshot::channel();
+        match self.pending.entry(index) {
+            dashmap::mapref::entry::Entry::Occupied(_) => {
+                return Err(SchedulerClientError::DuplicateUtterance(index));
+            }
+            dashmap::mapref::entry::Entry::Vacant(v) => {
+                v.insert(waiter_tx);
+            }
+        }
+
+        if let Err(e) = self.send_utterance_frame(&request) {
+            self.pending.remove(&index);
+            return Err(e);
+        }
+
+        let timeout_ms = self.config.request_timeout_ms;
+        match tokio::time::timeout(Duration::from_millis(timeout_ms), waiter_rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(SchedulerClientError::ConnectionClosed),
+            Err(_) => {
+                self.pending.remove(&index);
+                self.emit(json!({
+                    "type": "error",
+                    "code": "JOB_TIMEOUT",
+                    "message": format!("no translation_result within {} ms", timeout_ms),
+                    "details": { "utterance_index": index },
+                }));
+                Err(SchedulerClientError::Timeout {
+                    operation: "translation_result",
+                    timeout_ms,
+                })
+            }
+        }
+    }
+
+    fn send_utterance_frame(&self, request: &UtteranceRequest) -> Result<(), SchedulerClientError> {
+        let mut guard = self.link.lock().unwrap();
+        let link = guard.as_mut().ok_or(SchedulerClientError::ConnectionClosed)?;
+        let offset = *link.index_offset.get_or_insert(request.utterance_index);
+        let scheduler_index = request
+            .utterance_index
+            .checked_sub(offset)
+            .ok_or_else(|| {
+                SchedulerClientError::Protocol(format!(
+                    "utterance {} predates reconnect (offset {})",
+                    request.utterance_index, offset
+                ))
+            })?;
+
+        let utterance_msg = json!({
+            "type": "utterance",
+            "session_id": link.scheduler_session_id,
+            "utterance_index": scheduler_index,
+            "manual_cut": false,
+            "src_lang": request.src_lang,
+            "tgt_lang": request.tgt_lang,
+            "dialect": request.dialect,
+            "features": request.features,
+            "audio": general_purpose::STANDARD.encode(&request.audio_data),
+            "audio_format": request.audio_format,
+            "sample_rate": request.sample_rate,
+        });
+        link.outbound
+            .send(Message::Text(utterance_msg.to_string()))
+            .map_err(|_| SchedulerClientError::ConnectionClosed)
+    }
+
+    /// 路由调度服务器回传的一帧
+    fn handle_frame(&self, generation: u64, text: &str) {
+        let frame: serde_json::Value = match serde_json::from_str(text) {
+            Ok(v) => v,
+            Err(e) => {
+                warn!(gateway_session_id = %self.gateway_session_id, error = %e, "无法解析调度服务器消息");
+                return;
+            }
+        };
+
+        let offset = {
+            let guard = self.link.lock().unwrap();
+            match guard.as_ref() {
+                Some(link) if link.generation == generation => {
+                    if let Some(sid) = frame["session_id"].as_str() {
+                        if sid != link.scheduler_session_id {
+                            debug!(session_id = %sid, "忽略其他会话的消息");
+                            return;
+                        }
+                    }
+                    link.index_offset.unwrap_or(0)
+                }
+                // 过期连接上的残留帧
+                _ => return,
+            }
+        };
+        let gateway_index = frame["utterance_index"].as_u64().map(|i| i + offset);
+
+        if matches!(
+            frame["type"].as_str(),
+            Some("translation_result" | "missing_result" | "error")
+        ) && self.events.receiver_count() > 0
+        {
+            let mut event = frame.clone();
+            if let Some(index) = gateway_index {
+                event["utterance_index"] = json!(index);
+            }
+            if let Some(index) = event["details"]["utterance_index"].as_u64() {
+                event["details"]["utterance_index"] = json!(index + offset);
+            }
+            self.emit(event);
+        }
+
+        match frame["type"].as_str() {
+            Some("translation_result") => {
+                let Some(index) = gateway_index else { return };
+                if let Some((_, waiter)) = self.pending.remove(&index) {
+                    let _ = waiter.send(Ok(TranslationResult::from_frame(index, frame)));
+                } else {
+                    debug!(utterance_index = index, "translation_result 没有等待者（可能已超时）");
+                }
+            }
+            Some("missing_result") => {
+                let Some(index) = gateway_index else { return };
+                if let Some((_, waiter)) = self.pending.remove(&index) {
+                    let _ = waiter.send(Err(SchedulerClientError::MissingResult {
+                        utterance_index: index,
+                        reason: frame["reason"].as_str().unwrap_or("unknown").to_string(),
+                    }));
+                }
+            }
+            Some("asr_partial") => {
+                let Some(index) = gateway_index else { return };
+                let _ = self.partials.send(AsrPartial {
+                    utterance_index: index,
+                    text: frame["text"].as_str().unwrap_or("").to_string(),
+                    is_final: frame["is_final"].as_bool().unwrap_or(false),
+                });
+            }
+            Some("error") => {
+                // error 帧不携带 utterance_index：优先按 details.utterance_index 路由，否则交给最早的等待者
+                let target = frame["details"]["utterance_index"]
+                    .as_u64()
+                    .map(|i| i + offset)
+                    .or_else(|| self.pending.iter().map(|e| *e.key()).min());
+                if let Some((_, waiter)) = target.and_then(|i| self.pending.remove(&i)) {
+                    let _ = waiter.send(Err(scheduler_error(&frame)));
+                } else {
+                    warn!(
+                        gateway_session_id = %self.gateway_session_id,
+                        code = frame["code"].as_str().unwrap_or(""),
+                        message = frame["message"].as_str().unwrap_or(""),
+                        "调度服务器返回错误（无等待者）"
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn on_disconnected(&self, generation: u64) {
+        {
+            let mut guard = self.link.lock().unwrap();
+            match guard.as_ref() {
+                Some(link) if link.generation == generation => {
+                    *guard = None;
+                }
+                _ => return,
+            }
+        }
+        warn!(
+            gateway_session_id = %self.gateway_session_id,
+            generation = generation,
+            pending = self.pending.len(),
+            "调度服务器连接断开，下次请求时重连"
+        );
+        self.fail_pending(SchedulerClientError::ConnectionClosed);
+    }
+
+    fn fail_pending(&self, error: SchedulerClientError) {
+        let keys: Vec<u64> = self.pending.iter().map(|e| *e.key()).collect();
+        for key in keys {
+            if let Some((_, waiter)) = self.pending.remove(&key) {
+                let _ = waiter.send(Err(error.clone()));
+            }
+        }
+    }
+
+    /// 关闭会话：通知调度服务器并断开连接
+    pub(super) fn close(&self, reason: &str) {
+        self.emit(json!({
+            "type": "session_close",
+            "session_id": self.gateway_session_id,
+            "reason": reason,
+        }));
+        let link = self.link.lock().unwrap().take();
+        if let Some(link) = link {
+            let close_msg = json!({
+                "type": "session_close",
+                "session_id": link.scheduler_session_id,
+                "reason": reason,
+            });
+            let _ = link.outbound.send(Message::Text(close_msg.to_string()));
+            // drop(outbound) 后写任务会关闭 socket
+        }
+        self.fail_pending(SchedulerClientError::ConnectionClosed);
+    }
+}
+
+fn is_retryable(error: &SchedulerClientError) -> bool {
+    matches!(
+        error,
+        SchedulerClientError::Connect(_)
+            | SchedulerClientError::ConnectionClosed
+            | SchedulerClientError::Timeout { .. }
+    )
+}
+
+fn scheduler_error(frame: &serde_json::Value) -> SchedulerClientError {
+    SchedulerClientError::Scheduler {
+        code: frame["code"].as_str().unwrap_or("UNKNOWN").to_string(),
+        message: frame["message"].as_str().unwrap_or("").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn test_connection() -> Arc<SessionConnection> {
+        let conn = SessionConnection::new(
+            "gw-test".to_string(),
+            SessionParams {
+                tenant_id: "tenant-a".to_string(),
+                max_sessions: 0,
+                src_lang: "zh".to_string(),
+                tgt_lang: "en".to_string(),
+                dialect: None,
+                features: None,
+            },
+            Config::default().scheduler,
+            broadcast::channel(16).0,
+        );
+        let (outbound, _rx) = mpsc::unbounded_channel();
+        *conn.link.lock().unwrap() = Some(Link {
+            outbound,
+            scheduler_session_id: "s-1".to_string(),
+            generation: 2,
+            index_offset: Some(5),
+        });
+        conn
+    }
+
+    #[tokio::test]
+    async fn translation_result_is_routed_by_offset_index() {
+        let conn = test_connection();
+        let (tx, rx) = oneshot::channel();
+        conn.pending.insert(7, tx);
+
+        conn.handle_frame(
+            2,
+            r#"{"type":"translation_result","session_id":"s-1","utterance_index":2,"text_asr":"你好","text_translated":"hello","tts_audio":""}"#,
+        );
+
+        let result = rx.await.unwrap().unwrap();
+        assert_eq!(result.utterance_index, 7);
+        assert_eq!(result.text_translated, "hello");
+    }
+
+    #[tokio::test]
+    async fn frames_from_other_sessions_or_stale_links_are_ignored() {
+        let conn = test_connection();
+        let (tx, _rx) = oneshot::channel();
+        conn.pending.insert(5, tx);
+
+        conn.handle_frame(2, r#"{"type":"translation_result","session_id":"s-other","utterance_index":0}"#);
+        conn.handle_frame(1, r#"{"type":"translation_result","session_id":"s-1","utterance_index":0}"#);
+
+        assert!(conn.pending.contains_key(&5));
+    }
+
+    #[tokio::test]
+    async fn error_frame_fails_earliest_waiter_and_disconnect_fails_rest() {
+        let conn = test_connection();
+        let (tx_a, rx_a) = oneshot::channel();
+        let (tx_b, rx_b) = oneshot::channel();
+        conn.pending.insert(6, tx_a);
+        conn.pending.insert(9, tx_b);
+
+        conn.handle_frame(2, r#"{"type":"error","code":"NO_AVAILABLE_NODE","message":"busy"}"#);
+        assert!(matches!(
+            rx_a.await.unwrap(),
+            Err(SchedulerClientError::Scheduler { ref code, .. }) if code == "NO_AVAILABLE_NODE"
+        ));
+
+        conn.on_disconnected(2);
+        assert!(matches!(rx_b.await.unwrap(), Err(SchedulerClientError::ConnectionClosed)));
+        assert!(!conn.is_connected());
+    }
+
+    #[tokio::test]
+    async fn events_use_gateway_session_id_and_index() {
+        let conn = test_connection();
+        let mut events = conn.events.subscribe();
+
+        conn.handle_frame(
+            2,
+            r#"{"type":"missing_result","session_id":"s-1","utterance_index":1,"reason":"silence_detected"}"#,
+        );
+        conn.handle_frame(2, r#"{"type":"asr_partial","session_id":"s-1","utterance_index":1,"text":"你"}"#);
+        conn.close("client_closed");
+
+        let missing = events.try_recv().unwrap();
+        assert_eq!(missing.tenant_id, "tenant-a");
+        assert_eq!(missing.frame["session_id"], "gw-test");
+        assert_eq!(missing.frame["utterance_index"], 6);
+        let closed = events.try_recv().unwrap();
+        assert_eq!(closed.frame["type"], "session_close");
+        assert!(events.try_recv().is_err());
+    }
+}
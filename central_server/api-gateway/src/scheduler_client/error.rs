@@ -0,0 +1,68 @@
+use axum::http::StatusCode;
+use thiserror::Error;
+
+/// 调度服务器客户端错误
+///
+/// 需要 `Clone`：连接断开时同一个错误会分发给该会话上所有等待中的请求。
+#[derive(Debug, Clone, Error)]
+pub enum SchedulerClientError {
+    #[error("Failed to connect to scheduler: {0}")]
+    Connect(String),
+    #[error("Timed out waiting for {operation} after {timeout_ms}ms")]
+    Timeout {
+        operation: &'static str,
+        timeout_ms: u64,
+    },
+    #[error("Scheduler connection closed")]
+    ConnectionClosed,
+    #[error("Scheduler error {code}: {message}")]
+    Scheduler { code: String, message: String },
+    #[error("Result for utterance {utterance_index} is missing: {reason}")]
+    MissingResult { utterance_index: u64, reason: String },
+    #[error("Unknown gateway session: {0}")]
+    UnknownSession(String),
+    #[error("Utterance {0} is already waiting for a result")]
+    DuplicateUtterance(u64),
+    #[error("Protocol error: {0}")]
+    Protocol(String),
+    #[error("Scheduler HTTP API returned status {0}")]
+    Http(u16),
+    #[error("Tenant {tenant_id} has reached its concurrent session limit ({max_sessions})")]
+    TenantSessionLimitExceeded { tenant_id: String, max_sessions: usize },
+}
+
+/// 与调度服务器 ErrorCode 保持一致
+pub const TENANT_SESSION_LIMIT_EXCEEDED: &str = "TENANT_SESSION_LIMIT_EXCEEDED";
+
+impl SchedulerClientError {
+    /// 对外 REST 接口使用的 HTTP 状态码
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            SchedulerClientError::Timeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+            SchedulerClientError::Connect(_) | SchedulerClientError::ConnectionClosed => {
+                StatusCode::BAD_GATEWAY
+            }
+            SchedulerClientError::UnknownSession(_) => StatusCode::NOT_FOUND,
+            SchedulerClientError::DuplicateUtterance(_) => StatusCode::CONFLICT,
+            SchedulerClientError::TenantSessionLimitExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
+            SchedulerClientError::Scheduler { code, .. } if code == TENANT_SESSION_LIMIT_EXCEEDED => {
+                StatusCode::TOO_MANY_REQUESTS
+            }
+            // 调度端 4xx（参数错误等）原样透传，5xx 视为网关上游故障
+            SchedulerClientError::Http(status) => match StatusCode::from_u16(*status) {
+                Ok(s) if s.is_client_error() => s,
+                _ => StatusCode::BAD_GATEWAY,
+            },
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// 对外错误码（WebSocket error 帧的 code 字段）
+    pub fn error_code(&self) -> Option<&str> {
+        match self {
+            SchedulerClientError::TenantSessionLimitExceeded { .. } => Some(TENANT_SESSION_LIMIT_EXCEEDED),
+            SchedulerClientError::Scheduler { code, .. } => Some(code),
+            _ => None,
+        }
+    }
+}
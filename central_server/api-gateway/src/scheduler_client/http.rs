@@ -0,0 +1,117 @@
+// 调度服务器内部 HTTP API（用量查询/导出、会话转写）
+
+use super::{SchedulerClient, SchedulerClientError};
+
+/// 导出文件（原样转发调度服务器的响应体）
+#[derive(Debug, Clone)]
+pub struct UsageExport {
+    pub content_type: String,
+    pub content_disposition: Option<String>,
+    pub body: String,
+}
+
+impl SchedulerClient {
+    /// GET /api/v1/usage
+    pub async fn get_usage(&self, query: &[(&str, String)]) -> Result<serde_json::Value, SchedulerClientError> {
+        let response = self.http_get("/api/v1/usage", query).await?;
+        response
+            .json()
+            .await
+            .map_err(|e| SchedulerClientError::Protocol(e.to_string()))
+    }
+
+    /// GET /api/v1/usage/export
+    pub async fn export_usage(&self, query: &[(&str, String)]) -> Result<UsageExport, SchedulerClientError> {
+        let response = self.http_get("/api/v1/usage/export", query).await?;
+        let header = |name: reqwest::header::HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string())
+        };
+        let content_type = header(reqwest::header::CONTENT_TYPE).unwrap_or_else(|| "text/plain".to_string());
+        let content_disposition = header(reqwest::header::CONTENT_DISPOSITION);
+        let body = response
+            .text()
+            .await
+            .map_err(|e| SchedulerClientError::Protocol(e.to_string()))?;
+        Ok(UsageExport {
+            content_type,
+            content_disposition,
+            body,
+        })
+    }
+
+    /// GET /api/v1/sessions/:session_id/transcript（携带 admin token，限定租户）
+    pub async fn get_transcript(
+        &self,
+        session_id: &str,
+        tenant_id: &str,
+    ) -> Result<serde_json::Value, SchedulerClientError> {
+        let response = self
+            .http_request(reqwest::Method::GET, &transcript_path(session_id), &[("tenant_id", tenant_id.to_string())])
+            .await?;
+        response
+            .json()
+            .await
+            .map_err(|e| SchedulerClientError::Protocol(e.to_string()))
+    }
+
+    /// DELETE /api/v1/sessions/:session_id/transcript（携带 admin token，限定租户）
+    pub async fn delete_transcript(&self, session_id: &str, tenant_id: &str) -> Result<(), SchedulerClientError> {
+        self.http_request(reqwest::Method::DELETE, &transcript_path(session_id), &[("tenant_id", tenant_id.to_string())])
+            .await?;
+        Ok(())
+    }
+
+    async fn http_get(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<reqwest::Response, SchedulerClientError> {
+        self.http_request(reqwest::Method::GET, path, query).await
+    }
+
+    async fn http_request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<reqwest::Response, SchedulerClientError> {
+        let url = format!("{}{}", self.config.http_base_url(), path);
+        let mut request = self
+            .http
+            .request(method, &url)
+            .query(query)
+            .timeout(std::time::Duration::from_millis(self.config.request_timeout_ms));
+        if let Some(token) = self.config.resolved_admin_token() {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    SchedulerClientError::Timeout {
+                        operation: "scheduler http request",
+                        timeout_ms: self.config.request_timeout_ms,
+                    }
+                } else {
+                    SchedulerClientError::Connect(e.to_string())
+                }
+            })?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(SchedulerClientError::Http(status.as_u16()));
+        }
+        Ok(response)
+    }
+}
+
+/// session_id 来自调用方，按路径段编码，避免拼出其他内部接口
+fn transcript_path(session_id: &str) -> String {
+    let mut url = reqwest::Url::parse("http://scheduler/api/v1/sessions").expect("static url");
+    url.path_segments_mut().expect("http url has path").push(session_id).push("transcript");
+    url.path().to_string()
+}
@@ -0,0 +1,248 @@
+// 调度服务器客户端
+//
+// 每个网关会话对应一条到调度服务器的长连接（见 connection.rs），
+// 由 SchedulerClient 按网关 session_id 管理。
+
+mod connection;
+mod error;
+mod http;
+
+pub use error::SchedulerClientError;
+
+use crate::config::SchedulerConfig;
+use connection::SessionConnection;
+use dashmap::DashMap;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tracing::info;
+
+#[derive(Clone)]
+pub struct SchedulerClient {
+    config: SchedulerConfig,
+    sessions: Arc<DashMap<String, Arc<SessionConnection>>>,
+    /// 租户当前占用的会话名额（创建中的会话也计入）
+    tenant_sessions: Arc<DashMap<String, usize>>,
+    /// 调度服务器内部 HTTP API
+    http: reqwest::Client,
+    /// 全部会话的事件流（Webhook 等消费）
+    events: broadcast::Sender<SessionEvent>,
+}
+
+/// session_init 参数（重连时原样重发）
+#[derive(Debug, Clone)]
+pub struct SessionParams {
+    pub tenant_id: String,
+    /// 租户并发会话上限（随 session_init 发给调度服务器执行全局配额，0 表示不限制）
+    pub max_sessions: usize,
+    pub src_lang: String,
+    pub tgt_lang: String,
+    pub dialect: Option<String>,
+    pub features: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UtteranceRequest {
+    pub utterance_index: u64,
+    pub audio_data: Vec<u8>,
+    pub audio_format: String,
+    pub sample_rate: u32,
+    pub src_lang: String,
+    pub tgt_lang: String,
+    pub dialect: Option<String>,
+    pub features: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TranslationResult {
+    /// 网关侧 utterance_index
+    pub utterance_index: u64,
+    pub text_asr: String,
+    pub text_translated: String,
+    pub tts_audio: String,
+    pub processing_time_ms: Option<u64>,
+    /// 原始 translation_result 帧（其余字段按需读取）
+    pub frame: serde_json::Value,
+}
+
+impl TranslationResult {
+    fn from_frame(utterance_index: u64, frame: serde_json::Value) -> Self {
+        Self {
+            utterance_index,
+            text_asr: frame["text_asr"].as_str().unwrap_or("").to_string(),
+            text_translated: frame["text_translated"].as_str().unwrap_or("").to_string(),
+            tts_audio: frame["tts_audio"].as_str().unwrap_or("").to_string(),
+            processing_time_ms: frame["service_timings"]["total_ms"].as_u64(),
+            frame,
+        }
+    }
+}
+
+/// 会话事件：调度服务器回传的帧（SessionMessage 格式），以及网关侧生成的
+/// session_init_ack / session_close / 超时 error 帧。
+/// 帧中的 session_id、utterance_index 已换算为网关侧编号。
+#[derive(Debug, Clone)]
+pub struct SessionEvent {
+    pub tenant_id: String,
+    pub session_id: String,
+    pub frame: serde_json::Value,
+}
+
+/// 流式 ASR 部分结果（asr_partial）
+#[derive(Debug, Clone)]
+pub struct AsrPartial {
+    pub utterance_index: u64,
+    pub text: String,
+    pub is_final: bool,
+}
+
+impl SchedulerClient {
+    pub fn new(config: SchedulerConfig) -> Self {
+        let (events, _) = broadcast::channel(1024);
+        Self {
+            config,
+            sessions: Arc::new(DashMap::new()),
+            tenant_sessions: Arc::new(DashMap::new()),
+            http: reqwest::Client::new(),
+            events,
+        }
+    }
+
+    /// 订阅全部会话的事件（无订阅者时不生成事件）
+    pub fn subscribe_events(&self) -> broadcast::Receiver<SessionEvent> {
+        self.events.subscribe()
+    }
+
+    /// 创建网关会话并建立到调度服务器的长连接，返回网关 session_id
+    ///
+    /// `max_sessions` 为租户并发会话上限（0 表示不限制），超限时直接拒绝，不连接调度服务器。
+    pub async fn create_session(
+        &self,
+        tenant_id: String,
+        max_sessions: usize,
+        src_lang: String,
+        tgt_lang: String,
+        dialect: Option<String>,
+        features: Option<serde_json::Value>,
+    ) -> Result<String, SchedulerClientError> {
+        self.reserve_slot(&tenant_id, max_sessions)?;
+        let gateway_session_id = format!("gw-{}", uuid::Uuid::new_v4());
+        let params = SessionParams {
+            tenant_id,
+            max_sessions,
+            src_lang,
+            tgt_lang,
+            dialect,
+            features,
+        };
+        let conn = SessionConnection::new(
+            gateway_session_id.clone(),
+            params,
+            self.config.clone(),
+            self.events.clone(),
+        );
+        if let Err(e) = conn.ensure_connected().await {
+            self.release_slot(&conn.params().tenant_id);
+            return Err(e);
+        }
+        self.sessions.insert(gateway_session_id.clone(), conn);
+        Ok(gateway_session_id)
+    }
+
+    /// 在会话的长连接上发送 utterance 并等待对应的 translation_result
+    pub async fn send_utterance(
+        &self,
+        session_id: &str,
+        request: UtteranceRequest,
+    ) -> Result<TranslationResult, SchedulerClientError> {
+        let conn = self.connection(session_id)?;
+        conn.send_utterance(request).await
+    }
+
+    /// 订阅会话的 asr_partial 帧
+    pub fn subscribe_partials(
+        &self,
+        session_id: &str,
+    ) -> Result<broadcast::Receiver<AsrPartial>, SchedulerClientError> {
+        Ok(self.connection(session_id)?.subscribe_partials())
+    }
+
+    /// 当前绑定的调度端 session_id（重连后会变化）
+    pub fn scheduler_session_id(&self, session_id: &str) -> Option<String> {
+        self.sessions.get(session_id)?.scheduler_session_id()
+    }
+
+    pub fn session_params(&self, session_id: &str) -> Option<SessionParams> {
+        self.sessions.get(session_id).map(|c| c.params().clone())
+    }
+
+    /// 租户当前持有的网关会话数
+    pub fn active_sessions(&self, tenant_id: &str) -> usize {
+        self.tenant_sessions.get(tenant_id).map_or(0, |c| *c)
+    }
+
+    /// 关闭网关会话（幂等）
+    pub fn close_session(&self, session_id: &str, reason: &str) {
+        if let Some((_, conn)) = self.sessions.remove(session_id) {
+            conn.close(reason);
+            self.release_slot(&conn.params().tenant_id);
+            info!(gateway_session_id = %session_id, reason = %reason, "网关会话已关闭");
+        }
+    }
+
+    /// 检查并占用租户名额（DashMap entry 持有分片锁，检查与自增是原子的）
+    fn reserve_slot(&self, tenant_id: &str, max_sessions: usize) -> Result<(), SchedulerClientError> {
+        let mut count = self.tenant_sessions.entry(tenant_id.to_string()).or_insert(0);
+        if max_sessions > 0 && *count >= max_sessions {
+            return Err(SchedulerClientError::TenantSessionLimitExceeded {
+                tenant_id: tenant_id.to_string(),
+                max_sessions,
+            });
+        }
+        *count += 1;
+        Ok(())
+    }
+
+    fn release_slot(&self, tenant_id: &str) {
+        self.tenant_sessions
+            .remove_if_mut(tenant_id, |_, count| {
+                *count = count.saturating_sub(1);
+                *count == 0
+            });
+    }
+
+    fn connection(&self, session_id: &str) -> Result<Arc<SessionConnection>, SchedulerClientError> {
+        self.sessions
+            .get(session_id)
+            .map(|c| c.clone())
+            .ok_or_else(|| SchedulerClientError::UnknownSession(session_id.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tenant_slots_are_bounded_and_released() {
+        let client = SchedulerClient::new(crate::config::Config::default().scheduler);
+        client.reserve_slot("t1", 2).unwrap();
+        client.reserve_slot("t1", 2).unwrap();
+        assert!(matches!(
+            client.reserve_slot("t1", 2),
+            Err(SchedulerClientError::TenantSessionLimitExceeded { max_sessions: 2, .. })
+        ));
+        assert_eq!(client.active_sessions("t1"), 2);
+
+        client.release_slot("t1");
+        client.reserve_slot("t1", 2).unwrap();
+        client.release_slot("t1");
+        client.release_slot("t1");
+        assert_eq!(client.active_sessions("t1"), 0);
+        assert!(client.tenant_sessions.is_empty());
+
+        // 0 表示不限制
+        for _ in 0..5 {
+            client.reserve_slot("t2", 0).unwrap();
+        }
+    }
+}
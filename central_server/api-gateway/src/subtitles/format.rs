@@ -0,0 +1,194 @@
+// SRT / WebVTT 渲染
+
+use serde::Serialize;
+
+/// 一条字幕（时间为相对会话音频开头的毫秒数）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SubtitleCue {
+    pub utterance_index: u64,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub source: String,
+    pub target: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubtitleFormat {
+    Srt,
+    WebVtt,
+}
+
+impl SubtitleFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "srt" => Some(SubtitleFormat::Srt),
+            "vtt" | "webvtt" => Some(SubtitleFormat::WebVtt),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "application/x-subrip; charset=utf-8",
+            SubtitleFormat::WebVtt => "text/vtt; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "srt",
+            SubtitleFormat::WebVtt => "vtt",
+        }
+    }
+}
+
+/// 双语（原文一行 + 译文一行）或仅译文
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubtitleMode {
+    Bilingual,
+    TargetOnly,
+}
+
+impl SubtitleMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "bilingual" => Some(SubtitleMode::Bilingual),
+            "target" => Some(SubtitleMode::TargetOnly),
+            _ => None,
+        }
+    }
+}
+
+/// 文件头：WebVTT 必须以 `WEBVTT` 开头，SRT 无文件头
+pub fn header(format: SubtitleFormat) -> &'static str {
+    match format {
+        SubtitleFormat::Srt => "",
+        SubtitleFormat::WebVtt => "WEBVTT\n\n",
+    }
+}
+
+/// 渲染完整字幕文件
+pub fn render(cues: &[SubtitleCue], format: SubtitleFormat, mode: SubtitleMode) -> String {
+    let mut out = String::from(header(format));
+    let mut seq = 0;
+    for cue in cues {
+        if let Some(block) = render_cue(cue, seq + 1, format, mode) {
+            out.push_str(&block);
+            seq += 1;
+        }
+    }
+    out
+}
+
+/// 渲染单条字幕块（以空行结尾）；按模式没有可显示文本时返回 None
+pub fn render_cue(
+    cue: &SubtitleCue,
+    seq: usize,
+    format: SubtitleFormat,
+    mode: SubtitleMode,
+) -> Option<String> {
+    let mut lines = Vec::new();
+    if mode == SubtitleMode::Bilingual {
+        lines.extend(clean_text(&cue.source, format));
+    }
+    lines.extend(clean_text(&cue.target, format));
+    if lines.is_empty() {
+        return None;
+    }
+
+    let sep = match format {
+        SubtitleFormat::Srt => ',',
+        SubtitleFormat::WebVtt => '.',
+    };
+    Some(format!(
+        "{}\n{} --> {}\n{}\n\n",
+        seq,
+        timestamp(cue.start_ms, sep),
+        timestamp(cue.end_ms.max(cue.start_ms), sep),
+        lines.join("\n")
+    ))
+}
+
+/// 去掉空行（空行在两种格式中都表示字幕块结束）；WebVTT 需转义 `& < >`
+fn clean_text(text: &str, format: SubtitleFormat) -> Option<String> {
+    let text = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if text.is_empty() {
+        return None;
+    }
+    Some(match format {
+        SubtitleFormat::Srt => text,
+        SubtitleFormat::WebVtt => text
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;"),
+    })
+}
+
+fn timestamp(ms: u64, sep: char) -> String {
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        sep,
+        ms % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cues() -> Vec<SubtitleCue> {
+        vec![
+            SubtitleCue {
+                utterance_index: 0,
+                start_ms: 0,
+                end_ms: 2500,
+                source: "你好".to_string(),
+                target: "Hello".to_string(),
+            },
+            SubtitleCue {
+                utterance_index: 1,
+                start_ms: 2500,
+                end_ms: 3_725_042,
+                source: "嗯".to_string(),
+                target: String::new(),
+            },
+            SubtitleCue {
+                utterance_index: 2,
+                start_ms: 3_725_042,
+                end_ms: 3_726_000,
+                source: "a<b".to_string(),
+                target: "x & y\n\n-->".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn srt_bilingual() {
+        let srt = render(&cues(), SubtitleFormat::Srt, SubtitleMode::Bilingual);
+        assert_eq!(
+            srt,
+            "1\n00:00:00,000 --> 00:00:02,500\n你好\nHello\n\n\
+             2\n00:00:02,500 --> 01:02:05,042\n嗯\n\n\
+             3\n01:02:05,042 --> 01:02:06,000\na<b\nx & y\n-->\n\n"
+        );
+    }
+
+    #[test]
+    fn webvtt_target_only_skips_empty_and_escapes() {
+        let vtt = render(&cues(), SubtitleFormat::WebVtt, SubtitleMode::TargetOnly);
+        assert_eq!(
+            vtt,
+            "WEBVTT\n\n\
+             1\n00:00:00.000 --> 00:00:02.500\nHello\n\n\
+             2\n01:02:05.042 --> 01:02:06.000\nx &amp; y\n--&gt;\n\n"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+// 会话字幕（SRT / WebVTT）
+//
+// 每个 WebSocket 会话一条字幕轨：发送音频时按时长推进时间轴，收到翻译结果后
+// 按 utterance_index 顺序释放字幕（与调度端 ResultQueueManager 的排序一致），
+// 支持会话结束后下载，以及会话进行中的增量 WebVTT 推流。
+
+mod format;
+
+pub use format::{header, render, render_cue, SubtitleCue, SubtitleFormat, SubtitleMode};
+
+use dashmap::DashMap;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tokio::time::interval;
+
+/// 推流事件
+#[derive(Debug, Clone)]
+pub enum LiveEvent {
+    Cue(SubtitleCue),
+    /// 会话已结束
+    End,
+}
+
+#[derive(Default)]
+struct TrackState {
+    /// 下一段音频的起点
+    cursor_ms: u64,
+    /// utterance_index -> (start_ms, end_ms)
+    timings: BTreeMap<u64, (u64, u64)>,
+    /// 已到达但尚未按序释放的结果；None 表示该段失败，直接跳过
+    pending: BTreeMap<u64, Option<(String, String)>>,
+    next_index: u64,
+    cues: Vec<SubtitleCue>,
+    finished_at: Option<Instant>,
+}
+
+pub struct SubtitleTrack {
+    tenant_id: String,
+    state: Mutex<TrackState>,
+    live: broadcast::Sender<LiveEvent>,
+}
+
+impl SubtitleTrack {
+    fn new(tenant_id: String) -> Self {
+        let (live, _) = broadcast::channel(256);
+        Self {
+            tenant_id,
+            state: Mutex::new(TrackState::default()),
+            live,
+        }
+    }
+
+    /// 记录一段发往调度服务器的音频（按发送顺序调用）
+    pub fn record_audio(&self, utterance_index: u64, duration_ms: u64) {
+        let mut state = self.state.lock().unwrap();
+        let start = state.cursor_ms;
+        state.cursor_ms += duration_ms;
+        state.timings.insert(utterance_index, (start, start + duration_ms));
+    }
+
+    /// 记录翻译结果；结果可乱序到达，按 utterance_index 顺序生成字幕
+    pub fn record_result(&self, utterance_index: u64, source: &str, target: &str) {
+        self.push(utterance_index, Some((source.trim().to_string(), target.trim().to_string())));
+    }
+
+    /// 该段翻译失败：不生成字幕，但不阻塞后续段
+    pub fn record_failure(&self, utterance_index: u64) {
+        self.push(utterance_index, None);
+    }
+
+    fn push(&self, utterance_index: u64, result: Option<(String, String)>) {
+        let mut state = self.state.lock().unwrap();
+        if utterance_index < state.next_index {
+            return;
+        }
+        state.pending.insert(utterance_index, result);
+        loop {
+            let index = state.next_index;
+            let Some(result) = state.pending.remove(&index) else {
+                break;
+            };
+            state.next_index += 1;
+            let timing = state.timings.remove(&index);
+            if let (Some((source, target)), Some((start_ms, end_ms))) = (result, timing) {
+                if source.is_empty() && target.is_empty() {
+                    continue;
+                }
+                let cue = SubtitleCue {
+                    utterance_index: index,
+                    start_ms,
+                    end_ms,
+                    source,
+                    target,
+                };
+                let _ = self.live.send(LiveEvent::Cue(cue.clone()));
+                state.cues.push(cue);
+            }
+        }
+    }
+
+    pub fn cues(&self) -> Vec<SubtitleCue> {
+        self.state.lock().unwrap().cues.clone()
+    }
+
+    /// 已生成的字幕 + 后续推流；会话已结束时不返回接收端
+    pub fn subscribe(&self) -> (Vec<SubtitleCue>, Option<broadcast::Receiver<LiveEvent>>) {
+        // 持锁订阅，保证快照与后续事件之间不丢不重
+        let state = self.state.lock().unwrap();
+        let rx = state.finished_at.is_none().then(|| self.live.subscribe());
+        (state.cues.clone(), rx)
+    }
+
+    fn finish(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.finished_at.is_none() {
+            state.finished_at = Some(Instant::now());
+            let _ = self.live.send(LiveEvent::End);
+        }
+    }
+
+    fn finished_at(&self) -> Option<Instant> {
+        self.state.lock().unwrap().finished_at
+    }
+}
+
+/// 按网关 session_id 保存字幕轨；会话结束后保留 `retention` 供下载
+#[derive(Clone)]
+pub struct SubtitleStore {
+    retention: Duration,
+    tracks: Arc<DashMap<String, Arc<SubtitleTrack>>>,
+}
+
+impl SubtitleStore {
+    pub fn new(retention: Duration) -> Self {
+        let store = Self {
+            retention,
+            tracks: Arc::new(DashMap::new()),
+        };
+        store.start_cleanup_task();
+        store
+    }
+
+    pub fn open(&self, session_id: &str, tenant_id: &str) -> Arc<SubtitleTrack> {
+        let track = Arc::new(SubtitleTrack::new(tenant_id.to_string()));
+        self.tracks.insert(session_id.to_string(), track.clone());
+        track
+    }
+
+    /// 只返回属于该租户的字幕轨
+    pub fn get(&self, tenant_id: &str, session_id: &str) -> Option<Arc<SubtitleTrack>> {
+        self.tracks
+            .get(session_id)
+            .filter(|t| t.tenant_id == tenant_id)
+            .map(|t| t.clone())
+    }
+
+    /// 会话结束：停止推流，字幕保留至过期
+    pub fn finish(&self, session_id: &str) {
+        if let Some(track) = self.tracks.get(session_id) {
+            track.finish();
+        }
+    }
+
+    fn start_cleanup_task(&self) {
+        let tracks = self.tracks.clone();
+        let retention = self.retention;
+        tokio::spawn(async move {
+            let mut interval = interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                tracks.retain(|_, track| {
+                    track.finished_at().is_none_or(|t| t.elapsed() < retention)
+                });
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn cues_are_released_in_utterance_order() {
+        let store = SubtitleStore::new(Duration::from_secs(60));
+        let track = store.open("s1", "t1");
+        let (_, rx) = track.subscribe();
+        let mut rx = rx.unwrap();
+
+        track.record_audio(0, 1000);
+        track.record_audio(1, 2000);
+        track.record_audio(2, 500);
+        track.record_audio(3, 1500);
+
+        // 1 先到：等待 0
+        track.record_result(1, "二", "two");
+        assert!(track.cues().is_empty());
+        track.record_result(0, "一", "one");
+        track.record_failure(2);
+        track.record_result(3, "四", "four");
+
+        let bounds: Vec<_> = track
+            .cues()
+            .iter()
+            .map(|c| (c.utterance_index, c.start_ms, c.end_ms))
+            .collect();
+        assert_eq!(bounds, vec![(0, 0, 1000), (1, 1000, 3000), (3, 3500, 5000)]);
+
+        store.finish("s1");
+        let mut live = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            live.push(event);
+        }
+        assert_eq!(live.len(), 4);
+        assert!(matches!(&live[0], LiveEvent::Cue(c) if c.target == "one"));
+        assert!(matches!(live[3], LiveEvent::End));
+
+        assert!(store.get("t2", "s1").is_none());
+        let (cues, rx) = store.get("t1", "s1").unwrap().subscribe();
+        assert_eq!(cues.len(), 3);
+        assert!(rx.is_none());
+    }
+}
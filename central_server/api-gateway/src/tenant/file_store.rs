@@ -0,0 +1,149 @@
+use super::store::{MemoryTenantStore, TenantStore, TenantStoreError, TenantUpdateFn};
+use super::Tenant;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::sync::Mutex;
+use tracing::info;
+
+#[derive(Serialize, Deserialize, Default)]
+struct TenantFile {
+    tenants: Vec<Tenant>,
+}
+
+/// JSON 文件存储（单副本部署）
+///
+/// 启动时整体加载到内存，每次写操作后整体落盘（先写临时文件再 rename，避免半写文件）。
+/// 写操作全程持有 write_lock，读取-修改-写回与落盘之间不会被其他写操作插入。
+pub struct FileTenantStore {
+    path: PathBuf,
+    inner: MemoryTenantStore,
+    write_lock: Mutex<()>,
+}
+
+impl FileTenantStore {
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self, TenantStoreError> {
+        let path = path.into();
+        let file: TenantFile = match tokio::fs::read(&path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => TenantFile::default(),
+            Err(e) => return Err(e.into()),
+        };
+        info!(path = %path.display(), tenants = file.tenants.len(), "已加载租户文件");
+        let tenants = file
+            .tenants
+            .into_iter()
+            .map(|t| (t.tenant_id.clone(), t))
+            .collect();
+        Ok(Self {
+            path,
+            inner: MemoryTenantStore::with_tenants(tenants),
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    /// 整体落盘（调用方需持有 write_lock）
+    async fn persist(&self) -> Result<(), TenantStoreError> {
+        let mut tenants = self.inner.snapshot().await;
+        tenants.sort_by_key(|t| t.created_at);
+        let bytes = serde_json::to_vec_pretty(&TenantFile { tenants })?;
+
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, bytes).await?;
+        tokio::fs::rename(&tmp, &self.path).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TenantStore for FileTenantStore {
+    async fn get(&self, tenant_id: &str) -> Result<Option<Tenant>, TenantStoreError> {
+        self.inner.get(tenant_id).await
+    }
+
+    async fn list(&self) -> Result<Vec<Tenant>, TenantStoreError> {
+        self.inner.list().await
+    }
+
+    async fn find_by_key_hash(&self, key_hash: &str) -> Result<Option<Tenant>, TenantStoreError> {
+        self.inner.find_by_key_hash(key_hash).await
+    }
+
+    async fn put(&self, tenant: &Tenant) -> Result<(), TenantStoreError> {
+        let _guard = self.write_lock.lock().await;
+        self.inner.put(tenant).await?;
+        self.persist().await
+    }
+
+    async fn update(
+        &self,
+        tenant_id: &str,
+        f: &mut TenantUpdateFn<'_>,
+    ) -> Result<Tenant, TenantStoreError> {
+        let _guard = self.write_lock.lock().await;
+        let tenant = self.inner.update(tenant_id, f).await?;
+        self.persist().await?;
+        Ok(tenant)
+    }
+
+    async fn delete(&self, tenant_id: &str) -> Result<(), TenantStoreError> {
+        let _guard = self.write_lock.lock().await;
+        self.inner.delete(tenant_id).await?;
+        self.persist().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tenant::TenantManager;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn tenants_survive_reopen() {
+        let path = std::env::temp_dir()
+            .join(format!("lingua-gw-{}", uuid::Uuid::new_v4()))
+            .join("tenants.json");
+
+        let store = Arc::new(FileTenantStore::open(&path).await.unwrap());
+        let mgr = TenantManager::new(store);
+        let tenant = mgr.create_tenant("acme".to_string(), "persisted-key".to_string()).await.unwrap();
+
+        let reopened = TenantManager::new(Arc::new(FileTenantStore::open(&path).await.unwrap()));
+        let found = reopened.validate_api_key("persisted-key").await.unwrap().unwrap();
+        assert_eq!(found.tenant_id, tenant.tenant_id);
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("persisted-key"));
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn concurrent_updates_are_not_lost() {
+        let path = std::env::temp_dir()
+            .join(format!("lingua-gw-{}", uuid::Uuid::new_v4()))
+            .join("tenants.json");
+
+        let mgr = Arc::new(TenantManager::new(Arc::new(FileTenantStore::open(&path).await.unwrap())));
+        let tenant = mgr.create_tenant("acme".to_string(), "k".to_string()).await.unwrap();
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let mgr = mgr.clone();
+                let tenant_id = tenant.tenant_id.clone();
+                tokio::spawn(async move { mgr.issue_api_key(&tenant_id, None).await.unwrap() })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let reopened = FileTenantStore::open(&path).await.unwrap();
+        let stored = reopened.get(&tenant.tenant_id).await.unwrap().unwrap();
+        assert_eq!(stored.api_keys.len(), 9);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
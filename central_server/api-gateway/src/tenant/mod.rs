@@ -0,0 +1,298 @@
+// 租户与 API Key 管理
+//
+// API Key 只以 SHA-256 哈希形式保存；校验时对请求携带的 key 求哈希后按哈希查找。
+// 每个租户可同时持有多个有效 key（支持轮换与过期），停用/启用直接写入存储，无需重启。
+
+mod file_store;
+mod redis_store;
+mod store;
+
+pub use file_store::FileTenantStore;
+pub use redis_store::RedisTenantStore;
+pub use store::{MemoryTenantStore, TenantStore, TenantStoreError};
+
+use crate::config::Config;
+use crate::webhooks::WebhookSubscription;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tenant {
+    pub tenant_id: String,
+    pub name: String,
+    pub max_concurrent_sessions: usize,
+    pub max_requests_per_second: usize,
+    /// 请求令牌桶突发容量（None 使用 rate_limit.default_burst）
+    #[serde(default)]
+    pub request_burst: Option<usize>,
+    /// 每分钟音频秒数上限（None 使用 rate_limit 默认值；0 表示不限制）
+    #[serde(default)]
+    pub max_audio_seconds_per_minute: Option<u64>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeyRecord>,
+    /// 会话事件 Webhook 订阅
+    #[serde(default)]
+    pub webhooks: Vec<WebhookSubscription>,
+}
+
+/// 单个 API Key 的元数据（不含明文）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyRecord {
+    pub key_id: String,
+    pub key_hash: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub revoked: bool,
+}
+
+impl ApiKeyRecord {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        !self.revoked && self.expires_at.is_none_or(|t| t > now)
+    }
+}
+
+/// 新签发的 API Key：明文只在签发时返回一次
+#[derive(Debug, Clone)]
+pub struct IssuedApiKey {
+    pub record: ApiKeyRecord,
+    pub api_key: String,
+}
+
+#[derive(Clone)]
+pub struct TenantManager {
+    store: Arc<dyn TenantStore>,
+}
+
+impl TenantManager {
+    pub fn new(store: Arc<dyn TenantStore>) -> Self {
+        Self { store }
+    }
+
+    /// 按配置创建存储后端
+    pub async fn from_config(config: &Config) -> Result<Self, TenantStoreError> {
+        let store: Arc<dyn TenantStore> = match config.tenant.store.as_str() {
+            "file" => Arc::new(FileTenantStore::open(&config.tenant.file_path).await?),
+            "redis" => Arc::new(RedisTenantStore::connect(&config.redis).await?),
+            "memory" => Arc::new(MemoryTenantStore::new()),
+            other => return Err(TenantStoreError::UnknownBackend(other.to_string())),
+        };
+        Ok(Self::new(store))
+    }
+
+    /// 校验 API Key：哈希匹配、key 未撤销/未过期、租户已启用
+    pub async fn validate_api_key(&self, api_key: &str) -> Result<Option<Tenant>, TenantStoreError> {
+        let key_hash = hash_api_key(api_key);
+        let Some(tenant) = self.store.find_by_key_hash(&key_hash).await? else {
+            return Ok(None);
+        };
+        if !tenant.enabled {
+            return Ok(None);
+        }
+        let now = Utc::now();
+        let valid = tenant
+            .api_keys
+            .iter()
+            .any(|k| k.is_active(now) && constant_time_eq(k.key_hash.as_bytes(), key_hash.as_bytes()));
+        Ok(valid.then_some(tenant))
+    }
+
+    pub async fn get_tenant(&self, tenant_id: &str) -> Result<Option<Tenant>, TenantStoreError> {
+        self.store.get(tenant_id).await
+    }
+
+    pub async fn create_tenant(&self, name: String, api_key: String) -> Result<Tenant, TenantStoreError> {
+        let now = Utc::now();
+        let tenant = Tenant {
+            tenant_id: format!("tenant-{}", uuid::Uuid::new_v4()),
+            name,
+            max_concurrent_sessions: 10,
+            max_requests_per_second: 100,
+            request_burst: None,
+            max_audio_seconds_per_minute: None,
+            enabled: true,
+            created_at: now,
+            api_keys: vec![new_key_record(&api_key, now, None)],
+            webhooks: Vec::new(),
+        };
+        self.store.put(&tenant).await?;
+        Ok(tenant)
+    }
+
+    /// 创建租户并签发一个随机 API Key（明文只在返回值中出现一次），全部限额一次写入
+    pub async fn onboard_tenant(
+        &self,
+        name: String,
+        max_requests_per_second: usize,
+        max_concurrent_sessions: usize,
+        request_burst: Option<usize>,
+        max_audio_seconds_per_minute: Option<u64>,
+    ) -> Result<(Tenant, IssuedApiKey), TenantStoreError> {
+        let now = Utc::now();
+        let api_key = generate_api_key();
+        let record = new_key_record(&api_key, now, None);
+        let tenant = Tenant {
+            tenant_id: format!("tenant-{}", uuid::Uuid::new_v4()),
+            name,
+            max_concurrent_sessions,
+            max_requests_per_second,
+            request_burst,
+            max_audio_seconds_per_minute,
+            enabled: true,
+            created_at: now,
+            api_keys: vec![record.clone()],
+            webhooks: Vec::new(),
+        };
+        self.store.put(&tenant).await?;
+        Ok((tenant, IssuedApiKey { record, api_key }))
+    }
+
+    pub async fn list_tenants(&self) -> Result<Vec<Tenant>, TenantStoreError> {
+        self.store.list().await
+    }
+
+    /// 为租户签发新的 API Key（可选过期时间）
+    pub async fn issue_api_key(
+        &self,
+        tenant_id: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<IssuedApiKey, TenantStoreError> {
+        let api_key = generate_api_key();
+        let record = new_key_record(&api_key, Utc::now(), expires_at);
+        let issued = record.clone();
+        self.update(tenant_id, move |t| t.api_keys.push(record.clone())).await?;
+        Ok(IssuedApiKey { record: issued, api_key })
+    }
+
+    /// 轮换：签发新 key，旧的有效 key 在 grace 时间后过期
+    pub async fn rotate_api_key(
+        &self,
+        tenant_id: &str,
+        grace: Duration,
+    ) -> Result<IssuedApiKey, TenantStoreError> {
+        let now = Utc::now();
+        let api_key = generate_api_key();
+        let record = new_key_record(&api_key, now, None);
+        let issued = record.clone();
+        self.update(tenant_id, move |t| {
+            let deadline = now + grace;
+            for key in t.api_keys.iter_mut().filter(|k| k.is_active(now)) {
+                key.expires_at = Some(key.expires_at.map_or(deadline, |e| e.min(deadline)));
+            }
+            t.api_keys.push(record.clone());
+        })
+        .await?;
+        Ok(IssuedApiKey { record: issued, api_key })
+    }
+
+    pub async fn revoke_api_key(&self, tenant_id: &str, key_id: &str) -> Result<Tenant, TenantStoreError> {
+        let key_id = key_id.to_string();
+        let mut found = false;
+        let tenant = self
+            .update(tenant_id, |t| {
+                found = false;
+                for key in t.api_keys.iter_mut().filter(|k| k.key_id == key_id) {
+                    key.revoked = true;
+                    found = true;
+                }
+            })
+            .await?;
+        if !found {
+            return Err(TenantStoreError::ApiKeyNotFound(key_id));
+        }
+        Ok(tenant)
+    }
+
+    /// 停用/启用租户（立即生效）
+    pub async fn set_enabled(&self, tenant_id: &str, enabled: bool) -> Result<Tenant, TenantStoreError> {
+        self.update(tenant_id, |t| t.enabled = enabled).await
+    }
+
+    /// 原子读取-修改-写回（并发冲突重试时 f 可能被调用多次，需每次从传入的记录重新计算）
+    pub async fn update<F>(&self, tenant_id: &str, mut f: F) -> Result<Tenant, TenantStoreError>
+    where
+        F: FnMut(&mut Tenant) + Send,
+    {
+        self.store.update(tenant_id, &mut f).await
+    }
+}
+
+fn new_key_record(api_key: &str, now: DateTime<Utc>, expires_at: Option<DateTime<Utc>>) -> ApiKeyRecord {
+    ApiKeyRecord {
+        key_id: format!("key-{}", uuid::Uuid::new_v4().simple()),
+        key_hash: hash_api_key(api_key),
+        created_at: now,
+        expires_at,
+        revoked: false,
+    }
+}
+
+fn generate_api_key() -> String {
+    format!("lk_{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+pub(crate) fn hash_api_key(api_key: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(api_key.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager() -> TenantManager {
+        TenantManager::new(Arc::new(MemoryTenantStore::new()))
+    }
+
+    #[tokio::test]
+    async fn validates_by_hash_and_respects_enabled_flag() {
+        let mgr = manager();
+        let tenant = mgr.create_tenant("acme".to_string(), "secret-key".to_string()).await.unwrap();
+        assert_ne!(tenant.api_keys[0].key_hash, "secret-key");
+
+        let found = mgr.validate_api_key("secret-key").await.unwrap().unwrap();
+        assert_eq!(found.tenant_id, tenant.tenant_id);
+        assert!(mgr.validate_api_key("wrong-key").await.unwrap().is_none());
+
+        mgr.set_enabled(&tenant.tenant_id, false).await.unwrap();
+        assert!(mgr.validate_api_key("secret-key").await.unwrap().is_none());
+        mgr.set_enabled(&tenant.tenant_id, true).await.unwrap();
+        assert!(mgr.validate_api_key("secret-key").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn rotation_keeps_old_key_during_grace_and_revoke_disables_it() {
+        let mgr = manager();
+        let tenant = mgr.create_tenant("acme".to_string(), "old-key".to_string()).await.unwrap();
+
+        let issued = mgr.rotate_api_key(&tenant.tenant_id, Duration::minutes(5)).await.unwrap();
+        assert!(mgr.validate_api_key("old-key").await.unwrap().is_some());
+        assert!(mgr.validate_api_key(&issued.api_key).await.unwrap().is_some());
+
+        let old_key_id = tenant.api_keys[0].key_id.clone();
+        mgr.revoke_api_key(&tenant.tenant_id, &old_key_id).await.unwrap();
+        assert!(mgr.validate_api_key("old-key").await.unwrap().is_none());
+        assert!(mgr.validate_api_key(&issued.api_key).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn expired_keys_are_rejected() {
+        let mgr = manager();
+        let tenant = mgr.create_tenant("acme".to_string(), "k".to_string()).await.unwrap();
+        let issued = mgr
+            .issue_api_key(&tenant.tenant_id, Some(Utc::now() - Duration::seconds(1)))
+            .await
+            .unwrap();
+        assert!(mgr.validate_api_key(&issued.api_key).await.unwrap().is_none());
+    }
+}
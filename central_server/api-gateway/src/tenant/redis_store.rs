@@ -0,0 +1,168 @@
+use super::store::{TenantStore, TenantStoreError, TenantUpdateFn};
+use super::Tenant;
+use crate::config::RedisConfig;
+use crate::redis_client::GatewayRedis;
+use async_trait::async_trait;
+
+/// update 乐观并发冲突的最大重试次数
+const MAX_UPDATE_ATTEMPTS: usize = 16;
+
+/// 比较并写入：租户 JSON 未被其他写入者修改时才写入，并同步 key 哈希索引
+/// KEYS[1]=tenant key，KEYS[2..]=key 哈希索引；ARGV[1]=读取时的 JSON，ARGV[2]=新 JSON，
+/// ARGV[3]=tenant_id，ARGV[4..]="1"（有效 key，写索引）/"0"（已撤销，删索引）
+const UPDATE_CAS_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) ~= ARGV[1] then
+  return 0
+end
+redis.call('SET', KEYS[1], ARGV[2])
+for i = 2, #KEYS do
+  if ARGV[i + 2] == '1' then
+    redis.call('SET', KEYS[i], ARGV[3])
+  else
+    redis.call('DEL', KEYS[i])
+  end
+end
+return 1
+"#;
+
+/// Redis 存储（多网关副本共享）
+///
+/// Keys（均带 `redis.key_prefix` 前缀）：
+/// - `tenant:{tenant_id}`：租户 JSON
+/// - `tenants`：租户 ID 集合
+/// - `tenant_key:{key_hash}`：API Key 哈希 -> tenant_id（仅未撤销的 key）
+pub struct RedisTenantStore {
+    redis: GatewayRedis,
+}
+
+impl RedisTenantStore {
+    pub async fn connect(cfg: &RedisConfig) -> Result<Self, TenantStoreError> {
+        Ok(Self::new(GatewayRedis::connect(cfg).await?))
+    }
+
+    pub fn new(redis: GatewayRedis) -> Self {
+        Self { redis }
+    }
+
+    fn tenant_key(&self, tenant_id: &str) -> String {
+        self.redis.key(&format!("tenant:{}", tenant_id))
+    }
+
+    fn index_key(&self, key_hash: &str) -> String {
+        self.redis.key(&format!("tenant_key:{}", key_hash))
+    }
+
+    fn set_key(&self) -> String {
+        self.redis.key("tenants")
+    }
+}
+
+#[async_trait]
+impl TenantStore for RedisTenantStore {
+    async fn get(&self, tenant_id: &str) -> Result<Option<Tenant>, TenantStoreError> {
+        let mut cmd = redis::cmd("GET");
+        cmd.arg(self.tenant_key(tenant_id));
+        let raw: Option<String> = self.redis.query(cmd).await?;
+        Ok(match raw {
+            Some(json) => Some(serde_json::from_str(&json)?),
+            None => None,
+        })
+    }
+
+    async fn list(&self) -> Result<Vec<Tenant>, TenantStoreError> {
+        let mut cmd = redis::cmd("SMEMBERS");
+        cmd.arg(self.set_key());
+        let ids: Vec<String> = self.redis.query(cmd).await?;
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut cmd = redis::cmd("MGET");
+        for id in &ids {
+            cmd.arg(self.tenant_key(id));
+        }
+        let raws: Vec<Option<String>> = self.redis.query(cmd).await?;
+        raws.into_iter()
+            .flatten()
+            .map(|json| serde_json::from_str(&json).map_err(TenantStoreError::from))
+            .collect()
+    }
+
+    async fn find_by_key_hash(&self, key_hash: &str) -> Result<Option<Tenant>, TenantStoreError> {
+        let mut cmd = redis::cmd("GET");
+        cmd.arg(self.index_key(key_hash));
+        let tenant_id: Option<String> = self.redis.query(cmd).await?;
+        match tenant_id {
+            Some(id) => self.get(&id).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn put(&self, tenant: &Tenant) -> Result<(), TenantStoreError> {
+        let json = serde_json::to_string(tenant)?;
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        pipe.cmd("SET").arg(self.tenant_key(&tenant.tenant_id)).arg(json).ignore();
+        pipe.cmd("SADD").arg(self.set_key()).arg(&tenant.tenant_id).ignore();
+        for key in &tenant.api_keys {
+            if key.revoked {
+                pipe.cmd("DEL").arg(self.index_key(&key.key_hash)).ignore();
+            } else {
+                pipe.cmd("SET")
+                    .arg(self.index_key(&key.key_hash))
+                    .arg(&tenant.tenant_id)
+                    .ignore();
+            }
+        }
+        self.redis.query_pipe::<()>(pipe).await?;
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        tenant_id: &str,
+        f: &mut TenantUpdateFn<'_>,
+    ) -> Result<Tenant, TenantStoreError> {
+        let tenant_key = self.tenant_key(tenant_id);
+        for _ in 0..MAX_UPDATE_ATTEMPTS {
+            let mut cmd = redis::cmd("GET");
+            cmd.arg(&tenant_key);
+            let raw: Option<String> = self.redis.query(cmd).await?;
+            let raw = raw.ok_or_else(|| TenantStoreError::TenantNotFound(tenant_id.to_string()))?;
+            let mut tenant: Tenant = serde_json::from_str(&raw)?;
+            f(&mut tenant);
+            let json = serde_json::to_string(&tenant)?;
+
+            let mut cmd = redis::cmd("EVAL");
+            cmd.arg(UPDATE_CAS_SCRIPT)
+                .arg(1 + tenant.api_keys.len())
+                .arg(&tenant_key);
+            for key in &tenant.api_keys {
+                cmd.arg(self.index_key(&key.key_hash));
+            }
+            cmd.arg(&raw).arg(&json).arg(&tenant.tenant_id);
+            for key in &tenant.api_keys {
+                cmd.arg(if key.revoked { "0" } else { "1" });
+            }
+            let written: i64 = self.redis.query(cmd).await?;
+            if written == 1 {
+                return Ok(tenant);
+            }
+        }
+        Err(TenantStoreError::Conflict(tenant_id.to_string()))
+    }
+
+    async fn delete(&self, tenant_id: &str) -> Result<(), TenantStoreError> {
+        let Some(tenant) = self.get(tenant_id).await? else {
+            return Ok(());
+        };
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for key in &tenant.api_keys {
+            pipe.cmd("DEL").arg(self.index_key(&key.key_hash)).ignore();
+        }
+        pipe.cmd("DEL").arg(self.tenant_key(tenant_id)).ignore();
+        pipe.cmd("SREM").arg(self.set_key()).arg(tenant_id).ignore();
+        self.redis.query_pipe::<()>(pipe).await?;
+        Ok(())
+    }
+}
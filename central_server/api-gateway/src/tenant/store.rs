@@ -0,0 +1,129 @@
+use super::Tenant;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+#[derive(Debug, Error)]
+pub enum TenantStoreError {
+    #[error("Tenant not found: {0}")]
+    TenantNotFound(String),
+    #[error("API key not found: {0}")]
+    ApiKeyNotFound(String),
+    #[error("Tenant store I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Tenant store serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("Tenant store Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+    #[error("Concurrent update conflict on tenant: {0}")]
+    Conflict(String),
+    #[error("Unknown tenant store backend: {0}")]
+    UnknownBackend(String),
+}
+
+impl TenantStoreError {
+    /// 管理 API 使用的 HTTP 状态码
+    pub fn status_code(&self) -> axum::http::StatusCode {
+        match self {
+            TenantStoreError::TenantNotFound(_) | TenantStoreError::ApiKeyNotFound(_) => {
+                axum::http::StatusCode::NOT_FOUND
+            }
+            TenantStoreError::Redis(_) | TenantStoreError::Io(_) => {
+                axum::http::StatusCode::SERVICE_UNAVAILABLE
+            }
+            TenantStoreError::Conflict(_) => axum::http::StatusCode::CONFLICT,
+            TenantStoreError::Serialization(_) | TenantStoreError::UnknownBackend(_) => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+}
+
+/// 租户原子更新的修改函数
+pub type TenantUpdateFn<'a> = dyn FnMut(&mut Tenant) + Send + 'a;
+
+/// 租户存储后端
+///
+/// `put` 写入整个租户记录（含全部 API Key 元数据），实现需同时维护 key 哈希索引。
+/// `update` 为原子的读取-修改-写回：并发更新不会互相覆盖（冲突重试时修改函数可能被调用多次）。
+#[async_trait]
+pub trait TenantStore: Send + Sync {
+    async fn get(&self, tenant_id: &str) -> Result<Option<Tenant>, TenantStoreError>;
+    async fn list(&self) -> Result<Vec<Tenant>, TenantStoreError>;
+    async fn find_by_key_hash(&self, key_hash: &str) -> Result<Option<Tenant>, TenantStoreError>;
+    async fn put(&self, tenant: &Tenant) -> Result<(), TenantStoreError>;
+    async fn update(
+        &self,
+        tenant_id: &str,
+        f: &mut TenantUpdateFn<'_>,
+    ) -> Result<Tenant, TenantStoreError>;
+    async fn delete(&self, tenant_id: &str) -> Result<(), TenantStoreError>;
+}
+
+/// 进程内存储（重启丢失，仅用于开发/测试）
+#[derive(Default)]
+pub struct MemoryTenantStore {
+    tenants: RwLock<HashMap<String, Tenant>>,
+}
+
+impl MemoryTenantStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(super) fn with_tenants(tenants: HashMap<String, Tenant>) -> Self {
+        Self {
+            tenants: RwLock::new(tenants),
+        }
+    }
+
+    pub(super) async fn snapshot(&self) -> Vec<Tenant> {
+        self.tenants.read().await.values().cloned().collect()
+    }
+}
+
+#[async_trait]
+impl TenantStore for MemoryTenantStore {
+    async fn get(&self, tenant_id: &str) -> Result<Option<Tenant>, TenantStoreError> {
+        Ok(self.tenants.read().await.get(tenant_id).cloned())
+    }
+
+    async fn list(&self) -> Result<Vec<Tenant>, TenantStoreError> {
+        Ok(self.snapshot().await)
+    }
+
+    async fn find_by_key_hash(&self, key_hash: &str) -> Result<Option<Tenant>, TenantStoreError> {
+        let tenants = self.tenants.read().await;
+        Ok(tenants
+            .values()
+            .find(|t| t.api_keys.iter().any(|k| k.key_hash == key_hash))
+            .cloned())
+    }
+
+    async fn put(&self, tenant: &Tenant) -> Result<(), TenantStoreError> {
+        self.tenants
+            .write()
+            .await
+            .insert(tenant.tenant_id.clone(), tenant.clone());
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        tenant_id: &str,
+        f: &mut TenantUpdateFn<'_>,
+    ) -> Result<Tenant, TenantStoreError> {
+        let mut tenants = self.tenants.write().await;
+        let tenant = tenants
+            .get_mut(tenant_id)
+            .ok_or_else(|| TenantStoreError::TenantNotFound(tenant_id.to_string()))?;
+        f(tenant);
+        Ok(tenant.clone())
+    }
+
+    async fn delete(&self, tenant_id: &str) -> Result<(), TenantStoreError> {
+        self.tenants.write().await.remove(tenant_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+// 按租户的请求计数（供管理 API 查看）
+//
+// rate_limit.backend = "redis" 时计数写入 Redis，多个网关副本共享；Redis 调用失败时退化为本进程计数。
+
+use crate::config::Config;
+use crate::redis_client::GatewayRedis;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TenantUsage {
+    /// 通过鉴权与限流的请求数
+    pub total_requests: u64,
+    /// 被限流拒绝的请求数
+    pub rejected_requests: u64,
+    pub last_request_at: Option<DateTime<Utc>>,
+}
+
+impl TenantUsage {
+    /// 从 Redis HASH（total_requests / rejected_requests / last_request_at_ms）解析
+    fn from_hash(fields: &HashMap<String, String>) -> Self {
+        let count = |name: &str| fields.get(name).and_then(|v| v.parse().ok()).unwrap_or(0);
+        Self {
+            total_requests: count("total_requests"),
+            rejected_requests: count("rejected_requests"),
+            last_request_at: fields
+                .get("last_request_at_ms")
+                .and_then(|v| v.parse().ok())
+                .and_then(DateTime::from_timestamp_millis),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct UsageCounters {
+    counters: DashMap<String, TenantUsage>,
+    redis: Option<GatewayRedis>,
+}
+
+impl UsageCounters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 按配置创建；限流使用 Redis 时计数也写入 Redis
+    pub async fn from_config(config: &Config) -> anyhow::Result<Self> {
+        let mut counters = Self::new();
+        if config.rate_limit.backend == "redis" {
+            counters.redis = Some(GatewayRedis::connect(&config.redis).await?);
+        }
+        Ok(counters)
+    }
+
+    pub async fn record_request(&self, tenant_id: &str) {
+        let now = Utc::now();
+        if let Some(redis) = self.redis.as_ref() {
+            let mut pipe = redis::pipe();
+            pipe.cmd("HINCRBY")
+                .arg(redis.key(&format!("usage:{}", tenant_id)))
+                .arg("total_requests")
+                .arg(1)
+                .ignore()
+                .cmd("HSET")
+                .arg(redis.key(&format!("usage:{}", tenant_id)))
+                .arg("last_request_at_ms")
+                .arg(now.timestamp_millis())
+                .ignore();
+            match redis.query_pipe::<()>(pipe).await {
+                Ok(()) => return,
+                Err(e) => tracing::warn!(tenant_id = %tenant_id, error = %e, "Redis 请求计数失败，记入本进程"),
+            }
+        }
+        let mut entry = self.counters.entry(tenant_id.to_string()).or_default();
+        entry.total_requests += 1;
+        entry.last_request_at = Some(now);
+    }
+
+    pub async fn record_rejected(&self, tenant_id: &str) {
+        if let Some(redis) = self.redis.as_ref() {
+            let mut cmd = redis::cmd("HINCRBY");
+            cmd.arg(redis.key(&format!("usage:{}", tenant_id)))
+                .arg("rejected_requests")
+                .arg(1);
+            match redis.query::<i64>(cmd).await {
+                Ok(_) => return,
+                Err(e) => tracing::warn!(tenant_id = %tenant_id, error = %e, "Redis 请求计数失败，记入本进程"),
+            }
+        }
+        self.counters
+            .entry(tenant_id.to_string())
+            .or_default()
+            .rejected_requests += 1;
+    }
+
+    /// 读取计数（Redis 计数与本进程退化期间的计数合并）
+    pub async fn get(&self, tenant_id: &str) -> TenantUsage {
+        let local = self
+            .counters
+            .get(tenant_id)
+            .map(|u| u.clone())
+            .unwrap_or_default();
+        let Some(redis) = self.redis.as_ref() else {
+            return local;
+        };
+        let mut cmd = redis::cmd("HGETALL");
+        cmd.arg(redis.key(&format!("usage:{}", tenant_id)));
+        match redis.query::<HashMap<String, String>>(cmd).await {
+            Ok(fields) => {
+                let shared = TenantUsage::from_hash(&fields);
+                TenantUsage {
+                    total_requests: shared.total_requests + local.total_requests,
+                    rejected_requests: shared.rejected_requests + local.rejected_requests,
+                    last_request_at: shared.last_request_at.max(local.last_request_at),
+                }
+            }
+            Err(e) => {
+                tracing::warn!(tenant_id = %tenant_id, error = %e, "Redis 读取请求计数失败，仅返回本进程计数");
+                local
+            }
+        }
+    }
+}
@@ -0,0 +1,591 @@
+// 会话事件 Webhook
+//
+// 租户通过 /v1/webhooks 订阅会话事件（订阅随租户记录一起保存）。事件来自 SchedulerClient 的
+// 事件流，载荷中的 `data` 即调度服务器的 SessionMessage 帧（session_init_ack / translation_result /
+// missing_result / error / session_close）。投递使用 HMAC-SHA256 签名，失败按指数退避重试，
+// 用尽重试次数后进入该租户的死信列表（进程内，有上限），可通过 API 查看并重放。
+
+mod signing;
+mod url_guard;
+
+pub use signing::SIGNATURE_HEADER;
+
+use crate::config::WebhookConfig;
+use crate::scheduler_client::SessionEvent;
+use crate::tenant::{TenantManager, TenantStoreError};
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::Semaphore;
+use tracing::{debug, info, warn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventType {
+    SessionStarted,
+    SessionClosed,
+    TranslationResult,
+    MissingResult,
+    JobFailed,
+    JobTimeout,
+}
+
+impl WebhookEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEventType::SessionStarted => "session_started",
+            WebhookEventType::SessionClosed => "session_closed",
+            WebhookEventType::TranslationResult => "translation_result",
+            WebhookEventType::MissingResult => "missing_result",
+            WebhookEventType::JobFailed => "job_failed",
+            WebhookEventType::JobTimeout => "job_timeout",
+        }
+    }
+
+    /// 按 SessionMessage 帧类型归类；不对外投递的帧返回 None
+    pub fn from_frame(frame: &serde_json::Value) -> Option<Self> {
+        match frame["type"].as_str()? {
+            "session_init_ack" => Some(WebhookEventType::SessionStarted),
+            "session_close" => Some(WebhookEventType::SessionClosed),
+            "translation_result" => Some(WebhookEventType::TranslationResult),
+            "missing_result" => Some(WebhookEventType::MissingResult),
+            "error" if frame["code"].as_str().is_some_and(|c| c.ends_with("_TIMEOUT")) => {
+                Some(WebhookEventType::JobTimeout)
+            }
+            "error" => Some(WebhookEventType::JobFailed),
+            _ => None,
+        }
+    }
+}
+
+/// 租户的一个 Webhook 订阅
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSubscription {
+    pub webhook_id: String,
+    pub url: String,
+    /// 签名密钥（只在创建时返回给租户）
+    pub secret: String,
+    /// 订阅的事件；为空表示全部
+    #[serde(default)]
+    pub events: Vec<WebhookEventType>,
+    /// translation_result 是否携带 tts_audio（默认不带，避免载荷过大）
+    #[serde(default)]
+    pub include_audio: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    fn wants(&self, event_type: WebhookEventType) -> bool {
+        self.events.is_empty() || self.events.contains(&event_type)
+    }
+
+    /// 对外展示（不含 secret）
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "webhook_id": self.webhook_id,
+            "url": self.url,
+            "events": self.events,
+            "include_audio": self.include_audio,
+            "created_at": self.created_at,
+        })
+    }
+}
+
+/// 创建订阅的参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    #[serde(default)]
+    pub events: Vec<WebhookEventType>,
+    #[serde(default)]
+    pub include_audio: bool,
+}
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error(transparent)]
+    Tenant(#[from] TenantStoreError),
+    #[error("Webhook URL must be an absolute http(s) URL: {0}")]
+    InvalidUrl(String),
+    #[error("Webhook URL is not allowed ({reason}): {url}")]
+    ForbiddenUrl { url: String, reason: String },
+    #[error("Tenant already has {max} webhook subscriptions")]
+    TooManySubscriptions { max: usize },
+    #[error("Webhook not found: {0}")]
+    WebhookNotFound(String),
+    #[error("Dead letter not found: {0}")]
+    DeadLetterNotFound(String),
+}
+
+impl WebhookError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            WebhookError::Tenant(e) => e.status_code(),
+            WebhookError::InvalidUrl(_) | WebhookError::ForbiddenUrl { .. } => StatusCode::BAD_REQUEST,
+            WebhookError::TooManySubscriptions { .. } => StatusCode::CONFLICT,
+            WebhookError::WebhookNotFound(_) | WebhookError::DeadLetterNotFound(_) => {
+                StatusCode::NOT_FOUND
+            }
+        }
+    }
+}
+
+/// 重试用尽的投递
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    pub event_id: String,
+    pub webhook_id: String,
+    pub event_type: WebhookEventType,
+    pub url: String,
+    /// 原始载荷（重放时原样发送，重新签名）
+    pub body: String,
+    pub attempts: u32,
+    pub last_error: String,
+    pub failed_at: DateTime<Utc>,
+}
+
+impl DeadLetter {
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "event_id": self.event_id,
+            "webhook_id": self.webhook_id,
+            "event_type": self.event_type,
+            "url": self.url,
+            "attempts": self.attempts,
+            "last_error": self.last_error,
+            "failed_at": self.failed_at,
+            "payload": serde_json::from_str::<serde_json::Value>(&self.body).unwrap_or_default(),
+        })
+    }
+}
+
+/// 一次投递（含重试）
+struct Delivery {
+    tenant_id: String,
+    webhook_id: String,
+    url: String,
+    secret: String,
+    event_id: String,
+    event_type: WebhookEventType,
+    body: String,
+}
+
+pub struct WebhookManager {
+    config: WebhookConfig,
+    tenant_manager: Arc<TenantManager>,
+    http: reqwest::Client,
+    permits: Arc<Semaphore>,
+    dead_letters: DashMap<String, VecDeque<DeadLetter>>,
+}
+
+impl WebhookManager {
+    pub fn new(config: WebhookConfig, tenant_manager: Arc<TenantManager>) -> Arc<Self> {
+        let permits = Arc::new(Semaphore::new(config.max_concurrent_deliveries.max(1)));
+        Arc::new(Self {
+            config,
+            tenant_manager,
+            http: http_client_builder().build().unwrap_or_default(),
+            permits,
+            dead_letters: DashMap::new(),
+        })
+    }
+
+    /// 消费 SchedulerClient 的事件流
+    pub fn start(self: &Arc<Self>, mut events: broadcast::Receiver<SessionEvent>) {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => manager.handle_event(event).await,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(skipped = skipped, "Webhook 事件消费落后，部分事件未投递");
+                    }
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        });
+    }
+
+    async fn handle_event(self: &Arc<Self>, event: SessionEvent) {
+        let Some(event_type) = WebhookEventType::from_frame(&event.frame) else {
+            return;
+        };
+        let tenant = match self.tenant_manager.get_tenant(&event.tenant_id).await {
+            Ok(Some(t)) if t.enabled && !t.webhooks.is_empty() => t,
+            Ok(_) => return,
+            Err(e) => {
+                warn!(tenant_id = %event.tenant_id, error = %e, "读取租户 Webhook 订阅失败");
+                return;
+            }
+        };
+
+        let event_id = format!("evt_{}", uuid::Uuid::new_v4().simple());
+        let created_at = Utc::now();
+        for sub in tenant.webhooks.iter().filter(|s| s.wants(event_type)) {
+            let mut data = event.frame.clone();
+            if event_type == WebhookEventType::TranslationResult && !sub.include_audio {
+                data["tts_audio"] = json!("");
+            }
+            let body = json!({
+                "event_id": event_id,
+                "event_type": event_type,
+                "tenant_id": event.tenant_id,
+                "session_id": event.session_id,
+                "created_at": created_at,
+                "data": data,
+            })
+            .to_string();
+            self.spawn_delivery(Delivery {
+                tenant_id: event.tenant_id.clone(),
+                webhook_id: sub.webhook_id.clone(),
+                url: sub.url.clone(),
+                secret: sub.secret.clone(),
+                event_id: event_id.clone(),
+                event_type,
+                body,
+            });
+        }
+    }
+
+    fn spawn_delivery(self: &Arc<Self>, delivery: Delivery) {
+        let manager = self.clone();
+        tokio::spawn(async move { manager.deliver(delivery).await });
+    }
+
+    async fn deliver(&self, delivery: Delivery) {
+        let max_attempts = self.config.max_attempts.max(1);
+        let mut last_error = String::new();
+        for attempt in 1..=max_attempts {
+            if attempt > 1 {
+                tokio::time::sleep(backoff_delay(&self.config, attempt - 1)).await;
+            }
+            let result = {
+                let _permit = self.permits.acquire().await;
+                self.post(&delivery, attempt).await
+            };
+            match result {
+                Ok(()) => {
+                    debug!(
+                        tenant_id = %delivery.tenant_id,
+                        webhook_id = %delivery.webhook_id,
+                        event_id = %delivery.event_id,
+                        attempt = attempt,
+                        "Webhook 投递成功"
+                    );
+                    return;
+                }
+                Err(e) => {
+                    debug!(
+                        webhook_id = %delivery.webhook_id,
+                        event_id = %delivery.event_id,
+                        attempt = attempt,
+                        error = %e,
+                        "Webhook 投递失败"
+                    );
+                    last_error = e;
+                }
+            }
+        }
+
+        warn!(
+            tenant_id = %delivery.tenant_id,
+            webhook_id = %delivery.webhook_id,
+            event_id = %delivery.event_id,
+            error = %last_error,
+            "Webhook 重试次数用尽，已进入死信列表"
+        );
+        self.push_dead_letter(
+            &delivery.tenant_id,
+            DeadLetter {
+                event_id: delivery.event_id,
+                webhook_id: delivery.webhook_id,
+                event_type: delivery.event_type,
+                url: delivery.url,
+                body: delivery.body,
+                attempts: max_attempts,
+                last_error,
+                failed_at: Utc::now(),
+            },
+        );
+    }
+
+    async fn post(&self, delivery: &Delivery, attempt: u32) -> Result<(), String> {
+        // 每次投递前重新校验：订阅后 DNS 记录可能被改为内部地址
+        let target = url_guard::check_url(&self.config, &delivery.url)
+            .await
+            .map_err(|reason| format!("target rejected: {}", reason))?;
+        // 主机名目标只连接刚校验过的地址，不再让 reqwest 重新解析
+        let http = if target.addrs.is_empty() {
+            self.http.clone()
+        } else {
+            http_client_builder()
+                .resolve_to_addrs(&target.host, &target.addrs)
+                .build()
+                .map_err(|e| e.to_string())?
+        };
+        let timestamp = Utc::now().timestamp();
+        let response = http
+            .post(&delivery.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(
+                SIGNATURE_HEADER,
+                signing::signature_header(&delivery.secret, timestamp, &delivery.body),
+            )
+            .header("X-Lingua-Event", delivery.event_type.as_str())
+            .header("X-Lingua-Event-Id", &delivery.event_id)
+            .header("X-Lingua-Delivery-Attempt", attempt.to_string())
+            .body(delivery.body.clone())
+            .timeout(Duration::from_millis(self.config.request_timeout_ms))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("HTTP {}", response.status().as_u16()))
+        }
+    }
+
+    fn push_dead_letter(&self, tenant_id: &str, letter: DeadLetter) {
+        let capacity = self.config.dead_letter_capacity.max(1);
+        let mut list = self.dead_letters.entry(tenant_id.to_string()).or_default();
+        while list.len() >= capacity {
+            list.pop_front();
+        }
+        list.push_back(letter);
+    }
+
+    /// 租户的死信（按失败时间先后）
+    pub fn dead_letters(&self, tenant_id: &str) -> Vec<DeadLetter> {
+        self.dead_letters
+            .get(tenant_id)
+            .map(|l| l.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// 重放一条死信：按订阅当前的 URL / 密钥重新投递（重新计数重试），再次失败会重新进入死信列表
+    pub async fn replay(self: &Arc<Self>, tenant_id: &str, event_id: &str) -> Result<(), WebhookError> {
+        let letter = self
+            .dead_letters(tenant_id)
+            .into_iter()
+            .find(|l| l.event_id == event_id)
+            .ok_or_else(|| WebhookError::DeadLetterNotFound(event_id.to_string()))?;
+        let tenant = self
+            .tenant_manager
+            .get_tenant(tenant_id)
+            .await?
+            .ok_or_else(|| TenantStoreError::TenantNotFound(tenant_id.to_string()))?;
+        let sub = tenant
+            .webhooks
+            .iter()
+            .find(|s| s.webhook_id == letter.webhook_id)
+            .ok_or_else(|| WebhookError::WebhookNotFound(letter.webhook_id.clone()))?;
+
+        if let Some(mut list) = self.dead_letters.get_mut(tenant_id) {
+            list.retain(|l| !(l.event_id == letter.event_id && l.webhook_id == letter.webhook_id));
+        }
+        info!(tenant_id = %tenant_id, webhook_id = %sub.webhook_id, event_id = %event_id, "重放 Webhook 死信");
+        self.spawn_delivery(Delivery {
+            tenant_id: tenant_id.to_string(),
+            webhook_id: sub.webhook_id.clone(),
+            url: sub.url.clone(),
+            secret: sub.secret.clone(),
+            event_id: letter.event_id,
+            event_type: letter.event_type,
+            body: letter.body,
+        });
+        Ok(())
+    }
+
+    /// 创建订阅，返回含 secret 的完整记录
+    pub async fn create_subscription(
+        &self,
+        tenant_id: &str,
+        request: CreateWebhookRequest,
+    ) -> Result<WebhookSubscription, WebhookError> {
+        validate_url(&self.config, &request.url).await?;
+        let subscription = WebhookSubscription {
+            webhook_id: format!("wh-{}", uuid::Uuid::new_v4().simple()),
+            url: request.url,
+            secret: signing::generate_secret(),
+            events: request.events,
+            include_audio: request.include_audio,
+            created_at: Utc::now(),
+        };
+        let max = self.config.max_subscriptions_per_tenant;
+        let mut too_many = false;
+        let created = subscription.clone();
+        self.tenant_manager
+            .update(tenant_id, |t| {
+                too_many = max > 0 && t.webhooks.len() >= max;
+                if !too_many {
+                    t.webhooks.push(created.clone());
+                }
+            })
+            .await?;
+        if too_many {
+            return Err(WebhookError::TooManySubscriptions { max });
+        }
+        Ok(subscription)
+    }
+
+    pub async fn delete_subscription(&self, tenant_id: &str, webhook_id: &str) -> Result<(), WebhookError> {
+        let mut found = false;
+        self.tenant_manager
+            .update(tenant_id, |t| {
+                let before = t.webhooks.len();
+                t.webhooks.retain(|s| s.webhook_id != webhook_id);
+                found = t.webhooks.len() != before;
+            })
+            .await?;
+        if !found {
+            return Err(WebhookError::WebhookNotFound(webhook_id.to_string()));
+        }
+        if let Some(mut list) = self.dead_letters.get_mut(tenant_id) {
+            list.retain(|l| l.webhook_id != webhook_id);
+        }
+        Ok(())
+    }
+}
+
+/// 不跟随重定向：重定向目标可能是内部地址，绕过投递前的地址校验
+fn http_client_builder() -> reqwest::ClientBuilder {
+    reqwest::Client::builder().redirect(reqwest::redirect::Policy::none())
+}
+
+async fn validate_url(config: &WebhookConfig, url: &str) -> Result<(), WebhookError> {
+    match url_guard::check_url(config, url).await {
+        Ok(_) => Ok(()),
+        Err(url_guard::UrlRejection::Malformed) => Err(WebhookError::InvalidUrl(url.to_string())),
+        Err(reason) => Err(WebhookError::ForbiddenUrl {
+            url: url.to_string(),
+            reason: reason.to_string(),
+        }),
+    }
+}
+
+/// 第 `failures` 次失败后的等待时间：initial * 2^(failures-1)，不超过 max
+fn backoff_delay(config: &WebhookConfig, failures: u32) -> Duration {
+    let factor = 1u64 << failures.saturating_sub(1).min(20);
+    Duration::from_millis(
+        config
+            .backoff_initial_ms
+            .saturating_mul(factor)
+            .min(config.backoff_max_ms),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tenant::MemoryTenantStore;
+
+    #[test]
+    fn frames_map_to_event_types() {
+        let of = |v: serde_json::Value| WebhookEventType::from_frame(&v);
+        assert_eq!(of(json!({"type": "session_init_ack"})), Some(WebhookEventType::SessionStarted));
+        assert_eq!(of(json!({"type": "translation_result"})), Some(WebhookEventType::TranslationResult));
+        assert_eq!(
+            of(json!({"type": "error", "code": "JOB_TIMEOUT"})),
+            Some(WebhookEventType::JobTimeout)
+        );
+        assert_eq!(
+            of(json!({"type": "error", "code": "NO_AVAILABLE_NODE"})),
+            Some(WebhookEventType::JobFailed)
+        );
+        assert_eq!(of(json!({"type": "asr_partial"})), None);
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let config = WebhookConfig {
+            backoff_initial_ms: 1000,
+            backoff_max_ms: 5000,
+            ..WebhookConfig::default()
+        };
+        let delays: Vec<u64> = (1..=5).map(|n| backoff_delay(&config, n).as_millis() as u64).collect();
+        assert_eq!(delays, vec![1000, 2000, 4000, 5000, 5000]);
+    }
+
+    #[tokio::test]
+    async fn failed_delivery_goes_to_dead_letters_and_can_be_replayed() {
+        let tenants = Arc::new(TenantManager::new(Arc::new(MemoryTenantStore::new())));
+        let tenant = tenants.create_tenant("t".to_string(), "key".to_string()).await.unwrap();
+        let manager = WebhookManager::new(
+            WebhookConfig {
+                max_attempts: 2,
+                backoff_initial_ms: 1,
+                request_timeout_ms: 500,
+                allowed_hosts: vec!["127.0.0.1".to_string()],
+                ..WebhookConfig::default()
+            },
+            tenants.clone(),
+        );
+
+        assert!(manager
+            .create_subscription(
+                &tenant.tenant_id,
+                CreateWebhookRequest {
+                    url: "ftp://example.com".to_string(),
+                    events: vec![],
+                    include_audio: false,
+                },
+            )
+            .await
+            .is_err());
+        // 端口 9 (discard) 上没有 HTTP 服务：连接失败
+        let sub = manager
+            .create_subscription(
+                &tenant.tenant_id,
+                CreateWebhookRequest {
+                    url: "http://127.0.0.1:9/hook".to_string(),
+                    events: vec![WebhookEventType::MissingResult],
+                    include_audio: false,
+                },
+            )
+            .await
+            .unwrap();
+
+        manager
+            .handle_event(SessionEvent {
+                tenant_id: tenant.tenant_id.clone(),
+                session_id: "gw-1".to_string(),
+                frame: json!({"type": "asr_partial"}),
+            })
+            .await;
+        manager
+            .handle_event(SessionEvent {
+                tenant_id: tenant.tenant_id.clone(),
+                session_id: "gw-1".to_string(),
+                frame: json!({"type": "missing_result", "session_id": "gw-1", "utterance_index": 3}),
+            })
+            .await;
+
+        let mut letters = Vec::new();
+        for _ in 0..100 {
+            letters = manager.dead_letters(&tenant.tenant_id);
+            if !letters.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(letters.len(), 1);
+        let letter = &letters[0];
+        assert_eq!(letter.webhook_id, sub.webhook_id);
+        assert_eq!(letter.attempts, 2);
+        let payload = letter.to_json();
+        assert_eq!(payload["payload"]["event_type"], "missing_result");
+        assert_eq!(payload["payload"]["data"]["utterance_index"], 3);
+
+        manager.replay(&tenant.tenant_id, &letter.event_id).await.unwrap();
+        assert!(manager.dead_letters(&tenant.tenant_id).is_empty());
+        assert!(manager.replay(&tenant.tenant_id, "evt_missing").await.is_err());
+    }
+}
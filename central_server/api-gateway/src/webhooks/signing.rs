@@ -0,0 +1,59 @@
+// Webhook 签名：HMAC-SHA256(secret, "<timestamp>.<body>")
+//
+// 请求头 `X-Lingua-Signature: t=<unix 秒>,v1=<hex>`。接收方用同一 secret 重新计算并比较，
+// 同时检查 t 与当前时间的偏差以防重放。
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+pub const SIGNATURE_HEADER: &str = "X-Lingua-Signature";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 计算签名（hex）
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+/// 签名请求头的值
+pub fn signature_header(secret: &str, timestamp: i64, body: &str) -> String {
+    format!("t={},v1={}", timestamp, sign(secret, timestamp, body))
+}
+
+/// 生成订阅的签名密钥
+pub fn generate_secret() -> String {
+    format!("whsec_{}", uuid::Uuid::new_v4().simple())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_matches_rfc4231_vector() {
+        let mut mac = HmacSha256::new_from_slice(b"Jefe").unwrap();
+        mac.update(b"what do ya want for nothing?");
+        assert_eq!(
+            format!("{:x}", mac.finalize().into_bytes()),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn signature_covers_timestamp_and_body() {
+        let mut mac = HmacSha256::new_from_slice(b"whsec_test").unwrap();
+        mac.update(b"1700000000.{\"a\":1}");
+        let expected = format!("{:x}", mac.finalize().into_bytes());
+
+        assert_eq!(sign("whsec_test", 1_700_000_000, "{\"a\":1}"), expected);
+        assert_eq!(
+            signature_header("whsec_test", 1_700_000_000, "{\"a\":1}"),
+            format!("t=1700000000,v1={}", expected)
+        );
+        assert_ne!(sign("whsec_test", 1_700_000_001, "{\"a\":1}"), expected);
+    }
+}
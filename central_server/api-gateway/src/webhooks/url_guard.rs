@@ -0,0 +1,160 @@
+// Webhook 目标地址校验（防 SSRF）
+//
+// 订阅创建时与每次投递前都会校验：解析主机名，拒绝回环、私有网段、链路本地等内部地址；
+// require_https 时只允许 https。allowed_hosts 中的主机跳过上述限制（内网接收端、本地联调）。
+// 投递时只连接校验过的地址（ResolvedTarget.addrs），避免 DNS 重绑定在校验与连接之间换成内部地址。
+
+use crate::config::WebhookConfig;
+use reqwest::Url;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+/// 校验失败原因（用于错误信息）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlRejection {
+    /// 不是绝对的 http(s) URL
+    Malformed,
+    /// 要求 https
+    InsecureScheme,
+    /// 主机名无法解析
+    Unresolvable,
+    /// 解析到内部地址
+    InternalAddress(IpAddr),
+}
+
+impl std::fmt::Display for UrlRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UrlRejection::Malformed => write!(f, "must be an absolute http(s) URL"),
+            UrlRejection::InsecureScheme => write!(f, "must use https"),
+            UrlRejection::Unresolvable => write!(f, "host cannot be resolved"),
+            UrlRejection::InternalAddress(ip) => write!(f, "resolves to internal address {}", ip),
+        }
+    }
+}
+
+/// 校验通过的目标：主机名及其解析出的地址（IP 字面量或白名单主机时为空，无需固定）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedTarget {
+    pub host: String,
+    pub addrs: Vec<SocketAddr>,
+}
+
+pub async fn check_url(config: &WebhookConfig, url: &str) -> Result<ResolvedTarget, UrlRejection> {
+    let parsed = Url::parse(url).map_err(|_| UrlRejection::Malformed)?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(UrlRejection::Malformed);
+    }
+    let host_str = parsed.host_str().ok_or(UrlRejection::Malformed)?;
+    if config
+        .allowed_hosts
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(host_str))
+    {
+        return Ok(ResolvedTarget {
+            host: host_str.to_string(),
+            addrs: Vec::new(),
+        });
+    }
+    if config.require_https && parsed.scheme() != "https" {
+        return Err(UrlRejection::InsecureScheme);
+    }
+
+    // IPv6 字面量的 host_str 带方括号
+    let literal = host_str.trim_start_matches('[').trim_end_matches(']');
+    let port = parsed.port_or_known_default().unwrap_or(443);
+    let (addrs, pinned): (Vec<SocketAddr>, bool) = match literal.parse::<IpAddr>() {
+        Ok(ip) => (vec![SocketAddr::new(ip, port)], false),
+        Err(_) => (
+            tokio::net::lookup_host((host_str, port))
+                .await
+                .map_err(|_| UrlRejection::Unresolvable)?
+                .collect(),
+            true,
+        ),
+    };
+    if addrs.is_empty() {
+        return Err(UrlRejection::Unresolvable);
+    }
+    // 任一解析结果为内部地址即拒绝（避免多 A 记录混入内网地址）
+    if let Some(addr) = addrs.iter().find(|addr| !is_public(&addr.ip())) {
+        return Err(UrlRejection::InternalAddress(addr.ip()));
+    }
+    Ok(ResolvedTarget {
+        host: host_str.to_string(),
+        addrs: if pinned { addrs } else { Vec::new() },
+    })
+}
+
+fn is_public(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => is_public_v4(v4),
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_public_v4(&v4),
+            None => is_public_v6(v6),
+        },
+    }
+}
+
+fn is_public_v4(ip: &Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // 0.0.0.0/8、100.64.0.0/10（CGNAT）、198.18.0.0/15（基准测试）
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 198 && (b == 18 || b == 19)))
+}
+
+fn is_public_v6(ip: &Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // fc00::/7（唯一本地）、fe80::/10（链路本地）
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> WebhookConfig {
+        WebhookConfig {
+            require_https: true,
+            allowed_hosts: vec!["hooks.internal".to_string()],
+            ..WebhookConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_internal_and_insecure_targets() {
+        let config = config();
+        for url in [
+            "https://127.0.0.1/hook",
+            "https://10.0.0.8/hook",
+            "https://192.168.1.1/hook",
+            "https://169.254.169.254/latest/meta-data",
+            "https://[::1]/hook",
+            "https://[fd00::1]/hook",
+            "https://[::ffff:127.0.0.1]/hook",
+            "https://localhost/hook",
+        ] {
+            assert!(
+                matches!(check_url(&config, url).await, Err(UrlRejection::InternalAddress(_))),
+                "{} should be rejected",
+                url
+            );
+        }
+        assert_eq!(check_url(&config, "http://93.184.216.34/hook").await, Err(UrlRejection::InsecureScheme));
+        assert_eq!(check_url(&config, "ftp://93.184.216.34/hook").await, Err(UrlRejection::Malformed));
+        assert_eq!(check_url(&config, "https://93.184.216.34/hook").await.unwrap().addrs, Vec::new());
+        // 白名单主机跳过 https 与内网限制
+        assert_eq!(check_url(&config, "http://hooks.internal:8080/hook").await.unwrap().host, "hooks.internal");
+    }
+}
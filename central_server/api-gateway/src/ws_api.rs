@@ -1,15 +1,38 @@
 use axum::extract::ws::{Message, WebSocket};
+use base64::{engine::general_purpose, Engine as _};
 use futures_util::{SinkExt, StreamExt};
 use serde_json::json;
+use std::sync::Arc;
+use tokio::sync::mpsc;
 use crate::AppState;
+use crate::audio::estimate_audio_seconds;
+use crate::scheduler_client::UtteranceRequest;
+use crate::subtitles::SubtitleTrack;
+use crate::tenant::Tenant;
 
 pub async fn handle_public_websocket(
     socket: WebSocket,
-    tenant_id: String,
+    tenant: Tenant,
     state: AppState,
 ) {
     let (mut sender, mut receiver) = socket.split();
+
+    // 发送统一走 channel，便于 asr_partial 转发任务并发写入
+    let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
+    let send_task = tokio::spawn(async move {
+        while let Some(msg) = rx.recv().await {
+            if sender.send(msg).await.is_err() {
+                break;
+            }
+        }
+    });
+    let send_json = |value: serde_json::Value| {
+        let _ = tx.send(Message::Text(value.to_string()));
+    };
+
     let mut session_id: Option<String> = None;
+    let mut partial_task: Option<tokio::task::JoinHandle<()>> = None;
+    let mut subtitle_track: Option<Arc<SubtitleTrack>> = None;
     let mut utterance_index = 0u64;
     let mut src_lang = "zh".to_string();
     let mut tgt_lang = "en".to_string();
@@ -20,9 +43,7 @@ pub async fn handle_public_websocket(
                 let message: serde_json::Value = match serde_json::from_str(&text) {
                     Ok(m) => m,
                     Err(_) => {
-                        let _ = sender.send(Message::Text(
-                            json!({"type": "error", "message": "Invalid JSON"}).to_string()
-                        )).await;
+                        send_json(json!({"type": "error", "message": "Invalid JSON"}));
                         continue;
                     }
                 };
@@ -38,9 +59,19 @@ pub async fn handle_public_websocket(
                             .unwrap_or("en")
                             .to_string();
 
+                        // 重复 start：关闭旧会话
+                        if let Some(old) = session_id.take() {
+                            state.scheduler_client.close_session(&old, "restarted");
+                            state.subtitles.finish(&old);
+                        }
+                        if let Some(task) = partial_task.take() {
+                            task.abort();
+                        }
+
                         match state.scheduler_client
                             .create_session(
-                                tenant_id.clone(),
+                                tenant.tenant_id.clone(),
+                                tenant.max_concurrent_sessions,
                                 src_lang.clone(),
                                 tgt_lang.clone(),
                                 None,
@@ -49,15 +80,37 @@ pub async fn handle_public_websocket(
                             .await
                         {
                             Ok(sess_id) => {
+                                partial_task = state
+                                    .scheduler_client
+                                    .subscribe_partials(&sess_id)
+                                    .ok()
+                                    .map(|mut partials| {
+                                        let tx = tx.clone();
+                                        tokio::spawn(async move {
+                                            while let Ok(partial) = partials.recv().await {
+                                                let msg = json!({
+                                                    "type": "partial",
+                                                    "utterance_index": partial.utterance_index,
+                                                    "text": partial.text,
+                                                    "is_final": partial.is_final,
+                                                });
+                                                if tx.send(Message::Text(msg.to_string())).is_err() {
+                                                    break;
+                                                }
+                                            }
+                                        })
+                                    });
+                                utterance_index = 0;
+                                subtitle_track = Some(state.subtitles.open(&sess_id, &tenant.tenant_id));
+                                send_json(json!({"type": "started", "session_id": sess_id}));
                                 session_id = Some(sess_id);
-                                let _ = sender.send(Message::Text(
-                                    json!({"type": "started", "session_id": session_id}).to_string()
-                                )).await;
                             }
                             Err(e) => {
-                                let _ = sender.send(Message::Text(
-                                    json!({"type": "error", "message": format!("Failed to create session: {}", e)}).to_string()
-                                )).await;
+                                send_json(json!({
+                                    "type": "error",
+                                    "code": e.error_code(),
+                                    "message": format!("Failed to create session: {}", e),
+                                }));
                             }
                         }
                     }
@@ -66,63 +119,79 @@ pub async fn handle_public_websocket(
                             let audio_base64 = match message["chunk"].as_str() {
                                 Some(chunk) => chunk,
                                 None => {
-                                    let _ = sender.send(Message::Text(
-                                        json!({"type": "error", "message": "Missing audio chunk"}).to_string()
-                                    )).await;
+                                    send_json(json!({"type": "error", "message": "Missing audio chunk"}));
                                     continue;
                                 }
                             };
 
-                            let audio_data = match base64::decode(audio_base64) {
+                            let audio_data = match general_purpose::STANDARD.decode(audio_base64) {
                                 Ok(data) => data,
                                 Err(_) => {
-                                    let _ = sender.send(Message::Text(
-                                        json!({"type": "error", "message": "Invalid base64 audio"}).to_string()
-                                    )).await;
+                                    send_json(json!({"type": "error", "message": "Invalid base64 audio"}));
                                     continue;
                                 }
                             };
 
+                            // 音频时长限流：超限丢弃该段，不推进 utterance 编号
+                            let audio_seconds = estimate_audio_seconds("pcm16", audio_data.len(), 16000);
+                            if let Some(decision) = state.rate_limiter.check_audio(&tenant, audio_seconds).await {
+                                if !decision.allowed {
+                                    state.usage.record_rejected(&tenant.tenant_id).await;
+                                    send_json(json!({
+                                        "type": "error",
+                                        "code": "RATE_LIMITED",
+                                        "message": "Audio rate limit exceeded",
+                                        "retry_after_secs": decision.retry_after_secs,
+                                    }));
+                                    continue;
+                                }
+                            }
+
+                            if let Some(track) = subtitle_track.as_ref() {
+                                track.record_audio(utterance_index, (audio_seconds * 1000.0).round() as u64);
+                            }
+
                             match state.scheduler_client
                                 .send_utterance(
-                                    sess_id.clone(),
-                                    utterance_index,
-                                    audio_data,
-                                    src_lang.clone(),
-                                    tgt_lang.clone(),
-                                    None,
-                                    None,
-                                    "pcm16".to_string(),
-                                    16000,
+                                    sess_id,
+                                    UtteranceRequest {
+                                        utterance_index,
+                                        audio_data,
+                                        audio_format: "pcm16".to_string(),
+                                        sample_rate: 16000,
+                                        src_lang: src_lang.clone(),
+                                        tgt_lang: tgt_lang.clone(),
+                                        dialect: None,
+                                        features: None,
+                                    },
                                 )
                                 .await
                             {
                                 Ok(result) => {
-                                    let _ = sender.send(Message::Text(
-                                        json!({
-                                            "type": "final",
-                                            "text": result.text_translated,
-                                            "audio": result.tts_audio,
-                                        }).to_string()
-                                    )).await;
-                                    utterance_index += 1;
+                                    if let Some(track) = subtitle_track.as_ref() {
+                                        track.record_result(utterance_index, &result.text_asr, &result.text_translated);
+                                    }
+                                    send_json(json!({
+                                        "type": "final",
+                                        "text": result.text_translated,
+                                        "audio": result.tts_audio,
+                                    }));
                                 }
                                 Err(e) => {
-                                    let _ = sender.send(Message::Text(
-                                        json!({"type": "error", "message": format!("Translation failed: {}", e)}).to_string()
-                                    )).await;
+                                    if let Some(track) = subtitle_track.as_ref() {
+                                        track.record_failure(utterance_index);
+                                    }
+                                    send_json(json!({"type": "error", "message": format!("Translation failed: {}", e)}));
                                 }
                             }
+                            // 失败也推进编号，避免与调度端已接收的 utterance 冲突
+                            utterance_index += 1;
                         } else {
-                            let _ = sender.send(Message::Text(
-                                json!({"type": "error", "message": "Session not started"}).to_string()
-                            )).await;
+                            send_json(json!({"type": "error", "message": "Session not started"}));
                         }
                     }
                     _ => {
-                        let _ = sender.send(Message::Text(
-                            json!({"type": "error", "message": "Unknown message type"}).to_string()
-                        )).await;
+                        send_json(json!({"type": "error", "message": "Unknown message type"}));
                     }
                 }
             }
@@ -134,5 +203,15 @@ pub async fn handle_public_websocket(
             _ => {}
         }
     }
-}
 
+    // 客户端断开：释放到调度服务器的会话连接
+    if let Some(sess_id) = session_id {
+        state.scheduler_client.close_session(&sess_id, "client_disconnected");
+        state.subtitles.finish(&sess_id);
+    }
+    if let Some(task) = partial_task {
+        task.abort();
+    }
+    drop(tx);
+    let _ = send_task.await;
+}
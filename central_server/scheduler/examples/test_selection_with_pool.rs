@@ -166,7 +166,7 @@ async fn main() {
     
     for (src, tgt) in pool_test_pairs {
         print!("语言对 {}:{} => ", src, tgt);
-        match pool_service.select_node(src, tgt, None, None).await {
+        match pool_service.select_node(src, tgt, None, None, &[], None).await {
             Ok(node_id) => {
                 println!("✅ 找到节点: {}", node_id);
             }
@@ -0,0 +1,31 @@
+-- claim_job_hedge.lua
+-- KEYS[1] = job_key
+-- ARGV[1] = hedge_node_id
+-- ARGV[2] = expected_attempt_id
+-- ARGV[3] = now_ms
+
+local exists = redis.call("EXISTS", KEYS[1])
+if exists == 0 then
+  return 0  -- job not found
+end
+
+local attempt = tonumber(redis.call("HGET", KEYS[1], "dispatch_attempt_id") or "0")
+if attempt ~= tonumber(ARGV[2]) then
+  return -1
+end
+
+if redis.call("HGET", KEYS[1], "dispatched_to_node") ~= "true" then
+  return -2
+end
+if redis.call("HEXISTS", KEYS[1], "hedge_node_id") == 1 then
+  return -2
+end
+if redis.call("HGET", KEYS[1], "assigned_node_id") == ARGV[1] then
+  return -2
+end
+
+redis.call("HSET", KEYS[1],
+  "hedge_node_id", ARGV[1],
+  "hedged_at_ms", ARGV[3]
+)
+return 1
@@ -21,5 +21,7 @@ redis.call("HSET", KEYS[1],
   "assigned_node_id", ARGV[1],
   "dispatch_attempt_id", new_attempt
 )
+-- 新 attempt 不继承上一轮的对冲
+redis.call("HDEL", KEYS[1], "hedge_node_id", "hedged_at_ms")
 redis.call("EXPIRE", KEYS[1], ARGV[3])
 return new_attempt
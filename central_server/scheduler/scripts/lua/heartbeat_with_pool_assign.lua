@@ -1,5 +1,7 @@
 -- 节点心跳并自动分配池（有向语言对版本，使用 SCARD）
 -- 被动清理：仅对节点级 key 设置 TTL；pool 集合不做 EXPIRE，由 select_node 按需 SREM 死节点。
+-- 私有节点：accept_public_jobs=false 的节点不进入公共池；有归属租户的节点（owner_tenant_id 由注册流程
+-- 经节点凭证或调度器配置确认后写入节点 hash，心跳不接受节点自报）额外进入租户池（pair_key 前缀 "tenant:{tenant_id}:"，例如 tenant:acme:zh:en）。
 -- ARGV[1]: node_id
 -- ARGV[2]: ttl_seconds（建议 3 * 节点端心跳周期；持续收到心跳则刷新，否则自动过期）
 
@@ -23,6 +25,11 @@ end
 redis.call("HSET", node_key, "last_heartbeat_ts", tostring(now_ts))
 redis.call("EXPIRE", node_key, ttl_sec)
 
+-- 排空中的节点只续期，不再加入任何池（见 node_drain.lua）
+if redis.call("HGET", node_key, "status") == "draining" then
+    return "OK:draining"
+end
+
 -- 获取节点的语言能力（池分配按语义修复能力：asr_langs × semantic_langs，建立两个单向池 zh:en / en:zh 等）
 local asr_langs_json = redis.call("HGET", node_key, "asr_langs")
 local semantic_langs_json = redis.call("HGET", node_key, "semantic_langs")
@@ -46,6 +53,27 @@ if #directed_pairs == 0 then
     return "ERROR:NO_DIRECTED_PAIRS"
 end
 
+-- 池作用域：公共池 "" 与租户池 "tenant:{tenant_id}:"
+local scopes = {}
+if redis.call("HGET", node_key, "accept_public_jobs") ~= "false" then
+    table.insert(scopes, "")
+end
+local owner_tenant_id = redis.call("HGET", node_key, "owner_tenant_id")
+if owner_tenant_id and owner_tenant_id ~= "" then
+    table.insert(scopes, "tenant:" .. owner_tenant_id .. ":")
+end
+if #scopes == 0 then
+    -- 私有且无归属租户：只接受配对会话（不进任何池）
+    return "OK:private_unpooled"
+end
+
+local scoped_pairs = {}
+for _, scope in ipairs(scopes) do
+    for _, pair in ipairs(directed_pairs) do
+        table.insert(scoped_pairs, scope .. pair)
+    end
+end
+
 -- 节点到池的映射
 local node_pools_key = "lingua:v1:node:" .. node_id .. ":pools"
 
@@ -54,7 +82,7 @@ local MAX_POOL_SIZE = 100
 local MAX_POOL_ID = 999
 
 -- 为每个有向语言对分配池
-for _, pair_key in ipairs(directed_pairs) do
+for _, pair_key in ipairs(scoped_pairs) do
     -- 检查是否已分配
     local existing_pool_id = redis.call("HGET", node_pools_key, pair_key)
     
@@ -93,4 +121,4 @@ end
 -- 不対 node:pools 设 TTL，供 select_node 懒清理时 HGETALL 查出所有池并 SREM
 -- （仅 node key 过期即可判死；node:pools 用于多池一次性清理）
 
-return "OK:" .. #directed_pairs .. "_pairs"
+return "OK:" .. #scoped_pairs .. "_pairs"
@@ -0,0 +1,27 @@
+-- 节点排空：标记 status=draining 并移出所有池（节点 key 保留，在途任务继续执行）
+-- 返回原 {pair_key1, pool_id1, pair_key2, pool_id2, ...} 映射，供会话迁移选择同池目标节点
+-- ARGV[1]: node_id
+
+local node_id = ARGV[1]
+local node_key = "lingua:v1:node:" .. node_id
+
+if redis.call("EXISTS", node_key) == 0 then
+    return redis.error_reply("NODE_NOT_REGISTERED")
+end
+
+-- 先打标记：之后的心跳不会再把节点加回池
+redis.call("HSET", node_key, "status", "draining")
+
+local node_pools_key = "lingua:v1:node:" .. node_id .. ":pools"
+local pool_mappings = redis.call("HGETALL", node_pools_key)
+
+for i = 1, #pool_mappings, 2 do
+    local pool_key = "lingua:v1:pool:" .. pool_mappings[i] .. ":" .. pool_mappings[i + 1] .. ":nodes"
+    redis.call("SREM", pool_key, node_id)
+    if redis.call("SCARD", pool_key) == 0 then
+        redis.call("DEL", pool_key)
+    end
+end
+redis.call("DEL", node_pools_key)
+
+return pool_mappings
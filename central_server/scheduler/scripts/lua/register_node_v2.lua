@@ -4,20 +4,54 @@
 -- ARGV[2]: asr_langs_json (例如 ["zh","en"] 或 [])
 -- ARGV[3]: semantic_langs_json
 -- ARGV[4]: tts_langs_json
+-- ARGV[5]: migration_base_url（节点声明的会话迁移地址，空串表示未声明）
+-- ARGV[6]: accept_public_jobs（"true" | "false"）
+-- ARGV[7]: owner_tenant_id（私有节点所属租户，空串表示无）
 
 local node_id = ARGV[1]
 local asr_langs_json = ARGV[2] or "[]"
 local semantic_langs_json = ARGV[3] or "[]"
 local tts_langs_json = ARGV[4] or "[]"
+local migration_base_url = ARGV[5] or ""
+local accept_public_jobs = ARGV[6] or "true"
+local owner_tenant_id = ARGV[7] or ""
 local now_ts = redis.call("TIME")[1]
 
 local node_key = "lingua:v1:node:" .. node_id
+
+-- 公共/私有属性变化时清空原池映射（心跳按新属性重新分配公共池或租户池）
+local prev_public = redis.call("HGET", node_key, "accept_public_jobs") or "true"
+local prev_owner = redis.call("HGET", node_key, "owner_tenant_id") or ""
+if prev_public ~= accept_public_jobs or prev_owner ~= owner_tenant_id then
+    local node_pools_key = "lingua:v1:node:" .. node_id .. ":pools"
+    local pool_mappings = redis.call("HGETALL", node_pools_key)
+    for i = 1, #pool_mappings, 2 do
+        local pool_key = "lingua:v1:pool:" .. pool_mappings[i] .. ":" .. pool_mappings[i + 1] .. ":nodes"
+        redis.call("SREM", pool_key, node_id)
+        if redis.call("SCARD", pool_key) == 0 then
+            redis.call("DEL", pool_key)
+        end
+    end
+    redis.call("DEL", node_pools_key)
+end
+
 redis.call("HMSET", node_key,
     "asr_langs", asr_langs_json,
     "semantic_langs", semantic_langs_json,
     "tts_langs", tts_langs_json,
     "last_heartbeat_ts", tostring(now_ts)
 )
+if migration_base_url ~= "" then
+    redis.call("HSET", node_key, "migration_base_url", migration_base_url)
+else
+    redis.call("HDEL", node_key, "migration_base_url")
+end
+redis.call("HSET", node_key, "accept_public_jobs", accept_public_jobs)
+if owner_tenant_id ~= "" then
+    redis.call("HSET", node_key, "owner_tenant_id", owner_tenant_id)
+else
+    redis.call("HDEL", node_key, "owner_tenant_id")
+end
 redis.call("EXPIRE", node_key, 3600)
 redis.call("SADD", "lingua:v1:nodes:all", node_id)
 return "OK"
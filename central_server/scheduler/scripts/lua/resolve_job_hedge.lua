@@ -0,0 +1,23 @@
+-- resolve_job_hedge.lua
+-- KEYS[1] = job_key
+-- ARGV[1] = expected_attempt_id
+-- ARGV[2] = keep_node_id
+
+local hedge = redis.call("HGET", KEYS[1], "hedge_node_id")
+if not hedge then
+  return 0  -- job not found or not hedged
+end
+
+local attempt = tonumber(redis.call("HGET", KEYS[1], "dispatch_attempt_id") or "0")
+if attempt ~= tonumber(ARGV[1]) then
+  return -1
+end
+
+local primary = redis.call("HGET", KEYS[1], "assigned_node_id")
+if ARGV[2] ~= hedge and ARGV[2] ~= primary then
+  return -2
+end
+
+redis.call("HSET", KEYS[1], "assigned_node_id", ARGV[2])
+redis.call("HDEL", KEYS[1], "hedge_node_id", "hedged_at_ms")
+return 1
@@ -0,0 +1,108 @@
+-- 选择候选节点（供 Rust 侧负载均衡策略挑选，见 src/pool/selection_strategy.rs）
+-- 绑定优先级与 select_node.lua 一致：job 级绑定 > session 亲和；命中绑定时不再采样。
+-- 被动清理：采样到 node key 已过期的节点时从其所有池 SREM；排空中的节点不作为候选。
+-- ARGV[1]: pair_key (格式: "zh:en" 或 "tenant:{tenant_id}:zh:en")
+-- ARGV[2]: job_id (optional, MaxDuration job 级绑定)
+-- ARGV[3]: session_id (optional, session affinity assigned_node_id)
+-- ARGV[4]: sample_size（候选数量上限）
+-- 返回: {"bound", node_id} | {"candidates", node_id...}（无可用节点时只有 "candidates"）
+
+local pair_key = ARGV[1]
+local job_id = ARGV[2]
+local session_id = ARGV[3]
+local sample_size = tonumber(ARGV[4]) or 8
+if sample_size < 1 then
+    sample_size = 1
+end
+local MAX_POOL_ID = 999
+
+local function is_live(node_id)
+    local node_key = "lingua:v1:node:" .. node_id
+    return redis.call("EXISTS", node_key) == 1 and redis.call("HGET", node_key, "status") ~= "draining"
+end
+
+-- 1. job 级绑定（MaxDuration 同 job 链）
+if job_id and job_id ~= "" then
+    local binding_key = "lingua:v1:job:" .. job_id .. ":node"
+    local bound_node = redis.call("GET", binding_key)
+    if bound_node then
+        if is_live(bound_node) then
+            return {"bound", bound_node}
+        end
+        redis.call("DEL", binding_key)
+    end
+end
+
+-- 2. Session 亲和：scheduler:session:{session_id} assigned_node_id（须仍在该语言对的池中）
+--    节点所在池直接从 node:{id}:pools 映射读取，不逐个池号探测
+if session_id and session_id ~= "" then
+    local session_node_id = redis.call("HGET", "scheduler:session:" .. session_id, "assigned_node_id")
+    if session_node_id and session_node_id ~= "" and is_live(session_node_id) then
+        local pool_id = redis.call("HGET", "lingua:v1:node:" .. session_node_id .. ":pools", pair_key)
+        if pool_id and redis.call("SISMEMBER", "lingua:v1:pool:" .. pair_key .. ":" .. pool_id .. ":nodes", session_node_id) == 1 then
+            return {"bound", session_node_id}
+        end
+    end
+end
+
+-- 3. 从各非空池采样候选
+local function drop_dead(node_id, pool_key)
+    local node_pools_key = "lingua:v1:node:" .. node_id .. ":pools"
+    local mapping = redis.call("HGETALL", node_pools_key)
+    if mapping and #mapping >= 2 then
+        for i = 1, #mapping, 2 do
+            local pk_key = "lingua:v1:pool:" .. mapping[i] .. ":" .. mapping[i + 1] .. ":nodes"
+            redis.call("SREM", pk_key, node_id)
+            if redis.call("SCARD", pk_key) == 0 then
+                redis.call("DEL", pk_key)
+            end
+        end
+        redis.call("DEL", node_pools_key)
+    else
+        redis.call("SREM", pool_key, node_id)
+        if redis.call("SCARD", pool_key) == 0 then
+            redis.call("DEL", pool_key)
+        end
+    end
+end
+
+-- 池编号连续分配：已遇到非空池后的第一个空池即视为结束（与 select_node.lua 的 collect_pool_ids 一致）
+local pool_ids = {}
+for pool_id = 0, MAX_POOL_ID do
+    if redis.call("SCARD", "lingua:v1:pool:" .. pair_key .. ":" .. pool_id .. ":nodes") > 0 then
+        table.insert(pool_ids, pool_id)
+    elseif pool_id > 0 and #pool_ids > 0 then
+        break
+    end
+end
+
+-- 从随机起始池轮转采样，避免候选总是集中在 0 号池
+local result = {"candidates"}
+local seen = {}
+if #pool_ids > 0 then
+    math.randomseed(tonumber(redis.call("TIME")[1]) + tonumber(redis.call("TIME")[2]))
+    local start = math.random(#pool_ids)
+    for i = 0, #pool_ids - 1 do
+        if #result - 1 >= sample_size then
+            break
+        end
+        local pool_key = "lingua:v1:pool:" .. pair_key .. ":" .. pool_ids[((start - 1 + i) % #pool_ids) + 1] .. ":nodes"
+        local members = redis.call("SRANDMEMBER", pool_key, sample_size)
+        for _, node_id in ipairs(members) do
+            if #result - 1 >= sample_size then
+                break
+            end
+            if not seen[node_id] then
+                seen[node_id] = true
+                local node_key = "lingua:v1:node:" .. node_id
+                if redis.call("EXISTS", node_key) == 0 then
+                    drop_dead(node_id, pool_key)
+                elseif redis.call("HGET", node_key, "status") ~= "draining" then
+                    table.insert(result, node_id)
+                end
+            end
+        end
+    end
+end
+
+return result
@@ -1,5 +1,6 @@
 -- 选择节点（用于调度，有向语言对版本，使用 SCARD）
 -- 被动清理：从池取出节点后 EXISTS 校验；若 node key 已过期则 SREM 并重试，避免派发到已断开节点。
+-- 排空：status=draining 的节点不再被选中（含 job 级绑定）。
 -- ARGV[1]: pair_key (格式: "zh:en")
 -- ARGV[2]: job_id (optional, MaxDuration job 级绑定)
 -- ARGV[3]: session_id (optional, session affinity assigned_node_id)
@@ -18,7 +19,7 @@ if job_id and job_id ~= "" then
 
     if bound_node then
         local node_key = "lingua:v1:node:" .. bound_node
-        if redis.call("EXISTS", node_key) == 1 then
+        if redis.call("EXISTS", node_key) == 1 and redis.call("HGET", node_key, "status") ~= "draining" then
             return bound_node
         end
         redis.call("DEL", binding_key)
@@ -32,12 +33,10 @@ if session_id and session_id ~= "" then
     if session_node_id and session_node_id ~= "" then
         local node_key = "lingua:v1:node:" .. session_node_id
         if redis.call("EXISTS", node_key) == 1 then
-            for pool_id = 0, MAX_POOL_ID do
-                local pool_key = "lingua:v1:pool:" .. pair_key .. ":" .. pool_id .. ":nodes"
-                if redis.call("SISMEMBER", pool_key, session_node_id) == 1 then
-                    chosen_node_id = session_node_id
-                    break
-                end
+            -- 节点所在池直接从 node:{id}:pools 映射读取，不逐个池号探测
+            local pool_id = redis.call("HGET", "lingua:v1:node:" .. session_node_id .. ":pools", pair_key)
+            if pool_id and redis.call("SISMEMBER", "lingua:v1:pool:" .. pair_key .. ":" .. pool_id .. ":nodes", session_node_id) == 1 then
+                chosen_node_id = session_node_id
             end
         end
     end
@@ -79,7 +78,8 @@ while tries < MAX_TRIES do
         redis.call("DEL", pool_key)
     else
         local node_key = "lingua:v1:node:" .. node_id
-        if redis.call("EXISTS", node_key) == 1 then
+        -- 排空中的节点按死节点处理（正常情况下已不在池中）
+        if redis.call("EXISTS", node_key) == 1 and redis.call("HGET", node_key, "status") ~= "draining" then
             if job_id and job_id ~= "" then
                 redis.call("SET", "lingua:v1:job:" .. job_id .. ":node", node_id, "EX", 3600)
             end
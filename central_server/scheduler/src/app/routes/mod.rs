@@ -1,6 +1,12 @@
 pub mod routes_handlers;
 pub mod routes_api;
 pub mod routes_dashboard;
+pub mod routes_usage;
+pub mod routes_transcripts;
+pub mod routes_node_auth;
+pub mod routes_node_drain;
+pub mod routes_migrations;
+pub mod routes_pool_selection;
 
 pub use routes_handlers::{handle_session_ws, handle_node_ws, start_server};
 pub use routes_api::{
@@ -8,13 +14,19 @@ pub use routes_api::{
     get_prometheus_metrics,
     // get_phase3_pools 已删除
 };
+pub use routes_usage::{get_usage, export_usage};
+pub use routes_transcripts::{get_transcript, delete_transcript};
+pub use routes_node_auth::{create_enrollment, list_revoked_nodes, revoke_node, unrevoke_node};
+pub use routes_node_drain::{drain_node, get_node_drain};
+pub use routes_migrations::get_session_migrations;
+pub use routes_pool_selection::get_selection_decisions;
 pub use routes_dashboard::{
     serve_dashboard, serve_compute_power, serve_models, serve_languages, serve_cluster,
 };
 
 use crate::core::AppState;
 use axum::{
-    routing::get,
+    routing::{get, post},
     Router,
 };
 
@@ -28,6 +40,21 @@ pub fn create_router(app_state: AppState) -> Router {
         // /api/v1/pool_hashing/simulate 已删除（Phase3已删除）
         .route("/api/v1/metrics", get(get_metrics))
         .route("/api/v1/cluster", get(get_cluster_stats))
+        .route("/api/v1/usage", get(get_usage))
+        .route("/api/v1/usage/export", get(export_usage))
+        .route(
+            "/api/v1/sessions/:session_id/transcript",
+            get(get_transcript).delete(delete_transcript),
+        )
+        .route("/api/v1/sessions/:session_id/migrations", get(get_session_migrations))
+        .route("/api/v1/nodes/enrollments", post(create_enrollment))
+        .route("/api/v1/nodes/revoked", get(list_revoked_nodes))
+        .route(
+            "/api/v1/nodes/:node_id/revoke",
+            post(revoke_node).delete(unrevoke_node),
+        )
+        .route("/api/v1/nodes/:node_id/drain", post(drain_node).get(get_node_drain))
+        .route("/api/v1/pools/selection-decisions", get(get_selection_decisions))
         .route("/metrics", get(get_prometheus_metrics))
         .route("/dashboard", get(serve_dashboard))
         .route("/cluster", get(serve_cluster))
@@ -45,6 +45,7 @@ pub struct ClusterStatsResponse {
     total_nodes: usize,
     online_nodes: usize,
     ready_nodes: usize, // 服务就绪的节点数
+    degraded_nodes: usize, // 有服务处于熔断（open/half_open）的节点数
     total_sessions: usize,
     total_pending: u64,
     total_dlq: u64,
@@ -68,6 +69,8 @@ pub struct NodeInfo {
     services: Vec<ServiceStatusInfo>,
     // 能力状态（按 ServiceType）
     capabilities: Vec<CapabilityStatusInfo>,
+    // 熔断状态（按 ServiceType，仅列出有记录的服务）
+    circuit_breakers: Vec<crate::services::node_circuit_breaker::BreakerSnapshot>,
 }
 
 #[derive(serde::Serialize)]
@@ -235,6 +238,7 @@ pub async fn get_cluster_stats(
     }
 
     // 获取节点列表和服务状态（从 Redis 直查）
+    let mut degraded_nodes = 0usize;
     let (total_nodes, online_nodes, ready_nodes, nodes_list) = {
         // 使用 Redis 直查获取所有节点
         let nodes = match state.node_registry.list_sched_nodes().await {
@@ -271,24 +275,44 @@ pub async fn get_cluster_stats(
                 }).collect();
                 
                 // 构建能力状态列表（基于 installed_services，按 ServiceType 单源）
+                // 熔断状态（open/half_open 的服务视为未就绪，节点整体显示为 degraded）
+                let circuit_breakers = match state.node_circuit_breaker.as_ref() {
+                    Some(breaker) => breaker.node_snapshot(&node.node_id).await.unwrap_or_else(|e| {
+                        tracing::warn!(node_id = %node.node_id, error = %e, "查询节点熔断状态失败");
+                        Vec::new()
+                    }),
+                    None => Vec::new(),
+                };
+                let tripped = |service_type: &crate::messages::ServiceType| {
+                    let name = format!("{:?}", service_type).to_lowercase();
+                    circuit_breakers
+                        .iter()
+                        .find(|b| b.service_type == name && b.state != crate::services::node_circuit_breaker::BreakerState::Closed)
+                        .map(|b| format!("circuit_{}", b.state.as_str()))
+                };
                 let capabilities: Vec<CapabilityStatusInfo> = crate::messages::ServiceType::all()
                     .iter()
                     .map(|service_type| {
-                        let ready = node.installed_services.iter().any(|s| s.r#type == *service_type);
+                        let installed = node.installed_services.iter().any(|s| s.r#type == *service_type);
+                        let reason = tripped(service_type);
                         CapabilityStatusInfo {
                             service_type: format!("{:?}", service_type),
-                            ready,
-                            reason: None,
+                            ready: installed && reason.is_none(),
+                            reason,
                             ready_impl_ids: None,
                         }
                     })
                     .collect();
+                let degraded = capabilities.iter().any(|c| c.reason.is_some());
+                if degraded {
+                    degraded_nodes += 1;
+                }
             
                 result.push(NodeInfo {
                     node_id: node.node_id.clone(),
                     platform: "".to_string(), // SchedNodeInfo 中没有 platform
                     online: node.online,
-                    status: node.status.clone(),
+                    status: if degraded && node.status == "online" { "degraded".to_string() } else { node.status.clone() },
                     cpu_usage: node.cpu_usage,
                     gpu_usage: node.gpu_usage,
                     memory_usage: node.memory_usage,
@@ -297,6 +321,7 @@ pub async fn get_cluster_stats(
                     last_heartbeat: node.last_heartbeat_ts,
                     services,
                     capabilities,
+                    circuit_breakers,
                 });
             }
             result
@@ -317,6 +342,7 @@ pub async fn get_cluster_stats(
         total_nodes,
         online_nodes,
         ready_nodes,
+        degraded_nodes,
         total_sessions,
         total_pending,
         total_dlq,
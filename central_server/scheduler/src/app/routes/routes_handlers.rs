@@ -73,6 +73,8 @@ pub async fn start_server(
         let _ = shutdown_tx1.send(());
     });
     
+    // 连接全部关闭后最后一次落盘用量（优雅关闭期间仍可能有 JobResult 计量）
+    let usage_meter = app_state_for_shutdown.usage_meter.clone();
     let shutdown_signal = async move {
         // 等待关闭信号
         if shutdown_rx.recv().await.is_none() {
@@ -112,10 +114,14 @@ pub async fn start_server(
     
     // 使用优雅关闭启动服务器
     info!("调度服务器已启动，等待连接...");
-    match axum::serve(listener, app)
+    let served = axum::serve(listener, app)
         .with_graceful_shutdown(shutdown_signal)
-        .await
-    {
+        .await;
+    if let Some(meter) = usage_meter {
+        meter.flush().await;
+        info!("用量汇总已落盘");
+    }
+    match served {
         Ok(_) => {
             info!("调度服务器已优雅关闭");
         }
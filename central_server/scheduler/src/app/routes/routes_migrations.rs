@@ -0,0 +1,66 @@
+// 会话迁移历史 API（内部接口，数据来自 SessionAffinityService::record_migration_event）
+
+use crate::core::AppState;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use serde::Deserialize;
+
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1000;
+
+#[derive(Debug, Deserialize)]
+pub struct MigrationHistoryQuery {
+    /// 返回最近 N 条（默认 100，最多 1000）
+    limit: Option<usize>,
+}
+
+/// GET /api/v1/sessions/:session_id/migrations[?limit=N]
+pub async fn get_session_migrations(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+    Query(q): Query<MigrationHistoryQuery>,
+) -> Result<axum::Json<serde_json::Value>, StatusCode> {
+    let orchestrator = state
+        .session_migration_orchestrator
+        .as_ref()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let limit = q.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let events = orchestrator
+        .affinity()
+        .list_migration_events(&session_id, limit)
+        .await
+        .map_err(|e| {
+            tracing::warn!(session_id = %session_id, error = %e, "读取会话迁移历史失败");
+            StatusCode::SERVICE_UNAVAILABLE
+        })?;
+    let assigned_node_id = orchestrator
+        .affinity()
+        .get_assigned_node_id(&session_id)
+        .await
+        .ok()
+        .flatten();
+
+    let events: Vec<serde_json::Value> = events
+        .iter()
+        .map(|event| {
+            let (reason, fields) = event.reason_parts();
+            let mut value = serde_json::json!({
+                "from_node_id": event.from_node_id,
+                "to_node_id": event.to_node_id,
+                "reason": reason,
+                "timestamp_ms": event.timestamp_ms,
+            });
+            for (k, v) in fields {
+                value[k] = serde_json::Value::String(v.to_string());
+            }
+            value
+        })
+        .collect();
+
+    Ok(axum::Json(serde_json::json!({
+        "session_id": session_id,
+        "assigned_node_id": assigned_node_id,
+        "count": events.len(),
+        "events": events,
+    })))
+}
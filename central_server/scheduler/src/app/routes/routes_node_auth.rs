@@ -0,0 +1,98 @@
+// 节点认证管理 API：签发 enrollment token、吊销/解除吊销节点（需 node_auth.admin_token）
+
+use crate::core::AppState;
+use crate::services::node_auth::NodeAuthError;
+use crate::services::NodeAuthService;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::extract::ws::Message;
+use serde::Deserialize;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct EnrollmentRequest {
+    /// 不指定时生成新的 node_id
+    node_id: Option<String>,
+    ttl_seconds: Option<u64>,
+    /// 绑定节点归属租户（节点注册时必须声明同一 owner_tenant_id）
+    owner_tenant_id: Option<String>,
+}
+
+/// 校验 `Authorization: Bearer <node_auth.admin_token>`；未启用节点认证时接口不可用
+pub(crate) fn authorize<'a>(state: &'a AppState, headers: &HeaderMap) -> Result<&'a NodeAuthService, StatusCode> {
+    let auth = state.node_auth.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let bearer = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    if auth.is_admin(bearer) {
+        Ok(auth)
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+fn redis_err(node_id: &str, e: redis::RedisError) -> StatusCode {
+    tracing::warn!(node_id = %node_id, error = %e, "访问节点吊销名单失败");
+    StatusCode::SERVICE_UNAVAILABLE
+}
+
+/// POST /api/v1/nodes/enrollments  body: {"node_id"?: "...", "ttl_seconds"?: N, "owner_tenant_id"?: "..."}
+pub async fn create_enrollment(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Option<axum::Json<EnrollmentRequest>>,
+) -> Result<axum::Json<serde_json::Value>, StatusCode> {
+    let auth = authorize(&state, &headers)?;
+    let req = body.map(|b| b.0).unwrap_or_default();
+    let enrollment = auth
+        .issue_enrollment(req.node_id, req.ttl_seconds, req.owner_tenant_id)
+        .map_err(|e| match e {
+            NodeAuthError::InvalidOwnerTenant => StatusCode::BAD_REQUEST,
+            _ => StatusCode::SERVICE_UNAVAILABLE,
+        })?;
+    tracing::info!(
+        node_id = %enrollment.node_id,
+        expires_at = enrollment.expires_at,
+        owner_tenant_id = ?enrollment.owner_tenant_id,
+        "节点 enrollment token 已签发"
+    );
+    Ok(axum::Json(serde_json::json!(enrollment)))
+}
+
+/// GET /api/v1/nodes/revoked
+pub async fn list_revoked_nodes(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<axum::Json<serde_json::Value>, StatusCode> {
+    let auth = authorize(&state, &headers)?;
+    let revoked = auth.list_revoked().await.map_err(|e| redis_err("*", e))?;
+    Ok(axum::Json(serde_json::json!({ "revoked": revoked })))
+}
+
+/// POST /api/v1/nodes/:node_id/revoke：加入吊销名单并断开本实例上的在线连接
+pub async fn revoke_node(
+    State(state): State<AppState>,
+    Path(node_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<StatusCode, StatusCode> {
+    let auth = authorize(&state, &headers)?;
+    auth.revoke(&node_id).await.map_err(|e| redis_err(&node_id, e))?;
+    let disconnected = state.node_connections.send(&node_id, Message::Close(None)).await;
+    tracing::warn!(node_id = %node_id, disconnected = disconnected, "节点已吊销");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// DELETE /api/v1/nodes/:node_id/revoke
+pub async fn unrevoke_node(
+    State(state): State<AppState>,
+    Path(node_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<StatusCode, StatusCode> {
+    let auth = authorize(&state, &headers)?;
+    if auth.unrevoke(&node_id).await.map_err(|e| redis_err(&node_id, e))? {
+        tracing::info!(node_id = %node_id, "节点已解除吊销");
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(StatusCode::NOT_FOUND)
+    }
+}
@@ -0,0 +1,52 @@
+// 节点排空 API（内部接口，供运维升级节点前使用；发起排空需 node_auth.admin_token）
+
+use super::routes_node_auth::authorize;
+use crate::core::AppState;
+use crate::services::node_drain::{start_node_drain, NodeDrainError};
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use serde::Deserialize;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DrainRequest {
+    reason: Option<String>,
+}
+
+/// POST /api/v1/nodes/:node_id/drain  body: {"reason"?: "..."}
+pub async fn drain_node(
+    State(state): State<AppState>,
+    Path(node_id): Path<String>,
+    headers: HeaderMap,
+    body: Option<axum::Json<DrainRequest>>,
+) -> Result<(StatusCode, axum::Json<serde_json::Value>), StatusCode> {
+    authorize(&state, &headers)?;
+    let reason = body
+        .and_then(|b| b.0.reason)
+        .unwrap_or_else(|| "admin requested".to_string());
+    match start_node_drain(&state, &node_id, &reason, "admin").await {
+        Ok(record) => Ok((StatusCode::ACCEPTED, axum::Json(serde_json::json!(record)))),
+        Err(NodeDrainError::AlreadyDraining) => Err(StatusCode::CONFLICT),
+        Err(NodeDrainError::NotRegistered) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::warn!(node_id = %node_id, error = %e, "发起节点排空失败");
+            Err(StatusCode::SERVICE_UNAVAILABLE)
+        }
+    }
+}
+
+/// GET /api/v1/nodes/:node_id/drain：排空进度
+pub async fn get_node_drain(
+    State(state): State<AppState>,
+    Path(node_id): Path<String>,
+) -> Result<axum::Json<serde_json::Value>, StatusCode> {
+    let drain = state.node_drain.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let record = drain
+        .get(&node_id)
+        .await
+        .map_err(|e| {
+            tracing::warn!(node_id = %node_id, error = %e, "读取节点排空记录失败");
+            StatusCode::SERVICE_UNAVAILABLE
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(axum::Json(serde_json::json!(record)))
+}
@@ -0,0 +1,34 @@
+// 节点选择决策 API（排查负载均衡策略，数据为本实例最近的选择）
+
+use crate::core::AppState;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use serde::Deserialize;
+
+const DEFAULT_LIMIT: usize = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct SelectionDecisionsQuery {
+    limit: Option<usize>,
+    /// 只看某个池（pair_key，如 "zh:en"）
+    pair_key: Option<String>,
+}
+
+/// GET /api/v1/pools/selection-decisions[?limit=N&pair_key=zh:en]
+pub async fn get_selection_decisions(
+    State(state): State<AppState>,
+    Query(q): Query<SelectionDecisionsQuery>,
+) -> Result<axum::Json<serde_json::Value>, StatusCode> {
+    let pool_service = state.pool_service.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let limit = q.limit.unwrap_or(DEFAULT_LIMIT);
+    let decisions: Vec<_> = pool_service
+        .recent_decisions(usize::MAX)
+        .into_iter()
+        .filter(|d| q.pair_key.as_deref().is_none_or(|pk| d.pair_key == pk))
+        .take(limit)
+        .collect();
+    Ok(axum::Json(serde_json::json!({
+        "count": decisions.len(),
+        "decisions": decisions,
+    })))
+}
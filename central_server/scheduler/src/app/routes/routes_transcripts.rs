@@ -0,0 +1,77 @@
+// 会话转写查询与删除 API（内部接口，供 API Gateway / 运维使用；需 node_auth.admin_token 且必须指定租户）
+
+use super::routes_node_auth::authorize;
+use crate::core::AppState;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct TranscriptQuery {
+    /// 只允许访问属于该租户的转写
+    tenant_id: String,
+}
+
+fn redis_err(session_id: &str, e: redis::RedisError) -> StatusCode {
+    tracing::warn!(session_id = %session_id, error = %e, "访问会话转写失败");
+    StatusCode::SERVICE_UNAVAILABLE
+}
+
+/// GET /api/v1/sessions/:session_id/transcript?tenant_id=...
+pub async fn get_transcript(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+    Query(q): Query<TranscriptQuery>,
+    headers: HeaderMap,
+) -> Result<axum::Json<serde_json::Value>, StatusCode> {
+    authorize(&state, &headers)?;
+    let store = state.transcript_store.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let (meta, entries) = store
+        .fetch(&session_id)
+        .await
+        .map_err(|e| redis_err(&session_id, e))?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    // 租户不匹配按不存在处理，避免泄露其他租户的 session_id
+    if meta.tenant_id.as_deref() != Some(q.tenant_id.as_str()) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(axum::Json(serde_json::json!({
+        "session_id": meta.session_id,
+        "tenant_id": meta.tenant_id,
+        "src_lang": meta.src_lang,
+        "tgt_lang": meta.tgt_lang,
+        "created_at_ms": meta.created_at_ms,
+        "count": entries.len(),
+        "entries": entries,
+    })))
+}
+
+/// DELETE /api/v1/sessions/:session_id/transcript?tenant_id=...
+pub async fn delete_transcript(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+    Query(q): Query<TranscriptQuery>,
+    headers: HeaderMap,
+) -> Result<StatusCode, StatusCode> {
+    authorize(&state, &headers)?;
+    let store = state.transcript_store.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let (meta, _) = store
+        .fetch(&session_id)
+        .await
+        .map_err(|e| redis_err(&session_id, e))?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if meta.tenant_id.as_deref() != Some(q.tenant_id.as_str()) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let removed = store
+        .delete(&session_id)
+        .await
+        .map_err(|e| redis_err(&session_id, e))?;
+    if removed {
+        tracing::info!(session_id = %session_id, "会话转写已删除");
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(StatusCode::NOT_FOUND)
+    }
+}
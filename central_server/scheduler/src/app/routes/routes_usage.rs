@@ -0,0 +1,139 @@
+// 用量查询与导出 API（内部接口，供 API Gateway / 运维使用）
+
+use crate::core::AppState;
+use crate::services::usage_metering::{UsageGranularity, UsageRollup, MAX_QUERY_HOURS};
+use axum::extract::{Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct UsageQuery {
+    tenant_id: Option<String>,
+    /// RFC3339，默认 `to - 24h`
+    from: Option<DateTime<Utc>>,
+    /// RFC3339，默认当前时间
+    to: Option<DateTime<Utc>>,
+    /// "hour" | "day"，默认 "hour"
+    granularity: Option<String>,
+    /// 导出格式："csv" | "jsonl"，默认 "csv"
+    format: Option<String>,
+}
+
+impl UsageQuery {
+    fn range(&self) -> Result<(DateTime<Utc>, DateTime<Utc>, UsageGranularity), StatusCode> {
+        let to = self.to.unwrap_or_else(Utc::now);
+        let from = self.from.unwrap_or(to - Duration::hours(24));
+        if from >= to || (to - from) > Duration::hours(MAX_QUERY_HOURS) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        let granularity = UsageGranularity::parse(self.granularity.as_deref().unwrap_or("hour"))
+            .ok_or(StatusCode::BAD_REQUEST)?;
+        Ok((from, to, granularity))
+    }
+}
+
+/// GET /api/v1/usage?tenant_id=...&from=...&to=...&granularity=hour|day
+pub async fn get_usage(
+    State(state): State<AppState>,
+    Query(q): Query<UsageQuery>,
+) -> Result<axum::Json<serde_json::Value>, StatusCode> {
+    let meter = state.usage_meter.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let tenant_id = q.tenant_id.clone().ok_or(StatusCode::BAD_REQUEST)?;
+    let (from, to, granularity) = q.range()?;
+
+    let items = meter
+        .query(&tenant_id, from, to, granularity)
+        .await
+        .map_err(|e| {
+            tracing::warn!(tenant_id = %tenant_id, error = %e, "查询用量失败");
+            StatusCode::SERVICE_UNAVAILABLE
+        })?;
+
+    let mut totals = UsageRollup {
+        tenant_id: tenant_id.clone(),
+        period_start: from,
+        ..Default::default()
+    };
+    for item in &items {
+        totals.jobs += item.jobs;
+        totals.audio_seconds_in += item.audio_seconds_in;
+        totals.asr_chars += item.asr_chars;
+        totals.nmt_chars += item.nmt_chars;
+        totals.tts_seconds_out += item.tts_seconds_out;
+        totals.processing_ms += item.processing_ms;
+    }
+
+    Ok(axum::Json(serde_json::json!({
+        "tenant_id": tenant_id,
+        "from": from,
+        "to": to,
+        "granularity": q.granularity.as_deref().unwrap_or("hour"),
+        "totals": totals,
+        "items": items,
+    })))
+}
+
+/// GET /api/v1/usage/export?format=csv|jsonl&from=...&to=...[&tenant_id=...]
+///
+/// 不指定 tenant_id 时导出全部有用量记录的租户。
+pub async fn export_usage(
+    State(state): State<AppState>,
+    Query(q): Query<UsageQuery>,
+) -> Result<Response, StatusCode> {
+    let meter = state.usage_meter.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let (from, to, granularity) = q.range()?;
+    let format = q.format.as_deref().unwrap_or("csv");
+    if format != "csv" && format != "jsonl" {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let redis_err = |e: redis::RedisError| {
+        tracing::warn!(error = %e, "导出用量失败");
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let tenants = match q.tenant_id.clone() {
+        Some(t) => vec![t],
+        None => meter.list_tenants().await.map_err(redis_err)?,
+    };
+
+    let mut rows = Vec::new();
+    for tenant_id in &tenants {
+        rows.extend(meter.query(tenant_id, from, to, granularity).await.map_err(redis_err)?);
+    }
+
+    let (content_type, body) = if format == "csv" {
+        let mut body = String::from(UsageRollup::CSV_HEADER);
+        body.push('\n');
+        for row in &rows {
+            body.push_str(&row.to_csv_row());
+            body.push('\n');
+        }
+        ("text/csv; charset=utf-8", body)
+    } else {
+        let mut body = String::new();
+        for row in &rows {
+            body.push_str(&serde_json::to_string(row).unwrap_or_default());
+            body.push('\n');
+        }
+        ("application/x-ndjson", body)
+    };
+    let filename = format!(
+        "usage_{}_{}.{}",
+        from.format("%Y%m%d%H"),
+        to.format("%Y%m%d%H"),
+        format
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
@@ -59,7 +59,6 @@ pub async fn initialize_app(config: &Config) -> anyhow::Result<AppState> {
     // 阶段3：NodeRegistry 将在 Phase2 初始化后创建（需要 RedisHandle）
     // 临时：先创建其他不依赖 NodeRegistry 的组件
     
-    let pairing_service = PairingService::new();
     // ModelHub 已删除（未实现）
     // ServiceCatalog：优先走 ModelHub HTTP；若失败则可用本地 services_index.json 兜底（单机冷启动/离线）
     let local_services_index = config.model_hub.storage_path.join("services_index.json");
@@ -81,9 +80,6 @@ pub async fn initialize_app(config: &Config) -> anyhow::Result<AppState> {
     
     // NodeStatusManager 将在创建 NodeRegistry 后初始化
     
-    // 初始化 RoomManager
-    let room_manager = RoomManager::new();
-
     // Phase 2：Redis/多实例运行时（可选）
     let redis_runtime = RedisRuntime::new(
         config.scheduler.redis_runtime.clone(),
@@ -93,6 +89,9 @@ pub async fn initialize_app(config: &Config) -> anyhow::Result<AppState> {
     .await?
     .map(std::sync::Arc::new);
 
+    // 初始化 RoomManager（启用 Redis 运行时时房间多实例共享）
+    let room_manager = RoomManager::new(redis_runtime.clone());
+
     // 阶段3：初始化 Redis 连接（Phase2 启用或降级到本地连接）
     use crate::redis_runtime::RedisHandle;
     let redis_arc = if redis_runtime.is_some() {
@@ -122,6 +121,19 @@ pub async fn initialize_app(config: &Config) -> anyhow::Result<AppState> {
     // NodeRegistry::new() 内部已打印初始化日志，无需重复
     
     // NodeStatusManager 已删除（与Redis直查架构冲突）
+
+    // 节点熔断器（状态写入 Redis，选节点时按所需服务类型准入）
+    let node_circuit_breaker = config.scheduler.node_health.circuit_breaker.enabled.then(|| {
+        crate::services::NodeCircuitBreaker::new(
+            redis_arc.clone(),
+            config.scheduler.redis_runtime.redis.key_prefix.clone(),
+            &config.scheduler.node_health.failure_threshold,
+            config.scheduler.node_health.circuit_breaker.clone(),
+        )
+    });
+    if let Some(ref breaker) = node_circuit_breaker {
+        node_registry.set_circuit_breaker(breaker.clone()).await;
+    }
     
     let (minimal_scheduler, pool_service) = if redis_runtime.is_some() {
         // 初始化 MinimalScheduler
@@ -143,6 +155,9 @@ pub async fn initialize_app(config: &Config) -> anyhow::Result<AppState> {
         ).await {
             Ok(ps) => {
                 info!("Pool 服务已初始化");
+                let ps = ps
+                    .with_selection_strategies(&config.scheduler.load_balancer, redis_runtime.clone())
+                    .with_circuit_breaker(node_circuit_breaker.clone());
                 Some(std::sync::Arc::new(ps))
             }
             Err(e) => {
@@ -179,15 +194,81 @@ pub async fn initialize_app(config: &Config) -> anyhow::Result<AppState> {
     let job_result_deduplicator = crate::core::JobResultDeduplicator::new();
     // Utterance 路径：按 utterance_index 顺序派发
     let pending_job_dispatches = PendingJobDispatches::new();
+    // 租户并发会话配额
+    let tenant_quota = crate::core::TenantSessionQuota::new(
+        config.scheduler.tenant_quota.clone(),
+        redis_runtime.clone(),
+    );
 
     let session_migration_orchestrator = redis_runtime.as_ref().map(|rt| {
         let affinity = std::sync::Arc::new(SessionAffinityService::new(rt.clone()));
-        std::sync::Arc::new(SessionMigrationOrchestrator::new(affinity))
+        std::sync::Arc::new(SessionMigrationOrchestrator::new(affinity, rt.clone()))
     });
     if session_migration_orchestrator.is_some() {
-        info!("SessionMigrationOrchestrator enabled (NODE_MIGRATION_BASE_URL_*)");
+        info!("SessionMigrationOrchestrator enabled (NODE_MIGRATION_BASE_URL_<ID>, then authenticated node migration_endpoint)");
     }
 
+    // 按租户用量计量（小时汇总写入 Redis）
+    let usage_meter = config.scheduler.usage_metering.enabled.then(|| {
+        let meter = crate::services::UsageMeter::new(
+            redis_arc.clone(),
+            config.scheduler.redis_runtime.redis.key_prefix.clone(),
+            config.scheduler.usage_metering.retention_days,
+        );
+        meter.start_background_flush(config.scheduler.usage_metering.flush_interval_seconds);
+        meter
+    });
+
+    // 会话转写持久化（按租户/会话开启，写入 Redis）
+    let transcript_store = config.scheduler.transcripts.enabled.then(|| {
+        crate::services::TranscriptStore::new(
+            redis_arc.clone(),
+            config.scheduler.redis_runtime.redis.key_prefix.clone(),
+            config.scheduler.transcripts.clone(),
+        )
+    });
+
+    // 断线会话恢复（宽限期快照与待重放结果写入 Redis，支持跨实例恢复）
+    let session_resume = config.scheduler.session_resume.enabled.then(|| {
+        crate::services::SessionResumeService::new(
+            redis_arc.clone(),
+            config.scheduler.redis_runtime.redis.key_prefix.clone(),
+            config.scheduler.session_resume.clone(),
+        )
+    });
+
+    // 节点认证（challenge 校验、enrollment token、吊销名单）
+    let node_auth = config.scheduler.node_auth.enabled.then(|| {
+        crate::services::NodeAuthService::new(
+            redis_arc.clone(),
+            config.scheduler.redis_runtime.redis.key_prefix.clone(),
+            config.scheduler.node_auth.clone(),
+        )
+    });
+
+    // 节点排空（排空记录写入 Redis，任意实例可查询）
+    let node_drain = config.scheduler.node_drain.enabled.then(|| {
+        crate::services::NodeDrainService::new(
+            redis_arc.clone(),
+            config.scheduler.redis_runtime.redis.key_prefix.clone(),
+            config.scheduler.node_drain.clone(),
+        )
+    });
+
+    // 对冲派发（时延样本与预算按实例统计，对冲占用写入 Job Hash）
+    let hedged_dispatch = config
+        .scheduler
+        .hedged_dispatch
+        .enabled
+        .then(|| crate::services::HedgedDispatch::new(config.scheduler.hedged_dispatch.clone()));
+
+    // 私有节点配对码（Redis 共享，任意实例可校验）
+    let pairing_service = PairingService::new(
+        redis_arc.clone(),
+        config.scheduler.redis_runtime.redis.key_prefix.clone(),
+        config.scheduler.pairing.clone(),
+    );
+
     // 创建应用状态
     let app_state = AppState {
         session_manager,
@@ -198,6 +279,8 @@ pub async fn initialize_app(config: &Config) -> anyhow::Result<AppState> {
         dashboard_snapshot,
         model_not_available_bus,
         web_task_segmentation: config.scheduler.web_task_segmentation.clone(),
+        binary_frame: config.scheduler.binary_frame.clone(),
+        private_pools: config.scheduler.private_pools.clone(),
         session_connections: session_connections.clone(),
         node_connections,
         result_queue,
@@ -207,6 +290,14 @@ pub async fn initialize_app(config: &Config) -> anyhow::Result<AppState> {
         job_idempotency,
         job_result_deduplicator,
         pending_job_dispatches,
+        tenant_quota,
+        usage_meter,
+        transcript_store,
+        session_resume,
+        node_auth,
+        node_drain,
+        node_circuit_breaker,
+        hedged_dispatch,
         redis_runtime: redis_runtime.clone(),
         minimal_scheduler,
         pool_service,
@@ -281,13 +372,7 @@ pub async fn initialize_app(config: &Config) -> anyhow::Result<AppState> {
                         room_code: room_code.clone(),
                         message: "30分钟无人发言，房间已过期".to_string(),
                     };
-                    let expired_json = serde_json::to_string(&expired_msg).unwrap_or_default();
-                    
-                    for member in members {
-                        if let Some(member_tx) = app_state_for_cleanup.session_connections.get(&member.session_id).await {
-                            let _ = member_tx.send(axum::extract::ws::Message::Text(expired_json.clone()));
-                        }
-                    }
+                    crate::redis_runtime::broadcast_to_room_members(&app_state_for_cleanup, &members, &expired_msg, None).await;
                 }
             }
         }
@@ -1,15 +1,15 @@
 // 应用状态定义
 
-use super::{JobDispatcher, SessionManager, JobIdempotencyManager, JobResultDeduplicator, PendingJobDispatches};
+use super::{JobDispatcher, SessionManager, JobIdempotencyManager, JobResultDeduplicator, PendingJobDispatches, TenantSessionQuota};
 use crate::node_registry::NodeRegistry;
-use crate::services::{PairingService, ServiceCatalogCache, MinimalSchedulerService};
+use crate::services::{HedgedDispatch, NodeAuthService, NodeCircuitBreaker, NodeDrainService, PairingService, ServiceCatalogCache, MinimalSchedulerService, SessionResumeService, TranscriptStore, UsageMeter};
 use crate::managers::{
     AudioBufferManager, GroupManager,
     ResultQueueManager, RoomManager, SessionConnectionManager, NodeConnectionManager,
 };
 use crate::metrics::DashboardSnapshotCache;
 use crate::model_not_available::ModelNotAvailableBus;
-use super::config::WebTaskSegmentationConfig;
+use super::config::{BinaryFrameConfig, PrivatePoolConfig, WebTaskSegmentationConfig};
 use crate::redis_runtime::RedisRuntime;
 use crate::pool::PoolService;
 use crate::services::SessionMigrationOrchestrator;
@@ -29,6 +29,10 @@ pub struct AppState {
     pub model_not_available_bus: ModelNotAvailableBus,
     /// Web AudioChunk 分段配置（>pause_ms 视为任务结束）
     pub web_task_segmentation: WebTaskSegmentationConfig,
+    /// /ws/session Binary Frame 协商与限制
+    pub binary_frame: BinaryFrameConfig,
+    /// 租户私有节点池策略（租户任务优先私有池，按策略回退公共池）
+    pub private_pools: PrivatePoolConfig,
     pub session_connections: SessionConnectionManager,
     pub node_connections: NodeConnectionManager,
     pub result_queue: ResultQueueManager,
@@ -42,6 +46,22 @@ pub struct AppState {
     pub job_result_deduplicator: JobResultDeduplicator,
     /// Utterance 消息路径：按 utterance_index 顺序派发（客户端可能乱序发送 Utterance）
     pub pending_job_dispatches: PendingJobDispatches,
+    /// 租户并发会话配额（启用 Redis 运行时时多实例共享）
+    pub tenant_quota: TenantSessionQuota,
+    /// 按租户用量计量（usage_metering.enabled = false 时为 None）
+    pub usage_meter: Option<UsageMeter>,
+    /// 会话转写持久化（transcripts.enabled = false 时为 None）
+    pub transcript_store: Option<TranscriptStore>,
+    /// 断线会话恢复（session_resume.enabled = false 时为 None）
+    pub session_resume: Option<SessionResumeService>,
+    /// 节点认证（node_auth.enabled = false 时为 None）
+    pub node_auth: Option<NodeAuthService>,
+    /// 节点排空（node_drain.enabled = false 时为 None）
+    pub node_drain: Option<NodeDrainService>,
+    /// 节点熔断器（node_health.circuit_breaker.enabled = false 时为 None）
+    pub node_circuit_breaker: Option<NodeCircuitBreaker>,
+    /// 对冲派发（hedged_dispatch.enabled = false 时为 None）
+    pub hedged_dispatch: Option<HedgedDispatch>,
     /// Redis 运行时（多实例 presence、Streams inbox 等，可选）
     pub redis_runtime: Option<std::sync::Arc<RedisRuntime>>,
     /// 极简无锁调度服务（可选，需要 Redis 运行时启用）
@@ -210,6 +210,26 @@ pub fn default_status_scan_interval() -> u64 {
     30
 }
 
+pub fn default_circuit_breaker_cooldown_seconds() -> u64 {
+    30
+}
+
+pub fn default_circuit_breaker_probe_interval_ms() -> u64 {
+    5000
+}
+
+pub fn default_circuit_breaker_half_open_success_count() -> u32 {
+    2
+}
+
+pub fn default_circuit_breaker_ignored_error_codes() -> Vec<String> {
+    vec!["MODEL_NOT_AVAILABLE".to_string(), "JOB_CANCELLED".to_string()]
+}
+
+pub fn default_circuit_breaker_state_ttl_seconds() -> u64 {
+    86400
+}
+
 // Load Balancer 默认值函数
 pub fn default_resource_threshold() -> f32 {
     85.0 // 默认 85%（CPU、GPU、内存使用率超过此值将被跳过）
@@ -219,6 +239,10 @@ pub fn default_load_balancer_strategy() -> String {
     "least_connections".to_string()
 }
 
+pub fn default_candidate_sample_size() -> usize {
+    8
+}
+
 // Observability 默认值函数
 pub fn default_obs_lock_wait_warn_ms() -> u64 {
     10
@@ -289,6 +313,36 @@ pub fn default_pool_min_count() -> u16 { 1 }
 pub fn default_phase2_reclaim_interval_seconds() -> u64 { 5 }
 pub fn default_phase2_dlq_scan_interval_min_ms() -> u64 { 1000 }
 
+// TenantQuotaConfig 默认值函数
+pub fn default_tenant_session_lease_ttl_seconds() -> u64 { 60 }
+
+// UsageMeteringConfig 默认值函数
+pub fn default_usage_flush_interval_seconds() -> u64 { 10 }
+pub fn default_usage_retention_days() -> u64 { 400 }
+pub fn default_transcript_retention_days() -> u64 { 30 }
+pub fn default_transcript_max_entries_per_session() -> u64 { 10000 }
+
+// BinaryFrameConfig 默认值函数
+pub fn default_binary_frame_max_frame_bytes() -> usize { 256 * 1024 }
+
+// SessionResumeConfig 默认值函数
+pub fn default_session_resume_grace_period_seconds() -> u64 { 60 }
+pub fn default_session_resume_replay_buffer_size() -> usize { 32 }
+
+// NodeAuthConfig 默认值函数
+pub fn default_node_enrollment_ttl_seconds() -> u64 { 86400 }
+pub fn default_node_auth_challenge_timeout_seconds() -> u64 { 30 }
+
+// PairingConfig 默认值函数
+pub fn default_pairing_code_length() -> usize { 6 }
+pub fn default_pairing_code_ttl_seconds() -> u64 { 300 }
+pub fn default_pairing_max_code_ttl_seconds() -> u64 { 3600 }
+
+// NodeDrainConfig 默认值函数
+pub fn default_node_drain_in_flight_timeout_seconds() -> u64 { 300 }
+pub fn default_node_drain_poll_interval_ms() -> u64 { 2000 }
+pub fn default_node_drain_record_ttl_seconds() -> u64 { 86400 }
+
 // TestingConfig 默认值函数
 pub fn default_test_redis_url() -> String { "redis://127.0.0.1:6379".to_string() }
 pub fn default_test_service_catalog_url() -> String { "http://127.0.0.1:0".to_string() }
@@ -301,3 +355,39 @@ pub fn default_ws_connection_pool_initial_capacity() -> usize { 100 }
 pub fn default_job_cache_initial_capacity() -> usize { 1000 }
 pub fn default_session_cache_initial_capacity() -> usize { 500 }
 
+// Hedged dispatch 默认值函数
+pub fn default_hedge_latency_quantile() -> f64 {
+    0.95
+}
+
+pub fn default_hedge_default_deadline_ms() -> u64 {
+    3000
+}
+
+pub fn default_hedge_min_deadline_ms() -> u64 {
+    500
+}
+
+pub fn default_hedge_max_deadline_ms() -> u64 {
+    10000
+}
+
+pub fn default_hedge_min_samples() -> usize {
+    20
+}
+
+pub fn default_hedge_sample_window() -> usize {
+    200
+}
+
+pub fn default_hedge_budget_ratio() -> f64 {
+    0.1
+}
+
+pub fn default_hedge_budget_min_per_window() -> usize {
+    2
+}
+
+pub fn default_hedge_budget_window_seconds() -> u64 {
+    60
+}
@@ -3,9 +3,11 @@ use std::path::PathBuf;
 
 use super::config_types_redis::RedisRuntimeConfig;
 use super::config_types_scheduler::{
-    AsrRerunConfig, BackgroundTasksConfig, CoreServicesConfig, DeveloperConfig, JobTimeoutPolicyConfig,
-    LoadBalancerConfig, LimitsConfig, ModelNotAvailableConfig, NodeHealthConfig, ObservabilityConfig,
-    PerformanceConfig, RetryConfig, TaskBindingConfig, TestingConfig, TimeoutsConfig, WebTaskSegmentationConfig,
+    AsrRerunConfig, BackgroundTasksConfig, BinaryFrameConfig, CoreServicesConfig, DeveloperConfig, HedgedDispatchConfig, JobTimeoutPolicyConfig,
+    LoadBalancerConfig, LimitsConfig, ModelNotAvailableConfig, NodeAuthConfig, NodeDrainConfig, PairingConfig, PrivatePoolConfig, NodeHealthConfig, ObservabilityConfig,
+    PerformanceConfig, RetryConfig, SessionResumeConfig, TaskBindingConfig, TenantQuotaConfig, TestingConfig, TimeoutsConfig, TranscriptConfig,
+    UsageMeteringConfig,
+    WebTaskSegmentationConfig,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +35,8 @@ pub struct SchedulerConfig {
     #[serde(default)]
     pub job_timeout: JobTimeoutPolicyConfig,
     pub job_timeout_seconds: u64,
+    #[serde(default)]
+    pub hedged_dispatch: HedgedDispatchConfig,
     pub heartbeat_interval_seconds: u64,
     #[serde(default)]
     pub load_balancer: LoadBalancerConfig,
@@ -47,6 +51,24 @@ pub struct SchedulerConfig {
     #[serde(default)]
     pub limits: LimitsConfig,
     #[serde(default)]
+    pub tenant_quota: TenantQuotaConfig,
+    #[serde(default)]
+    pub usage_metering: UsageMeteringConfig,
+    #[serde(default)]
+    pub transcripts: TranscriptConfig,
+    #[serde(default)]
+    pub binary_frame: BinaryFrameConfig,
+    #[serde(default)]
+    pub session_resume: SessionResumeConfig,
+    #[serde(default)]
+    pub node_auth: NodeAuthConfig,
+    #[serde(default)]
+    pub node_drain: NodeDrainConfig,
+    #[serde(default)]
+    pub pairing: PairingConfig,
+    #[serde(default)]
+    pub private_pools: PrivatePoolConfig,
+    #[serde(default)]
     pub testing: TestingConfig,
     #[serde(default)]
     pub performance: PerformanceConfig,
@@ -91,6 +113,7 @@ impl Default for SchedulerConfig {
             job_timeout_seconds: 30,
             heartbeat_interval_seconds: 15,
             job_timeout: JobTimeoutPolicyConfig::default(),
+            hedged_dispatch: HedgedDispatchConfig::default(),
             load_balancer: LoadBalancerConfig::default(),
             node_health: NodeHealthConfig::default(),
             model_not_available: ModelNotAvailableConfig::default(),
@@ -104,6 +127,15 @@ impl Default for SchedulerConfig {
             timeouts: TimeoutsConfig::default(),
             retry: RetryConfig::default(),
             limits: LimitsConfig::default(),
+            tenant_quota: TenantQuotaConfig::default(),
+            usage_metering: UsageMeteringConfig::default(),
+            transcripts: TranscriptConfig::default(),
+            binary_frame: BinaryFrameConfig::default(),
+            session_resume: SessionResumeConfig::default(),
+            node_auth: NodeAuthConfig::default(),
+            node_drain: NodeDrainConfig::default(),
+            pairing: PairingConfig::default(),
+            private_pools: PrivatePoolConfig::default(),
             testing: TestingConfig::default(),
             performance: PerformanceConfig::default(),
             developer: DeveloperConfig::default(),
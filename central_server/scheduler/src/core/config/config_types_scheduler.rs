@@ -16,6 +16,51 @@ pub struct JobTimeoutPolicyConfig {
     pub send_cancel: bool,
 }
 
+/// 对冲派发：任务派发后在池内 p95 开始时延内仍未收到 job_started / asr_partial 时，
+/// 向另一节点派发同一 attempt，先返回的成功结果胜出，另一节点收到 job_cancel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HedgedDispatchConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// 未单独配置的租户（及无租户任务）是否对冲
+    #[serde(default)]
+    pub default_enabled: bool,
+    /// 按租户覆盖：tenant_id -> 是否对冲
+    #[serde(default)]
+    pub tenants: std::collections::HashMap<String, bool>,
+    /// 对冲时限取池内「派发 → job_started」时延的该分位数
+    #[serde(default = "super::config_defaults::default_hedge_latency_quantile")]
+    pub latency_quantile: f64,
+    /// 样本不足 min_samples 时使用的时限（毫秒）
+    #[serde(default = "super::config_defaults::default_hedge_default_deadline_ms")]
+    pub default_deadline_ms: u64,
+    #[serde(default = "super::config_defaults::default_hedge_min_deadline_ms")]
+    pub min_deadline_ms: u64,
+    #[serde(default = "super::config_defaults::default_hedge_max_deadline_ms")]
+    pub max_deadline_ms: u64,
+    #[serde(default = "super::config_defaults::default_hedge_min_samples")]
+    pub min_samples: usize,
+    /// 每个池保留的最近样本数
+    #[serde(default = "super::config_defaults::default_hedge_sample_window")]
+    pub sample_window: usize,
+    /// 对冲预算：窗口内对冲次数不超过 budget_ratio × 开始处理的任务数（至少 budget_min_per_window 次）
+    #[serde(default = "super::config_defaults::default_hedge_budget_ratio")]
+    pub budget_ratio: f64,
+    #[serde(default = "super::config_defaults::default_hedge_budget_min_per_window")]
+    pub budget_min_per_window: usize,
+    #[serde(default = "super::config_defaults::default_hedge_budget_window_seconds")]
+    pub budget_window_seconds: u64,
+}
+
+impl HedgedDispatchConfig {
+    pub fn enabled_for(&self, tenant_id: Option<&str>) -> bool {
+        self.enabled
+            && tenant_id
+                .and_then(|t| self.tenants.get(t).copied())
+                .unwrap_or(self.default_enabled)
+    }
+}
+
 /// 核心链路服务包 ID（与 ModelHub services_index.json 中的 service_id 对齐）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoreServicesConfig {
@@ -98,6 +143,9 @@ pub struct NodeHealthConfig {
     pub failure_threshold: FailureThreshold,
     #[serde(default = "super::config_defaults::default_status_scan_interval")]
     pub status_scan_interval_seconds: u64,
+    /// 按节点 × 服务类型的熔断器（阈值取自 failure_threshold）
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,12 +155,47 @@ pub struct FailureThreshold {
     pub consecutive_failure_count: usize,
 }
 
+/// 节点熔断器：由 JobResult / JobError / 任务超时 / ASR 质量驱动
+/// closed --(窗口内失败数或连续失败数达到 failure_threshold)--> open --(冷却结束)--> half_open
+/// half_open 按间隔放行探测任务，连续成功 half_open_success_count 次后恢复 closed，失败则重新 open
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    #[serde(default = "super::config_defaults::default_true")]
+    pub enabled: bool,
+    /// open 状态冷却时间（秒）
+    #[serde(default = "super::config_defaults::default_circuit_breaker_cooldown_seconds")]
+    pub cooldown_seconds: u64,
+    /// half_open 状态下两次探测任务的最小间隔（毫秒）
+    #[serde(default = "super::config_defaults::default_circuit_breaker_probe_interval_ms")]
+    pub probe_interval_ms: u64,
+    /// half_open 恢复 closed 所需的连续成功次数
+    #[serde(default = "super::config_defaults::default_circuit_breaker_half_open_success_count")]
+    pub half_open_success_count: u32,
+    /// ASR quality_level = "bad" 是否计为 ASR 失败
+    #[serde(default = "super::config_defaults::default_true")]
+    pub count_bad_asr_quality: bool,
+    /// 不计入熔断的错误码（如 MODEL_NOT_AVAILABLE 已由服务临时不可用标记处理）
+    #[serde(default = "super::config_defaults::default_circuit_breaker_ignored_error_codes")]
+    pub ignored_error_codes: Vec<String>,
+    /// 熔断状态在 Redis 中的保留时间（秒，每次更新刷新）
+    #[serde(default = "super::config_defaults::default_circuit_breaker_state_ttl_seconds")]
+    pub state_ttl_seconds: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoadBalancerConfig {
+    /// 默认节点选择策略：random | least_reserved | weighted_efficiency | power_of_two | session_sticky
+    /// （least_connections 为旧值，等同 least_reserved）
     #[serde(default = "super::config_defaults::default_load_balancer_strategy")]
     pub strategy: String,
     #[serde(default = "super::config_defaults::default_resource_threshold")]
     pub resource_threshold: f32,
+    /// 按池覆盖策略：pair_key（如 "zh:en"，租户池可写 "tenant:acme:zh:en"）-> 策略名
+    #[serde(default)]
+    pub pool_strategies: std::collections::HashMap<String, String>,
+    /// 非 random 策略每次从池中采样的候选节点数
+    #[serde(default = "super::config_defaults::default_candidate_sample_size")]
+    pub candidate_sample_size: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -222,6 +305,170 @@ pub struct LimitsConfig {
     pub redis_dlq_scan_interval_min_ms: u64,
 }
 
+/// 租户并发会话配额（session_init 时占用，SessionClose / 连接断开时释放）
+///
+/// 上限以本配置为准；session_init.tenant_max_sessions（API Gateway 按租户记录填写）只能在此基础上收紧。
+/// 启用 Redis 运行时后配额在多实例间共享（每个租户一个 ZSET，成员为 session_id，score 为租约到期时间）。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantQuotaConfig {
+    /// 未单独配置的租户默认上限（0 表示不限制）
+    #[serde(default)]
+    pub default_max_sessions: usize,
+    /// 按租户覆盖上限：tenant_id -> max_sessions（0 表示不限制）
+    #[serde(default)]
+    pub tenant_max_sessions: std::collections::HashMap<String, usize>,
+    /// Redis 租约 TTL（秒）；实例崩溃未释放的占用在 TTL 后自动回收
+    #[serde(default = "super::config_defaults::default_tenant_session_lease_ttl_seconds")]
+    pub lease_ttl_seconds: u64,
+}
+
+/// 按租户的用量计量（小时汇总写入 Redis）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageMeteringConfig {
+    #[serde(default = "super::config_defaults::default_true")]
+    pub enabled: bool,
+    /// 内存增量写入 Redis 的间隔（秒）
+    #[serde(default = "super::config_defaults::default_usage_flush_interval_seconds")]
+    pub flush_interval_seconds: u64,
+    /// 小时汇总保留天数
+    #[serde(default = "super::config_defaults::default_usage_retention_days")]
+    pub retention_days: u64,
+}
+
+/// 会话转写持久化
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptConfig {
+    /// 总开关（关闭后 session_init.persist_transcript 也不生效）
+    #[serde(default = "super::config_defaults::default_true")]
+    pub enabled: bool,
+    /// 租户未配置且 session_init 未指定时是否持久化
+    #[serde(default)]
+    pub default_enabled: bool,
+    /// 按租户设置：true 默认持久化，false 禁止持久化（忽略 session_init）
+    #[serde(default)]
+    pub tenants: std::collections::HashMap<String, bool>,
+    /// 保留天数（每次写入刷新）
+    #[serde(default = "super::config_defaults::default_transcript_retention_days")]
+    pub retention_days: u64,
+    /// 单会话最多保留条数（超出丢弃最早的记录；0 表示不限）
+    #[serde(default = "super::config_defaults::default_transcript_max_entries_per_session")]
+    pub max_entries_per_session: u64,
+}
+
+/// /ws/session Binary Frame（session_init.supports_binary_frame 协商后启用）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinaryFrameConfig {
+    /// 关闭后 ack 始终返回 use_binary_frame = false，客户端继续使用 JSON audio_chunk
+    #[serde(default = "super::config_defaults::default_true")]
+    pub enabled: bool,
+    /// 单帧最大字节数（含头部），超出的帧直接拒绝
+    #[serde(default = "super::config_defaults::default_binary_frame_max_frame_bytes")]
+    pub max_frame_bytes: usize,
+}
+
+/// WebSocket 断线后的会话恢复（session_resume）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionResumeConfig {
+    /// 关闭后连接断开即销毁会话，ack 不下发 resume_token
+    #[serde(default = "super::config_defaults::default_true")]
+    pub enabled: bool,
+    /// 断开后保留会话的宽限期（秒）
+    #[serde(default = "super::config_defaults::default_session_resume_grace_period_seconds")]
+    pub grace_period_seconds: u64,
+    /// 宽限期内最多保留的待重放结果条数
+    #[serde(default = "super::config_defaults::default_session_resume_replay_buffer_size")]
+    pub replay_buffer_size: usize,
+}
+
+/// /ws/node 节点认证（node_register 后下发 challenge，凭节点密钥签名应答）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeAuthConfig {
+    /// 关闭时不做认证，node_id 冲突一律拒绝；开启后认证通过的注册可接管本实例上的旧连接
+    #[serde(default)]
+    pub enabled: bool,
+    /// 预共享密钥：node_id -> secret
+    #[serde(default)]
+    pub node_secrets: std::collections::HashMap<String, String>,
+    /// enrollment token 签名密钥（为空时不能签发/使用 enrollment token）
+    #[serde(default)]
+    pub enrollment_key: String,
+    /// enrollment token 默认有效期（秒）；token 只用于首次接入，之后节点使用长期凭证
+    #[serde(default = "super::config_defaults::default_node_enrollment_ttl_seconds")]
+    pub enrollment_ttl_seconds: u64,
+    /// 长期节点凭证签名密钥（为空时使用 enrollment_key）
+    #[serde(default)]
+    pub credential_key: String,
+    /// challenge 应答超时（秒）
+    #[serde(default = "super::config_defaults::default_node_auth_challenge_timeout_seconds")]
+    pub challenge_timeout_seconds: u64,
+    /// 静态吊销名单（运行时吊销写入 Redis）
+    #[serde(default)]
+    pub revoked_node_ids: Vec<String>,
+    /// 签发 enrollment / 吊销接口的 Bearer token（为空时接口禁用）
+    #[serde(default)]
+    pub admin_token: String,
+    /// 认证节点声明的 migration_endpoint 允许的主机（host 或 IP，精确匹配）；
+    /// 为空时只接受 IP 字面量，且拒绝回环、链路本地（含云元数据地址）、未指定与组播地址
+    #[serde(default)]
+    pub migration_endpoint_hosts: Vec<String>,
+}
+
+/// 私有节点池：租户任务优先派发到其自有节点（节点注册时声明 owner_tenant_id，须经凭证或 node_owner_tenants 确认）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivatePoolConfig {
+    #[serde(default = "super::config_defaults::default_true")]
+    pub enabled: bool,
+    /// 租户私有池无可用节点时是否回退到公共池（未单独配置的租户）
+    #[serde(default = "super::config_defaults::default_true")]
+    pub default_allow_public_fallback: bool,
+    /// 按租户覆盖：tenant_id -> 是否允许回退公共池
+    #[serde(default)]
+    pub tenant_allow_public_fallback: std::collections::HashMap<String, bool>,
+    /// 节点归属租户：node_id -> tenant_id。凭证未绑定租户的节点（未启用认证或使用预共享密钥）
+    /// 只能声明此处配置的租户，声明其他租户时拒绝注册
+    #[serde(default)]
+    pub node_owner_tenants: std::collections::HashMap<String, String>,
+}
+
+impl PrivatePoolConfig {
+    pub fn allows_public_fallback(&self, tenant_id: &str) -> bool {
+        self.tenant_allow_public_fallback
+            .get(tenant_id)
+            .copied()
+            .unwrap_or(self.default_allow_public_fallback)
+    }
+}
+
+/// 私有节点配对码（节点经 /ws/node 申请，客户端在 session_init 中出示）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairingConfig {
+    /// 配对码长度（4..=16）
+    #[serde(default = "super::config_defaults::default_pairing_code_length")]
+    pub code_length: usize,
+    /// 节点未指定时的有效期（秒）
+    #[serde(default = "super::config_defaults::default_pairing_code_ttl_seconds")]
+    pub code_ttl_seconds: u64,
+    /// 节点可申请的最长有效期（秒）
+    #[serde(default = "super::config_defaults::default_pairing_max_code_ttl_seconds")]
+    pub max_code_ttl_seconds: u64,
+}
+
+/// 节点排空（移出池 → 等待在途任务 → 迁移绑定会话 → 下线）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeDrainConfig {
+    #[serde(default = "super::config_defaults::default_true")]
+    pub enabled: bool,
+    /// 等待在途任务完成的上限（秒），超时后继续迁移会话
+    #[serde(default = "super::config_defaults::default_node_drain_in_flight_timeout_seconds")]
+    pub in_flight_timeout_seconds: u64,
+    /// 轮询在途任务的间隔（毫秒）
+    #[serde(default = "super::config_defaults::default_node_drain_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// 排空记录在 Redis 中的保留时间（秒）
+    #[serde(default = "super::config_defaults::default_node_drain_record_ttl_seconds")]
+    pub record_ttl_seconds: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestingConfig {
     #[serde(default = "super::config_defaults::default_test_redis_url")]
@@ -267,6 +514,25 @@ impl Default for JobTimeoutPolicyConfig {
     }
 }
 
+impl Default for HedgedDispatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            default_enabled: false,
+            tenants: std::collections::HashMap::new(),
+            latency_quantile: super::config_defaults::default_hedge_latency_quantile(),
+            default_deadline_ms: super::config_defaults::default_hedge_default_deadline_ms(),
+            min_deadline_ms: super::config_defaults::default_hedge_min_deadline_ms(),
+            max_deadline_ms: super::config_defaults::default_hedge_max_deadline_ms(),
+            min_samples: super::config_defaults::default_hedge_min_samples(),
+            sample_window: super::config_defaults::default_hedge_sample_window(),
+            budget_ratio: super::config_defaults::default_hedge_budget_ratio(),
+            budget_min_per_window: super::config_defaults::default_hedge_budget_min_per_window(),
+            budget_window_seconds: super::config_defaults::default_hedge_budget_window_seconds(),
+        }
+    }
+}
+
 impl Default for CoreServicesConfig {
     fn default() -> Self {
         Self {
@@ -340,6 +606,21 @@ impl Default for NodeHealthConfig {
             warmup_timeout_seconds: super::config_defaults::default_warmup_timeout(),
             failure_threshold: FailureThreshold::default(),
             status_scan_interval_seconds: super::config_defaults::default_status_scan_interval(),
+            circuit_breaker: CircuitBreakerConfig::default(),
+        }
+    }
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cooldown_seconds: super::config_defaults::default_circuit_breaker_cooldown_seconds(),
+            probe_interval_ms: super::config_defaults::default_circuit_breaker_probe_interval_ms(),
+            half_open_success_count: super::config_defaults::default_circuit_breaker_half_open_success_count(),
+            count_bad_asr_quality: true,
+            ignored_error_codes: super::config_defaults::default_circuit_breaker_ignored_error_codes(),
+            state_ttl_seconds: super::config_defaults::default_circuit_breaker_state_ttl_seconds(),
         }
     }
 }
@@ -349,6 +630,8 @@ impl Default for LoadBalancerConfig {
         Self {
             strategy: super::config_defaults::default_load_balancer_strategy(),
             resource_threshold: super::config_defaults::default_resource_threshold(),
+            pool_strategies: std::collections::HashMap::new(),
+            candidate_sample_size: super::config_defaults::default_candidate_sample_size(),
         }
     }
 }
@@ -436,6 +719,105 @@ impl Default for LimitsConfig {
     }
 }
 
+impl Default for TenantQuotaConfig {
+    fn default() -> Self {
+        Self {
+            default_max_sessions: 0,
+            tenant_max_sessions: std::collections::HashMap::new(),
+            lease_ttl_seconds: super::config_defaults::default_tenant_session_lease_ttl_seconds(),
+        }
+    }
+}
+
+impl Default for TranscriptConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_enabled: false,
+            tenants: std::collections::HashMap::new(),
+            retention_days: super::config_defaults::default_transcript_retention_days(),
+            max_entries_per_session: super::config_defaults::default_transcript_max_entries_per_session(),
+        }
+    }
+}
+
+impl Default for NodeAuthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            node_secrets: std::collections::HashMap::new(),
+            enrollment_key: String::new(),
+            enrollment_ttl_seconds: super::config_defaults::default_node_enrollment_ttl_seconds(),
+            credential_key: String::new(),
+            challenge_timeout_seconds: super::config_defaults::default_node_auth_challenge_timeout_seconds(),
+            revoked_node_ids: Vec::new(),
+            admin_token: String::new(),
+            migration_endpoint_hosts: Vec::new(),
+        }
+    }
+}
+
+impl Default for PrivatePoolConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_allow_public_fallback: true,
+            tenant_allow_public_fallback: std::collections::HashMap::new(),
+            node_owner_tenants: std::collections::HashMap::new(),
+        }
+    }
+}
+
+impl Default for PairingConfig {
+    fn default() -> Self {
+        Self {
+            code_length: super::config_defaults::default_pairing_code_length(),
+            code_ttl_seconds: super::config_defaults::default_pairing_code_ttl_seconds(),
+            max_code_ttl_seconds: super::config_defaults::default_pairing_max_code_ttl_seconds(),
+        }
+    }
+}
+
+impl Default for NodeDrainConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            in_flight_timeout_seconds: super::config_defaults::default_node_drain_in_flight_timeout_seconds(),
+            poll_interval_ms: super::config_defaults::default_node_drain_poll_interval_ms(),
+            record_ttl_seconds: super::config_defaults::default_node_drain_record_ttl_seconds(),
+        }
+    }
+}
+
+impl Default for SessionResumeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            grace_period_seconds: super::config_defaults::default_session_resume_grace_period_seconds(),
+            replay_buffer_size: super::config_defaults::default_session_resume_replay_buffer_size(),
+        }
+    }
+}
+
+impl Default for BinaryFrameConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_frame_bytes: super::config_defaults::default_binary_frame_max_frame_bytes(),
+        }
+    }
+}
+
+impl Default for UsageMeteringConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            flush_interval_seconds: super::config_defaults::default_usage_flush_interval_seconds(),
+            retention_days: super::config_defaults::default_usage_retention_days(),
+        }
+    }
+}
+
 impl Default for TestingConfig {
     fn default() -> Self {
         Self {
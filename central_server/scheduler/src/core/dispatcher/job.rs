@@ -82,6 +82,15 @@ pub struct Job {
     /// 预计处理时长（毫秒），用于动态计算 timeout
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expected_duration_ms: Option<u64>,
+    /// NMT 上下文（创建时取自 session 所在实例的 Utterance Group；重派/对冲沿用，不依赖其他实例的 GroupManager）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_text: Option<String>,
+    /// 对冲派发的第二个节点（与 assigned_node_id 共用 dispatch_attempt_id，先成功者胜出）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hedge_node_id: Option<String>,
+    /// 发起对冲的时间戳（ms）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hedged_at_ms: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -100,5 +109,17 @@ pub(crate) fn is_zero_u32(v: &u32) -> bool {
 }
 
 impl Job {
+    /// 对冲中另一方节点（node_id 不属于当前派发时为 None）
+    pub fn hedge_counterpart(&self, node_id: &str) -> Option<&str> {
+        let hedge = self.hedge_node_id.as_deref()?;
+        let primary = self.assigned_node_id.as_deref()?;
+        if node_id == primary {
+            Some(hedge)
+        } else if node_id == hedge {
+            Some(primary)
+        } else {
+            None
+        }
+    }
 }
 
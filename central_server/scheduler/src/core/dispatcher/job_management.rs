@@ -110,6 +110,39 @@ impl JobDispatcher {
         }
     }
 
+    /// 对冲派发：原子性占用第二个节点（同一 dispatch_attempt_id）
+    /// 返回 true 表示占用成功（其他实例未抢先对冲、attempt 未变化）
+    pub async fn claim_job_hedge(&self, job: &Job, hedge_node_id: &str, now_ms: i64) -> bool {
+        match self
+            .job_repo
+            .claim_job_hedge_atomic(&job.job_id, hedge_node_id, job.dispatch_attempt_id, now_ms)
+            .await
+        {
+            Ok(1) => true,
+            Ok(code) => {
+                tracing::debug!(job_id = %job.job_id, hedge_node_id = %hedge_node_id, code = code, "claim_job_hedge: 未占用");
+                false
+            }
+            Err(e) => {
+                tracing::warn!(job_id = %job.job_id, error = %e, "claim_job_hedge: Lua 脚本执行失败");
+                false
+            }
+        }
+    }
+
+    /// 对冲派发：结束对冲，保留 keep_node_id 为当前节点
+    /// 返回 true 表示本次调用结束了对冲（已被其他结果/实例结束时返回 false）
+    pub async fn resolve_job_hedge(&self, job_id: &str, attempt_id: u32, keep_node_id: &str) -> bool {
+        match self.job_repo.resolve_job_hedge_atomic(job_id, attempt_id, keep_node_id).await {
+            Ok(1) => true,
+            Ok(_) => false,
+            Err(e) => {
+                tracing::warn!(job_id = %job_id, error = %e, "resolve_job_hedge: Lua 脚本执行失败");
+                false
+            }
+        }
+    }
+
     pub async fn required_types_for_job(&self, job: &Job) -> anyhow::Result<Vec<crate::messages::ServiceType>> {
         self.get_required_types_for_features(&job.pipeline, job.features.as_ref(), &job.src_lang, &job.tgt_lang)
     }
@@ -14,6 +14,16 @@ const KEY_PREFIX: &str = "lingua:v1";
 /// Job 数据 TTL（秒）- 1小时
 const JOB_TTL_SECS: i64 = 3600;
 
+/// 由 Lua 脚本直接维护的 Hash 字段（顺序与 apply_lua_managed_fields 对应）
+const LUA_MANAGED_FIELDS: [&str; 6] = [
+    "dispatched_to_node",
+    "dispatched_at_ms",
+    "dispatch_attempt_id",
+    "assigned_node_id",
+    "hedge_node_id",
+    "hedged_at_ms",
+];
+
 /// Redis Job 仓储（无状态，SSOT）
 #[derive(Clone)]
 pub struct JobRedisRepository {
@@ -62,6 +72,10 @@ redis.call('HSET', key, 'dispatch_attempt_id', dispatch_attempt_id)
 if assigned_node_id ~= '' then
     redis.call('HSET', key, 'assigned_node_id', assigned_node_id)
 end
+-- 对冲字段只由对冲脚本清除，这里不删除（避免过期副本覆盖并发占用）
+if ARGV[7] ~= '' then
+    redis.call('HSET', key, 'hedge_node_id', ARGV[7], 'hedged_at_ms', ARGV[8])
+end
 
 -- 存储完整 JSON（用于完整数据读取）
 redis.call('HSET', key, '_json', json)
@@ -81,7 +95,9 @@ return 1
             .arg(job.dispatched_at_ms.map(|v| v.to_string()).unwrap_or_else(|| "".to_string()))
             .arg(job.dispatch_attempt_id.to_string())
             .arg(job.assigned_node_id.as_deref().unwrap_or(""))
-            .arg(JOB_TTL_SECS);
+            .arg(JOB_TTL_SECS)
+            .arg(job.hedge_node_id.as_deref().unwrap_or(""))
+            .arg(job.hedged_at_ms.map(|v| v.to_string()).unwrap_or_default());
         
         self.redis.query::<i64>(cmd).await
             .map_err(|e| anyhow!("Redis EVAL 保存 Job 失败: {}", e))?;
@@ -102,16 +118,20 @@ return 1
             return Ok(None);
         }
         
-        // 优先尝试从 _json 字段读取（完整数据）
-        let mut cmd = redis::cmd("HGET");
-        cmd.arg(&key).arg("_json");
-        let job_json: Option<String> = self.redis.query(cmd).await
-            .map_err(|e| anyhow!("Redis HGET 失败: {}", e))?;
+        // 优先尝试从 _json 字段读取（完整数据），并带回 Lua 脚本直接维护的 Hash 字段
+        let mut cmd = redis::cmd("HMGET");
+        cmd.arg(&key).arg("_json").arg(&LUA_MANAGED_FIELDS[..]);
+        let mut values: Vec<Option<String>> = self.redis.query(cmd).await
+            .map_err(|e| anyhow!("Redis HMGET 失败: {}", e))?;
+        let job_json = if values.is_empty() { None } else { values.remove(0) };
         
         if let Some(json) = job_json {
             // 从 JSON 反序列化（完整数据）
             match serde_json::from_str::<Job>(&json) {
-                Ok(job) => return Ok(Some(job)),
+                Ok(mut job) => {
+                    Self::apply_lua_managed_fields(&mut job, &values);
+                    return Ok(Some(job));
+                }
                 Err(e) => {
                     // JSON 解析失败，尝试从 Hash 字段重建
                     debug!(job_id = %job_id, error = %e, "从 _json 字段解析失败，尝试从 Hash 字段重建");
@@ -134,6 +154,26 @@ return 1
         Ok(Some(job))
     }
     
+    /// mark_job_dispatched / failover_reassign_job / 对冲脚本只更新 Hash 字段，不改写 _json；
+    /// 读取时以 Hash 字段为准，避免 _json 中的派发状态过期（再经 save_job 写回时覆盖 Hash）
+    fn apply_lua_managed_fields(job: &mut Job, values: &[Option<String>]) {
+        let field = |i: usize| values.get(i).and_then(|v| v.as_deref()).filter(|v| !v.is_empty());
+        if let Some(v) = field(0) {
+            job.dispatched_to_node = v == "true";
+        }
+        if let Some(v) = field(1).and_then(|v| v.parse().ok()) {
+            job.dispatched_at_ms = Some(v);
+        }
+        if let Some(v) = field(2).and_then(|v| v.parse().ok()) {
+            job.dispatch_attempt_id = v;
+        }
+        if let Some(v) = field(3) {
+            job.assigned_node_id = Some(v.to_string());
+        }
+        job.hedge_node_id = field(4).map(String::from);
+        job.hedged_at_ms = field(5).and_then(|v| v.parse().ok());
+    }
+    
     /// 从 Hash 字段重建 Job（用于兼容）
     fn reconstruct_job_from_hash(&self, hash: &std::collections::HashMap<String, String>, job_id: &str) -> Result<Job> {
         // 如果 _json 存在，优先使用 JSON（完整数据）
@@ -207,6 +247,43 @@ return 1
         ).await
     }
     
+    /// 原子性占用对冲（使用 Lua 脚本）：仅当前 attempt、已派发且尚未对冲时写入 hedge_node_id
+    /// 返回: 0=NotFound, -1=StaleCaller, -2=AlreadyHedged/未派发, 1=Claimed
+    pub async fn claim_job_hedge_atomic(
+        &self,
+        job_id: &str,
+        hedge_node_id: &str,
+        expected_attempt_id: u32,
+        now_ms: i64,
+    ) -> Result<i64> {
+        let script = include_str!("../../../scripts/lua/claim_job_hedge.lua");
+        let key = Self::job_key(job_id);
+        
+        self.eval_lua_script::<i64>(
+            script,
+            &[&key],
+            &[hedge_node_id, &expected_attempt_id.to_string(), &now_ms.to_string()],
+        ).await
+    }
+    
+    /// 原子性结束对冲（使用 Lua 脚本）：保留 keep_node_id 为 assigned_node_id 并清除对冲字段
+    /// 返回: 0=NotFound/无对冲, -1=StaleCaller, -2=keep_node_id 不属于当前派发, 1=Resolved
+    pub async fn resolve_job_hedge_atomic(
+        &self,
+        job_id: &str,
+        expected_attempt_id: u32,
+        keep_node_id: &str,
+    ) -> Result<i64> {
+        let script = include_str!("../../../scripts/lua/resolve_job_hedge.lua");
+        let key = Self::job_key(job_id);
+        
+        self.eval_lua_script::<i64>(
+            script,
+            &[&key],
+            &[&expected_attempt_id.to_string(), keep_node_id],
+        ).await
+    }
+    
     /// 更新 Job 状态
     pub async fn update_job_status(&self, job_id: &str, status: JobStatus) -> Result<()> {
         // 获取当前 Job
@@ -147,6 +147,14 @@ impl JobResultDeduplicator {
         }
     }
 
+    /// 移除单个 job 的记录（对冲派发中一方失败被忽略时调用，允许另一方的结果正常处理）
+    pub async fn forget(&self, session_id: &str, job_id: &str) {
+        let mut records = self.records.write().await;
+        if let Some(session_records) = records.get_mut(session_id) {
+            session_records.remove(job_id);
+        }
+    }
+
     /// 移除session的所有记录（当session结束时调用）
     pub async fn remove_session(&self, session_id: &str) {
         let mut records = self.records.write().await;
@@ -5,6 +5,7 @@ pub mod session;
 pub mod job_idempotency;
 pub mod job_result_deduplicator;
 pub mod pending_job_dispatches;
+pub mod tenant_quota;
 
 #[cfg(test)]
 mod job_idempotency_test;
@@ -16,4 +17,5 @@ pub use session::SessionManager;
 pub use job_idempotency::JobIdempotencyManager;
 pub use job_result_deduplicator::JobResultDeduplicator;
 pub use pending_job_dispatches::PendingJobDispatches;
+pub use tenant_quota::TenantSessionQuota;
 
@@ -96,6 +96,9 @@ mod tests {
             is_max_duration_triggered: false,
             turn_id: None,
             expected_duration_ms: None,
+            context_text: None,
+            hedge_node_id: None,
+            hedged_at_ms: None,
         }
     }
 
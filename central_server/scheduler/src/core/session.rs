@@ -121,6 +121,12 @@ impl SessionManager {
         session
     }
 
+    /// 按快照恢复会话（session_resume 跨实例接管）
+    pub async fn restore_session(&self, session: Session) {
+        let mut sessions = self.sessions.write().await;
+        sessions.insert(session.session_id.clone(), session);
+    }
+
     pub async fn get_session(&self, session_id: &str) -> Option<Session> {
         let sessions = self.sessions.read().await;
         sessions.get(session_id).cloned()
@@ -0,0 +1,214 @@
+// 租户并发会话配额
+//
+// session_init 时占用名额，SessionClose 或 WebSocket 断开时释放（release 幂等，两条路径都会调用）。
+// 启用 Redis 运行时以 Redis 计数为准（多实例共享）；Redis 调用失败时退化为本实例计数，
+// 避免依赖故障导致所有新会话被拒绝。
+
+use super::config::TenantQuotaConfig;
+use crate::redis_runtime::RedisRuntime;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+/// 占用失败：租户已达上限
+#[derive(Debug, Clone, PartialEq)]
+pub struct TenantSessionLimitExceeded {
+    pub tenant_id: String,
+    pub max_sessions: usize,
+    pub active_sessions: u64,
+}
+
+#[derive(Clone)]
+pub struct TenantSessionQuota {
+    config: TenantQuotaConfig,
+    /// 本实例持有的占用：session_id -> tenant_id
+    held: Arc<RwLock<HashMap<String, String>>>,
+    redis_runtime: Option<Arc<RedisRuntime>>,
+}
+
+impl TenantSessionQuota {
+    pub fn new(config: TenantQuotaConfig, redis_runtime: Option<Arc<RedisRuntime>>) -> Self {
+        Self {
+            config,
+            held: Arc::new(RwLock::new(HashMap::new())),
+            redis_runtime,
+        }
+    }
+
+    /// 租户的会话上限（0 表示不限制）
+    ///
+    /// `declared` 为 session_init 携带的租户记录上限（由客户端发送，不可信）：只能收紧静态配置，
+    /// 不能放宽；为 0 时视为未声明，不表示不限制。
+    pub fn max_sessions_for(&self, tenant_id: &str, declared: Option<usize>) -> usize {
+        let configured = self
+            .config
+            .tenant_max_sessions
+            .get(tenant_id)
+            .copied()
+            .unwrap_or(self.config.default_max_sessions);
+        match declared.filter(|&d| d > 0) {
+            Some(declared) if configured == 0 => declared,
+            Some(declared) => declared.min(configured),
+            None => configured,
+        }
+    }
+
+    /// 为 session 占用租户名额
+    pub async fn try_acquire(
+        &self,
+        tenant_id: &str,
+        session_id: &str,
+        declared_max_sessions: Option<usize>,
+    ) -> Result<(), TenantSessionLimitExceeded> {
+        let max_sessions = self.max_sessions_for(tenant_id, declared_max_sessions);
+        if max_sessions == 0 {
+            return Ok(());
+        }
+
+        if let Some(rt) = self.redis_runtime.as_ref() {
+            match rt
+                .tenant_session_try_acquire(tenant_id, session_id, max_sessions, self.config.lease_ttl_seconds)
+                .await
+            {
+                Ok((true, _)) => {
+                    self.held
+                        .write()
+                        .await
+                        .insert(session_id.to_string(), tenant_id.to_string());
+                    return Ok(());
+                }
+                Ok((false, active_sessions)) => {
+                    return Err(TenantSessionLimitExceeded {
+                        tenant_id: tenant_id.to_string(),
+                        max_sessions,
+                        active_sessions,
+                    });
+                }
+                Err(e) => {
+                    warn!(tenant_id = %tenant_id, error = %e, "租户会话配额：Redis 不可用，退化为本实例计数");
+                }
+            }
+        }
+
+        let mut held = self.held.write().await;
+        if held.contains_key(session_id) {
+            return Ok(());
+        }
+        let active_sessions = held.values().filter(|t| t.as_str() == tenant_id).count();
+        if active_sessions >= max_sessions {
+            return Err(TenantSessionLimitExceeded {
+                tenant_id: tenant_id.to_string(),
+                max_sessions,
+                active_sessions: active_sessions as u64,
+            });
+        }
+        held.insert(session_id.to_string(), tenant_id.to_string());
+        Ok(())
+    }
+
+    /// 释放 session 占用的名额（幂等）
+    pub async fn release(&self, session_id: &str) {
+        let tenant_id = self.held.write().await.remove(session_id);
+        if let (Some(tenant_id), Some(rt)) = (tenant_id, self.redis_runtime.as_ref()) {
+            rt.tenant_session_release(&tenant_id, session_id).await;
+        }
+    }
+
+    /// 接管其他实例的会话（session_resume）：沿用原租约，由本实例续约
+    pub async fn adopt(&self, tenant_id: &str, session_id: &str) {
+        self.held
+            .write()
+            .await
+            .insert(session_id.to_string(), tenant_id.to_string());
+    }
+
+    /// 会话已被其他实例接管：只移除本实例的记录，不释放 Redis 租约
+    pub async fn forget(&self, session_id: &str) {
+        self.held.write().await.remove(session_id);
+    }
+
+    /// 续约本实例持有的 Redis 租约（由 Redis 后台任务周期调用）
+    pub async fn renew_leases(&self) {
+        let Some(rt) = self.redis_runtime.as_ref() else {
+            return;
+        };
+        let held: Vec<(String, String)> = self
+            .held
+            .read()
+            .await
+            .iter()
+            .map(|(s, t)| (s.clone(), t.clone()))
+            .collect();
+        for (session_id, tenant_id) in held {
+            rt.tenant_session_renew(&tenant_id, &session_id, self.config.lease_ttl_seconds)
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quota(default_max_sessions: usize) -> TenantSessionQuota {
+        let config = TenantQuotaConfig {
+            default_max_sessions,
+            tenant_max_sessions: HashMap::from([("vip".to_string(), 0)]),
+            ..TenantQuotaConfig::default()
+        };
+        TenantSessionQuota::new(config, None)
+    }
+
+    #[tokio::test]
+    async fn rejects_over_limit_and_frees_slot_on_release() {
+        let q = quota(2);
+        q.try_acquire("t1", "s1", None).await.unwrap();
+        q.try_acquire("t1", "s2", None).await.unwrap();
+        // 同一 session 重复占用不额外计数
+        q.try_acquire("t1", "s2", None).await.unwrap();
+
+        let err = q.try_acquire("t1", "s3", None).await.unwrap_err();
+        assert_eq!(err.max_sessions, 2);
+        assert_eq!(err.active_sessions, 2);
+        // 其他租户不受影响
+        q.try_acquire("t2", "s4", None).await.unwrap();
+
+        q.release("s1").await;
+        q.release("s1").await;
+        q.try_acquire("t1", "s3", None).await.unwrap();
+        assert!(q.try_acquire("t1", "s5", None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn zero_limit_means_unlimited() {
+        let q = quota(1);
+        for i in 0..5 {
+            q.try_acquire("vip", &format!("s{}", i), None).await.unwrap();
+        }
+        let q = quota(0);
+        for i in 0..5 {
+            q.try_acquire("t1", &format!("s{}", i), None).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn declared_limit_only_tightens_config() {
+        let q = quota(2);
+        // 声明值低于配置：收紧
+        q.try_acquire("t1", "s1", Some(1)).await.unwrap();
+        let err = q.try_acquire("t1", "s2", Some(1)).await.unwrap_err();
+        assert_eq!(err.max_sessions, 1);
+
+        // 声明值高于配置或为 0：仍按配置
+        assert_eq!(q.max_sessions_for("t2", Some(100)), 2);
+        assert_eq!(q.max_sessions_for("t2", Some(0)), 2);
+        q.try_acquire("t2", "s3", Some(0)).await.unwrap();
+        q.try_acquire("t2", "s4", Some(0)).await.unwrap();
+        assert!(q.try_acquire("t2", "s5", Some(0)).await.is_err());
+
+        // 配置为不限制的租户，以租户记录上限为准
+        q.try_acquire("vip", "s6", Some(1)).await.unwrap();
+        assert!(q.try_acquire("vip", "s7", Some(1)).await.is_err());
+    }
+}
@@ -107,6 +107,18 @@ impl NodeConnectionManager {
         connections.remove(node_id);
     }
 
+    /// 仅当登记的仍是该连接时注销；已被同一节点的新连接接管时返回 false
+    pub async fn unregister_if_current(&self, node_id: &str, sender: &mpsc::UnboundedSender<Message>) -> bool {
+        let mut connections = self.connections.write().await;
+        match connections.get(node_id) {
+            Some(current) if current.same_channel(sender) => {
+                connections.remove(node_id);
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub async fn send(&self, node_id: &str, message: Message) -> bool {
         let connections = self.connections.read().await;
         if let Some(sender) = connections.get(node_id) {
@@ -122,6 +134,12 @@ impl NodeConnectionManager {
     }
     
 
+    /// 获取节点当前的连接发送端（用于判断 node_id 是否已被其他连接占用）
+    pub async fn get(&self, node_id: &str) -> Option<mpsc::UnboundedSender<Message>> {
+        let connections = self.connections.read().await;
+        connections.get(node_id).cloned()
+    }
+
     /// Phase 2：用于 owner 续约，获取当前活跃 node_id 列表快照
     pub async fn list_node_ids(&self) -> Vec<String> {
         let connections = self.connections.read().await;
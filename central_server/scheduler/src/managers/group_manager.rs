@@ -12,8 +12,10 @@ pub type GroupId = String;
 
 #[derive(Clone, Debug)]
 pub struct GroupPart {
+    /// 源语言 ASR Final（NMT 上下文只使用原文）
     pub asr_text: String,
-    pub translated_text: Option<String>, // 允许为空（NMT 失败场景）
+    /// 产生该段的 Job 源语言；双向模式（src_lang = "auto"）或 Job 不在本实例时为 None，不参与上下文
+    pub src_lang: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -70,6 +72,7 @@ impl GroupManager {
 
     /// 修复8: 批量处理 ASR Final 和 NMT Done（在一次写锁内完成）
     /// 返回: (group_id, context_text, part_index)
+    #[allow(clippy::too_many_arguments)]
     pub async fn on_asr_final_and_nmt_done(
         &self,
         session_id: &str,
@@ -77,6 +80,7 @@ impl GroupManager {
         utterance_index: u64,
         asr_text: String,
         translated_text: Option<String>,
+        src_lang: Option<String>,
         now_ms: u64,
     ) -> (GroupId, String, u64) {
         let group_id = self.ensure_target_group(session_id, now_ms).await;
@@ -88,7 +92,7 @@ impl GroupManager {
 
         let part = GroupPart {
             asr_text: asr_text.clone(),
-            translated_text: translated_text.clone(),
+            src_lang: src_lang.filter(|l| l != "auto"),
         };
 
         group.parts.push_back(part);
@@ -113,6 +117,30 @@ impl GroupManager {
         (group_id, context, part_index)
     }
 
+    /// 下一个 Job 的 NMT 上下文：session 活跃 group 未关闭且仍在 group_window_ms 内时，
+    /// 返回其中与 `src_lang` 相同源语言的原文（双向模式 src_lang = "auto" 时各段语言不确定，不携带）
+    /// （group 只在 session owner 实例上由 JobResult 维护，非 owner 实例返回 None）
+    pub async fn context_for_next_job(&self, session_id: &str, src_lang: &str, now_ms: u64) -> Option<String> {
+        if src_lang == "auto" {
+            return None;
+        }
+        let gid = {
+            let active = self.active.read().await;
+            active.get(session_id).cloned()?
+        };
+        let groups = self.groups.read().await;
+        let group = groups.get(&gid)?;
+        if group.is_closed || group.parts.is_empty() {
+            return None;
+        }
+        if now_ms.saturating_sub(group.last_tts_end_at_ms) > self.cfg.group_window_ms {
+            return None;
+        }
+        let same_lang = group.parts.iter().filter(|p| p.src_lang.as_deref() == Some(src_lang));
+        let context = Self::build_context(same_lang, self.cfg.max_context_length);
+        (!context.is_empty()).then_some(context)
+    }
+
     /// 处理 TTS 播放开始
     pub async fn on_tts_started(&self, group_id: &str, tts_start_ms: u64) {
         let mut groups = self.groups.write().await;
@@ -346,30 +374,83 @@ impl GroupManager {
         // Step 3: 最近优先已由 pop_front 实现（保留尾部最近）
     }
 
-    /// 估算上下文长度
+    /// 估算上下文长度（与 build_context 一致：asr_text + 分隔空格）
     fn estimate_context_len(parts: &VecDeque<GroupPart>) -> usize {
-        // 粗略估算：asr_text + translated_text + 分隔符
-        parts.iter().map(|p| {
-            p.asr_text.len() + p.translated_text.as_ref().map(|t| t.len()).unwrap_or(0) + 8
-        }).sum()
+        parts.iter().map(|p| p.asr_text.trim().len() + 1).sum()
     }
 
-    /// 构建上下文文本
-    fn build_context(parts: &VecDeque<GroupPart>, max_len: usize) -> String {
-        // v1.1：仅使用 ASR Final（可选拼接已译文本作为辅助，但必须固定格式）
-        // 推荐格式：User: ... / Target: ...
-        let mut buf = String::new();
-        for p in parts.iter() {
-            let line = format!("User: {}\n", p.asr_text.trim());
-            buf.push_str(&line);
-            if let Some(t) = &p.translated_text {
-                buf.push_str(&format!("Target: {}\n", t.trim()));
-            }
-        }
+    /// 构建上下文文本：仅拼接此前的源语言 ASR Final。
+    /// NMT（m2m100）把 context + 哨兵 + 当前句整体作为源语言编码，
+    /// 混入译文或 "User:/Target:" 标签会污染源语言输入，因此不带译文与标签
+    fn build_context<'a>(parts: impl IntoIterator<Item = &'a GroupPart>, max_len: usize) -> String {
+        let mut buf = parts
+            .into_iter()
+            .map(|p| p.asr_text.trim())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
         if buf.len() > max_len {
-            buf.truncate(max_len);
+            // 保留最近的内容；按字符边界裁剪，避免多字节文本截断到字符中间
+            let mut start = buf.len() - max_len;
+            while !buf.is_char_boundary(start) {
+                start += 1;
+            }
+            buf.drain(..start);
         }
         buf
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn context_for_next_job_respects_group_window() {
+        let manager = GroupManager::new(GroupConfig::default());
+        assert!(manager.context_for_next_job("s1", "zh", 1_000).await.is_none());
+
+        manager
+            .on_asr_final_and_nmt_done("s1", "t1", 0, "你好".to_string(), Some("Hello".to_string()), Some("zh".to_string()), 1_000)
+            .await;
+        let context = manager.context_for_next_job("s1", "zh", 1_500).await.unwrap();
+        assert_eq!(context, "你好");
+        assert!(manager.context_for_next_job("s2", "zh", 1_500).await.is_none());
+
+        // 超过 group_window_ms（以 last_tts_end_at_ms 为锚）后不再携带上下文
+        assert!(manager.context_for_next_job("s1", "zh", 1_000 + 2_001).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn context_only_uses_parts_in_job_source_language() {
+        let manager = GroupManager::new(GroupConfig::default());
+        manager
+            .on_asr_final_and_nmt_done("s1", "t1", 0, "你好".to_string(), None, Some("zh".to_string()), 1_000)
+            .await;
+        manager
+            .on_asr_final_and_nmt_done("s1", "t1", 1, "Hello".to_string(), None, Some("en".to_string()), 1_000)
+            .await;
+        manager
+            .on_asr_final_and_nmt_done("s1", "t1", 2, "こんにちは".to_string(), None, Some("auto".to_string()), 1_000)
+            .await;
+
+        assert_eq!(manager.context_for_next_job("s1", "en", 1_500).await.unwrap(), "Hello");
+        assert_eq!(manager.context_for_next_job("s1", "zh", 1_500).await.unwrap(), "你好");
+        // 双向模式各段语言不确定，不携带上下文
+        assert!(manager.context_for_next_job("s1", "auto", 1_500).await.is_none());
+    }
+
+    #[test]
+    fn build_context_keeps_recent_text_on_char_boundary() {
+        let parts: VecDeque<GroupPart> = vec![
+            GroupPart { asr_text: "第一句话".to_string(), src_lang: Some("zh".to_string()) },
+            GroupPart { asr_text: "第二句话".to_string(), src_lang: Some("zh".to_string()) },
+        ]
+        .into();
+        // 只保留源语言原文；裁剪起点落在「一」的 UTF-8 字节中间
+        assert_eq!(GroupManager::build_context(&parts, 100), "第一句话 第二句话");
+        let context = GroupManager::build_context(&parts, 21);
+        assert_eq!(context, "句话 第二句话");
+    }
+}
+
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, BTreeMap};
+use std::collections::{HashMap, BTreeMap, VecDeque};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
@@ -29,6 +29,8 @@ struct SessionQueueState {
     pending_acknowledgments: HashMap<u64, PendingAcknowledgment>,
     /// 补位超时时间（毫秒），默认 5 秒
     ack_timeout_ms: i64,
+    /// 最近放行的结果（断线重连时按客户端确认位置重放）
+    delivered: VecDeque<SessionMessage>,
 }
 
 // 会话结果队列管理器
@@ -54,6 +56,9 @@ impl ResultQueueManager {
     /// 注意：第一个job（utterance_index=0）的TTS耗时可能更长，需要更长的补位时间
     const ACK_TIMEOUT_MS: i64 = 30 * 1000;
 
+    /// 每个会话保留的最近放行结果条数（session_resume 重放用）
+    const DELIVERED_HISTORY_MAX: usize = 32;
+
     pub async fn initialize_session(&self, session_id: String) {
         let mut queues = self.queues.write().await;
         let now_ms = chrono::Utc::now().timestamp_millis();
@@ -64,6 +69,7 @@ impl ResultQueueManager {
             pending_max: self.pending_max,
             pending_acknowledgments: HashMap::new(),
             ack_timeout_ms: Self::ACK_TIMEOUT_MS,
+            delivered: VecDeque::new(),
         });
     }
 
@@ -268,6 +274,13 @@ impl ResultQueueManager {
                 remaining_queue_size = state.pending.len(),
                 "Ready results extracted"
             );
+
+            for result in &ready {
+                state.delivered.push_back(result.clone());
+                while state.delivered.len() > Self::DELIVERED_HISTORY_MAX {
+                    state.delivered.pop_front();
+                }
+            }
             
             ready
         } else {
@@ -290,6 +303,15 @@ impl ResultQueueManager {
         pending_results
     }
     
+    /// 最近放行的结果（连接断开进入宽限期时写入重放列表）
+    pub async fn delivered_history(&self, session_id: &str) -> Vec<SessionMessage> {
+        let queues = self.queues.read().await;
+        queues
+            .get(session_id)
+            .map(|state| state.delivered.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
     /// 获取所有待发送的结果（用于 session 关闭时 flush）
     /// 返回所有 pending 的结果，不检查 expected 或补位状态
     pub async fn get_all_pending_results(&self, session_id: &str) -> Vec<SessionMessage> {
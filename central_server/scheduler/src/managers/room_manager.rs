@@ -1,14 +1,27 @@
 // 房间管理模块
 // 负责房间的创建、加入、退出和成员管理
+//
+// 启用 Redis 运行时：房间、成员与原声偏好存储在 Redis（多实例共享、重启不丢失），见 redis_runtime/runtime_rooms.rs；
+// 未启用：房间保存在本实例内存中。
 
 use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use chrono::{DateTime, Utc};
 use tracing::{info, warn};
 
+use crate::redis_runtime::RedisRuntime;
+
+/// 房间过期时间（分钟无人说话）
+const ROOM_IDLE_EXPIRE_MINUTES: i64 = 30;
+/// Redis 中房间 key 的 TTL：过期窗口的两倍，保证清理任务能先观察到过期房间并通知成员；
+/// 所有实例都未跟踪该房间时（例如全部重启），由 TTL 兜底回收
+const ROOM_REDIS_TTL_SECONDS: u64 = ROOM_IDLE_EXPIRE_MINUTES as u64 * 60 * 2;
+/// 房间码冲突时的重试次数（之后使用 UUID 前 6 位）
+const ROOM_CODE_MAX_RETRIES: usize = 3;
+
 /// 房间成员信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Participant {
@@ -26,6 +39,19 @@ pub struct Participant {
     pub joined_at: DateTime<Utc>,
 }
 
+impl Participant {
+    fn new(session_id: String, display_name: Option<String>, preferred_lang: Option<String>) -> Self {
+        Self {
+            participant_id: session_id.clone(),
+            session_id,
+            display_name,
+            preferred_lang,
+            raw_voice_preferences: Some(HashMap::new()), // 初始化为空，默认接收所有成员的原声
+            joined_at: Utc::now(),
+        }
+    }
+}
+
 /// 房间信息
 #[derive(Debug, Clone)]
 pub struct Room {
@@ -77,7 +103,7 @@ impl Room {
     pub fn is_expired(&self) -> bool {
         let now = Utc::now();
         let duration = now.signed_duration_since(self.last_speaking_at);
-        duration.num_minutes() >= 30
+        duration.num_minutes() >= ROOM_IDLE_EXPIRE_MINUTES
     }
 }
 
@@ -86,13 +112,20 @@ impl Room {
 pub struct RoomManager {
     rooms: Arc<RwLock<HashMap<String, Room>>>, // key: room_code
     room_id_to_code: Arc<RwLock<HashMap<String, String>>>, // room_id -> room_code
+    session_to_room: Arc<RwLock<HashMap<String, String>>>, // session_id -> room_code
+    redis_runtime: Option<Arc<RedisRuntime>>,
+    /// Redis 模式下本实例创建/加入过的房间码：过期清理只检查这些房间，避免全量 SCAN
+    tracked_rooms: Arc<RwLock<HashSet<String>>>,
 }
 
 impl RoomManager {
-    pub fn new() -> Self {
+    pub fn new(redis_runtime: Option<Arc<RedisRuntime>>) -> Self {
         Self {
             rooms: Arc::new(RwLock::new(HashMap::new())),
             room_id_to_code: Arc::new(RwLock::new(HashMap::new())),
+            session_to_room: Arc::new(RwLock::new(HashMap::new())),
+            redis_runtime,
+            tracked_rooms: Arc::new(RwLock::new(HashSet::new())),
         }
     }
 
@@ -102,6 +135,15 @@ impl RoomManager {
         format!("{:06}", rng.gen_range(100000..=999999))
     }
 
+    /// 第 attempt 次尝试使用的房间码（重试耗尽后使用 UUID 前6位）
+    fn candidate_room_code(attempt: usize) -> String {
+        if attempt <= ROOM_CODE_MAX_RETRIES {
+            Self::generate_room_code()
+        } else {
+            uuid::Uuid::new_v4().to_string()[..6].to_string()
+        }
+    }
+
     /// 创建房间
     /// 创建者自动成为第一个成员
     /// 返回 (room_code, room_id)
@@ -110,43 +152,54 @@ impl RoomManager {
         creator_session_id: String,
         creator_display_name: Option<String>,
         creator_preferred_lang: Option<String>,
-    ) -> (String, String) {
+    ) -> Result<(String, String), RoomError> {
+        let room_id = uuid::Uuid::new_v4().to_string();
+        let creator = Participant::new(creator_session_id.clone(), creator_display_name, creator_preferred_lang);
+
+        if let Some(rt) = self.redis_runtime.as_ref() {
+            let creator_json = participant_json(&creator)?;
+            for attempt in 0..=ROOM_CODE_MAX_RETRIES + 1 {
+                let room_code = Self::candidate_room_code(attempt);
+                match rt
+                    .room_try_create(&room_code, &room_id, &creator_session_id, &creator_json, ROOM_REDIS_TTL_SECONDS)
+                    .await
+                {
+                    Ok(true) => {
+                        self.index_session(rt, &creator_session_id, &room_code).await;
+                        self.tracked_rooms.write().await.insert(room_code.clone());
+                        info!(room_code = %room_code, session_id = %creator_session_id, "房间已创建，创建者已自动加入");
+                        return Ok((room_code, room_id));
+                    }
+                    Ok(false) => continue,
+                    Err(e) => {
+                        warn!(session_id = %creator_session_id, error = %e, "创建房间失败：Redis 不可用");
+                        return Err(RoomError::StorageUnavailable);
+                    }
+                }
+            }
+            warn!(session_id = %creator_session_id, "创建房间失败：房间码持续冲突");
+            return Err(RoomError::StorageUnavailable);
+        }
+
         let mut rooms = self.rooms.write().await;
         let mut room_id_to_code = self.room_id_to_code.write().await;
-        
-        // 生成房间码（最多重试3次）
-        let mut room_code = Self::generate_room_code();
-        let mut retries = 0;
-        while rooms.contains_key(&room_code) && retries < 3 {
-            room_code = Self::generate_room_code();
-            retries += 1;
-        }
-        
-        // 如果仍然冲突，使用 UUID 后6位
-        if rooms.contains_key(&room_code) {
-            let uuid = uuid::Uuid::new_v4();
-            room_code = uuid.to_string()[..6].to_string();
+
+        let mut attempt = 0;
+        let mut room_code = Self::candidate_room_code(attempt);
+        while rooms.contains_key(&room_code) && attempt <= ROOM_CODE_MAX_RETRIES {
+            attempt += 1;
+            room_code = Self::candidate_room_code(attempt);
         }
-        
-        let room_id = uuid::Uuid::new_v4().to_string();
+
         let mut room = Room::new(room_code.clone(), room_id.clone());
-        
-        // 将创建者添加为第一个成员
-        let creator = Participant {
-            participant_id: creator_session_id.clone(),
-            session_id: creator_session_id.clone(),
-            display_name: creator_display_name,
-            preferred_lang: creator_preferred_lang,
-            raw_voice_preferences: Some(HashMap::new()), // 初始化为空，默认接收所有成员的原声
-            joined_at: Utc::now(),
-        };
         room.add_participant(creator);
-        
+
         rooms.insert(room_code.clone(), room);
         room_id_to_code.insert(room_id.clone(), room_code.clone());
-        
+        self.session_to_room.write().await.insert(creator_session_id.clone(), room_code.clone());
+
         info!(room_code = %room_code, session_id = %creator_session_id, "房间已创建，创建者已自动加入");
-        (room_code, room_id)
+        Ok((room_code, room_id))
     }
 
     /// 加入房间
@@ -157,71 +210,125 @@ impl RoomManager {
         display_name: Option<String>,
         preferred_lang: Option<String>,
     ) -> Result<(), RoomError> {
+        let participant = Participant::new(session_id.clone(), display_name, preferred_lang);
+
+        if let Some(rt) = self.redis_runtime.as_ref() {
+            let json = participant_json(&participant)?;
+            match rt.room_join(room_code, &session_id, &json, ROOM_REDIS_TTL_SECONDS).await {
+                Ok(1) => {
+                    self.index_session(rt, &session_id, room_code).await;
+                    self.tracked_rooms.write().await.insert(room_code.to_string());
+                    info!(room_code = %room_code, session_id = %session_id, "成员已加入房间");
+                    return Ok(());
+                }
+                Ok(0) => return Err(RoomError::AlreadyInRoom),
+                Ok(_) => return Err(RoomError::RoomNotFound),
+                Err(e) => {
+                    warn!(room_code = %room_code, session_id = %session_id, error = %e, "加入房间失败：Redis 不可用");
+                    return Err(RoomError::StorageUnavailable);
+                }
+            }
+        }
+
         let mut rooms = self.rooms.write().await;
-        
+
         let room = rooms.get_mut(room_code)
             .ok_or(RoomError::RoomNotFound)?;
-        
+
         // 检查是否已经在房间中
         if room.participants.contains_key(&session_id) {
             return Err(RoomError::AlreadyInRoom);
         }
-        
-        let participant = Participant {
-            participant_id: session_id.clone(),
-            session_id: session_id.clone(),
-            display_name,
-            preferred_lang,
-            raw_voice_preferences: Some(HashMap::new()), // 初始化为空，默认接收所有成员的原声
-            joined_at: Utc::now(),
-        };
-        
+
         room.add_participant(participant);
+        self.session_to_room.write().await.insert(session_id.clone(), room_code.to_string());
         info!(room_code = %room_code, session_id = %session_id, "成员已加入房间");
-        
+
         Ok(())
     }
 
     /// 退出房间
     pub async fn leave_room(&self, room_code: &str, session_id: &str) -> Result<bool, RoomError> {
+        if let Some(rt) = self.redis_runtime.as_ref() {
+            let is_empty = match rt.room_leave(room_code, session_id).await {
+                Ok(-1) => return Err(RoomError::RoomNotFound),
+                Ok(v) => v == 1,
+                Err(e) => {
+                    warn!(room_code = %room_code, session_id = %session_id, error = %e, "退出房间失败：Redis 不可用");
+                    return Err(RoomError::StorageUnavailable);
+                }
+            };
+            rt.room_session_index_clear(session_id, room_code).await;
+            info!(room_code = %room_code, session_id = %session_id, "成员已退出房间");
+            if is_empty {
+                self.tracked_rooms.write().await.remove(room_code);
+                info!(room_code = %room_code, "房间已清理（最后一个成员离开）");
+            }
+            return Ok(is_empty);
+        }
+
         let mut rooms = self.rooms.write().await;
-        
+
         let room = rooms.get_mut(room_code)
             .ok_or(RoomError::RoomNotFound)?;
-        
+
         room.remove_participant(session_id);
+        {
+            let mut session_to_room = self.session_to_room.write().await;
+            if session_to_room.get(session_id).map(String::as_str) == Some(room_code) {
+                session_to_room.remove(session_id);
+            }
+        }
         info!(room_code = %room_code, session_id = %session_id, "成员已退出房间");
-        
+
         // 如果房间为空，清理房间
         let is_empty = room.is_empty();
         if is_empty {
             let room_id = room.room_id.clone();
             rooms.remove(room_code);
-            
+
             let mut room_id_to_code = self.room_id_to_code.write().await;
             room_id_to_code.remove(&room_id);
-            
+
             info!(room_code = %room_code, "房间已清理（最后一个成员离开）");
         }
-        
+
         Ok(is_empty)
     }
 
     /// 获取房间成员列表
     pub async fn get_room_members(&self, room_code: &str) -> Option<Vec<Participant>> {
+        if let Some(rt) = self.redis_runtime.as_ref() {
+            return match rt.room_snapshot(room_code).await {
+                Ok((members, raw_voice)) if !members.is_empty() => {
+                    Some(members_from_redis(room_code, members.into_values(), &raw_voice))
+                }
+                Ok(_) => None,
+                Err(e) => {
+                    warn!(room_code = %room_code, error = %e, "读取房间成员失败：Redis 不可用");
+                    None
+                }
+            };
+        }
+
         let rooms = self.rooms.read().await;
         rooms.get(room_code).map(|room| room.get_members())
     }
 
-    /// 根据 session_id 查找房间码
+    /// 根据 session_id 查找房间码（session -> room 索引，O(1)，读取时续期）
     pub async fn find_room_by_session(&self, session_id: &str) -> Option<String> {
-        let rooms = self.rooms.read().await;
-        for (room_code, room) in rooms.iter() {
-            if room.participants.contains_key(session_id) {
-                return Some(room_code.clone());
-            }
+        if let Some(rt) = self.redis_runtime.as_ref() {
+            return match rt.room_session_index_touch(session_id, ROOM_REDIS_TTL_SECONDS).await {
+                Ok(Some(room_code)) => Some(room_code),
+                Ok(None) => self.reindex_from_tracked_rooms(rt, session_id).await,
+                Err(e) => {
+                    warn!(session_id = %session_id, error = %e, "查找会话所在房间失败：Redis 不可用");
+                    None
+                }
+            };
         }
-        None
+
+        self.session_to_room.read().await.get(session_id).cloned()
     }
 
     /// 获取房间内所有不同的目标语言（排除发送者）
@@ -231,34 +338,37 @@ impl RoomManager {
         room_code: &str,
         exclude_session_id: &str, // 排除发送者
     ) -> Vec<(String, Vec<Participant>)> {
-        let rooms = self.rooms.read().await;
-        if let Some(room) = rooms.get(room_code) {
-            // 按 preferred_lang 分组
-            let mut lang_groups: std::collections::HashMap<String, Vec<Participant>> = std::collections::HashMap::new();
-            
-            for participant in room.participants.values() {
-                // 排除发送者
-                if participant.session_id == exclude_session_id {
-                    continue;
-                }
-                
-                // 如果成员有 preferred_lang，加入对应语言组
-                if let Some(ref lang) = participant.preferred_lang {
-                    lang_groups
-                        .entry(lang.clone())
-                        .or_insert_with(Vec::new)
-                        .push(participant.clone());
-                }
+        let Some(members) = self.get_room_members(room_code).await else {
+            return Vec::new();
+        };
+
+        // 按 preferred_lang 分组
+        let mut lang_groups: HashMap<String, Vec<Participant>> = HashMap::new();
+        for participant in members {
+            // 排除发送者
+            if participant.session_id == exclude_session_id {
+                continue;
+            }
+
+            // 如果成员有 preferred_lang，加入对应语言组
+            if let Some(lang) = participant.preferred_lang.clone() {
+                lang_groups.entry(lang).or_default().push(participant);
             }
-            
-            lang_groups.into_iter().collect()
-        } else {
-            Vec::new()
         }
+
+        lang_groups.into_iter().collect()
     }
 
     /// 更新房间最后说话时间
     pub async fn update_last_speaking_at(&self, room_code: &str) {
+        if let Some(rt) = self.redis_runtime.as_ref() {
+            // 成员的 session -> room 索引不在此续期（与房间 key 不同 slot），由 find_room_by_session 读取时续期
+            if let Err(e) = rt.room_touch(room_code, ROOM_REDIS_TTL_SECONDS).await {
+                warn!(room_code = %room_code, error = %e, "更新房间最后说话时间失败：Redis 不可用");
+            }
+            return;
+        }
+
         let mut rooms = self.rooms.write().await;
         if let Some(room) = rooms.get_mut(room_code) {
             room.update_last_speaking_at();
@@ -273,23 +383,32 @@ impl RoomManager {
         target_session_id: &str,
         receive_raw_voice: bool,
     ) -> Result<(), RoomError> {
-        let mut rooms = self.rooms.write().await;
-        let room = rooms.get_mut(room_code)
-            .ok_or(RoomError::RoomNotFound)?;
-        
-        let participant = room.participants.get_mut(session_id)
-            .ok_or(RoomError::RoomNotFound)?;
-        
-        // 初始化偏好设置（如果不存在）
-        if participant.raw_voice_preferences.is_none() {
-            participant.raw_voice_preferences = Some(HashMap::new());
-        }
-        
-        // 更新偏好
-        if let Some(ref mut prefs) = participant.raw_voice_preferences {
-            prefs.insert(target_session_id.to_string(), receive_raw_voice);
+        if let Some(rt) = self.redis_runtime.as_ref() {
+            match rt
+                .room_set_raw_voice_preference(room_code, session_id, target_session_id, receive_raw_voice, ROOM_REDIS_TTL_SECONDS)
+                .await
+            {
+                Ok(true) => {}
+                Ok(false) => return Err(RoomError::RoomNotFound),
+                Err(e) => {
+                    warn!(room_code = %room_code, session_id = %session_id, error = %e, "更新原声传递偏好失败：Redis 不可用");
+                    return Err(RoomError::StorageUnavailable);
+                }
+            }
+        } else {
+            let mut rooms = self.rooms.write().await;
+            let room = rooms.get_mut(room_code)
+                .ok_or(RoomError::RoomNotFound)?;
+
+            let participant = room.participants.get_mut(session_id)
+                .ok_or(RoomError::RoomNotFound)?;
+
+            participant
+                .raw_voice_preferences
+                .get_or_insert_with(HashMap::new)
+                .insert(target_session_id.to_string(), receive_raw_voice);
         }
-        
+
         info!(room_code = %room_code, session_id = %session_id, target_session_id = %target_session_id, receive_raw_voice = receive_raw_voice, "更新原声传递偏好");
         Ok(())
     }
@@ -302,6 +421,17 @@ impl RoomManager {
         receiver_session_id: &str,
         sender_session_id: &str,
     ) -> bool {
+        if let Some(rt) = self.redis_runtime.as_ref() {
+            return match rt.room_raw_voice_preference(room_code, receiver_session_id, sender_session_id).await {
+                Ok(preference) => preference.unwrap_or(false),
+                Err(e) => {
+                    // 读取失败按未设置偏好处理（默认接收）
+                    warn!(room_code = %room_code, error = %e, "读取原声传递偏好失败：Redis 不可用");
+                    true
+                }
+            };
+        }
+
         let rooms = self.rooms.read().await;
         if let Some(room) = rooms.get(room_code) {
             if let Some(participant) = room.participants.get(receiver_session_id) {
@@ -325,10 +455,15 @@ impl RoomManager {
     /// 扫描并清理过期房间
     /// 返回 (room_code, members) 列表，用于发送过期消息
     pub async fn cleanup_expired_rooms(&self) -> Vec<(String, Vec<Participant>)> {
+        if let Some(rt) = self.redis_runtime.as_ref() {
+            return self.cleanup_expired_rooms_redis(rt).await;
+        }
+
         let mut rooms = self.rooms.write().await;
         let mut room_id_to_code = self.room_id_to_code.write().await;
+        let mut session_to_room = self.session_to_room.write().await;
         let mut expired_rooms = Vec::new();
-        
+
         // 先收集过期房间的信息（包括成员列表）
         let mut to_remove = Vec::new();
         for (room_code, room) in rooms.iter() {
@@ -337,17 +472,117 @@ impl RoomManager {
                 to_remove.push((room_code.clone(), room.room_id.clone(), members));
             }
         }
-        
+
         // 清理过期房间
         for (room_code, room_id, members) in to_remove {
             rooms.remove(&room_code);
             room_id_to_code.remove(&room_id);
-            expired_rooms.push((room_code.clone(), members));
+            for member in &members {
+                session_to_room.remove(&member.session_id);
+            }
             warn!(room_code = %room_code, "房间已过期并清理");
+            expired_rooms.push((room_code, members));
+        }
+
+        expired_rooms
+    }
+
+    /// Redis 模式：检查本实例跟踪的房间，过期房间由首个删除成功的实例负责通知成员
+    async fn cleanup_expired_rooms_redis(&self, rt: &RedisRuntime) -> Vec<(String, Vec<Participant>)> {
+        let tracked: Vec<String> = self.tracked_rooms.read().await.iter().cloned().collect();
+        let idle_ms = ROOM_IDLE_EXPIRE_MINUTES * 60 * 1000;
+        let mut expired_rooms = Vec::new();
+
+        for room_code in tracked {
+            let result = match rt.room_expire_if_idle(&room_code, idle_ms).await {
+                Ok(v) => v,
+                Err(e) => {
+                    warn!(room_code = %room_code, error = %e, "检查房间过期失败：Redis 不可用");
+                    continue;
+                }
+            };
+            match result.first().map(String::as_str) {
+                Some("active") => {}
+                Some("expired") => {
+                    self.tracked_rooms.write().await.remove(&room_code);
+                    let members = members_from_redis(&room_code, result.into_iter().skip(1), &HashMap::new());
+                    for member in &members {
+                        rt.room_session_index_clear(&member.session_id, &room_code).await;
+                    }
+                    warn!(room_code = %room_code, "房间已过期并清理");
+                    expired_rooms.push((room_code, members));
+                }
+                // 房间已被删除（其他实例已清理或 TTL 回收）
+                _ => {
+                    self.tracked_rooms.write().await.remove(&room_code);
+                }
+            }
         }
-        
+
         expired_rooms
     }
+
+    async fn index_session(&self, rt: &RedisRuntime, session_id: &str, room_code: &str) {
+        if let Err(e) = rt.room_session_index_set(session_id, room_code, ROOM_REDIS_TTL_SECONDS).await {
+            warn!(room_code = %room_code, session_id = %session_id, error = %e, "写入会话房间索引失败");
+        }
+    }
+
+    /// 索引已过期（长时间未读取的成员）：在本实例跟踪的房间中回查成员关系并重建索引。
+    /// 会话加入房间的实例即跟踪该房间，因此会话所在实例总能找回
+    async fn reindex_from_tracked_rooms(&self, rt: &RedisRuntime, session_id: &str) -> Option<String> {
+        let tracked: Vec<String> = self.tracked_rooms.read().await.iter().cloned().collect();
+        for room_code in tracked {
+            if let Ok(true) = rt.room_has_member(&room_code, session_id).await {
+                self.index_session(rt, session_id, &room_code).await;
+                return Some(room_code);
+            }
+        }
+        None
+    }
+}
+
+/// Redis 中成员 JSON 不含原声偏好（单独存储），序列化前去掉
+fn participant_json(participant: &Participant) -> Result<String, RoomError> {
+    let stored = Participant {
+        raw_voice_preferences: None,
+        ..participant.clone()
+    };
+    serde_json::to_string(&stored).map_err(|e| {
+        warn!(session_id = %participant.session_id, error = %e, "成员信息序列化失败");
+        RoomError::StorageUnavailable
+    })
+}
+
+/// 解析 Redis 中的成员 JSON 并合并原声偏好（"<receiver>|<sender>" -> "1"/"0"），按加入时间排序
+fn members_from_redis(
+    room_code: &str,
+    member_jsons: impl IntoIterator<Item = String>,
+    raw_voice: &HashMap<String, String>,
+) -> Vec<Participant> {
+    let mut members: Vec<Participant> = member_jsons
+        .into_iter()
+        .filter_map(|json| match serde_json::from_str::<Participant>(&json) {
+            Ok(p) => Some(p),
+            Err(e) => {
+                warn!(room_code = %room_code, error = %e, "房间成员信息解析失败，已跳过");
+                None
+            }
+        })
+        .collect();
+
+    for member in members.iter_mut() {
+        let prefs = member.raw_voice_preferences.get_or_insert_with(HashMap::new);
+        for (field, value) in raw_voice {
+            if let Some((receiver, sender)) = field.split_once('|') {
+                if receiver == member.session_id {
+                    prefs.insert(sender.to_string(), value == "1");
+                }
+            }
+        }
+    }
+    members.sort_by_key(|m| m.joined_at);
+    members
 }
 
 /// 房间错误类型
@@ -355,6 +590,8 @@ impl RoomManager {
 pub enum RoomError {
     RoomNotFound,
     AlreadyInRoom,
+    /// 房间存储（Redis）不可用
+    StorageUnavailable,
 }
 
 impl std::fmt::Display for RoomError {
@@ -362,9 +599,68 @@ impl std::fmt::Display for RoomError {
         match self {
             RoomError::RoomNotFound => write!(f, "房间不存在"),
             RoomError::AlreadyInRoom => write!(f, "已在房间中"),
+            RoomError::StorageUnavailable => write!(f, "房间服务暂不可用"),
         }
     }
 }
 
 impl std::error::Error for RoomError {}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_find_room_by_session_tracks_membership() {
+        let manager = RoomManager::new(None);
+        let (room_code, _) = manager.create_room("s1".into(), None, Some("en".into())).await.unwrap();
+        manager.join_room(&room_code, "s2".into(), None, Some("zh".into())).await.unwrap();
+
+        assert_eq!(manager.find_room_by_session("s1").await.as_deref(), Some(room_code.as_str()));
+        assert_eq!(manager.find_room_by_session("s2").await.as_deref(), Some(room_code.as_str()));
+        assert!(matches!(
+            manager.join_room(&room_code, "s2".into(), None, None).await,
+            Err(RoomError::AlreadyInRoom)
+        ));
+
+        assert!(!manager.leave_room(&room_code, "s2").await.unwrap());
+        assert_eq!(manager.find_room_by_session("s2").await, None);
+        assert!(manager.leave_room(&room_code, "s1").await.unwrap());
+        assert_eq!(manager.find_room_by_session("s1").await, None);
+        assert!(manager.get_room_members(&room_code).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_raw_voice_preference_defaults_to_receive() {
+        let manager = RoomManager::new(None);
+        let (room_code, _) = manager.create_room("s1".into(), None, None).await.unwrap();
+        manager.join_room(&room_code, "s2".into(), None, None).await.unwrap();
+
+        assert!(manager.should_receive_raw_voice(&room_code, "s1", "s2").await);
+        manager.update_raw_voice_preference(&room_code, "s1", "s2", false).await.unwrap();
+        assert!(!manager.should_receive_raw_voice(&room_code, "s1", "s2").await);
+        assert!(!manager.should_receive_raw_voice(&room_code, "missing", "s2").await);
+    }
+
+    #[test]
+    fn test_members_from_redis_merges_raw_voice_preferences() {
+        let early = Participant::new("s1".into(), None, Some("en".into()));
+        let mut late = Participant::new("s2".into(), None, Some("zh".into()));
+        late.joined_at = early.joined_at + chrono::Duration::seconds(5);
+        let jsons = vec![
+            participant_json(&late).unwrap(),
+            participant_json(&early).unwrap(),
+            "not json".to_string(),
+        ];
+        let raw_voice = HashMap::from([
+            ("s1|s2".to_string(), "0".to_string()),
+            ("s2|s1".to_string(), "1".to_string()),
+        ]);
+
+        let members = members_from_redis("123456", jsons, &raw_voice);
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].session_id, "s1");
+        assert_eq!(members[0].raw_voice_preferences.as_ref().unwrap().get("s2"), Some(&false));
+        assert_eq!(members[1].raw_voice_preferences.as_ref().unwrap().get("s1"), Some(&true));
+    }
+}
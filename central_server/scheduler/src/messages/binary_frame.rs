@@ -0,0 +1,175 @@
+// 会话端 Binary Frame（与 web-client src/binary_protocol.ts 对应）
+//
+// AUDIO_CHUNK (0x01)，12 字节头：
+//   frame_type u8 | session_id_len u8 | seq u32 LE | timestamp u32 LE | is_final u8 | reserved u8
+//   后接 session_id (UTF-8) 与原始音频字节（pcm16 或 Opus Plan A 包序列）
+// FINAL (0x02)，10 字节头：
+//   frame_type u8 | session_id_len u8 | seq u32 LE | timestamp u32 LE
+//   后接 session_id (UTF-8)
+//
+// timestamp 为客户端 Date.now() 的低 32 位，服务端按当前时间还原。
+
+use thiserror::Error;
+
+pub const FRAME_TYPE_AUDIO_CHUNK: u8 = 0x01;
+pub const FRAME_TYPE_FINAL: u8 = 0x02;
+
+const AUDIO_CHUNK_HEADER_LEN: usize = 12;
+const FINAL_HEADER_LEN: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BinaryFrame {
+    AudioChunk {
+        session_id: String,
+        seq: u32,
+        timestamp_low32: u32,
+        is_final: bool,
+        audio: Vec<u8>,
+    },
+    Final {
+        session_id: String,
+        seq: u32,
+        timestamp_low32: u32,
+    },
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BinaryFrameError {
+    #[error("binary frame too short ({0} bytes)")]
+    TooShort(usize),
+    #[error("unknown binary frame type: 0x{0:02x}")]
+    UnknownType(u8),
+    #[error("session_id is not valid UTF-8")]
+    InvalidSessionId,
+}
+
+impl BinaryFrame {
+    pub fn decode(data: &[u8]) -> Result<Self, BinaryFrameError> {
+        if data.len() < FINAL_HEADER_LEN {
+            return Err(BinaryFrameError::TooShort(data.len()));
+        }
+        let frame_type = data[0];
+        let session_id_len = data[1] as usize;
+        let seq = u32::from_le_bytes([data[2], data[3], data[4], data[5]]);
+        let timestamp_low32 = u32::from_le_bytes([data[6], data[7], data[8], data[9]]);
+
+        let header_len = match frame_type {
+            FRAME_TYPE_AUDIO_CHUNK => AUDIO_CHUNK_HEADER_LEN,
+            FRAME_TYPE_FINAL => FINAL_HEADER_LEN,
+            other => return Err(BinaryFrameError::UnknownType(other)),
+        };
+        if data.len() < header_len + session_id_len {
+            return Err(BinaryFrameError::TooShort(data.len()));
+        }
+        let session_id = std::str::from_utf8(&data[header_len..header_len + session_id_len])
+            .map_err(|_| BinaryFrameError::InvalidSessionId)?
+            .to_string();
+
+        Ok(match frame_type {
+            FRAME_TYPE_AUDIO_CHUNK => BinaryFrame::AudioChunk {
+                session_id,
+                seq,
+                timestamp_low32,
+                is_final: data[10] != 0,
+                audio: data[header_len + session_id_len..].to_vec(),
+            },
+            _ => BinaryFrame::Final {
+                session_id,
+                seq,
+                timestamp_low32,
+            },
+        })
+    }
+
+    #[cfg(test)]
+    pub fn encode(&self) -> Vec<u8> {
+        let (frame_type, session_id, seq, timestamp_low32) = match self {
+            BinaryFrame::AudioChunk { session_id, seq, timestamp_low32, .. } => {
+                (FRAME_TYPE_AUDIO_CHUNK, session_id, *seq, *timestamp_low32)
+            }
+            BinaryFrame::Final { session_id, seq, timestamp_low32 } => {
+                (FRAME_TYPE_FINAL, session_id, *seq, *timestamp_low32)
+            }
+        };
+        let mut out = vec![frame_type, session_id.len() as u8];
+        out.extend_from_slice(&seq.to_le_bytes());
+        out.extend_from_slice(&timestamp_low32.to_le_bytes());
+        if let BinaryFrame::AudioChunk { is_final, .. } = self {
+            out.push(*is_final as u8);
+            out.push(0);
+        }
+        out.extend_from_slice(session_id.as_bytes());
+        if let BinaryFrame::AudioChunk { audio, .. } = self {
+            out.extend_from_slice(audio);
+        }
+        out
+    }
+
+    pub fn session_id(&self) -> &str {
+        match self {
+            BinaryFrame::AudioChunk { session_id, .. } | BinaryFrame::Final { session_id, .. } => session_id,
+        }
+    }
+}
+
+/// 由低 32 位还原完整毫秒时间戳：取与 `now_ms` 最接近的同余值
+pub fn restore_timestamp_ms(low32: u32, now_ms: i64) -> i64 {
+    const SPAN: i64 = 1 << 32;
+    let base = now_ms - now_ms.rem_euclid(SPAN);
+    [base - SPAN, base, base + SPAN]
+        .into_iter()
+        .map(|b| b + low32 as i64)
+        .min_by_key(|t| (t - now_ms).abs())
+        .unwrap_or(now_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audio_chunk_and_final_roundtrip() {
+        let chunk = BinaryFrame::AudioChunk {
+            session_id: "sess-1".to_string(),
+            seq: 7,
+            timestamp_low32: 0xdead_beef,
+            is_final: true,
+            audio: vec![1, 2, 3, 4],
+        };
+        let bytes = chunk.encode();
+        assert_eq!(bytes.len(), 12 + 6 + 4);
+        assert_eq!(BinaryFrame::decode(&bytes).unwrap(), chunk);
+
+        let fin = BinaryFrame::Final {
+            session_id: "sess-1".to_string(),
+            seq: 8,
+            timestamp_low32: 1,
+        };
+        assert_eq!(BinaryFrame::decode(&fin.encode()).unwrap(), fin);
+    }
+
+    #[test]
+    fn malformed_frames_are_rejected() {
+        assert_eq!(BinaryFrame::decode(&[1, 0, 0]), Err(BinaryFrameError::TooShort(3)));
+        assert_eq!(
+            BinaryFrame::decode(&[0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Err(BinaryFrameError::UnknownType(0x10))
+        );
+        // session_id_len 超出帧长度
+        assert_eq!(
+            BinaryFrame::decode(&[1, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, b'a']),
+            Err(BinaryFrameError::TooShort(13))
+        );
+    }
+
+    #[test]
+    fn timestamp_is_restored_around_now() {
+        let now = 1_767_000_000_123_i64;
+        let sent = now - 40;
+        assert_eq!(restore_timestamp_ms(sent as u32, now), sent);
+        // 低 32 位刚好回绕
+        let now = (5_i64 << 32) + 10;
+        let sent = (5_i64 << 32) - 20;
+        assert_eq!(restore_timestamp_ms(sent as u32, now), sent);
+    }
+}
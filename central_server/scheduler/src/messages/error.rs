@@ -28,6 +28,14 @@ pub enum ErrorCode {
     InvalidCapabilitySchema,
     /// 调度服务器依赖服务不可用（如 Redis 不可用）
     SchedulerDependencyDown,
+    /// 租户并发会话数已达上限
+    TenantSessionLimitExceeded,
+    /// 节点认证失败（challenge 签名错误、凭证缺失或过期）
+    NodeAuthFailed,
+    /// 节点已被吊销
+    NodeRevoked,
+    /// 节点声明的归属租户与凭证或调度器配置不符
+    NodeOwnerTenantMismatch,
 }
 
 impl ToString for ErrorCode {
@@ -54,6 +62,10 @@ impl ToString for ErrorCode {
             ErrorCode::NodeIdConflict => "NODE_ID_CONFLICT".to_string(),
             ErrorCode::InvalidCapabilitySchema => "INVALID_CAPABILITY_SCHEMA".to_string(),
             ErrorCode::SchedulerDependencyDown => "SCHEDULER_DEPENDENCY_DOWN".to_string(),
+            ErrorCode::TenantSessionLimitExceeded => "TENANT_SESSION_LIMIT_EXCEEDED".to_string(),
+            ErrorCode::NodeAuthFailed => "NODE_AUTH_FAILED".to_string(),
+            ErrorCode::NodeRevoked => "NODE_REVOKED".to_string(),
+            ErrorCode::NodeOwnerTenantMismatch => "NODE_OWNER_TENANT_MISMATCH".to_string(),
         }
     }
 }
@@ -70,9 +82,13 @@ pub fn get_error_hint(code: &ErrorCode) -> &'static str {
         ErrorCode::ModelVerifyFailed => "模型校验失败，请重新下载模型。",
         ErrorCode::ModelCorrupted => "模型文件损坏，请重新下载模型。",
         ErrorCode::NoGpuAvailable => "节点没有 GPU，无法注册为算力提供方。",
-        ErrorCode::NodeIdConflict => "节点 ID 冲突，请清除本地 node_id 后重新注册。",
+        ErrorCode::NodeIdConflict => "节点 ID 已被在线连接占用，节点将退避重连，旧连接下线后即可注册。",
         ErrorCode::InvalidCapabilitySchema => "不支持的能力描述版本，请更新节点客户端。",
         ErrorCode::SchedulerDependencyDown => "调度服务器依赖服务不可用，请稍后重试。",
+        ErrorCode::TenantSessionLimitExceeded => "当前租户的并发会话数已达上限，请关闭其他会话后重试。",
+        ErrorCode::NodeAuthFailed => "节点认证失败，请检查节点密钥或重新申请 enrollment token。",
+        ErrorCode::NodeRevoked => "节点已被吊销，请联系管理员。",
+        ErrorCode::NodeOwnerTenantMismatch => "节点声明的归属租户未经授权，请使用绑定该租户的 enrollment token 或联系管理员配置。",
         _ => "发生错误，请稍后重试。",
     }
 }
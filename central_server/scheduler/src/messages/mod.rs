@@ -1,6 +1,7 @@
 // WebSocket 消息协议定义（与 docs/PROTOCOLS.md 对应）
 
 // 子模块
+pub mod binary_frame;
 pub mod common;
 pub mod error;
 pub mod ui_event;
@@ -27,10 +27,17 @@ pub enum NodeMessage {
         /// 性能/调度相关高级能力（如 batched_inference、kv_cache）
         #[serde(skip_serializing_if = "Option::is_none")]
         advanced_features: Option<Vec<String>>,
+        /// false：私有节点，不进入公共池（只接配对会话或所属租户的任务）
         accept_public_jobs: bool,
+        /// 私有节点所属租户（该租户的任务优先派发到此节点）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        owner_tenant_id: Option<String>,
         /// 语言能力信息（新增，可选，向后兼容）
         #[serde(skip_serializing_if = "Option::is_none")]
         language_capabilities: Option<NodeLanguageCapabilities>,
+        /// 会话迁移 HTTP 地址（提供 /session-migration/evacuate 与 /import），调度器据此迁移会话
+        #[serde(skip_serializing_if = "Option::is_none")]
+        migration_endpoint: Option<String>,
     },
     #[serde(rename = "node_register_ack")]
     NodeRegisterAck {
@@ -38,6 +45,29 @@ pub enum NodeMessage {
         message: String,
         /// 节点状态（初始恒为 registering）
         status: String, // "registering" | "ready" | "degraded" | "draining" | "offline"
+        /// 以 enrollment token 认证成功时签发的长期节点凭证，节点应保存并在之后的认证中代替 token 使用
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        node_credential: Option<String>,
+    },
+    /// node_register 后下发（仅启用节点认证时）：节点需用密钥对 nonce 签名应答
+    #[serde(rename = "node_auth_challenge")]
+    NodeAuthChallenge {
+        node_id: String,
+        nonce: String,
+        /// 应答超时（毫秒）
+        expires_in_ms: u64,
+    },
+    #[serde(rename = "node_auth_response")]
+    NodeAuthResponse {
+        node_id: String,
+        /// hex(HMAC-SHA256(secret, "<nonce>.<node_id>"))
+        signature: String,
+        /// 使用 enrollment token 作为密钥时携带其过期时间（token 前缀），否则使用预共享密钥
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        enrollment_expires_at: Option<i64>,
+        /// 使用 node_register_ack 下发的长期节点凭证作为密钥
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        node_credential: bool,
     },
     #[serde(rename = "error")]
     Error {
@@ -236,6 +266,21 @@ pub enum NodeMessage {
         #[serde(skip_serializing_if = "Option::is_none")]
         reason: Option<String>,
     },
+    /// 节点申请配对码（私有会话）
+    #[serde(rename = "pairing_code_request")]
+    PairingCodeRequest {
+        node_id: String,
+        /// 有效期（秒），不指定时使用调度器默认值
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ttl_seconds: Option<u64>,
+    },
+    #[serde(rename = "pairing_code_issued")]
+    PairingCodeIssued {
+        node_id: String,
+        code: String,
+        /// 过期时间（毫秒时间戳）
+        expires_at_ms: i64,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +25,9 @@ pub enum SessionMessage {
         pairing_code: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         tenant_id: Option<String>, // 租户 ID（用于多租户支持）
+        /// 租户并发会话上限（API Gateway 按租户记录填写；只能收紧 scheduler.tenant_quota 配置，0 视为未提供）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tenant_max_sessions: Option<usize>,
         /// 翻译模式："one_way" | "two_way_auto"
         #[serde(skip_serializing_if = "Option::is_none")]
         mode: Option<String>,
@@ -46,6 +49,12 @@ pub enum SessionMessage {
         /// 追踪 ID（可选，客户端提供或由 Scheduler 生成）
         #[serde(skip_serializing_if = "Option::is_none")]
         trace_id: Option<String>,
+        /// 是否持久化会话转写（可选，未指定时按租户配置）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        persist_transcript: Option<bool>,
+        /// 客户端是否支持 Binary Frame 上行音频（协商结果见 ack.use_binary_frame）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        supports_binary_frame: Option<bool>,
     },
     #[serde(rename = "session_init_ack")]
     SessionInitAck {
@@ -58,7 +67,7 @@ pub enum SessionMessage {
         /// 协议版本（可选）
         #[serde(skip_serializing_if = "Option::is_none")]
         protocol_version: Option<String>,
-        /// 是否使用 Binary Frame（Phase 2，可选）
+        /// 是否使用 Binary Frame 上行音频（仅在 session_init.supports_binary_frame 为 true 且服务端启用时为 true）
         #[serde(skip_serializing_if = "Option::is_none")]
         use_binary_frame: Option<bool>,
         /// 协商后的编解码器（可选）
@@ -73,6 +82,34 @@ pub enum SessionMessage {
         /// 协商后的声道数（可选）
         #[serde(skip_serializing_if = "Option::is_none")]
         negotiated_channel_count: Option<u32>,
+        /// 断线重连凭证（session_resume 使用，未启用会话恢复时不下发）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        resume_token: Option<String>,
+    },
+    /// 断线重连：把新连接挂回宽限期内的会话
+    #[serde(rename = "session_resume")]
+    SessionResume {
+        session_id: String,
+        resume_token: String,
+        /// 客户端已收到的最后一个 utterance_index（之后的结果会重放）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        last_acked_utterance_index: Option<u64>,
+        /// 新连接是否支持 Binary Frame（与 session_init 相同，需重新协商）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        supports_binary_frame: Option<bool>,
+    },
+    #[serde(rename = "session_resume_ack")]
+    SessionResumeAck {
+        session_id: String,
+        trace_id: String,
+        /// 轮换后的新 resume_token（旧 token 已失效）
+        resume_token: String,
+        /// 下一个 utterance_index
+        next_utterance_index: u64,
+        /// 重放的结果条数（紧随 ack 下发）
+        replayed_count: usize,
+        /// 新连接是否使用 Binary Frame 上行音频
+        use_binary_frame: bool,
     },
     #[serde(rename = "utterance")]
     Utterance {
@@ -214,6 +214,7 @@ fn format_dispatch_exclude_reason(r: &DispatchExcludeReason) -> String {
         DispatchExcludeReason::LangPairUnsupported => "LangPairUnsupported",
         DispatchExcludeReason::AsrLangUnsupported => "AsrLangUnsupported",
         DispatchExcludeReason::TtsLangUnsupported => "TtsLangUnsupported",
+        DispatchExcludeReason::CircuitOpen => "CircuitOpen",
     }
     .to_string()
 }
@@ -5,8 +5,8 @@
 
 use crate::core::AppState;
 use prometheus::{
-    Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
-    TextEncoder,
+    Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
 };
 use std::collections::HashSet;
 use std::sync::Mutex;
@@ -226,6 +226,42 @@ lazy_static::lazy_static! {
     )
     .expect("metric");
 
+    // —— node circuit breaker —— //
+    static ref NODE_CIRCUIT_BREAKER_STATE: IntGaugeVec = IntGaugeVec::new(
+        Opts::new(
+            "node_circuit_breaker_state",
+            "Node circuit breaker state by node_id and service_type (0=closed, 1=half_open, 2=open)"
+        ),
+        &["node_id", "service_type"]
+    )
+    .expect("metric");
+    static ref NODE_CIRCUIT_BREAKER_TRANSITIONS_TOTAL: IntCounterVec = IntCounterVec::new(
+        Opts::new(
+            "node_circuit_breaker_transitions_total",
+            "Node circuit breaker state transitions by service_type and target state"
+        ),
+        &["service_type", "state"] // state=open|half_open|closed
+    )
+    .expect("metric");
+    static ref NODE_CIRCUIT_BREAKER_REJECTED_TOTAL: IntCounterVec = IntCounterVec::new(
+        Opts::new(
+            "node_circuit_breaker_rejected_total",
+            "Candidate nodes skipped during selection because the required service breaker is open"
+        ),
+        &["service_type"]
+    )
+    .expect("metric");
+
+    // 对冲派发
+    static ref HEDGED_DISPATCH_TOTAL: IntCounterVec = IntCounterVec::new(
+        Opts::new(
+            "hedged_dispatch_total",
+            "Hedged dispatch events by outcome"
+        ),
+        &["outcome"] // outcome=started|won_by_hedge|won_by_primary|attempt_failed|budget_exhausted|no_candidate|send_failed
+    )
+    .expect("metric");
+
     static ref SERVICE_KEYS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
     static ref REASON_KEYS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
     static ref RATE_LIMITED_NODE_KEYS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
@@ -269,6 +305,12 @@ pub fn init() {
     let _ = REGISTRY.register(Box::new(DISPATCH_LATENCY_SECONDS.clone()));
     let _ = REGISTRY.register(Box::new(ACK_TIMEOUT_TOTAL.clone()));
     let _ = REGISTRY.register(Box::new(NODE_OVERLOAD_REJECT_TOTAL.clone()));
+
+    let _ = REGISTRY.register(Box::new(NODE_CIRCUIT_BREAKER_STATE.clone()));
+    let _ = REGISTRY.register(Box::new(NODE_CIRCUIT_BREAKER_TRANSITIONS_TOTAL.clone()));
+    let _ = REGISTRY.register(Box::new(NODE_CIRCUIT_BREAKER_REJECTED_TOTAL.clone()));
+
+    let _ = REGISTRY.register(Box::new(HEDGED_DISPATCH_TOTAL.clone()));
 }
 
 pub fn observe_stats_request_duration_seconds(secs: f64) {
@@ -395,6 +437,23 @@ pub async fn update_gauges_from_state(state: &AppState) {
     }
 
     WEB_TASK_PAUSE_MS.set(state.web_task_segmentation.pause_ms as i64);
+
+    // 熔断状态以 Redis 为准（多实例共享），每次抓取时整体重建
+    if let Some(breaker) = state.node_circuit_breaker.as_ref() {
+        match breaker.all_snapshots().await {
+            Ok(all) => {
+                NODE_CIRCUIT_BREAKER_STATE.reset();
+                for (node_id, snapshots) in all {
+                    for s in snapshots {
+                        NODE_CIRCUIT_BREAKER_STATE
+                            .with_label_values(&[&node_id, &s.service_type])
+                            .set(s.state.gauge_value());
+                    }
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "读取节点熔断状态失败"),
+        }
+    }
 }
 
 pub async fn render_text(state: &AppState) -> (String, String) {
@@ -444,4 +503,28 @@ pub fn on_node_overload_reject(node_id: &str, reason: &'static str) {
         .inc();
 }
 
+// ===== Node circuit breaker =====
 
+/// 记录熔断状态迁移
+pub fn on_circuit_breaker_transition(
+    service_type: &crate::messages::ServiceType,
+    state: crate::services::node_circuit_breaker::BreakerState,
+) {
+    let service = format!("{:?}", service_type).to_lowercase();
+    NODE_CIRCUIT_BREAKER_TRANSITIONS_TOTAL
+        .with_label_values(&[&service, state.as_str()])
+        .inc();
+}
+
+/// 记录选节点时因熔断跳过的候选
+pub fn on_circuit_breaker_rejected(service_type: &crate::messages::ServiceType) {
+    let service = format!("{:?}", service_type).to_lowercase();
+    NODE_CIRCUIT_BREAKER_REJECTED_TOTAL
+        .with_label_values(&[&service])
+        .inc();
+}
+
+/// 记录对冲派发事件
+pub fn on_hedged_dispatch(outcome: &'static str) {
+    HEDGED_DISPATCH_TOTAL.with_label_values(&[outcome]).inc();
+}
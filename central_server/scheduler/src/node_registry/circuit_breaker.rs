@@ -0,0 +1,17 @@
+use super::NodeRegistry;
+use crate::messages::ServiceType;
+
+impl NodeRegistry {
+    /// 检查节点所需服务是否处于熔断中（未启用熔断器时总是放行）
+    ///
+    /// ## 实现方式
+    /// - 查询 Redis 中的熔断状态（{prefix}:breaker:{node:<node_id>}）
+    /// - half_open 时按 probe_interval_ms 放行探测任务（占用探测名额）
+    /// - Redis 失败时默认放行，避免误判
+    pub(crate) async fn is_circuit_open(&self, node_id: &str, required_types: &[ServiceType]) -> bool {
+        match self.circuit_breaker().await {
+            Some(breaker) => !breaker.admits(node_id, required_types).await,
+            None => false,
+        }
+    }
+}
@@ -5,6 +5,7 @@
 use crate::node_registry::{NodeData, NodeRedisRepository, NodeRegistrySimple, SchedNodeInfo};
 use crate::redis_runtime::RedisHandle;
 use crate::pool::PoolService;
+use crate::services::NodeCircuitBreaker;
 use anyhow::Result;
 use std::sync::Arc;
 use tracing::info;
@@ -23,6 +24,9 @@ pub struct NodeRegistry {
     /// Pool 服务（使用内部可变性以支持后期关联）
     pool_service: Arc<tokio::sync::RwLock<Option<Arc<PoolService>>>>,
     
+    /// 节点熔断器（可选，选节点时按所需服务类型准入）
+    circuit_breaker: Arc<tokio::sync::RwLock<Option<NodeCircuitBreaker>>>,
+    
     /// 资源使用率阈值
     pub(crate) resource_threshold: f32,
 }
@@ -41,6 +45,7 @@ impl NodeRegistry {
             redis_repo,
             simple_registry,
             pool_service: Arc::new(tokio::sync::RwLock::new(None)),
+            circuit_breaker: Arc::new(tokio::sync::RwLock::new(None)),
             resource_threshold: 0.9, // 默认 90%
         }
     }
@@ -56,6 +61,17 @@ impl NodeRegistry {
         self.pool_service.read().await.clone()
     }
     
+    /// 设置节点熔断器
+    pub async fn set_circuit_breaker(&self, breaker: NodeCircuitBreaker) {
+        *self.circuit_breaker.write().await = Some(breaker);
+        info!("NodeRegistry: 已启用节点熔断器");
+    }
+    
+    /// 获取节点熔断器（内部使用）
+    pub(crate) async fn circuit_breaker(&self) -> Option<NodeCircuitBreaker> {
+        self.circuit_breaker.read().await.clone()
+    }
+    
     /// 设置资源阈值（兼容方法）
     pub fn set_resource_threshold(&mut self, threshold: f32) {
         self.resource_threshold = threshold;
@@ -74,6 +74,7 @@ impl NodeRegistry {
                         s if s.contains("LangPairUnsupported") => DispatchExcludeReason::LangPairUnsupported,
                         s if s.contains("AsrLangUnsupported") => DispatchExcludeReason::AsrLangUnsupported,
                         s if s.contains("TtsLangUnsupported") => DispatchExcludeReason::TtsLangUnsupported,
+                        s if s.contains("CircuitOpen") => DispatchExcludeReason::CircuitOpen,
                         _ => continue, // 跳过无法识别的原因
                     };
                     result.insert(reason, (count, Vec::new())); // Vec 为空（不再保留 Top-K）
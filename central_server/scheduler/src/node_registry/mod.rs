@@ -4,6 +4,7 @@ mod types;
 mod validation;
 mod core;
 mod unavailable;
+mod circuit_breaker;
 mod exclude_stats;
 mod selection;
 
@@ -15,6 +15,11 @@ pub struct NoAvailableNodeBreakdown {
     pub asr_lang_unsupported: usize,
     pub tts_lang_unsupported: usize,
     pub src_auto_no_candidate: usize,
+    /// 所需服务熔断中的节点数
+    pub circuit_open: usize,
+    /// PoolService 本次选择的策略决策（候选、策略、命中绑定等）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection: Option<crate::pool::SelectionDecision>,
 }
 
 impl NoAvailableNodeBreakdown {
@@ -35,6 +40,7 @@ impl NoAvailableNodeBreakdown {
             ("asr_lang_unsupported", self.asr_lang_unsupported),
             ("tts_lang_unsupported", self.tts_lang_unsupported),
             ("src_auto_no_candidate", self.src_auto_no_candidate),
+            ("circuit_open", self.circuit_open),
         ];
         for (label, v) in candidates {
             if v > best.1 {
@@ -52,8 +52,10 @@ impl NodeRegistry {
                 "Redis 直查：使用 PoolService 选择节点"
             );
             
-            // 调用 PoolService（基于语言对的快速选择）
-            match pool_svc.select_node(src_lang, tgt_lang, None, None).await {
+            // 调用 PoolService（基于语言对的快速选择），记录策略决策便于排查
+            let (selected, decision) = pool_svc.select_node_explained(src_lang, tgt_lang, None, None).await;
+            breakdown.selection = Some(decision);
+            match selected {
                 Ok(candidate_node_id) => {
                     debug!(
                         node_id = %candidate_node_id,
@@ -241,6 +243,13 @@ impl NodeRegistry {
             }
         }
         
+        // 检查熔断（放在最后：half_open 探测名额只在其他条件都满足时占用）
+        if self.is_circuit_open(&node.node_id, required_types).await {
+            breakdown.circuit_open += 1;
+            self.record_exclude_reason(DispatchExcludeReason::CircuitOpen, candidate_node_id.to_string()).await;
+            return None;
+        }
+        
         // 通过所有验证
         Some(candidate_node_id.to_string())
     }
@@ -412,7 +421,27 @@ impl NodeRegistry {
             gpu_a.partial_cmp(&gpu_b).unwrap_or(std::cmp::Ordering::Equal)
         });
         
-        let selected_node_id = available_nodes[0].node_id.clone();
+        // Step 5: 按排序依次做熔断准入（只为最终选中的节点占用 half_open 探测名额）
+        let mut selected_node_id = None;
+        for node in &available_nodes {
+            if self.is_circuit_open(&node.node_id, required_types).await {
+                breakdown.circuit_open += 1;
+                self.record_exclude_reason(DispatchExcludeReason::CircuitOpen, node.node_id.clone()).await;
+                continue;
+            }
+            selected_node_id = Some(node.node_id.clone());
+            break;
+        }
+        let Some(selected_node_id) = selected_node_id else {
+            warn!(
+                circuit_open = breakdown.circuit_open,
+                required_types = ?required_types,
+                src_lang = %src_lang,
+                tgt_lang = %tgt_lang,
+                "Redis 直查：候选节点均处于熔断中（降级路径）"
+            );
+            return (None, breakdown.clone());
+        };
         
         info!(
             node_id = %selected_node_id,
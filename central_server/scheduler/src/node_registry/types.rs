@@ -49,5 +49,7 @@ pub enum DispatchExcludeReason {
     LangPairUnsupported,
     AsrLangUnsupported,
     TtsLangUnsupported,
+    /// 节点服务熔断中（连续失败/失败率超阈值）
+    CircuitOpen,
 }
 
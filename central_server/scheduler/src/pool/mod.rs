@@ -7,11 +7,13 @@
 pub mod types;
 // node_index 已删除（未使用）
 pub mod pool_service;
+pub mod selection_strategy;
 
 #[cfg(test)]
 mod tests;
 
 pub use pool_service::PoolService;
+pub use selection_strategy::SelectionDecision;
 // 以下导出仅用于测试
 #[allow(unused_imports)]
 pub use types::{DirectedLangPair, extract_directed_pairs, POOL_SIZE, MAX_POOL_ID};
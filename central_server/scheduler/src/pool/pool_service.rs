@@ -1,22 +1,45 @@
 //! PoolService：Pool 管理和节点选择
 
-use crate::redis_runtime::RedisHandle;
+use super::selection_strategy::{
+    strategy_from_name, NodeCandidate, NodeSelectionStrategy, RandomStrategy, SelectionContext, SelectionDecision,
+};
+use crate::core::config::LoadBalancerConfig;
+use crate::messages::ServiceType;
+use crate::redis_runtime::{RedisHandle, RedisRuntime};
+use crate::services::NodeCircuitBreaker;
 use anyhow::{anyhow, Result};
-use std::sync::Arc;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
 use tracing::{debug, info, warn};
 
+/// 保留最近的选择决策条数（GET /api/v1/pools/selection-decisions）
+const RECENT_DECISIONS_CAPACITY: usize = 200;
+
 pub struct PoolService {
     redis: Arc<RedisHandle>,
     scripts: ScriptsCache,
     /// 节点级 key 的 TTL（秒）。建议 3 × 心跳周期；持续心跳则刷新，否则自动过期，实现被动清理。
     node_ttl_secs: u64,
+    /// 未单独配置的池使用的策略
+    default_strategy: Arc<dyn NodeSelectionStrategy>,
+    /// 按 pair_key 配置的策略
+    pool_strategies: HashMap<String, Arc<dyn NodeSelectionStrategy>>,
+    /// 非 random 策略每次采样的候选数
+    candidate_sample_size: usize,
+    /// 读取节点预留槽位（least_reserved / power_of_two）
+    redis_runtime: Option<Arc<RedisRuntime>>,
+    /// 节点熔断器：选中节点须通过所需服务的熔断准入（half_open 探测名额只为最终选中的节点占用）
+    circuit_breaker: Option<NodeCircuitBreaker>,
+    recent_decisions: Mutex<VecDeque<SelectionDecision>>,
 }
 
 struct ScriptsCache {
     heartbeat_with_pool_assign: String,
     select_node: String,
+    select_candidates: String,
     node_offline: String,
     node_clear_pools: String,
+    node_drain: String,
 }
 
 impl PoolService {
@@ -31,15 +54,106 @@ impl PoolService {
             redis,
             scripts,
             node_ttl_secs,
+            default_strategy: Arc::new(RandomStrategy),
+            pool_strategies: HashMap::new(),
+            candidate_sample_size: LoadBalancerConfig::default().candidate_sample_size,
+            redis_runtime: None,
+            circuit_breaker: None,
+            recent_decisions: Mutex::new(VecDeque::new()),
         })
     }
+
+    /// 设置节点熔断器（未启用熔断时为 None）
+    pub fn with_circuit_breaker(mut self, circuit_breaker: Option<NodeCircuitBreaker>) -> Self {
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
+
+    /// 按 load_balancer 配置设置默认策略与按池策略；未知策略名回退 random
+    pub fn with_selection_strategies(
+        mut self,
+        config: &LoadBalancerConfig,
+        redis_runtime: Option<Arc<RedisRuntime>>,
+    ) -> Self {
+        fn resolve(name: &str) -> Arc<dyn NodeSelectionStrategy> {
+            match strategy_from_name(name) {
+                Some(strategy) => Arc::from(strategy),
+                None => {
+                    warn!(strategy = %name, "未知的节点选择策略，使用 random");
+                    Arc::new(RandomStrategy)
+                }
+            }
+        }
+        self.default_strategy = resolve(&config.strategy);
+        self.pool_strategies = config
+            .pool_strategies
+            .iter()
+            .map(|(pair_key, name)| (pair_key.clone(), resolve(name)))
+            .collect();
+        self.candidate_sample_size = config.candidate_sample_size.max(1);
+        self.redis_runtime = redis_runtime;
+        info!(
+            default_strategy = self.default_strategy.name(),
+            pool_overrides = self.pool_strategies.len(),
+            candidate_sample_size = self.candidate_sample_size,
+            "节点选择策略已配置"
+        );
+        self
+    }
+
+    /// 池的策略：pair_key 精确匹配 → 租户池按去掉租户前缀的语言对匹配 → 默认
+    fn strategy_for(&self, pair_key: &str) -> Arc<dyn NodeSelectionStrategy> {
+        let base_pair = pair_key
+            .strip_prefix("tenant:")
+            .and_then(|rest| rest.split_once(':'))
+            .map(|(_, pair)| pair);
+        self.pool_strategies
+            .get(pair_key)
+            .or_else(|| base_pair.and_then(|pair| self.pool_strategies.get(pair)))
+            .cloned()
+            .unwrap_or_else(|| self.default_strategy.clone())
+    }
+
+    /// 最近的选择决策（新的在前）
+    pub fn recent_decisions(&self, limit: usize) -> Vec<SelectionDecision> {
+        let decisions = self.recent_decisions.lock().unwrap_or_else(|e| e.into_inner());
+        decisions.iter().rev().take(limit).cloned().collect()
+    }
+
+    fn record_decision(&self, decision: &SelectionDecision) {
+        debug!(
+            pair_key = %decision.pair_key,
+            strategy = %decision.strategy,
+            bound = decision.bound,
+            candidates = decision.candidates.len(),
+            selected = ?decision.selected,
+            detail = %decision.detail,
+            "【节点选择】策略决策"
+        );
+        let mut decisions = self.recent_decisions.lock().unwrap_or_else(|e| e.into_inner());
+        if decisions.len() >= RECENT_DECISIONS_CAPACITY {
+            decisions.pop_front();
+        }
+        decisions.push_back(decision.clone());
+    }
+
+    /// 记录节点心跳上报的服务处理效率（weighted_efficiency 策略使用）
+    pub async fn record_service_efficiencies(&self, node_id: &str, efficiencies: &HashMap<String, f64>) -> Result<()> {
+        let json = serde_json::to_string(efficiencies)?;
+        self.redis
+            .hset_multi(&format!("lingua:v1:node:{}", node_id), &[("service_efficiencies", json.as_str())])
+            .await?;
+        Ok(())
+    }
     
     fn load_scripts() -> ScriptsCache {
         ScriptsCache {
             heartbeat_with_pool_assign: include_str!("../../scripts/lua/heartbeat_with_pool_assign.lua").to_string(),
             select_node: include_str!("../../scripts/lua/select_node.lua").to_string(),
+            select_candidates: include_str!("../../scripts/lua/select_candidates.lua").to_string(),
             node_offline: include_str!("../../scripts/lua/node_offline.lua").to_string(),
             node_clear_pools: include_str!("../../scripts/lua/node_clear_pools.lua").to_string(),
+            node_drain: include_str!("../../scripts/lua/node_drain.lua").to_string(),
         }
     }
     
@@ -67,14 +181,204 @@ impl PoolService {
     /// - `tgt_lang`: 目标语言（TTS + Semantic 输出的语言）
     /// - `job_id`: 任务 ID（MaxDuration job 级绑定）
     /// - `session_id`: 会话 ID（session affinity assigned_node_id）
+    /// - `required_types`: 任务所需服务类型，用于熔断准入（为空时不做准入）
+    /// - `exclude_node_id`: 不得选中的节点（对冲派发、排空迁移时排除当前节点）
     pub async fn select_node(
         &self,
         src_lang: &str,
         tgt_lang: &str,
         job_id: Option<&str>,
         session_id: Option<&str>,
+        required_types: &[ServiceType],
+        exclude_node_id: Option<&str>,
+    ) -> Result<String> {
+        let pair_key = format!("{}:{}", src_lang, tgt_lang);
+        let target = SelectionTarget { pair_key: &pair_key, job_id, session_id, required_types, exclude_node_id };
+        self.select_in_pair_explained(&target).await.0
+    }
+
+    /// 从租户私有池选择节点（节点注册时声明 owner_tenant_id，见 heartbeat_with_pool_assign.lua）
+    #[allow(clippy::too_many_arguments)]
+    pub async fn select_tenant_node(
+        &self,
+        tenant_id: &str,
+        src_lang: &str,
+        tgt_lang: &str,
+        job_id: Option<&str>,
+        session_id: Option<&str>,
+        required_types: &[ServiceType],
+        exclude_node_id: Option<&str>,
     ) -> Result<String> {
+        let pair_key = tenant_pair_key(tenant_id, src_lang, tgt_lang);
+        let target = SelectionTarget { pair_key: &pair_key, job_id, session_id, required_types, exclude_node_id };
+        self.select_in_pair_explained(&target).await.0
+    }
+
+    /// 选择节点并返回决策记录（NodeRegistry 写入 NoAvailableNodeBreakdown 用于排查；
+    /// 该路径在节点校验阶段自行做熔断准入，这里不传所需服务类型）
+    pub async fn select_node_explained(
+        &self,
+        src_lang: &str,
+        tgt_lang: &str,
+        job_id: Option<&str>,
+        session_id: Option<&str>,
+    ) -> (Result<String>, SelectionDecision) {
         let pair_key = format!("{}:{}", src_lang, tgt_lang);
+        let target = SelectionTarget { pair_key: &pair_key, job_id, session_id, required_types: &[], exclude_node_id: None };
+        self.select_in_pair_explained(&target).await
+    }
+
+    async fn select_in_pair_explained(&self, target: &SelectionTarget<'_>) -> (Result<String>, SelectionDecision) {
+        let strategy = self.strategy_for(target.pair_key);
+        let mut decision = SelectionDecision {
+            pair_key: target.pair_key.to_string(),
+            strategy: strategy.name().to_string(),
+            timestamp_ms: chrono::Utc::now().timestamp_millis(),
+            ..Default::default()
+        };
+        let breaker = self.circuit_breaker.as_ref().filter(|_| !target.required_types.is_empty());
+        let result = if strategy.name() == RandomStrategy.name() && breaker.is_none() && target.exclude_node_id.is_none() {
+            // random 且无需熔断准入、无排除节点：沿用 select_node.lua（脚本内完成绑定判断与随机选择）
+            let result = self.select_random(target.pair_key, target.job_id, target.session_id).await;
+            decision.detail = "select_node.lua".to_string();
+            result
+        } else {
+            self.select_with_strategy(strategy.as_ref(), target, breaker, &mut decision).await
+        };
+        decision.selected = result.as_ref().ok().cloned();
+        self.record_decision(&decision);
+        (result, decision)
+    }
+
+    /// select_candidates.lua 取候选（命中绑定直接返回），再由策略挑选。
+    /// 绑定节点被排除或未通过熔断准入时忽略绑定重新取候选；策略选中的节点未通过准入则换下一个
+    async fn select_with_strategy(
+        &self,
+        strategy: &dyn NodeSelectionStrategy,
+        target: &SelectionTarget<'_>,
+        breaker: Option<&NodeCircuitBreaker>,
+        decision: &mut SelectionDecision,
+    ) -> Result<String> {
+        let SelectionTarget { pair_key, job_id, session_id, required_types, exclude_node_id } = *target;
+        let sample_size = self.candidate_sample_size.to_string();
+        let mut binding = (job_id.unwrap_or(""), session_id.unwrap_or(""));
+        let node_ids = loop {
+            let reply: Vec<String> = self
+                .eval_script(&self.scripts.select_candidates, &[pair_key, binding.0, binding.1, &sample_size])
+                .await?;
+            let (kind, node_ids) = reply.split_first().ok_or_else(|| anyhow!("select_candidates 返回为空"))?;
+            if kind != "bound" {
+                break node_ids.to_vec();
+            }
+            let bound = node_ids
+                .first()
+                .cloned()
+                .ok_or_else(|| anyhow!("select_candidates 返回的绑定节点为空"))?;
+            if exclude_node_id == Some(bound.as_str()) {
+                binding = ("", "");
+                continue;
+            }
+            match breaker {
+                Some(breaker) if !breaker.admits(&bound, required_types).await => {
+                    // 绑定节点熔断中：忽略绑定重新取候选（选中的新节点会重新写入 job 绑定）
+                    decision.circuit_open.push(bound);
+                    binding = ("", "");
+                }
+                _ => {
+                    decision.candidates = vec![bound.clone()];
+                    decision.bound = true;
+                    decision.detail = "job/session binding".to_string();
+                    return Ok(bound);
+                }
+            }
+        };
+        let node_ids: Vec<String> = node_ids.into_iter().filter(|id| Some(id.as_str()) != exclude_node_id).collect();
+        decision.candidates = node_ids.clone();
+        if node_ids.is_empty() {
+            decision.detail = "no candidates".to_string();
+            warn!(pair_key = %pair_key, "【节点选择】没有可用的节点（语言对无池或池为空）");
+            return Err(anyhow!("没有可用的节点（语言对: {}）", pair_key));
+        }
+
+        let candidates = self.load_candidates(strategy, &node_ids).await;
+        let ctx = SelectionContext { session_id };
+        let (node_id, detail) = pick_admitted(strategy, &ctx, candidates, &mut decision.circuit_open, |node_id| async move {
+            match breaker {
+                Some(breaker) => breaker.admits(&node_id, required_types).await,
+                None => true,
+            }
+        })
+        .await
+        .ok_or_else(|| anyhow!("策略 {} 未选出可用节点（语言对: {}）", strategy.name(), pair_key))?;
+        decision.detail = detail;
+
+        // 与 select_node.lua 一致：MaxDuration job 链绑定到选中节点
+        if let Some(job_id) = job_id.filter(|j| !j.is_empty()) {
+            let mut cmd = redis::cmd("SET");
+            cmd.arg(format!("lingua:v1:job:{}:node", job_id)).arg(&node_id).arg("EX").arg(3600);
+            let _: Option<String> = self.redis.query(cmd).await?;
+        }
+        info!(pair_key = %pair_key, node_id = %node_id, strategy = strategy.name(), "【节点选择】成功");
+        Ok(node_id)
+    }
+
+    /// 按策略声明的输入读取候选节点数据（所有候选的字段在一个 pipeline 内读取）
+    async fn load_candidates(&self, strategy: &dyn NodeSelectionStrategy, node_ids: &[String]) -> Vec<NodeCandidate> {
+        let inputs = strategy.inputs();
+        let cap_runtime = self.redis_runtime.as_ref().filter(|_| inputs.reserved_slots);
+        let mut candidates: Vec<NodeCandidate> = node_ids
+            .iter()
+            .map(|node_id| NodeCandidate {
+                node_id: node_id.clone(),
+                ..Default::default()
+            })
+            .collect();
+        if cap_runtime.is_none() && !inputs.efficiencies {
+            return candidates;
+        }
+
+        let mut pipe = redis::pipe();
+        for node_id in node_ids {
+            if let Some(rt) = cap_runtime {
+                pipe.cmd("HGET").arg(rt.node_cap_key(node_id)).arg("reserved");
+            }
+            if inputs.efficiencies {
+                pipe.cmd("HGET").arg(format!("lingua:v1:node:{}", node_id)).arg("service_efficiencies");
+            }
+        }
+        let values: Vec<Option<String>> = match self.redis.query_pipe(&pipe).await {
+            Ok(values) => values,
+            Err(e) => {
+                warn!(error = %e, "读取候选节点数据失败，按空数据交给策略");
+                return candidates;
+            }
+        };
+        let mut values = values.into_iter();
+        for candidate in candidates.iter_mut() {
+            if cap_runtime.is_some() {
+                candidate.reserved_slots = values
+                    .next()
+                    .flatten()
+                    .and_then(|v| v.parse::<i64>().ok())
+                    .map_or(0, |v| v.max(0) as u64);
+            }
+            if inputs.efficiencies {
+                candidate.efficiencies = values
+                    .next()
+                    .flatten()
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default();
+            }
+        }
+        candidates
+    }
+
+    async fn select_random(
+        &self,
+        pair_key: &str,
+        job_id: Option<&str>,
+        session_id: Option<&str>,
+    ) -> Result<String> {
         let job_id_str = job_id.unwrap_or("");
         let session_id_str = session_id.unwrap_or("");
         
@@ -87,7 +391,7 @@ impl PoolService {
         
         let result: Option<String> = self.eval_script(
             &self.scripts.select_node,
-            &[&pair_key, job_id_str, session_id_str],
+            &[pair_key, job_id_str, session_id_str],
         ).await?;
         
         match result {
@@ -137,6 +441,46 @@ impl PoolService {
         Ok(())
     }
     
+    /// 节点排空：标记 draining 并移出所有池，返回原 pair_key -> pool_id 映射
+    pub async fn node_drain(&self, node_id: &str) -> Result<HashMap<String, String>> {
+        let flat: Vec<String> = self.eval_script(&self.scripts.node_drain, &[node_id]).await?;
+        let mapping: HashMap<String, String> = flat
+            .chunks_exact(2)
+            .map(|kv| (kv[0].clone(), kv[1].clone()))
+            .collect();
+        info!(node_id = %node_id, pools = mapping.len(), "节点已标记排空并移出所有池");
+        Ok(mapping)
+    }
+
+    /// 从指定池中随机取一个在线节点（排除 exclude），用于排空时选择同池迁移目标
+    pub async fn pick_pool_member(&self, pair_key: &str, pool_id: &str, exclude: &str) -> Result<Option<String>> {
+        let pool_key = format!("lingua:v1:pool:{}:{}:nodes", pair_key, pool_id);
+        let mut cmd = redis::cmd("SRANDMEMBER");
+        cmd.arg(&pool_key).arg(16);
+        let candidates: Vec<String> = self.redis.query(cmd).await?;
+        for node_id in candidates {
+            if node_id == exclude {
+                continue;
+            }
+            if self.redis.exists(&format!("lingua:v1:node:{}", node_id)).await? {
+                return Ok(Some(node_id));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 节点是否可直接派发（node key 未过期且未排空），用于配对会话等不经池选择的路径
+    pub async fn is_node_dispatchable(&self, node_id: &str) -> Result<bool> {
+        let node_key = format!("lingua:v1:node:{}", node_id);
+        if !self.redis.exists(&node_key).await? {
+            return Ok(false);
+        }
+        let mut cmd = redis::cmd("HGET");
+        cmd.arg(&node_key).arg("status");
+        let status: Option<String> = self.redis.query(cmd).await?;
+        Ok(status.as_deref() != Some("draining"))
+    }
+
     /// 执行 Lua 脚本
     async fn eval_script<T: redis::FromRedisValue>(
         &self,
@@ -154,6 +498,44 @@ impl PoolService {
     }
 }
 
+/// 一次池内选择的目标（绑定、熔断准入所需服务类型与排除节点）
+#[derive(Clone, Copy)]
+struct SelectionTarget<'a> {
+    pair_key: &'a str,
+    job_id: Option<&'a str>,
+    session_id: Option<&'a str>,
+    required_types: &'a [ServiceType],
+    exclude_node_id: Option<&'a str>,
+}
+
+/// 按策略挑选并做熔断准入：选中节点被拒绝时移出候选（记入 skipped）后重新挑选，
+/// 因此只有最终选中的节点会占用 half_open 探测名额
+async fn pick_admitted<F, Fut>(
+    strategy: &dyn NodeSelectionStrategy,
+    ctx: &SelectionContext<'_>,
+    mut candidates: Vec<NodeCandidate>,
+    skipped: &mut Vec<String>,
+    mut admit: F,
+) -> Option<(String, String)>
+where
+    F: FnMut(String) -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    while let Some((idx, detail)) = strategy.select(ctx, &candidates) {
+        let candidate = candidates.swap_remove(idx);
+        if admit(candidate.node_id.clone()).await {
+            return Some((candidate.node_id, detail));
+        }
+        skipped.push(candidate.node_id);
+    }
+    None
+}
+
+/// 租户私有池的 pair_key（"tenant:{tenant_id}:{src}:{tgt}"），池 key 与公共池同构
+pub fn tenant_pair_key(tenant_id: &str, src_lang: &str, tgt_lang: &str) -> String {
+    format!("tenant:{}:{}:{}", tenant_id, src_lang, tgt_lang)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -187,4 +569,67 @@ mod tests {
             "select_node.lua must not use affinity_node_id"
         );
     }
+
+    /// 节点排空：draining 节点不得被心跳加回池，也不得被选中
+    #[test]
+    fn test_draining_node_excluded_contract() {
+        let heartbeat = include_str!("../../scripts/lua/heartbeat_with_pool_assign.lua");
+        let select = include_str!("../../scripts/lua/select_node.lua");
+        let drain = include_str!("../../scripts/lua/node_drain.lua");
+        assert!(heartbeat.contains("\"draining\""), "heartbeat must skip pool assignment for draining nodes");
+        assert_eq!(select.matches("~= \"draining\"").count(), 2, "select_node must skip draining nodes");
+        assert!(drain.contains("\"status\", \"draining\""), "node_drain must mark status=draining");
+    }
+
+    /// 会话亲和按 node:{id}:pools 映射直接定位池，不逐个池号探测
+    #[test]
+    fn test_session_affinity_uses_node_pool_mapping() {
+        for script in [
+            include_str!("../../scripts/lua/select_node.lua"),
+            include_str!("../../scripts/lua/select_candidates.lua"),
+        ] {
+            let affinity = &script[script.find("\"assigned_node_id\")").unwrap()..];
+            let affinity = &affinity[..affinity.find("\nend\n").unwrap()];
+            assert!(affinity.contains("\":pools\", pair_key"));
+            assert!(!affinity.contains("MAX_POOL_ID"));
+        }
+    }
+
+    /// 私有节点：不进公共池，归属租户的节点进入 tenant 作用域池
+    #[test]
+    fn test_private_pool_scopes_contract() {
+        assert_eq!(super::tenant_pair_key("acme", "zh", "en"), "tenant:acme:zh:en");
+        let script = include_str!("../../scripts/lua/heartbeat_with_pool_assign.lua");
+        assert!(script.contains("accept_public_jobs"));
+        assert!(script.contains("\"tenant:\" .. owner_tenant_id .. \":\""));
+        let register = include_str!("../../scripts/lua/register_node_v2.lua");
+        assert!(register.contains("owner_tenant_id"));
+    }
+
+    /// 熔断准入：首次派发即跳过熔断中的节点，准入只对策略选中的节点进行
+    #[tokio::test]
+    async fn test_pick_admitted_skips_open_node_on_first_dispatch() {
+        use crate::pool::selection_strategy::{LeastReservedStrategy, NodeCandidate, SelectionContext};
+        let candidates = vec![
+            NodeCandidate { node_id: "node-open".to_string(), reserved_slots: 0, ..Default::default() },
+            NodeCandidate { node_id: "node-b".to_string(), reserved_slots: 3, ..Default::default() },
+            NodeCandidate { node_id: "node-c".to_string(), reserved_slots: 5, ..Default::default() },
+        ];
+        let mut admitted_checks = Vec::new();
+        let mut skipped = Vec::new();
+        let picked = super::pick_admitted(
+            &LeastReservedStrategy,
+            &SelectionContext::default(),
+            candidates,
+            &mut skipped,
+            |node_id| {
+                admitted_checks.push(node_id.clone());
+                async move { node_id != "node-open" }
+            },
+        )
+        .await;
+        assert_eq!(picked.map(|(node_id, _)| node_id).as_deref(), Some("node-b"));
+        assert_eq!(skipped, vec!["node-open".to_string()]);
+        assert_eq!(admitted_checks, vec!["node-open".to_string(), "node-b".to_string()]);
+    }
 }
@@ -0,0 +1,299 @@
+//! 节点选择策略（池内负载均衡）
+//!
+//! PoolService 通过 select_candidates.lua 取得候选节点（job/session 绑定优先，命中时不经策略），
+//! 再由按池配置的 `NodeSelectionStrategy` 挑选。内置策略：
+//! - `random`：随机（沿用 select_node.lua，不采样候选；需要熔断准入时改走候选路径）
+//! - `least_reserved`：预留槽位最少（RedisRuntime::node_reserved_count），并列时随机
+//! - `weighted_efficiency`：按心跳上报的 ProcessingMetrics.service_efficiencies 加权随机
+//! - `power_of_two`：随机取两个候选，选预留槽位较少者
+//! - `session_sticky`：按 session_id 做 rendezvous hash，同一会话在候选集合不变时稳定落到同一节点
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// 策略需要的候选节点数据（只按需读取，避免每次选择都查全部字段）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CandidateInputs {
+    pub reserved_slots: bool,
+    pub efficiencies: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NodeCandidate {
+    pub node_id: String,
+    /// 当前预留槽位数（未请求时为 0）
+    pub reserved_slots: u64,
+    /// 服务 ID -> 处理效率（未请求或节点未上报时为空）
+    pub efficiencies: HashMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SelectionContext<'a> {
+    pub session_id: Option<&'a str>,
+}
+
+pub trait NodeSelectionStrategy: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn inputs(&self) -> CandidateInputs {
+        CandidateInputs::default()
+    }
+
+    /// 从候选中选择一个，返回下标与决策说明
+    fn select(&self, ctx: &SelectionContext<'_>, candidates: &[NodeCandidate]) -> Option<(usize, String)>;
+}
+
+/// 一次选择的决策记录（写入 NoAvailableNodeBreakdown.selection 与 PoolService 最近决策）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SelectionDecision {
+    pub pair_key: String,
+    pub strategy: String,
+    /// 命中 job/session 绑定，未经策略
+    pub bound: bool,
+    pub candidates: Vec<String>,
+    /// 策略选中但熔断准入被拒绝而跳过的节点
+    pub circuit_open: Vec<String>,
+    pub selected: Option<String>,
+    pub detail: String,
+    pub timestamp_ms: i64,
+}
+
+pub struct RandomStrategy;
+
+impl NodeSelectionStrategy for RandomStrategy {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    fn select(&self, _ctx: &SelectionContext<'_>, candidates: &[NodeCandidate]) -> Option<(usize, String)> {
+        if candidates.is_empty() {
+            return None;
+        }
+        Some((rand::thread_rng().gen_range(0..candidates.len()), "uniform".to_string()))
+    }
+}
+
+pub struct LeastReservedStrategy;
+
+impl NodeSelectionStrategy for LeastReservedStrategy {
+    fn name(&self) -> &'static str {
+        "least_reserved"
+    }
+
+    fn inputs(&self) -> CandidateInputs {
+        CandidateInputs {
+            reserved_slots: true,
+            efficiencies: false,
+        }
+    }
+
+    fn select(&self, _ctx: &SelectionContext<'_>, candidates: &[NodeCandidate]) -> Option<(usize, String)> {
+        let min = candidates.iter().map(|c| c.reserved_slots).min()?;
+        let tied: Vec<usize> = (0..candidates.len())
+            .filter(|&i| candidates[i].reserved_slots == min)
+            .collect();
+        let idx = *tied.choose(&mut rand::thread_rng())?;
+        Some((idx, format!("reserved={} tied={}", min, tied.len())))
+    }
+}
+
+pub struct WeightedEfficiencyStrategy;
+
+/// 未上报效率的节点按中等权重参与，避免新节点永远选不到
+const NEUTRAL_WEIGHT: f64 = 0.5;
+const MIN_WEIGHT: f64 = 0.05;
+
+impl WeightedEfficiencyStrategy {
+    /// 各服务效率单位不同（ASR/TTS 为倍速，NMT 为字符/秒），先按服务在候选中的最大值归一化再取平均
+    fn weights(candidates: &[NodeCandidate]) -> Vec<f64> {
+        let mut max_by_service: HashMap<&str, f64> = HashMap::new();
+        for c in candidates {
+            for (service, &eff) in &c.efficiencies {
+                if eff.is_finite() && eff > 0.0 {
+                    let max = max_by_service.entry(service.as_str()).or_insert(0.0);
+                    *max = max.max(eff);
+                }
+            }
+        }
+        candidates
+            .iter()
+            .map(|c| {
+                let normalized: Vec<f64> = c
+                    .efficiencies
+                    .iter()
+                    .filter_map(|(service, &eff)| {
+                        let max = *max_by_service.get(service.as_str())?;
+                        (eff.is_finite() && eff > 0.0).then(|| eff / max)
+                    })
+                    .collect();
+                if normalized.is_empty() {
+                    NEUTRAL_WEIGHT
+                } else {
+                    (normalized.iter().sum::<f64>() / normalized.len() as f64).max(MIN_WEIGHT)
+                }
+            })
+            .collect()
+    }
+}
+
+impl NodeSelectionStrategy for WeightedEfficiencyStrategy {
+    fn name(&self) -> &'static str {
+        "weighted_efficiency"
+    }
+
+    fn inputs(&self) -> CandidateInputs {
+        CandidateInputs {
+            reserved_slots: false,
+            efficiencies: true,
+        }
+    }
+
+    fn select(&self, _ctx: &SelectionContext<'_>, candidates: &[NodeCandidate]) -> Option<(usize, String)> {
+        if candidates.is_empty() {
+            return None;
+        }
+        let weights = Self::weights(candidates);
+        let total: f64 = weights.iter().sum();
+        let mut point = rand::thread_rng().gen_range(0.0..total);
+        let mut idx = candidates.len() - 1;
+        for (i, w) in weights.iter().enumerate() {
+            if point < *w {
+                idx = i;
+                break;
+            }
+            point -= w;
+        }
+        Some((idx, format!("weight={:.3} total={:.3}", weights[idx], total)))
+    }
+}
+
+pub struct PowerOfTwoStrategy;
+
+impl NodeSelectionStrategy for PowerOfTwoStrategy {
+    fn name(&self) -> &'static str {
+        "power_of_two"
+    }
+
+    fn inputs(&self) -> CandidateInputs {
+        CandidateInputs {
+            reserved_slots: true,
+            efficiencies: false,
+        }
+    }
+
+    fn select(&self, _ctx: &SelectionContext<'_>, candidates: &[NodeCandidate]) -> Option<(usize, String)> {
+        let indices: Vec<usize> = (0..candidates.len()).collect();
+        let picked: Vec<usize> = indices.choose_multiple(&mut rand::thread_rng(), 2).copied().collect();
+        match picked.as_slice() {
+            [only] => Some((*only, "single candidate".to_string())),
+            [a, b] => {
+                let (ra, rb) = (candidates[*a].reserved_slots, candidates[*b].reserved_slots);
+                let idx = if rb < ra { *b } else { *a };
+                Some((idx, format!("{}(reserved={}) vs {}(reserved={})", candidates[*a].node_id, ra, candidates[*b].node_id, rb)))
+            }
+            _ => None,
+        }
+    }
+}
+
+pub struct SessionStickyStrategy;
+
+impl SessionStickyStrategy {
+    fn score(session_id: &str, node_id: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        session_id.hash(&mut hasher);
+        node_id.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl NodeSelectionStrategy for SessionStickyStrategy {
+    fn name(&self) -> &'static str {
+        "session_sticky"
+    }
+
+    fn select(&self, ctx: &SelectionContext<'_>, candidates: &[NodeCandidate]) -> Option<(usize, String)> {
+        let Some(session_id) = ctx.session_id.filter(|s| !s.is_empty()) else {
+            return RandomStrategy.select(ctx, candidates).map(|(i, _)| (i, "no session, random".to_string()));
+        };
+        let idx = (0..candidates.len()).max_by_key(|&i| Self::score(session_id, &candidates[i].node_id))?;
+        Some((idx, "rendezvous hash".to_string()))
+    }
+}
+
+/// 按名称构造内置策略；"least_connections" 为旧配置值，等同 least_reserved
+pub fn strategy_from_name(name: &str) -> Option<Box<dyn NodeSelectionStrategy>> {
+    match name.trim() {
+        "random" => Some(Box::new(RandomStrategy)),
+        "least_reserved" | "least_reserved_slots" | "least_connections" => Some(Box::new(LeastReservedStrategy)),
+        "weighted_efficiency" | "weighted" => Some(Box::new(WeightedEfficiencyStrategy)),
+        "power_of_two" | "power_of_two_choices" => Some(Box::new(PowerOfTwoStrategy)),
+        "session_sticky" => Some(Box::new(SessionStickyStrategy)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(node_id: &str, reserved_slots: u64, efficiencies: &[(&str, f64)]) -> NodeCandidate {
+        NodeCandidate {
+            node_id: node_id.to_string(),
+            reserved_slots,
+            efficiencies: efficiencies.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+        }
+    }
+
+    #[test]
+    fn strategy_names_resolve() {
+        for name in ["random", "least_reserved", "least_connections", "weighted_efficiency", "power_of_two", "session_sticky"] {
+            assert!(strategy_from_name(name).is_some(), "{}", name);
+        }
+        assert!(strategy_from_name("round_robin").is_none());
+        assert_eq!(strategy_from_name("least_connections").unwrap().name(), "least_reserved");
+    }
+
+    #[test]
+    fn least_reserved_and_power_of_two_prefer_idle_nodes() {
+        let ctx = SelectionContext::default();
+        let candidates = vec![candidate("busy", 5, &[]), candidate("idle", 0, &[]), candidate("mid", 2, &[])];
+        let (idx, _) = LeastReservedStrategy.select(&ctx, &candidates).unwrap();
+        assert_eq!(candidates[idx].node_id, "idle");
+
+        let pair = vec![candidate("busy", 5, &[]), candidate("idle", 0, &[])];
+        for _ in 0..16 {
+            let (idx, _) = PowerOfTwoStrategy.select(&ctx, &pair).unwrap();
+            assert_eq!(pair[idx].node_id, "idle");
+        }
+        assert!(PowerOfTwoStrategy.select(&ctx, &[]).is_none());
+    }
+
+    #[test]
+    fn efficiency_weights_are_normalized_per_service() {
+        let candidates = vec![
+            candidate("fast", 0, &[("asr", 4.0), ("nmt", 200.0)]),
+            candidate("slow", 0, &[("asr", 1.0), ("nmt", 50.0)]),
+            candidate("new", 0, &[]),
+        ];
+        let weights = WeightedEfficiencyStrategy::weights(&candidates);
+        assert!((weights[0] - 1.0).abs() < 1e-9);
+        assert!((weights[1] - 0.25).abs() < 1e-9);
+        assert!((weights[2] - NEUTRAL_WEIGHT).abs() < 1e-9);
+    }
+
+    #[test]
+    fn session_sticky_is_stable_for_a_session() {
+        let candidates = vec![candidate("a", 0, &[]), candidate("b", 0, &[]), candidate("c", 0, &[])];
+        let ctx = SelectionContext { session_id: Some("s-1") };
+        let (first, _) = SessionStickyStrategy.select(&ctx, &candidates).unwrap();
+        let mut reversed = candidates.clone();
+        reversed.reverse();
+        let (again, _) = SessionStickyStrategy.select(&ctx, &reversed).unwrap();
+        assert_eq!(candidates[first].node_id, reversed[again].node_id);
+    }
+}
@@ -100,6 +100,8 @@ include!("redis_runtime/runtime_routing_session_state.rs");
 // runtime_routing_lang_index.rs 已删除（语言索引已废弃，现在使用 PoolService）
 include!("redis_runtime/runtime_cold_start.rs");
 include!("redis_runtime/runtime_job_fsm.rs");
+include!("redis_runtime/runtime_tenant_sessions.rs");
+include!("redis_runtime/runtime_rooms.rs");
 include!("redis_runtime/runtime_background.rs");
 // runtime_snapshot.rs 已删除（Redis 直查架构不再需要）
 include!("redis_runtime/runtime_streams.rs");
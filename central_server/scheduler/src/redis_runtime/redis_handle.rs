@@ -40,6 +40,15 @@ impl RedisHandle {
         }
     }
 
+    /// 执行 pipeline（Cluster 模式下各命令的 key 需在同一 slot）
+    pub async fn query_pipe<T: redis::FromRedisValue>(&self, pipe: &redis::Pipeline) -> redis::RedisResult<T> {
+        let mut guard = self.inner.lock().await;
+        match &mut *guard {
+            RedisConn::Single(c) => pipe.query_async(c).await,
+            RedisConn::Cluster(c) => pipe.query_async(c).await,
+        }
+    }
+
     pub async fn set_ex_string(&self, key: &str, val: &str, ttl_seconds: u64) -> redis::RedisResult<()> {
         let mut cmd = redis::cmd("SET");
         cmd.arg(key).arg(val).arg("EX").arg(ttl_seconds.max(self.min_ttl_seconds));
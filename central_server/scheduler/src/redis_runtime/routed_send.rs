@@ -46,3 +46,34 @@ pub async fn send_session_message_routed(state: &AppState, session_id: &str, msg
     }).await
 }
 
+
+/// 向房间成员广播 SessionMessage：本实例持有的会话直接发送，其余经 Streams 投递到持有该会话的实例
+pub async fn broadcast_to_room_members(
+    state: &AppState,
+    members: &[crate::managers::room_manager::Participant],
+    msg: &SessionMessage,
+    exclude_session_id: Option<&str>,
+) {
+    let json = match serde_json::to_string(msg) {
+        Ok(v) => v,
+        Err(e) => {
+            warn!(error = %e, "房间广播消息序列化失败");
+            return;
+        }
+    };
+    for member in members {
+        if exclude_session_id == Some(member.session_id.as_str()) {
+            continue;
+        }
+        if state
+            .session_connections
+            .send(&member.session_id, WsMessage::Text(json.clone()))
+            .await
+        {
+            continue;
+        }
+        if state.redis_runtime.is_some() && !send_session_message_routed(state, &member.session_id, msg.clone()).await {
+            warn!(session_id = %member.session_id, "房间广播失败：无法投递到会话所在实例");
+        }
+    }
+}
@@ -28,6 +28,13 @@ impl RedisRuntime {
                 for nid in node_ids {
                     rt.set_node_owner(&nid).await;
                 }
+                // 断线宽限期内的会话仍由本实例持有，直到被恢复或过期
+                if let Some(resume) = state_for_owners.session_resume.as_ref() {
+                    for sid in resume.detached_session_ids().await {
+                        rt.renew_session_owner_if_owned(&sid).await;
+                    }
+                }
+                state_for_owners.tenant_quota.renew_leases().await;
             }
         });
 
@@ -122,7 +122,7 @@ impl RedisRuntime {
     /// 存储: max, running, reserved
     /// 注意: 文档中的 node:runtime 对应 current_jobs/max_jobs/health_score
     /// 当前实现拆分为 cap (max/running/reserved) 和 meta (health/其他元数据)
-    fn node_cap_key(&self, node_id: &str) -> String {
+    pub(crate) fn node_cap_key(&self, node_id: &str) -> String {
         // hash tag: {node:<id>}
         format!("{}:nodes:cap:{{node:{}}}", self.v1_prefix(), node_id)
     }
@@ -240,6 +240,23 @@ impl RedisRuntime {
             .await;
     }
 
+    /// 仅当 owner 仍是本实例时续约（宽限期会话可能已被其他实例恢复接管）
+    pub async fn renew_session_owner_if_owned(&self, session_id: &str) {
+        let script = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+  return redis.call('EXPIRE', KEYS[1], ARGV[2])
+end
+return 0
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(1)
+            .arg(self.session_owner_key(session_id))
+            .arg(&self.instance_id)
+            .arg(self.cfg.owner_ttl_seconds.max(2));
+        let _: redis::RedisResult<i64> = self.redis.query(cmd).await;
+    }
+
     pub async fn clear_node_owner(&self, node_id: &str) {
         let _ = self.redis.del(&self.node_owner_key(node_id)).await;
     }
@@ -151,4 +151,11 @@ return 1
         self.redis.query::<i64>(cmd).await.map(|v| v == 1).unwrap_or(false)
     }
 
+    /// 当前 FSM 状态（CREATED / DISPATCHED / ACCEPTED / RUNNING / FINISHED / RELEASED；不存在或 Redis 异常时为 None）
+    pub async fn job_fsm_state(&self, job_id: &str) -> Option<String> {
+        let key = self.job_fsm_key(job_id);
+        let mut cmd = redis::cmd("HGET");
+        cmd.arg(&key).arg("state");
+        self.redis.query::<Option<String>>(cmd).await.ok().flatten()
+    }
 }
@@ -0,0 +1,317 @@
+// 房间注册表（多实例共享）
+//
+// 每个房间三个 key，共用 hash tag `{room:<code>}`，Lua 原子更新满足 Redis Cluster 同 slot 约束：
+// - meta：HASH room_id / created_at_ms / last_speaking_at_ms（房间码唯一性由 EXISTS 判定）
+// - members：HASH session_id -> Participant JSON（不含原声偏好）
+// - raw_voice：HASH "<receiver>|<sender>" -> "1"/"0"（原声传递偏好）
+// session -> room 索引单独一个 key（hash tag `{session:<id>}`），与房间 key 不同 slot，由调用方 best-effort 维护：
+// 房间 touch 不续期索引（不能在同一脚本内跨 slot），索引在读取时续期，过期后由调用方按成员关系重建。
+
+impl RedisRuntime {
+    fn room_meta_key(&self, room_code: &str) -> String {
+        format!("{}:rooms:meta:{{room:{}}}", self.key_prefix(), room_code)
+    }
+
+    fn room_members_key(&self, room_code: &str) -> String {
+        format!("{}:rooms:members:{{room:{}}}", self.key_prefix(), room_code)
+    }
+
+    fn room_raw_voice_key(&self, room_code: &str) -> String {
+        format!("{}:rooms:raw_voice:{{room:{}}}", self.key_prefix(), room_code)
+    }
+
+    fn room_session_index_key(&self, session_id: &str) -> String {
+        format!("{}:rooms:by_session:{{session:{}}}", self.key_prefix(), session_id)
+    }
+
+    /// 原子创建房间（房间码已存在时返回 false），创建者作为第一个成员写入
+    pub async fn room_try_create(
+        &self,
+        room_code: &str,
+        room_id: &str,
+        creator_session_id: &str,
+        creator_json: &str,
+        ttl_seconds: u64,
+    ) -> redis::RedisResult<bool> {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let script = r#"
+if redis.call('EXISTS', KEYS[1]) == 1 then
+  return 0
+end
+redis.call('HSET', KEYS[1], 'room_id', ARGV[1], 'created_at_ms', ARGV[4], 'last_speaking_at_ms', ARGV[4])
+redis.call('HSET', KEYS[2], ARGV[2], ARGV[3])
+redis.call('DEL', KEYS[3])
+redis.call('EXPIRE', KEYS[1], ARGV[5])
+redis.call('EXPIRE', KEYS[2], ARGV[5])
+return 1
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(3)
+            .arg(self.room_meta_key(room_code))
+            .arg(self.room_members_key(room_code))
+            .arg(self.room_raw_voice_key(room_code))
+            .arg(room_id)
+            .arg(creator_session_id)
+            .arg(creator_json)
+            .arg(now_ms)
+            .arg(ttl_seconds.max(1));
+        let created: i64 = self.redis.query(cmd).await?;
+        Ok(created == 1)
+    }
+
+    /// 原子加入房间
+    ///
+    /// 返回 1 = 已加入，0 = 已在房间中，-1 = 房间不存在
+    pub async fn room_join(
+        &self,
+        room_code: &str,
+        session_id: &str,
+        participant_json: &str,
+        ttl_seconds: u64,
+    ) -> redis::RedisResult<i64> {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let script = r#"
+if redis.call('EXISTS', KEYS[1]) == 0 then
+  return -1
+end
+if redis.call('HEXISTS', KEYS[2], ARGV[1]) == 1 then
+  return 0
+end
+redis.call('HSET', KEYS[2], ARGV[1], ARGV[2])
+redis.call('HSET', KEYS[1], 'last_speaking_at_ms', ARGV[3])
+for i = 1, 3 do
+  redis.call('EXPIRE', KEYS[i], ARGV[4])
+end
+return 1
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(3)
+            .arg(self.room_meta_key(room_code))
+            .arg(self.room_members_key(room_code))
+            .arg(self.room_raw_voice_key(room_code))
+            .arg(session_id)
+            .arg(participant_json)
+            .arg(now_ms)
+            .arg(ttl_seconds.max(1));
+        self.redis.query(cmd).await
+    }
+
+    /// 原子退出房间（同时删除该成员设置的原声偏好），最后一个成员离开时删除房间
+    ///
+    /// 返回 1 = 房间已清空并删除，0 = 房间仍有成员，-1 = 房间不存在
+    pub async fn room_leave(&self, room_code: &str, session_id: &str) -> redis::RedisResult<i64> {
+        let script = r#"
+if redis.call('EXISTS', KEYS[1]) == 0 then
+  return -1
+end
+redis.call('HDEL', KEYS[2], ARGV[1])
+local prefix = ARGV[1] .. '|'
+for _, field in ipairs(redis.call('HKEYS', KEYS[3])) do
+  if string.sub(field, 1, #prefix) == prefix then
+    redis.call('HDEL', KEYS[3], field)
+  end
+end
+if redis.call('HLEN', KEYS[2]) == 0 then
+  redis.call('DEL', KEYS[1], KEYS[2], KEYS[3])
+  return 1
+end
+return 0
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(3)
+            .arg(self.room_meta_key(room_code))
+            .arg(self.room_members_key(room_code))
+            .arg(self.room_raw_voice_key(room_code))
+            .arg(session_id);
+        self.redis.query(cmd).await
+    }
+
+    /// 读取房间成员（session_id -> Participant JSON）与原声偏好（"<receiver>|<sender>" -> "1"/"0"）
+    pub async fn room_snapshot(
+        &self,
+        room_code: &str,
+    ) -> redis::RedisResult<(HashMap<String, String>, HashMap<String, String>)> {
+        let members = self.redis.hgetall(&self.room_members_key(room_code)).await?;
+        if members.is_empty() {
+            return Ok((members, HashMap::new()));
+        }
+        let raw_voice = self.redis.hgetall(&self.room_raw_voice_key(room_code)).await?;
+        Ok((members, raw_voice))
+    }
+
+    /// 更新最后说话时间并续期房间 key（房间不存在时返回 false）
+    pub async fn room_touch(&self, room_code: &str, ttl_seconds: u64) -> redis::RedisResult<bool> {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let script = r#"
+if redis.call('EXISTS', KEYS[1]) == 0 then
+  return 0
+end
+redis.call('HSET', KEYS[1], 'last_speaking_at_ms', ARGV[1])
+for i = 1, 3 do
+  redis.call('EXPIRE', KEYS[i], ARGV[2])
+end
+return 1
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(3)
+            .arg(self.room_meta_key(room_code))
+            .arg(self.room_members_key(room_code))
+            .arg(self.room_raw_voice_key(room_code))
+            .arg(now_ms)
+            .arg(ttl_seconds.max(1));
+        let touched: i64 = self.redis.query(cmd).await?;
+        Ok(touched == 1)
+    }
+
+    /// 会话是否为房间成员
+    pub async fn room_has_member(&self, room_code: &str, session_id: &str) -> redis::RedisResult<bool> {
+        let mut cmd = redis::cmd("HEXISTS");
+        cmd.arg(self.room_members_key(room_code)).arg(session_id);
+        self.redis.query(cmd).await
+    }
+
+    /// 设置原声传递偏好（receiver 不在房间中时返回 false）
+    pub async fn room_set_raw_voice_preference(
+        &self,
+        room_code: &str,
+        receiver_session_id: &str,
+        sender_session_id: &str,
+        receive_raw_voice: bool,
+        ttl_seconds: u64,
+    ) -> redis::RedisResult<bool> {
+        let script = r#"
+if redis.call('HEXISTS', KEYS[1], ARGV[1]) == 0 then
+  return 0
+end
+redis.call('HSET', KEYS[2], ARGV[1] .. '|' .. ARGV[2], ARGV[3])
+redis.call('EXPIRE', KEYS[2], ARGV[4])
+return 1
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(2)
+            .arg(self.room_members_key(room_code))
+            .arg(self.room_raw_voice_key(room_code))
+            .arg(receiver_session_id)
+            .arg(sender_session_id)
+            .arg(if receive_raw_voice { "1" } else { "0" })
+            .arg(ttl_seconds.max(1));
+        let updated: i64 = self.redis.query(cmd).await?;
+        Ok(updated == 1)
+    }
+
+    /// 查询原声传递偏好
+    ///
+    /// 返回 Some(true/false)（未设置时为 true）；receiver 不在房间中时返回 None
+    pub async fn room_raw_voice_preference(
+        &self,
+        room_code: &str,
+        receiver_session_id: &str,
+        sender_session_id: &str,
+    ) -> redis::RedisResult<Option<bool>> {
+        let script = r#"
+if redis.call('HEXISTS', KEYS[1], ARGV[1]) == 0 then
+  return -1
+end
+local v = redis.call('HGET', KEYS[2], ARGV[1] .. '|' .. ARGV[2])
+if v == false then
+  return 1
+end
+return tonumber(v)
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(2)
+            .arg(self.room_members_key(room_code))
+            .arg(self.room_raw_voice_key(room_code))
+            .arg(receiver_session_id)
+            .arg(sender_session_id);
+        let v: i64 = self.redis.query(cmd).await?;
+        Ok(if v < 0 { None } else { Some(v == 1) })
+    }
+
+    /// 房间超过 idle_ms 无人说话时原子删除
+    ///
+    /// 返回 ["gone"]（房间已不存在）、["active"]（未过期）或 ["expired", member_json...]。
+    /// 多个实例同时清理时只有一个实例拿到 "expired"（负责通知成员）。
+    pub async fn room_expire_if_idle(&self, room_code: &str, idle_ms: i64) -> redis::RedisResult<Vec<String>> {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let script = r#"
+local last = redis.call('HGET', KEYS[1], 'last_speaking_at_ms')
+if last == false then
+  return {'gone'}
+end
+if tonumber(ARGV[1]) - tonumber(last) < tonumber(ARGV[2]) then
+  return {'active'}
+end
+local result = {'expired'}
+for _, v in ipairs(redis.call('HVALS', KEYS[2])) do
+  table.insert(result, v)
+end
+redis.call('DEL', KEYS[1], KEYS[2], KEYS[3])
+return result
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(3)
+            .arg(self.room_meta_key(room_code))
+            .arg(self.room_members_key(room_code))
+            .arg(self.room_raw_voice_key(room_code))
+            .arg(now_ms)
+            .arg(idle_ms.max(1));
+        self.redis.query(cmd).await
+    }
+
+    /// 写入 session -> room 索引
+    pub async fn room_session_index_set(
+        &self,
+        session_id: &str,
+        room_code: &str,
+        ttl_seconds: u64,
+    ) -> redis::RedisResult<()> {
+        self.redis
+            .set_ex_string(&self.room_session_index_key(session_id), room_code, ttl_seconds)
+            .await
+    }
+
+    /// 读取 session -> room 索引并续期（O(1)）
+    pub async fn room_session_index_touch(
+        &self,
+        session_id: &str,
+        ttl_seconds: u64,
+    ) -> redis::RedisResult<Option<String>> {
+        let script = r#"
+local room_code = redis.call('GET', KEYS[1])
+if room_code then
+  redis.call('EXPIRE', KEYS[1], ARGV[1])
+end
+return room_code
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(1)
+            .arg(self.room_session_index_key(session_id))
+            .arg(ttl_seconds.max(1));
+        self.redis.query(cmd).await
+    }
+
+    /// 删除 session -> room 索引（仅当仍指向该房间时，避免误删已加入新房间的索引）
+    pub async fn room_session_index_clear(&self, session_id: &str, room_code: &str) {
+        let script = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+  return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(1)
+            .arg(self.room_session_index_key(session_id))
+            .arg(room_code);
+        let _r: redis::RedisResult<i64> = self.redis.query(cmd).await;
+    }
+}
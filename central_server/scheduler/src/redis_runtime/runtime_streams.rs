@@ -268,6 +268,17 @@ impl RedisRuntime {
                     .send(&session_id, WsMessage::Text(json))
                     .await;
                 if !ok {
+                    // 会话处于断线宽限期：结果写入重放列表，恢复时补发，不再保留 pending
+                    if let Some(resume) = state.session_resume.as_ref() {
+                        if resume.is_detached(&session_id).await {
+                            if crate::services::session_resume::replay_utterance_index(&message).is_some() {
+                                if let Err(e) = resume.buffer(&session_id, &message).await {
+                                    warn!(session_id = %session_id, error = %e, "写入会话重放列表失败");
+                                }
+                            }
+                            return true;
+                        }
+                    }
                     warn!(
                         session_id = %session_id,
                         "SendToSession 失败：本地 session 不在线（连接已断开或未注册），Web 端将收不到该消息"
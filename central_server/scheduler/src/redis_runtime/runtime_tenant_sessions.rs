@@ -0,0 +1,69 @@
+// 租户并发会话配额（多实例共享）
+//
+// 每个租户一个 ZSET：member = session_id，score = 租约到期时间（ms）。
+// 占用/续约/释放都只操作单个 key，满足 Redis Cluster 同 slot 约束。
+
+impl RedisRuntime {
+    fn tenant_sessions_key(&self, tenant_id: &str) -> String {
+        // hash tag: {tenant:<id>}
+        format!("{}:tenants:sessions:{{tenant:{}}}", self.key_prefix(), tenant_id)
+    }
+
+    /// 尝试占用一个会话名额：先清理过期租约，再检查上限
+    ///
+    /// 返回 `(是否占用成功, 当前占用数)`；同一 session 重复占用视为续约。
+    pub async fn tenant_session_try_acquire(
+        &self,
+        tenant_id: &str,
+        session_id: &str,
+        max_sessions: usize,
+        ttl_seconds: u64,
+    ) -> redis::RedisResult<(bool, u64)> {
+        let key = self.tenant_sessions_key(tenant_id);
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let ttl_ms = (ttl_seconds.max(1) * 1000) as i64;
+        let script = r#"
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', ARGV[2])
+local exists = redis.call('ZSCORE', KEYS[1], ARGV[1])
+local count = redis.call('ZCARD', KEYS[1])
+if exists == false and count >= tonumber(ARGV[4]) then
+  return {0, count}
+end
+redis.call('ZADD', KEYS[1], ARGV[3], ARGV[1])
+redis.call('PEXPIRE', KEYS[1], ARGV[5])
+if exists == false then count = count + 1 end
+return {1, count}
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(1)
+            .arg(&key)
+            .arg(session_id)
+            .arg(now_ms)
+            .arg(now_ms + ttl_ms)
+            .arg(max_sessions)
+            .arg(ttl_ms);
+        let (ok, count): (i64, u64) = self.redis.query(cmd).await?;
+        Ok((ok == 1, count))
+    }
+
+    /// 续约（后台任务周期调用，只续约仍存在的成员）
+    pub async fn tenant_session_renew(&self, tenant_id: &str, session_id: &str, ttl_seconds: u64) {
+        let key = self.tenant_sessions_key(tenant_id);
+        let ttl_ms = (ttl_seconds.max(1) * 1000) as i64;
+        let expire_at_ms = chrono::Utc::now().timestamp_millis() + ttl_ms;
+        let mut cmd = redis::cmd("ZADD");
+        cmd.arg(&key).arg("XX").arg(expire_at_ms).arg(session_id);
+        let _r: redis::RedisResult<i64> = self.redis.query(cmd).await;
+        let mut cmd = redis::cmd("PEXPIRE");
+        cmd.arg(&key).arg(ttl_ms);
+        let _r: redis::RedisResult<i64> = self.redis.query(cmd).await;
+    }
+
+    pub async fn tenant_session_release(&self, tenant_id: &str, session_id: &str) {
+        let key = self.tenant_sessions_key(tenant_id);
+        let mut cmd = redis::cmd("ZREM");
+        cmd.arg(&key).arg(session_id);
+        let _r: redis::RedisResult<i64> = self.redis.query(cmd).await;
+    }
+}
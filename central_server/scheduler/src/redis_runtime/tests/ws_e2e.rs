@@ -198,6 +198,7 @@
             features: None,
             pairing_code: None,
             tenant_id: None,
+            tenant_max_sessions: None,
             mode: None,
             lang_a: None,
             lang_b: None,
@@ -205,6 +206,8 @@
             enable_streaming_asr: Some(true),
             partial_update_interval_ms: Some(100),
             trace_id: Some("trace-ws-e2e".to_string()),
+            persist_transcript: None,
+            supports_binary_frame: None,
         };
         sess_write
             .send(tokio_tungstenite::tungstenite::Message::Text(
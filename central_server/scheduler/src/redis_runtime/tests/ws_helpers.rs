@@ -62,7 +62,7 @@
 
         let group_manager = GroupManager::new(GroupConfig::default());
 
-        let room_manager = RoomManager::new();
+        let room_manager = RoomManager::new(None);
 
         let state = AppState {
             session_manager,
@@ -0,0 +1,230 @@
+// 对冲派发（hedged dispatch）
+//
+// 已派发任务超过池内「派发 → job_started/asr_partial」时延的 latency_quantile 分位（默认 p95）仍未开始处理时，
+// 以同一 dispatch_attempt_id 向另一节点再派发一次；先返回成功结果的节点胜出，落后节点收到 job_cancel。
+//
+// 时延样本与对冲预算按实例保存在内存中（各实例独立统计）；同一任务只能对冲一次，
+// 由 Job Hash 上的 hedge_node_id 字段原子占用保证（见 JobRedisRepository::claim_job_hedge_atomic）。
+
+use crate::core::config::HedgedDispatchConfig;
+use crate::core::dispatcher::Job;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// attempt 标记的保留时长：远大于任务派发超时，超过后视为任务已结束（取消、会话关闭等未经结果结算的路径）
+const ATTEMPT_MARK_RETENTION_MS: i64 = 10 * 60 * 1000;
+
+#[derive(Default)]
+struct HedgeStats {
+    /// pool_key -> 最近的派发 → 开始处理时延（毫秒）
+    samples: HashMap<String, VecDeque<u64>>,
+    /// 预算窗口内开始处理的任务时间戳（毫秒）
+    started_at_ms: VecDeque<i64>,
+    /// 预算窗口内发起对冲的时间戳（毫秒）
+    hedged_at_ms: VecDeque<i64>,
+    /// 已评估过的 (job_id, attempt_id) -> 标记时间：每个 attempt 只尝试一次对冲，避免每轮扫描重复选节点
+    attempted: HashMap<(String, u32), i64>,
+    /// 标记顺序（按时间递增），用于过期清理
+    attempted_order: VecDeque<(i64, (String, u32))>,
+}
+
+#[derive(Clone)]
+pub struct HedgedDispatch {
+    config: HedgedDispatchConfig,
+    stats: Arc<Mutex<HedgeStats>>,
+}
+
+impl HedgedDispatch {
+    pub fn new(config: HedgedDispatchConfig) -> Self {
+        Self {
+            config,
+            stats: Arc::new(Mutex::new(HedgeStats::default())),
+        }
+    }
+
+    pub fn enabled_for(&self, job: &Job) -> bool {
+        self.config.enabled_for(job.tenant_id.as_deref())
+    }
+
+    /// 时延统计所属的池：租户任务按租户私有池，其余按语言对
+    pub fn pool_key(job: &Job) -> String {
+        match job.tenant_id.as_deref() {
+            Some(tenant_id) => crate::pool::pool_service::tenant_pair_key(tenant_id, &job.src_lang, &job.tgt_lang),
+            None => format!("{}:{}", job.src_lang, job.tgt_lang),
+        }
+    }
+
+    /// 记录一次「派发 → 开始处理」时延（收到 job_started 时）
+    pub fn record_started(&self, pool_key: &str, latency_ms: u64, now_ms: i64) {
+        let mut stats = self.stats.lock().unwrap();
+        let window = self.config.sample_window.max(1);
+        let samples = stats.samples.entry(pool_key.to_string()).or_default();
+        samples.push_back(latency_ms);
+        while samples.len() > window {
+            samples.pop_front();
+        }
+        stats.started_at_ms.push_back(now_ms);
+        self.trim_budget_window(&mut stats, now_ms);
+    }
+
+    /// 当前池的对冲时限（毫秒）
+    pub fn deadline_ms(&self, pool_key: &str) -> u64 {
+        let stats = self.stats.lock().unwrap();
+        let min = self.config.min_deadline_ms;
+        let max = self.config.max_deadline_ms.max(min);
+        let deadline = match stats.samples.get(pool_key) {
+            Some(samples) if samples.len() >= self.config.min_samples.max(1) => {
+                let mut sorted: Vec<u64> = samples.iter().copied().collect();
+                sorted.sort_unstable();
+                let q = self.config.latency_quantile.clamp(0.0, 1.0);
+                let idx = ((sorted.len() as f64 * q).ceil() as usize).clamp(1, sorted.len()) - 1;
+                sorted[idx]
+            }
+            _ => self.config.default_deadline_ms,
+        };
+        deadline.clamp(min, max)
+    }
+
+    /// 标记该 attempt 已评估过对冲；返回 false 表示之前已评估。顺带清理过期标记
+    pub fn mark_attempted(&self, job_id: &str, attempt_id: u32, now_ms: i64) -> bool {
+        let mut stats = self.stats.lock().unwrap();
+        let cutoff = now_ms - ATTEMPT_MARK_RETENTION_MS;
+        while stats.attempted_order.front().is_some_and(|(t, _)| *t < cutoff) {
+            let Some((marked_at, key)) = stats.attempted_order.pop_front() else { break };
+            // 只删除与本条顺序记录对应的标记（forget_job 后重新标记的保留）
+            if stats.attempted.get(&key) == Some(&marked_at) {
+                stats.attempted.remove(&key);
+            }
+        }
+        let key = (job_id.to_string(), attempt_id);
+        if stats.attempted.contains_key(&key) {
+            return false;
+        }
+        stats.attempted.insert(key.clone(), now_ms);
+        stats.attempted_order.push_back((now_ms, key));
+        true
+    }
+
+    /// 任务结束后清理 attempt 标记
+    pub fn forget_job(&self, job_id: &str) {
+        self.stats.lock().unwrap().attempted.retain(|(id, _), _| id != job_id);
+    }
+
+    /// 占用一次对冲预算；窗口内已用尽时返回 false
+    pub fn try_acquire_budget(&self, now_ms: i64) -> bool {
+        let mut stats = self.stats.lock().unwrap();
+        self.trim_budget_window(&mut stats, now_ms);
+        let by_ratio = (stats.started_at_ms.len() as f64 * self.config.budget_ratio.max(0.0)).floor() as usize;
+        let limit = by_ratio.max(self.config.budget_min_per_window);
+        if stats.hedged_at_ms.len() >= limit {
+            return false;
+        }
+        stats.hedged_at_ms.push_back(now_ms);
+        true
+    }
+
+    /// 归还 `try_acquire_budget(now_ms)` 占用的预算（对冲未实际下发时）
+    pub fn release_budget(&self, acquired_at_ms: i64) {
+        let mut stats = self.stats.lock().unwrap();
+        if let Some(pos) = stats.hedged_at_ms.iter().rposition(|&t| t == acquired_at_ms) {
+            stats.hedged_at_ms.remove(pos);
+        }
+    }
+
+    fn trim_budget_window(&self, stats: &mut HedgeStats, now_ms: i64) {
+        let cutoff = now_ms - (self.config.budget_window_seconds.max(1) as i64) * 1000;
+        while stats.started_at_ms.front().is_some_and(|&t| t < cutoff) {
+            stats.started_at_ms.pop_front();
+        }
+        while stats.hedged_at_ms.front().is_some_and(|&t| t < cutoff) {
+            stats.hedged_at_ms.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> HedgedDispatchConfig {
+        HedgedDispatchConfig {
+            enabled: true,
+            default_enabled: true,
+            min_samples: 5,
+            sample_window: 100,
+            default_deadline_ms: 3000,
+            min_deadline_ms: 200,
+            max_deadline_ms: 5000,
+            budget_ratio: 0.1,
+            budget_min_per_window: 1,
+            budget_window_seconds: 60,
+            ..HedgedDispatchConfig::default()
+        }
+    }
+
+    #[test]
+    fn deadline_uses_quantile_after_min_samples() {
+        let hedging = HedgedDispatch::new(config());
+        assert_eq!(hedging.deadline_ms("zh:en"), 3000);
+        for latency in 1..=100u64 {
+            hedging.record_started("zh:en", latency * 10, 0);
+        }
+        assert_eq!(hedging.deadline_ms("zh:en"), 950);
+        assert_eq!(hedging.deadline_ms("en:zh"), 3000);
+
+        for _ in 0..100 {
+            hedging.record_started("ja:en", 60_000, 0);
+        }
+        assert_eq!(hedging.deadline_ms("ja:en"), 5000);
+    }
+
+    #[test]
+    fn budget_scales_with_started_jobs() {
+        let hedging = HedgedDispatch::new(config());
+        assert!(hedging.try_acquire_budget(0));
+        assert!(!hedging.try_acquire_budget(0));
+        // 未下发的对冲归还预算
+        hedging.release_budget(0);
+        assert!(hedging.try_acquire_budget(0));
+        assert!(!hedging.try_acquire_budget(0));
+
+        for _ in 0..30 {
+            hedging.record_started("zh:en", 100, 1_000);
+        }
+        assert!(hedging.try_acquire_budget(1_000));
+        assert!(hedging.try_acquire_budget(1_000));
+        assert!(!hedging.try_acquire_budget(1_000));
+
+        // 窗口滑过后预算恢复到最小值
+        assert!(hedging.try_acquire_budget(120_000));
+        assert!(!hedging.try_acquire_budget(120_000));
+    }
+
+    #[test]
+    fn tenant_overrides_and_attempt_marks() {
+        let mut cfg = config();
+        cfg.default_enabled = false;
+        cfg.tenants.insert("vip".to_string(), true);
+        assert!(cfg.enabled_for(Some("vip")));
+        assert!(!cfg.enabled_for(Some("other")));
+        assert!(!cfg.enabled_for(None));
+
+        let hedging = HedgedDispatch::new(cfg);
+        assert!(hedging.mark_attempted("job-1", 1, 0));
+        assert!(!hedging.mark_attempted("job-1", 1, 0));
+        assert!(hedging.mark_attempted("job-1", 2, 0));
+        hedging.forget_job("job-1");
+        assert!(hedging.mark_attempted("job-1", 1, 0));
+    }
+
+    #[test]
+    fn attempt_marks_expire_without_result() {
+        let hedging = HedgedDispatch::new(config());
+        assert!(hedging.mark_attempted("job-cancelled", 1, 0));
+        assert!(!hedging.mark_attempted("job-cancelled", 1, ATTEMPT_MARK_RETENTION_MS));
+        assert!(hedging.mark_attempted("job-2", 1, ATTEMPT_MARK_RETENTION_MS + 1));
+        let stats = hedging.stats.lock().unwrap();
+        assert_eq!(stats.attempted.len(), 1);
+        assert_eq!(stats.attempted_order.len(), 1);
+    }
+}
@@ -20,6 +20,19 @@ pub struct RegisterNodeRequest {
     pub asr_langs_json: String,       // ASR 语言 JSON
     pub semantic_langs_json: String,  // Semantic 语言 JSON（能力校验 + 池分配用 asr×semantic）
     pub tts_langs_json: String,       // TTS 语言 JSON（注册校验用）
+    /// 节点声明的会话迁移 HTTP 地址（evacuate/import），空串表示未声明
+    #[serde(default)]
+    pub migration_base_url: String,
+    /// false：私有节点，不进入公共池
+    #[serde(default = "default_accept_public_jobs")]
+    pub accept_public_jobs: bool,
+    /// 私有节点所属租户（进入租户池），空串表示无
+    #[serde(default)]
+    pub owner_tenant_id: String,
+}
+
+fn default_accept_public_jobs() -> bool {
+    true
 }
 
 // HeartbeatRequest 已删除（心跳已由 PoolService 处理）
@@ -100,6 +113,9 @@ impl MinimalSchedulerService {
                 &req.asr_langs_json,
                 &req.semantic_langs_json,
                 &req.tts_langs_json,
+                &req.migration_base_url,
+                if req.accept_public_jobs { "true" } else { "false" },
+                &req.owner_tenant_id,
             ],
         )
         .await?;
@@ -302,6 +318,9 @@ mod tests {
             asr_langs_json: r#"["zh","en","de"]"#.to_string(),
             semantic_langs_json: r#"["zh","en"]"#.to_string(),
             tts_langs_json: r#"["zh","en","ja"]"#.to_string(),
+            migration_base_url: String::new(),
+            accept_public_jobs: true,
+            owner_tenant_id: String::new(),
         };
         
         let json = serde_json::to_string(&req).unwrap();
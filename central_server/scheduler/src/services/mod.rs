@@ -1,15 +1,28 @@
+pub mod hedged_dispatch;
 pub mod minimal_scheduler;
+pub mod node_auth;
+pub mod node_circuit_breaker;
+pub mod node_drain;
 pub mod pairing;
 pub mod service_catalog;
 pub mod session_affinity;
 pub mod session_migration_orchestrator;
+pub mod session_resume;
+pub mod transcript_store;
+pub mod usage_metering;
 
 // ModelHub 已删除（未实现）
+pub use hedged_dispatch::HedgedDispatch;
 pub use minimal_scheduler::MinimalSchedulerService;
+pub use node_auth::NodeAuthService;
+pub use node_circuit_breaker::NodeCircuitBreaker;
+pub use node_drain::NodeDrainService;
 pub use pairing::PairingService;
 pub use service_catalog::ServiceCatalogCache;
 pub use session_affinity::{SessionAffinityService, SessionMigrationEvent};
 pub use session_migration_orchestrator::{
     SessionMigrationOrchestrator, SessionMigrationOrchestratorResult, SchedulerSessionMigrationEvent,
 };
-
+pub use session_resume::SessionResumeService;
+pub use transcript_store::TranscriptStore;
+pub use usage_metering::UsageMeter;
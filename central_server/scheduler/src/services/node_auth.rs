@@ -0,0 +1,517 @@
+// 节点认证（/ws/node）
+//
+// 启用后 node_register 不再直接生效：调度器下发 node_auth_challenge（随机 nonce），
+// 节点用自己的密钥应答 node_auth_response，签名为 hex(HMAC-SHA256(secret, "<nonce>.<node_id>"))，
+// 校验通过后才继续原注册流程。密钥来源：
+// - 预共享密钥：配置 node_auth.node_secrets；
+// - enrollment token：调度器签发 "<expires_at>.<hex(HMAC(enrollment_key, "enroll.<node_id>.<expires_at>"))>"，
+//   节点把整个 token 当作密钥，应答时携带 expires_at，调度器据此重算 token（无需存储）；
+//   token 只用于首次接入，认证通过后随 node_register_ack 下发长期节点凭证；
+// - 长期节点凭证：hex(HMAC(credential_key, "credential.<node_id>"))，应答时置 node_credential = true，
+//   同样由调度器重算，撤销靠吊销名单（或更换 credential_key 令全部凭证失效）。
+// 签发 enrollment 时可指定 owner_tenant_id：token 与之后的长期凭证都绑定该租户（签名数据包含租户），
+// 节点注册声明的 owner_tenant_id 参与重算，声明其他租户即签名不符。
+// challenge 按连接暂存：同一 node_id 的新连接发起注册不会覆盖其他连接的 challenge，应答必须来自同一连接。
+// 吊销名单 = 配置静态名单 ∪ Redis 集合 {prefix}:node_auth:revoked（运行时吊销，多实例共享）。
+
+use crate::core::config::NodeAuthConfig;
+use crate::messages::NodeMessage;
+use crate::redis_runtime::RedisHandle;
+use axum::extract::ws::Message;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::{mpsc, RwLock};
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum NodeAuthError {
+    #[error("no pending challenge for this connection")]
+    NotPending,
+    #[error("challenge expired")]
+    ChallengeExpired,
+    #[error("no credential configured for node")]
+    NoCredential,
+    #[error("enrollment token expired")]
+    EnrollmentExpired,
+    #[error("enrollment is disabled (enrollment_key not configured)")]
+    EnrollmentDisabled,
+    #[error("invalid owner_tenant_id (must be non-empty without ':' or whitespace)")]
+    InvalidOwnerTenant,
+    #[error("signature mismatch")]
+    BadSignature,
+}
+
+/// 应答签名所用密钥的类型
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeSecretKind {
+    PreShared,
+    Enrollment { expires_at: i64 },
+    Credential,
+}
+
+/// 认证通过后的附加结果
+#[derive(Debug, Clone, Default)]
+pub struct NodeAuthGrant {
+    /// 以 enrollment token 认证时签发的长期节点凭证（随 node_register_ack 下发）
+    pub node_credential: Option<String>,
+    /// 凭证绑定的归属租户（Some("") 表示绑定为无租户）；预共享密钥不绑定租户，为 None
+    pub owner_tenant_id: Option<String>,
+}
+
+/// 签发结果
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct NodeEnrollment {
+    pub node_id: String,
+    pub enrollment_token: String,
+    pub expires_at: i64,
+    /// 绑定的归属租户：节点注册时必须声明同一 owner_tenant_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner_tenant_id: Option<String>,
+}
+
+struct PendingChallenge {
+    nonce: String,
+    tx: mpsc::UnboundedSender<Message>,
+    register: NodeMessage,
+    issued_at: Instant,
+}
+
+/// 等待应答的 challenge：node_id -> 各连接上待完成的注册
+#[derive(Default)]
+struct PendingChallenges {
+    by_node: HashMap<String, Vec<PendingChallenge>>,
+}
+
+impl PendingChallenges {
+    /// 登记 challenge，只替换同一连接上的旧 challenge；顺带清理超时或连接已关闭的条目
+    fn insert(&mut self, node_id: &str, challenge: PendingChallenge, timeout: Duration) {
+        self.by_node.retain(|_, list| {
+            list.retain(|p| p.issued_at.elapsed() < timeout && !p.tx.is_closed());
+            !list.is_empty()
+        });
+        let list = self.by_node.entry(node_id.to_string()).or_default();
+        list.retain(|p| !p.tx.same_channel(&challenge.tx));
+        list.push(challenge);
+    }
+
+    /// 取出该连接上的 challenge
+    fn take(&mut self, node_id: &str, tx: &mpsc::UnboundedSender<Message>) -> Option<PendingChallenge> {
+        let list = self.by_node.get_mut(node_id)?;
+        let index = list.iter().position(|p| p.tx.same_channel(tx))?;
+        let challenge = list.swap_remove(index);
+        if list.is_empty() {
+            self.by_node.remove(node_id);
+        }
+        Some(challenge)
+    }
+}
+
+#[derive(Clone)]
+pub struct NodeAuthService {
+    redis: Arc<RedisHandle>,
+    key_prefix: String,
+    config: NodeAuthConfig,
+    pending: Arc<RwLock<PendingChallenges>>,
+}
+
+impl NodeAuthService {
+    pub fn new(redis: Arc<RedisHandle>, key_prefix: String, config: NodeAuthConfig) -> Self {
+        Self {
+            redis,
+            key_prefix,
+            config,
+            pending: Arc::new(RwLock::new(PendingChallenges::default())),
+        }
+    }
+
+    fn revoked_key(&self) -> String {
+        format!("{}:node_auth:revoked", self.key_prefix)
+    }
+
+    fn challenge_timeout(&self) -> Duration {
+        Duration::from_secs(self.config.challenge_timeout_seconds.max(1))
+    }
+
+    /// 为 node_register 生成 challenge；只覆盖同一连接上的旧 challenge
+    pub async fn challenge(
+        &self,
+        node_id: &str,
+        tx: &mpsc::UnboundedSender<Message>,
+        register: NodeMessage,
+    ) -> (String, u64) {
+        let nonce = uuid::Uuid::new_v4().simple().to_string();
+        let timeout = self.challenge_timeout();
+        self.pending.write().await.insert(
+            node_id,
+            PendingChallenge {
+                nonce: nonce.clone(),
+                tx: tx.clone(),
+                register,
+                issued_at: Instant::now(),
+            },
+            timeout,
+        );
+        (nonce, timeout.as_millis() as u64)
+    }
+
+    /// 校验 challenge 应答（只接受发起注册的同一连接），通过后返回暂存的 node_register
+    pub async fn verify(
+        &self,
+        node_id: &str,
+        tx: &mpsc::UnboundedSender<Message>,
+        signature: &str,
+        kind: NodeSecretKind,
+    ) -> Result<(NodeMessage, NodeAuthGrant), NodeAuthError> {
+        let challenge = self
+            .pending
+            .write()
+            .await
+            .take(node_id, tx)
+            .ok_or(NodeAuthError::NotPending)?;
+        if challenge.issued_at.elapsed() >= self.challenge_timeout() {
+            return Err(NodeAuthError::ChallengeExpired);
+        }
+
+        let owner_tenant_id = declared_owner_tenant(&challenge.register);
+        let secret = resolve_secret(
+            &self.config,
+            node_id,
+            owner_tenant_id.as_deref(),
+            kind,
+            chrono::Utc::now().timestamp(),
+        )?;
+        let expected = challenge_signature(&secret, &challenge.nonce, node_id);
+        if !constant_time_eq(expected.as_bytes(), signature.to_ascii_lowercase().as_bytes()) {
+            return Err(NodeAuthError::BadSignature);
+        }
+        let grant = match kind {
+            NodeSecretKind::PreShared => NodeAuthGrant::default(),
+            NodeSecretKind::Enrollment { .. } => NodeAuthGrant {
+                node_credential: Some(node_credential(
+                    credential_key(&self.config),
+                    node_id,
+                    owner_tenant_id.as_deref(),
+                )),
+                owner_tenant_id: Some(owner_tenant_id.unwrap_or_default()),
+            },
+            NodeSecretKind::Credential => NodeAuthGrant {
+                node_credential: None,
+                owner_tenant_id: Some(owner_tenant_id.unwrap_or_default()),
+            },
+        };
+        Ok((challenge.register, grant))
+    }
+
+    /// 签发 enrollment token（未指定 node_id 时生成新的；指定 owner_tenant_id 时绑定该租户）
+    pub fn issue_enrollment(
+        &self,
+        node_id: Option<String>,
+        ttl_seconds: Option<u64>,
+        owner_tenant_id: Option<String>,
+    ) -> Result<NodeEnrollment, NodeAuthError> {
+        if self.config.enrollment_key.is_empty() {
+            return Err(NodeAuthError::EnrollmentDisabled);
+        }
+        if owner_tenant_id
+            .as_deref()
+            .is_some_and(|t| t.is_empty() || t.contains(':') || t.chars().any(char::is_whitespace))
+        {
+            return Err(NodeAuthError::InvalidOwnerTenant);
+        }
+        let node_id = node_id
+            .unwrap_or_else(|| format!("node-{}", uuid::Uuid::new_v4().to_string()[..8].to_uppercase()));
+        let ttl = ttl_seconds.unwrap_or(self.config.enrollment_ttl_seconds).max(1);
+        let expires_at = chrono::Utc::now().timestamp() + ttl as i64;
+        Ok(NodeEnrollment {
+            enrollment_token: enrollment_token(
+                &self.config.enrollment_key,
+                &node_id,
+                owner_tenant_id.as_deref(),
+                expires_at,
+            ),
+            node_id,
+            expires_at,
+            owner_tenant_id,
+        })
+    }
+
+    /// 节点是否已吊销；读取 Redis 吊销名单失败时返回错误，由调用方拒绝注册（fail closed）
+    pub async fn is_revoked(&self, node_id: &str) -> redis::RedisResult<bool> {
+        if self.config.revoked_node_ids.iter().any(|n| n == node_id) {
+            return Ok(true);
+        }
+        let mut cmd = redis::cmd("SISMEMBER");
+        cmd.arg(self.revoked_key()).arg(node_id);
+        self.redis.query::<bool>(cmd).await
+    }
+
+    pub async fn revoke(&self, node_id: &str) -> redis::RedisResult<()> {
+        let mut cmd = redis::cmd("SADD");
+        cmd.arg(self.revoked_key()).arg(node_id);
+        let _: i64 = self.redis.query(cmd).await?;
+        Ok(())
+    }
+
+    /// 解除运行时吊销（静态名单中的节点仍保持吊销）
+    pub async fn unrevoke(&self, node_id: &str) -> redis::RedisResult<bool> {
+        let mut cmd = redis::cmd("SREM");
+        cmd.arg(self.revoked_key()).arg(node_id);
+        let removed: i64 = self.redis.query(cmd).await?;
+        Ok(removed > 0)
+    }
+
+    pub async fn list_revoked(&self) -> redis::RedisResult<Vec<String>> {
+        let mut revoked = self.redis.smembers_strings(&self.revoked_key()).await?;
+        for node_id in &self.config.revoked_node_ids {
+            if !revoked.contains(node_id) {
+                revoked.push(node_id.clone());
+            }
+        }
+        revoked.sort();
+        Ok(revoked)
+    }
+
+    /// 管理接口鉴权：admin_token 为空时接口禁用
+    pub fn is_admin(&self, bearer: Option<&str>) -> bool {
+        !self.config.admin_token.is_empty()
+            && bearer.is_some_and(|t| constant_time_eq(t.as_bytes(), self.config.admin_token.as_bytes()))
+    }
+
+    /// 认证节点声明的迁移地址是否可信（调度器会向该地址发起 evacuate/import 请求）
+    pub fn allows_migration_endpoint(&self, endpoint: &str) -> bool {
+        migration_endpoint_allowed(&self.config.migration_endpoint_hosts, endpoint)
+    }
+}
+
+fn migration_endpoint_allowed(allowed_hosts: &[String], endpoint: &str) -> bool {
+    let Ok(url) = reqwest::Url::parse(endpoint) else {
+        return false;
+    };
+    if !matches!(url.scheme(), "http" | "https") {
+        return false;
+    }
+    let Some(host) = url.host_str().map(|h| h.trim_start_matches('[').trim_end_matches(']')) else {
+        return false;
+    };
+    if !allowed_hosts.is_empty() {
+        return allowed_hosts.iter().any(|h| h.eq_ignore_ascii_case(host));
+    }
+    // 未配置白名单时不解析域名（解析结果可被篡改），只接受 IP 字面量
+    match host.parse::<std::net::IpAddr>() {
+        Ok(std::net::IpAddr::V4(ip)) => {
+            !(ip.is_loopback() || ip.is_link_local() || ip.is_unspecified() || ip.is_multicast() || ip.is_broadcast())
+        }
+        Ok(std::net::IpAddr::V6(ip)) => match ip.to_ipv4_mapped() {
+            Some(v4) => migration_endpoint_allowed(&[], &format!("http://{}", v4)),
+            None => !(ip.is_loopback() || ip.is_unspecified() || ip.is_multicast() || (ip.segments()[0] & 0xffc0) == 0xfe80),
+        },
+        Err(_) => false,
+    }
+}
+
+fn hmac_hex(key: &str, data: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(key.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(data.as_bytes());
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+/// 节点对 challenge 的签名
+pub fn challenge_signature(secret: &str, nonce: &str, node_id: &str) -> String {
+    hmac_hex(secret, &format!("{}.{}", nonce, node_id))
+}
+
+/// 注册声明的归属租户（去掉首尾空白，空串视为未声明），参与 enrollment token / 长期凭证重算
+fn declared_owner_tenant(register: &NodeMessage) -> Option<String> {
+    match register {
+        NodeMessage::NodeRegister { owner_tenant_id: Some(t), .. } if !t.trim().is_empty() => {
+            Some(t.trim().to_string())
+        }
+        _ => None,
+    }
+}
+
+/// 应答所用密钥：enrollment token / 长期凭证由调度器重算（绑定 node_id 与归属租户），预共享密钥从配置读取
+fn resolve_secret(
+    config: &NodeAuthConfig,
+    node_id: &str,
+    owner_tenant_id: Option<&str>,
+    kind: NodeSecretKind,
+    now_secs: i64,
+) -> Result<String, NodeAuthError> {
+    match kind {
+        NodeSecretKind::Enrollment { expires_at } => {
+            if config.enrollment_key.is_empty() {
+                return Err(NodeAuthError::EnrollmentDisabled);
+            }
+            if expires_at <= now_secs {
+                return Err(NodeAuthError::EnrollmentExpired);
+            }
+            Ok(enrollment_token(&config.enrollment_key, node_id, owner_tenant_id, expires_at))
+        }
+        NodeSecretKind::Credential => {
+            let key = credential_key(config);
+            if key.is_empty() {
+                return Err(NodeAuthError::EnrollmentDisabled);
+            }
+            Ok(node_credential(key, node_id, owner_tenant_id))
+        }
+        NodeSecretKind::PreShared => config
+            .node_secrets
+            .get(node_id)
+            .cloned()
+            .ok_or(NodeAuthError::NoCredential),
+    }
+}
+
+fn credential_key(config: &NodeAuthConfig) -> &str {
+    if config.credential_key.is_empty() {
+        &config.enrollment_key
+    } else {
+        &config.credential_key
+    }
+}
+
+/// 绑定租户时签名数据改用换行分隔，避免与未绑定格式或含 '.' 的 node_id 混淆
+fn node_credential(credential_key: &str, node_id: &str, owner_tenant_id: Option<&str>) -> String {
+    match owner_tenant_id {
+        Some(tenant) => hmac_hex(credential_key, &format!("credential\n{}\n{}", node_id, tenant)),
+        None => hmac_hex(credential_key, &format!("credential.{}", node_id)),
+    }
+}
+
+fn enrollment_token(enrollment_key: &str, node_id: &str, owner_tenant_id: Option<&str>, expires_at: i64) -> String {
+    let data = match owner_tenant_id {
+        Some(tenant) => format!("enroll\n{}\n{}\n{}", node_id, expires_at, tenant),
+        None => format!("enroll.{}.{}", node_id, expires_at),
+    };
+    format!("{}.{}", expires_at, hmac_hex(enrollment_key, &data))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> NodeAuthConfig {
+        NodeAuthConfig {
+            enabled: true,
+            node_secrets: HashMap::from([("node-psk".to_string(), "s3cret".to_string())]),
+            enrollment_key: "enroll-key".to_string(),
+            ..NodeAuthConfig::default()
+        }
+    }
+
+    #[test]
+    fn migration_endpoint_guard() {
+        assert!(migration_endpoint_allowed(&[], "http://10.0.0.5:5011"));
+        assert!(!migration_endpoint_allowed(&[], "http://127.0.0.1:5011"));
+        assert!(!migration_endpoint_allowed(&[], "http://169.254.169.254/latest"));
+        assert!(!migration_endpoint_allowed(&[], "http://[::ffff:127.0.0.1]:5011"));
+        assert!(!migration_endpoint_allowed(&[], "http://node-a.internal:5011"));
+        assert!(!migration_endpoint_allowed(&[], "ftp://10.0.0.5"));
+        let hosts = vec!["node-a.internal".to_string()];
+        assert!(migration_endpoint_allowed(&hosts, "https://NODE-A.internal:5011"));
+        assert!(!migration_endpoint_allowed(&hosts, "http://10.0.0.5:5011"));
+    }
+
+    #[test]
+    fn psk_secret_resolution() {
+        let cfg = config();
+        assert_eq!(resolve_secret(&cfg, "node-psk", None, NodeSecretKind::PreShared, 0).unwrap(), "s3cret");
+        assert_eq!(
+            resolve_secret(&cfg, "node-x", None, NodeSecretKind::PreShared, 0),
+            Err(NodeAuthError::NoCredential)
+        );
+    }
+
+    #[test]
+    fn enrollment_token_is_bound_to_node_and_expiry() {
+        let cfg = config();
+        let token = enrollment_token(&cfg.enrollment_key, "node-new", None, 1_000);
+        assert!(token.starts_with("1000."));
+        let enrollment = |expires_at| NodeSecretKind::Enrollment { expires_at };
+        assert_eq!(resolve_secret(&cfg, "node-new", None, enrollment(1_000), 999).unwrap(), token);
+        // 同一 token 冒用其他 node_id 或篡改过期时间，重算结果不同
+        assert_ne!(resolve_secret(&cfg, "node-other", None, enrollment(1_000), 999).unwrap(), token);
+        assert_ne!(resolve_secret(&cfg, "node-new", None, enrollment(2_000), 999).unwrap(), token);
+        assert_eq!(
+            resolve_secret(&cfg, "node-new", None, enrollment(1_000), 1_000),
+            Err(NodeAuthError::EnrollmentExpired)
+        );
+
+        let disabled = NodeAuthConfig::default();
+        assert_eq!(
+            resolve_secret(&disabled, "node-new", None, enrollment(1_000), 0),
+            Err(NodeAuthError::EnrollmentDisabled)
+        );
+    }
+
+    #[test]
+    fn credential_outlives_enrollment_and_is_bound_to_node() {
+        let mut cfg = config();
+        let credential = resolve_secret(&cfg, "node-new", None, NodeSecretKind::Credential, i64::MAX).unwrap();
+        assert_eq!(credential, node_credential("enroll-key", "node-new", None));
+        assert_ne!(credential, resolve_secret(&cfg, "node-other", None, NodeSecretKind::Credential, 0).unwrap());
+        // 单独配置 credential_key 后与 enrollment_key 解耦
+        cfg.credential_key = "cred-key".to_string();
+        assert_ne!(credential, resolve_secret(&cfg, "node-new", None, NodeSecretKind::Credential, 0).unwrap());
+    }
+
+    #[test]
+    fn owner_tenant_is_bound_to_token_and_credential() {
+        let cfg = config();
+        let enrollment = NodeSecretKind::Enrollment { expires_at: 1_000 };
+        let token = enrollment_token(&cfg.enrollment_key, "node-new", Some("tenant-a"), 1_000);
+        assert_eq!(resolve_secret(&cfg, "node-new", Some("tenant-a"), enrollment, 0).unwrap(), token);
+        // 声明其他租户或不声明租户，重算结果都不同
+        assert_ne!(resolve_secret(&cfg, "node-new", Some("tenant-b"), enrollment, 0).unwrap(), token);
+        assert_ne!(resolve_secret(&cfg, "node-new", None, enrollment, 0).unwrap(), token);
+
+        let credential = node_credential(credential_key(&cfg), "node-new", Some("tenant-a"));
+        let kind = NodeSecretKind::Credential;
+        assert_eq!(resolve_secret(&cfg, "node-new", Some("tenant-a"), kind, 0).unwrap(), credential);
+        assert_ne!(resolve_secret(&cfg, "node-new", Some("tenant-b"), kind, 0).unwrap(), credential);
+        assert_ne!(resolve_secret(&cfg, "node-new", None, kind, 0).unwrap(), credential);
+    }
+
+    #[test]
+    fn pending_challenges_are_kept_per_connection() {
+        let (tx_a, _rx_a) = mpsc::unbounded_channel::<Message>();
+        let (tx_b, _rx_b) = mpsc::unbounded_channel::<Message>();
+        let challenge = |nonce: &str, tx: &mpsc::UnboundedSender<Message>| PendingChallenge {
+            nonce: nonce.to_string(),
+            tx: tx.clone(),
+            register: NodeMessage::Error { code: String::new(), message: String::new(), details: None },
+            issued_at: Instant::now(),
+        };
+        let timeout = Duration::from_secs(30);
+        let mut pending = PendingChallenges::default();
+        pending.insert("node-1", challenge("a1", &tx_a), timeout);
+        // 另一连接用同一 node_id 发起注册，不影响 a 的 challenge
+        pending.insert("node-1", challenge("b1", &tx_b), timeout);
+        pending.insert("node-1", challenge("a2", &tx_a), timeout);
+
+        assert_eq!(pending.take("node-1", &tx_a).map(|p| p.nonce).as_deref(), Some("a2"));
+        assert!(pending.take("node-1", &tx_a).is_none());
+        assert_eq!(pending.take("node-1", &tx_b).map(|p| p.nonce).as_deref(), Some("b1"));
+        assert!(pending.by_node.is_empty());
+    }
+
+    #[test]
+    fn challenge_signature_depends_on_nonce_and_node() {
+        let sig = challenge_signature("s3cret", "n1", "node-psk");
+        assert_eq!(sig.len(), 64);
+        assert!(constant_time_eq(sig.as_bytes(), challenge_signature("s3cret", "n1", "node-psk").as_bytes()));
+        assert_ne!(sig, challenge_signature("s3cret", "n2", "node-psk"));
+        assert_ne!(sig, challenge_signature("s3cret", "n1", "node-other"));
+        assert!(!constant_time_eq(b"abc", b"ab"));
+    }
+}
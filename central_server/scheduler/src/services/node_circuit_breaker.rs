@@ -0,0 +1,600 @@
+// 节点熔断器（按节点 × 服务类型）
+//
+// 输入：JobResult.success / JobError.code / 任务派发超时 / ASR quality_level。
+// 阈值沿用 node_health.failure_threshold：最近 window_size 次结果中失败数 >= failure_count，
+// 或连续失败 >= consecutive_failure_count 时熔断（open），冷却 cooldown_seconds 后进入 half_open，
+// 按 probe_interval_ms 放行探测任务，连续成功 half_open_success_count 次恢复 closed，探测失败重新 open。
+//
+// 状态存 Redis（多实例共享）：{prefix}:breaker:{node:<node_id>} Hash，field = 服务类型，value = BreakerEntry JSON；
+// 更新为「读 → 纯函数状态迁移 → CAS 写回」（脚本只访问该节点一个 key，兼容 Redis Cluster）。
+// {prefix}:breaker:nodes 记录有熔断状态的节点（供指标汇总），与节点 key 不同 slot，CAS 成功后 best-effort SADD。
+// 健康节点（无失败记录）上的成功结果不写 Redis，常态下每个 JobResult 只有一次 HMGET。
+
+use crate::core::config::{CircuitBreakerConfig, FailureThreshold};
+use crate::messages::ServiceType;
+use crate::redis_runtime::RedisHandle;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const CAS_MAX_ATTEMPTS: usize = 3;
+
+/// 按 field 比较后写回（ARGV: ttl, 然后每组 field / expected / new；expected 为空串表示原先不存在）
+const CAS_FIELDS_LUA: &str = r#"
+for i = 2, #ARGV, 3 do
+    local cur = redis.call('HGET', KEYS[1], ARGV[i])
+    if (cur or '') ~= ARGV[i + 1] then
+        return 0
+    end
+end
+for i = 2, #ARGV, 3 do
+    redis.call('HSET', KEYS[1], ARGV[i], ARGV[i + 2])
+end
+redis.call('EXPIRE', KEYS[1], ARGV[1])
+return 1
+"#;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    #[default]
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl BreakerState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BreakerState::Closed => "closed",
+            BreakerState::Open => "open",
+            BreakerState::HalfOpen => "half_open",
+        }
+    }
+
+    /// Prometheus gauge 取值
+    pub fn gauge_value(&self) -> i64 {
+        match self {
+            BreakerState::Closed => 0,
+            BreakerState::HalfOpen => 1,
+            BreakerState::Open => 2,
+        }
+    }
+}
+
+/// 状态迁移参数（由 FailureThreshold + CircuitBreakerConfig 合成）
+#[derive(Debug, Clone)]
+pub struct BreakerPolicy {
+    pub window_size: usize,
+    pub failure_count: usize,
+    pub consecutive_failure_count: usize,
+    pub cooldown_ms: i64,
+    pub probe_interval_ms: i64,
+    pub half_open_success_count: u32,
+}
+
+impl BreakerPolicy {
+    pub fn new(threshold: &FailureThreshold, config: &CircuitBreakerConfig) -> Self {
+        Self {
+            window_size: threshold.window_size.max(1),
+            failure_count: threshold.failure_count.max(1),
+            consecutive_failure_count: threshold.consecutive_failure_count.max(1),
+            cooldown_ms: (config.cooldown_seconds.max(1) * 1000) as i64,
+            probe_interval_ms: config.probe_interval_ms as i64,
+            half_open_success_count: config.half_open_success_count.max(1),
+        }
+    }
+}
+
+/// 单个节点服务的熔断状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BreakerEntry {
+    #[serde(default)]
+    pub state: BreakerState,
+    /// 最近结果（'1' 失败 / '0' 成功，最多 window_size 个）
+    #[serde(default)]
+    pub window: String,
+    #[serde(default)]
+    pub consecutive_failures: u32,
+    #[serde(default)]
+    pub open_until_ms: i64,
+    #[serde(default)]
+    pub last_probe_at_ms: i64,
+    #[serde(default)]
+    pub half_open_successes: u32,
+    /// 累计熔断次数
+    #[serde(default)]
+    pub trips: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure: Option<String>,
+    #[serde(default)]
+    pub updated_at_ms: i64,
+}
+
+/// 派发准入结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerAdmission {
+    Allowed,
+    /// half_open 探测任务
+    Probe,
+    Rejected,
+}
+
+impl BreakerEntry {
+    /// 冷却结束的 open 视为 half_open（写回发生在下一次准入/记录时）
+    pub fn effective_state(&self, now_ms: i64) -> BreakerState {
+        match self.state {
+            BreakerState::Open if now_ms >= self.open_until_ms => BreakerState::HalfOpen,
+            s => s,
+        }
+    }
+
+    pub fn failures_in_window(&self) -> usize {
+        self.window.bytes().filter(|b| *b == b'1').count()
+    }
+
+    /// 记录一次结果（failure = Some(原因)），返回发生的状态迁移
+    pub fn record(&mut self, failure: Option<&str>, now_ms: i64, policy: &BreakerPolicy) -> Option<BreakerState> {
+        let before = self.clone();
+        let state = self.effective_state(now_ms);
+        // 冷却期内到达的结果来自熔断前派发的任务，不计入
+        if state == BreakerState::Open {
+            return None;
+        }
+        if let Some(reason) = failure {
+            self.last_failure = Some(reason.to_string());
+        }
+        let transition = match state {
+            BreakerState::Open => None,
+            BreakerState::HalfOpen => {
+                self.state = BreakerState::HalfOpen;
+                if failure.is_some() {
+                    self.trip(now_ms, policy);
+                    Some(BreakerState::Open)
+                } else {
+                    self.half_open_successes += 1;
+                    if self.half_open_successes >= policy.half_open_success_count {
+                        self.reset();
+                        Some(BreakerState::Closed)
+                    } else {
+                        None
+                    }
+                }
+            }
+            BreakerState::Closed => {
+                self.window.push(if failure.is_some() { '1' } else { '0' });
+                if self.window.len() > policy.window_size {
+                    self.window.drain(..self.window.len() - policy.window_size);
+                }
+                self.consecutive_failures = if failure.is_some() { self.consecutive_failures + 1 } else { 0 };
+                if self.failures_in_window() >= policy.failure_count
+                    || self.consecutive_failures as usize >= policy.consecutive_failure_count
+                {
+                    self.trip(now_ms, policy);
+                    Some(BreakerState::Open)
+                } else {
+                    if self.failures_in_window() == 0 {
+                        // 全成功的窗口与空窗口等价，避免健康节点每个结果都写 Redis
+                        self.window.clear();
+                    }
+                    None
+                }
+            }
+        };
+        if *self != before {
+            self.updated_at_ms = now_ms;
+        }
+        transition
+    }
+
+    /// 派发准入（half_open 时按间隔放行一个探测任务）
+    pub fn admit(&mut self, now_ms: i64, policy: &BreakerPolicy) -> BreakerAdmission {
+        match self.effective_state(now_ms) {
+            BreakerState::Closed => BreakerAdmission::Allowed,
+            BreakerState::Open => BreakerAdmission::Rejected,
+            BreakerState::HalfOpen => {
+                if now_ms - self.last_probe_at_ms < policy.probe_interval_ms {
+                    return BreakerAdmission::Rejected;
+                }
+                self.state = BreakerState::HalfOpen;
+                self.last_probe_at_ms = now_ms;
+                self.updated_at_ms = now_ms;
+                BreakerAdmission::Probe
+            }
+        }
+    }
+
+    fn trip(&mut self, now_ms: i64, policy: &BreakerPolicy) {
+        self.state = BreakerState::Open;
+        self.open_until_ms = now_ms + policy.cooldown_ms;
+        self.window.clear();
+        self.consecutive_failures = 0;
+        self.half_open_successes = 0;
+        self.trips += 1;
+    }
+
+    fn reset(&mut self) {
+        self.state = BreakerState::Closed;
+        self.window.clear();
+        self.consecutive_failures = 0;
+        self.half_open_successes = 0;
+    }
+}
+
+/// 一次任务结果对各服务的影响：None = 成功，Some(原因) = 失败
+pub type ServiceOutcome = (ServiceType, Option<String>);
+
+/// 按 JobResult 归类各服务结果
+///
+/// - 成功：任务涉及的服务均记成功；ASR quality_level = "bad" 时 ASR 记失败（count_bad_asr_quality）
+/// - 失败：错误码在 ignored_error_codes 中则不计；ASR_/NMT_/TTS_ 等前缀归到对应服务，否则任务涉及的服务均记失败
+pub fn classify_job_result(
+    config: &CircuitBreakerConfig,
+    services: &[ServiceType],
+    success: bool,
+    error_code: Option<&str>,
+    asr_quality_level: Option<&str>,
+) -> Vec<ServiceOutcome> {
+    if success {
+        let asr_bad = config.count_bad_asr_quality && asr_quality_level == Some("bad");
+        return services
+            .iter()
+            .map(|s| {
+                let failure = (asr_bad && *s == ServiceType::Asr).then(|| "ASR_QUALITY_BAD".to_string());
+                (s.clone(), failure)
+            })
+            .collect();
+    }
+    let code = error_code.unwrap_or("JOB_FAILED");
+    if config.ignored_error_codes.iter().any(|c| c == code) {
+        return Vec::new();
+    }
+    match service_from_error_code(code).filter(|s| services.contains(s)) {
+        Some(service) => vec![(service, Some(code.to_string()))],
+        None => services.iter().map(|s| (s.clone(), Some(code.to_string()))).collect(),
+    }
+}
+
+/// 错误码前缀 -> 服务类型（如 NMT_TIMEOUT -> Nmt）
+fn service_from_error_code(code: &str) -> Option<ServiceType> {
+    code.split('_').next()?.parse().ok()
+}
+
+/// 对外展示的熔断状态（/api/v1/cluster）
+#[derive(Debug, Clone, Serialize)]
+pub struct BreakerSnapshot {
+    pub service_type: String,
+    pub state: BreakerState,
+    pub failures_in_window: usize,
+    pub consecutive_failures: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_until_ms: Option<i64>,
+    pub trips: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_failure: Option<String>,
+    pub updated_at_ms: i64,
+}
+
+impl BreakerSnapshot {
+    fn from_entry(service_type: &str, entry: &BreakerEntry, now_ms: i64) -> Self {
+        let state = entry.effective_state(now_ms);
+        Self {
+            service_type: service_type.to_string(),
+            state,
+            failures_in_window: entry.failures_in_window(),
+            consecutive_failures: entry.consecutive_failures,
+            open_until_ms: (state == BreakerState::Open).then_some(entry.open_until_ms),
+            trips: entry.trips,
+            last_failure: entry.last_failure.clone(),
+            updated_at_ms: entry.updated_at_ms,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct NodeCircuitBreaker {
+    redis: Arc<RedisHandle>,
+    key_prefix: String,
+    policy: BreakerPolicy,
+    config: CircuitBreakerConfig,
+}
+
+impl NodeCircuitBreaker {
+    pub fn new(
+        redis: Arc<RedisHandle>,
+        key_prefix: String,
+        threshold: &FailureThreshold,
+        config: CircuitBreakerConfig,
+    ) -> Self {
+        Self {
+            redis,
+            key_prefix,
+            policy: BreakerPolicy::new(threshold, &config),
+            config,
+        }
+    }
+
+    fn node_key(&self, node_id: &str) -> String {
+        format!("{}:breaker:{{node:{}}}", self.key_prefix, node_id)
+    }
+
+    fn nodes_key(&self) -> String {
+        format!("{}:breaker:nodes", self.key_prefix)
+    }
+
+    fn field(service: &ServiceType) -> String {
+        format!("{:?}", service).to_lowercase()
+    }
+
+    async fn load(&self, node_id: &str, fields: &[String]) -> anyhow::Result<Vec<Option<String>>> {
+        let mut cmd = redis::cmd("HMGET");
+        cmd.arg(self.node_key(node_id));
+        for f in fields {
+            cmd.arg(f);
+        }
+        Ok(self.redis.query(cmd).await?)
+    }
+
+    async fn compare_and_set(
+        &self,
+        node_id: &str,
+        changes: &[(String, Option<String>, String)],
+    ) -> anyhow::Result<bool> {
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(CAS_FIELDS_LUA)
+            .arg(1)
+            .arg(self.node_key(node_id))
+            .arg(self.config.state_ttl_seconds.max(1));
+        for (field, expected, new) in changes {
+            cmd.arg(field).arg(expected.as_deref().unwrap_or("")).arg(new);
+        }
+        let written: i64 = self.redis.query(cmd).await?;
+        if written == 1 {
+            // 索引只影响指标汇总，失败时下次写入再补
+            let mut sadd = redis::cmd("SADD");
+            sadd.arg(self.nodes_key()).arg(node_id);
+            if let Err(e) = self.redis.query::<i64>(sadd).await {
+                tracing::debug!(node_id = %node_id, error = %e, "写入熔断节点索引失败");
+            }
+        }
+        Ok(written == 1)
+    }
+
+    /// 记录任务结果，返回发生的状态迁移（服务类型, 新状态）
+    pub async fn record_outcomes(
+        &self,
+        node_id: &str,
+        outcomes: &[ServiceOutcome],
+    ) -> anyhow::Result<Vec<(ServiceType, BreakerState)>> {
+        if outcomes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let fields: Vec<String> = outcomes.iter().map(|(s, _)| Self::field(s)).collect();
+        for _ in 0..CAS_MAX_ATTEMPTS {
+            let now_ms = chrono::Utc::now().timestamp_millis();
+            let current = self.load(node_id, &fields).await?;
+            let mut changes = Vec::new();
+            let mut transitions = Vec::new();
+            for (((service, failure), field), raw) in outcomes.iter().zip(&fields).zip(current) {
+                let mut entry: BreakerEntry = raw
+                    .as_deref()
+                    .and_then(|s| serde_json::from_str(s).ok())
+                    .unwrap_or_default();
+                let before = entry.clone();
+                if let Some(to) = entry.record(failure.as_deref(), now_ms, &self.policy) {
+                    transitions.push((service.clone(), to));
+                }
+                if entry != before {
+                    changes.push((field.clone(), raw, serde_json::to_string(&entry)?));
+                }
+            }
+            if changes.is_empty() || self.compare_and_set(node_id, &changes).await? {
+                for (service, to) in &transitions {
+                    crate::metrics::prometheus_metrics::on_circuit_breaker_transition(service, *to);
+                    tracing::warn!(node_id = %node_id, service_type = ?service, state = to.as_str(), "【熔断】节点服务熔断状态变更");
+                }
+                return Ok(transitions);
+            }
+        }
+        Err(anyhow::anyhow!("circuit breaker state contention for node {}", node_id))
+    }
+
+    /// 记录 JobResult（按 classify_job_result 归类到各服务）
+    pub async fn record_job_result(
+        &self,
+        node_id: &str,
+        services: &[ServiceType],
+        success: bool,
+        error_code: Option<&str>,
+        asr_quality_level: Option<&str>,
+    ) -> anyhow::Result<Vec<(ServiceType, BreakerState)>> {
+        let outcomes = classify_job_result(&self.config, services, success, error_code, asr_quality_level);
+        self.record_outcomes(node_id, &outcomes).await
+    }
+
+    /// 记录任务派发超时（任务涉及的服务均记失败）
+    pub async fn record_job_timeout(
+        &self,
+        node_id: &str,
+        services: &[ServiceType],
+    ) -> anyhow::Result<Vec<(ServiceType, BreakerState)>> {
+        let outcomes: Vec<ServiceOutcome> = services
+            .iter()
+            .map(|s| (s.clone(), Some("JOB_TIMEOUT".to_string())))
+            .collect();
+        self.record_outcomes(node_id, &outcomes).await
+    }
+
+    /// 派发准入：任一所需服务熔断中则拒绝（返回该服务）；half_open 的探测名额在此占用
+    pub async fn admit(&self, node_id: &str, required: &[ServiceType]) -> anyhow::Result<Result<BreakerAdmission, ServiceType>> {
+        if required.is_empty() {
+            return Ok(Ok(BreakerAdmission::Allowed));
+        }
+        let fields: Vec<String> = required.iter().map(Self::field).collect();
+        let current = self.load(node_id, &fields).await?;
+        if current.iter().all(|v| v.is_none()) {
+            return Ok(Ok(BreakerAdmission::Allowed));
+        }
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut admission = BreakerAdmission::Allowed;
+        let mut changes = Vec::new();
+        let mut half_opened = Vec::new();
+        for ((service, field), raw) in required.iter().zip(&fields).zip(current) {
+            let Some(mut entry) = raw.as_deref().and_then(|s| serde_json::from_str::<BreakerEntry>(s).ok()) else {
+                continue;
+            };
+            let before = entry.clone();
+            match entry.admit(now_ms, &self.policy) {
+                BreakerAdmission::Rejected => return Ok(Err(service.clone())),
+                BreakerAdmission::Probe => admission = BreakerAdmission::Probe,
+                BreakerAdmission::Allowed => {}
+            }
+            if before.state == BreakerState::Open && entry.state == BreakerState::HalfOpen {
+                half_opened.push(service);
+            }
+            if entry != before {
+                changes.push((field.clone(), raw, serde_json::to_string(&entry)?));
+            }
+        }
+        // 探测名额被其他实例抢先占用时视为拒绝
+        if !changes.is_empty() && !self.compare_and_set(node_id, &changes).await? {
+            return Ok(Err(required[0].clone()));
+        }
+        for service in half_opened {
+            crate::metrics::prometheus_metrics::on_circuit_breaker_transition(service, BreakerState::HalfOpen);
+        }
+        Ok(Ok(admission))
+    }
+
+    /// 派发准入的布尔形式：拒绝时记指标，half_open 放行探测任务时记日志；Redis 失败时默认放行，避免误判
+    pub async fn admits(&self, node_id: &str, required: &[ServiceType]) -> bool {
+        match self.admit(node_id, required).await {
+            Ok(Ok(BreakerAdmission::Probe)) => {
+                tracing::info!(node_id = %node_id, required_types = ?required, "【熔断】half_open 放行探测任务");
+                true
+            }
+            Ok(Ok(_)) => true,
+            Ok(Err(service_type)) => {
+                crate::metrics::prometheus_metrics::on_circuit_breaker_rejected(&service_type);
+                tracing::debug!(node_id = %node_id, service_type = ?service_type, "【熔断】节点服务熔断中，跳过");
+                false
+            }
+            Err(e) => {
+                tracing::warn!(node_id = %node_id, error = %e, "检查熔断状态失败，默认放行");
+                true
+            }
+        }
+    }
+
+    /// 节点各服务的熔断状态（无记录返回空）
+    pub async fn node_snapshot(&self, node_id: &str) -> anyhow::Result<Vec<BreakerSnapshot>> {
+        let raw = self.redis.hgetall(&self.node_key(node_id)).await?;
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut snapshots: Vec<BreakerSnapshot> = raw
+            .iter()
+            .filter_map(|(service, json)| {
+                let entry: BreakerEntry = serde_json::from_str(json).ok()?;
+                Some(BreakerSnapshot::from_entry(service, &entry, now_ms))
+            })
+            .collect();
+        snapshots.sort_by(|a, b| a.service_type.cmp(&b.service_type));
+        Ok(snapshots)
+    }
+
+    /// 所有有熔断记录的节点（已过期的节点从索引中移除）
+    pub async fn all_snapshots(&self) -> anyhow::Result<HashMap<String, Vec<BreakerSnapshot>>> {
+        let mut result = HashMap::new();
+        for node_id in self.redis.smembers_strings(&self.nodes_key()).await? {
+            let snapshots = self.node_snapshot(&node_id).await?;
+            if snapshots.is_empty() {
+                let mut cmd = redis::cmd("SREM");
+                cmd.arg(self.nodes_key()).arg(&node_id);
+                let _ = self.redis.query::<i64>(cmd).await;
+                continue;
+            }
+            result.insert(node_id, snapshots);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> BreakerPolicy {
+        BreakerPolicy::new(
+            &FailureThreshold {
+                window_size: 5,
+                failure_count: 3,
+                consecutive_failure_count: 2,
+            },
+            &CircuitBreakerConfig {
+                cooldown_seconds: 10,
+                probe_interval_ms: 1000,
+                half_open_success_count: 2,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn trips_on_consecutive_or_windowed_failures() {
+        let p = policy();
+        let mut e = BreakerEntry::default();
+        assert_eq!(e.record(None, 0, &p), None);
+        assert_eq!(e, BreakerEntry::default(), "healthy successes are not persisted");
+        assert_eq!(e.record(Some("NMT_TIMEOUT"), 1, &p), None);
+        assert_eq!(e.record(Some("NMT_TIMEOUT"), 2, &p), Some(BreakerState::Open));
+        assert_eq!(e.trips, 1);
+        assert_eq!(e.open_until_ms, 10_002);
+
+        let mut e = BreakerEntry::default();
+        for (i, failure) in [Some("x"), None, Some("x"), None, Some("x")].into_iter().enumerate() {
+            let t = e.record(failure, i as i64, &p);
+            assert_eq!(t.is_some(), i == 4);
+        }
+        assert_eq!(e.state, BreakerState::Open);
+    }
+
+    #[test]
+    fn half_open_probes_then_closes_or_reopens() {
+        let p = policy();
+        let mut e = BreakerEntry::default();
+        e.record(Some("x"), 0, &p);
+        e.record(Some("x"), 0, &p);
+        assert_eq!(e.admit(5_000, &p), BreakerAdmission::Rejected);
+        // 冷却期内的迟到结果不计入
+        assert_eq!(e.record(None, 5_000, &p), None);
+        assert_eq!(e.admit(10_000, &p), BreakerAdmission::Probe);
+        assert_eq!(e.admit(10_500, &p), BreakerAdmission::Rejected);
+        assert_eq!(e.record(None, 10_600, &p), None);
+        assert_eq!(e.record(None, 10_700, &p), Some(BreakerState::Closed));
+        assert_eq!(e.admit(10_800, &p), BreakerAdmission::Allowed);
+
+        e.record(Some("x"), 20_000, &p);
+        e.record(Some("x"), 20_000, &p);
+        assert_eq!(e.admit(30_000, &p), BreakerAdmission::Probe);
+        assert_eq!(e.record(Some("x"), 30_100, &p), Some(BreakerState::Open));
+        assert_eq!(e.open_until_ms, 40_100);
+        assert_eq!(e.trips, 3);
+    }
+
+    #[test]
+    fn classifies_job_results_per_service() {
+        let config = CircuitBreakerConfig::default();
+        let services = [ServiceType::Asr, ServiceType::Nmt, ServiceType::Tts];
+
+        let ok = classify_job_result(&config, &services, true, None, Some("bad"));
+        assert_eq!(ok[0], (ServiceType::Asr, Some("ASR_QUALITY_BAD".to_string())));
+        assert!(ok[1..].iter().all(|(_, f)| f.is_none()));
+
+        let nmt = classify_job_result(&config, &services, false, Some("NMT_TIMEOUT"), None);
+        assert_eq!(nmt, vec![(ServiceType::Nmt, Some("NMT_TIMEOUT".to_string()))]);
+
+        let generic = classify_job_result(&config, &services, false, Some("PROCESSING_ERROR"), None);
+        assert_eq!(generic.len(), 3);
+
+        assert!(classify_job_result(&config, &services, false, Some("MODEL_NOT_AVAILABLE"), None).is_empty());
+    }
+}
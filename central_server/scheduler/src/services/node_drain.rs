@@ -0,0 +1,410 @@
+// 节点排空（升级 GPU 机器时不中断在线会话）
+//
+// 触发：POST /api/v1/nodes/:node_id/drain，或节点自己发送 node_control {command:"drain"}。
+// 流程：
+// 1. node_drain.lua：节点 status=draining 并移出所有池（不再被选中，心跳也不会再加回池）；
+// 2. 等待该节点上的在途任务（Assigned / Processing）完成，最长 in_flight_timeout_seconds；
+// 3. 绑定到该节点的会话（session affinity）经 SessionMigrationOrchestrator 迁移到同池其他节点；
+// 4. node_offline.lua 下线节点，向节点发送 node_control {command:"drain_complete"}。
+// 排空记录写入 {prefix}:node_drain:{node:<id>}（JSON），任意实例可查询；节点重新注册后清除。
+
+use crate::core::config::NodeDrainConfig;
+use crate::core::dispatcher::{Job, JobStatus};
+use crate::core::AppState;
+use crate::messages::{NodeMessage, NodeStatus};
+use crate::redis_runtime::RedisHandle;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tracing::{info, warn};
+
+const MIGRATION_REASON: &str = "node_drain";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DrainStage {
+    WaitingInFlight,
+    MigratingSessions,
+    Completed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeDrainRecord {
+    pub node_id: String,
+    /// Draining → Offline
+    pub status: NodeStatus,
+    pub stage: DrainStage,
+    pub reason: String,
+    /// "admin" | "node"
+    pub requested_by: String,
+    pub started_at_ms: i64,
+    pub updated_at_ms: i64,
+    pub in_flight_jobs: usize,
+    pub sessions_total: usize,
+    pub sessions_migrated: usize,
+    pub sessions_failed: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum NodeDrainError {
+    #[error("node drain is disabled")]
+    Disabled,
+    #[error("node is already draining")]
+    AlreadyDraining,
+    #[error("node is not registered")]
+    NotRegistered,
+    #[error("node drain unavailable: {0}")]
+    Unavailable(String),
+}
+
+#[derive(Clone)]
+pub struct NodeDrainService {
+    redis: Arc<RedisHandle>,
+    key_prefix: String,
+    config: NodeDrainConfig,
+}
+
+impl NodeDrainService {
+    pub fn new(redis: Arc<RedisHandle>, key_prefix: String, config: NodeDrainConfig) -> Self {
+        Self {
+            redis,
+            key_prefix,
+            config,
+        }
+    }
+
+    fn record_key(&self, node_id: &str) -> String {
+        format!("{}:node_drain:{{node:{}}}", self.key_prefix, node_id)
+    }
+
+    /// 写入排空记录；已有进行中的排空（且仍在更新）时返回 false
+    async fn begin(&self, record: &NodeDrainRecord) -> redis::RedisResult<bool> {
+        // 记录超过 stale_ms 未更新视为执行实例已退出，允许重新发起
+        let script = r#"
+local cur = redis.call('GET', KEYS[1])
+if cur then
+  local ok, rec = pcall(cjson.decode, cur)
+  if ok and rec.status == 'draining' and tonumber(rec.updated_at_ms) > tonumber(ARGV[3]) then
+    return 0
+  end
+end
+redis.call('SET', KEYS[1], ARGV[1], 'EX', ARGV[2])
+return 1
+"#;
+        let stale_ms = (self.config.poll_interval_ms.max(100) * 10).max(60_000) as i64;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(1)
+            .arg(self.record_key(&record.node_id))
+            .arg(serde_json::to_string(record).unwrap_or_default())
+            .arg(self.config.record_ttl_seconds.max(1))
+            .arg(record.updated_at_ms - stale_ms);
+        let started: i64 = self.redis.query(cmd).await?;
+        Ok(started == 1)
+    }
+
+    async fn save(&self, record: &mut NodeDrainRecord) {
+        record.updated_at_ms = chrono::Utc::now().timestamp_millis();
+        let json = serde_json::to_string(record).unwrap_or_default();
+        if let Err(e) = self
+            .redis
+            .set_ex_string(&self.record_key(&record.node_id), &json, self.config.record_ttl_seconds.max(1))
+            .await
+        {
+            warn!(node_id = %record.node_id, error = %e, "保存节点排空记录失败");
+        }
+    }
+
+    pub async fn get(&self, node_id: &str) -> redis::RedisResult<Option<NodeDrainRecord>> {
+        let raw = self.redis.get_string(&self.record_key(node_id)).await?;
+        Ok(raw.and_then(|s| serde_json::from_str(&s).ok()))
+    }
+
+    /// 排空已完成（节点已下线）：心跳不再刷新节点与池，直到节点重新注册
+    pub async fn is_offline(&self, node_id: &str) -> bool {
+        matches!(self.get(node_id).await, Ok(Some(r)) if r.status == NodeStatus::Offline)
+    }
+
+    /// 节点重新注册（如升级后重启）：清除已完成的排空记录
+    pub async fn clear_if_offline(&self, node_id: &str) {
+        if self.is_offline(node_id).await {
+            let _ = self.redis.del(&self.record_key(node_id)).await;
+            info!(node_id = %node_id, "节点重新注册，清除排空记录");
+        }
+    }
+
+    fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.config.poll_interval_ms.max(100))
+    }
+
+    fn in_flight_timeout(&self) -> Duration {
+        Duration::from_secs(self.config.in_flight_timeout_seconds)
+    }
+}
+
+/// 发起节点排空：节点立即移出池，其余步骤在后台执行
+pub async fn start_node_drain(
+    state: &AppState,
+    node_id: &str,
+    reason: &str,
+    requested_by: &str,
+) -> Result<NodeDrainRecord, NodeDrainError> {
+    let drain = state.node_drain.as_ref().ok_or(NodeDrainError::Disabled)?;
+    let pool_service = state
+        .pool_service
+        .clone()
+        .ok_or_else(|| NodeDrainError::Unavailable("pool service not initialized".to_string()))?;
+
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let record = NodeDrainRecord {
+        node_id: node_id.to_string(),
+        status: NodeStatus::Draining,
+        stage: DrainStage::WaitingInFlight,
+        reason: reason.to_string(),
+        requested_by: requested_by.to_string(),
+        started_at_ms: now_ms,
+        updated_at_ms: now_ms,
+        in_flight_jobs: 0,
+        sessions_total: 0,
+        sessions_migrated: 0,
+        sessions_failed: 0,
+    };
+    if !drain
+        .begin(&record)
+        .await
+        .map_err(|e| NodeDrainError::Unavailable(e.to_string()))?
+    {
+        return Err(NodeDrainError::AlreadyDraining);
+    }
+
+    let pools = match pool_service.node_drain(node_id).await {
+        Ok(pools) => pools,
+        Err(e) => {
+            let _ = drain.redis.del(&drain.record_key(node_id)).await;
+            return Err(if e.to_string().contains("NODE_NOT_REGISTERED") {
+                NodeDrainError::NotRegistered
+            } else {
+                NodeDrainError::Unavailable(e.to_string())
+            });
+        }
+    };
+
+    notify_node(state, node_id, "drain", Some(reason.to_string())).await;
+    info!(node_id = %node_id, reason = %reason, requested_by = %requested_by, pools = pools.len(), "节点开始排空");
+
+    let state = state.clone();
+    let drain = drain.clone();
+    let task_record = record.clone();
+    tokio::spawn(async move {
+        run_node_drain(&state, &drain, task_record, pools).await;
+    });
+    Ok(record)
+}
+
+async fn run_node_drain(
+    state: &AppState,
+    drain: &NodeDrainService,
+    mut record: NodeDrainRecord,
+    pools: HashMap<String, String>,
+) {
+    let node_id = record.node_id.clone();
+
+    // 1) 等待在途任务完成
+    let started = Instant::now();
+    let jobs = loop {
+        let jobs = state.dispatcher.list_jobs_snapshot().await;
+        record.in_flight_jobs = in_flight_job_count(&jobs, &node_id);
+        drain.save(&mut record).await;
+        if record.in_flight_jobs == 0 {
+            break jobs;
+        }
+        if started.elapsed() >= drain.in_flight_timeout() {
+            warn!(node_id = %node_id, in_flight = record.in_flight_jobs, "等待在途任务超时，继续迁移会话");
+            break jobs;
+        }
+        tokio::time::sleep(drain.poll_interval()).await;
+    };
+
+    // 2) 迁移绑定到该节点的会话
+    record.stage = DrainStage::MigratingSessions;
+    drain.save(&mut record).await;
+    if let Some(orchestrator) = state.session_migration_orchestrator.as_ref() {
+        let sessions = bound_sessions(state, &node_id, &jobs).await;
+        record.sessions_total = sessions.len();
+        for (session_id, (src_lang, tgt_lang)) in sessions {
+            let Some(target) = pick_target(state, &pools, &node_id, &src_lang, &tgt_lang).await else {
+                warn!(node_id = %node_id, session_id = %session_id, "没有可迁移的目标节点");
+                record.sessions_failed += 1;
+                continue;
+            };
+            match orchestrator
+                .migrate_session(&session_id, &node_id, &target, MIGRATION_REASON)
+                .await
+            {
+                Ok(result) if result.ok => record.sessions_migrated += 1,
+                Ok(result) => {
+                    warn!(node_id = %node_id, session_id = %session_id, status = %result.status, error = ?result.error, "排空迁移会话失败");
+                    record.sessions_failed += 1;
+                }
+                Err(e) => {
+                    warn!(node_id = %node_id, session_id = %session_id, error = %e, "排空迁移会话失败");
+                    record.sessions_failed += 1;
+                }
+            }
+            drain.save(&mut record).await;
+        }
+    }
+
+    // 3) 下线
+    if let Some(pool_service) = state.pool_service.as_ref() {
+        if let Err(e) = pool_service.node_offline(&node_id).await {
+            warn!(node_id = %node_id, error = %e, "排空完成后下线节点失败");
+        }
+    }
+    record.status = NodeStatus::Offline;
+    record.stage = DrainStage::Completed;
+    drain.save(&mut record).await;
+    notify_node(state, &node_id, "drain_complete", None).await;
+    info!(
+        node_id = %node_id,
+        sessions_total = record.sessions_total,
+        sessions_migrated = record.sessions_migrated,
+        sessions_failed = record.sessions_failed,
+        elapsed_ms = (record.updated_at_ms - record.started_at_ms),
+        "节点排空完成，已下线"
+    );
+}
+
+async fn notify_node(state: &AppState, node_id: &str, command: &str, reason: Option<String>) {
+    let msg = NodeMessage::NodeControl {
+        command: command.to_string(),
+        reason,
+    };
+    let _ = crate::redis_runtime::send_node_message_routed(state, node_id, msg).await;
+}
+
+/// 已下发到该节点、尚未结束的任务数
+fn in_flight_job_count(jobs: &[Job], node_id: &str) -> usize {
+    jobs.iter()
+        .filter(|job| {
+            job.dispatched_to_node
+                && job.assigned_node_id.as_deref() == Some(node_id)
+                && matches!(job.status, JobStatus::Assigned | JobStatus::Processing)
+        })
+        .count()
+}
+
+/// 绑定到该节点的会话：session_id -> (src_lang, tgt_lang)
+/// 候选来自本实例会话与 Redis 中的任务（覆盖其他实例上的会话），再按 affinity 绑定过滤
+async fn bound_sessions(state: &AppState, node_id: &str, jobs: &[Job]) -> HashMap<String, (String, String)> {
+    let mut candidates: HashMap<String, (String, String)> = HashMap::new();
+    for session in state.session_manager.list_all_sessions().await {
+        candidates.insert(session.session_id, (session.src_lang, session.tgt_lang));
+    }
+    for job in jobs {
+        candidates
+            .entry(job.session_id.clone())
+            .or_insert_with(|| (job.src_lang.clone(), job.tgt_lang.clone()));
+    }
+
+    let Some(orchestrator) = state.session_migration_orchestrator.as_ref() else {
+        return HashMap::new();
+    };
+    let mut bound = HashMap::new();
+    for (session_id, langs) in candidates {
+        match orchestrator.affinity().get_assigned_node_id(&session_id).await {
+            Ok(Some(assigned)) if assigned == node_id => {
+                bound.insert(session_id, langs);
+            }
+            Ok(_) => {}
+            Err(e) => warn!(session_id = %session_id, error = %e, "读取会话绑定节点失败"),
+        }
+    }
+    bound
+}
+
+/// 迁移目标：优先与排空节点同池的其他节点，否则按语言对重新选择
+async fn pick_target(
+    state: &AppState,
+    pools: &HashMap<String, String>,
+    node_id: &str,
+    src_lang: &str,
+    tgt_lang: &str,
+) -> Option<String> {
+    let pool_service = state.pool_service.as_ref()?;
+    if let Some((pair_key, pool_id)) = pool_for_pair(pools, src_lang, tgt_lang) {
+        if let Ok(Some(target)) = pool_service.pick_pool_member(pair_key, pool_id, node_id).await {
+            return Some(target);
+        }
+    }
+    pool_service
+        .select_node(src_lang, tgt_lang, None, None, &[], Some(node_id))
+        .await
+        .ok()
+}
+
+/// 会话语言对在排空节点上所属的池；src_lang = "auto" 时取任一目标语言匹配的池
+fn pool_for_pair<'a>(
+    pools: &'a HashMap<String, String>,
+    src_lang: &str,
+    tgt_lang: &str,
+) -> Option<(&'a str, &'a str)> {
+    let pair_key = format!("{}:{}", src_lang, tgt_lang);
+    if let Some((k, v)) = pools.get_key_value(&pair_key) {
+        return Some((k, v));
+    }
+    if src_lang != "auto" {
+        return None;
+    }
+    // 只在公共池中匹配（租户池 pair_key 以 "tenant:" 开头）
+    let suffix = format!(":{}", tgt_lang);
+    let mut matching: Vec<_> = pools
+        .iter()
+        .filter(|(k, _)| k.ends_with(&suffix) && !k.starts_with("tenant:"))
+        .collect();
+    matching.sort();
+    matching.first().map(|(k, v)| (k.as_str(), v.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pool_for_pair_prefers_exact_pair_then_auto_source() {
+        let pools = HashMap::from([
+            ("zh:en".to_string(), "3".to_string()),
+            ("ja:en".to_string(), "0".to_string()),
+            ("en:zh".to_string(), "1".to_string()),
+            ("tenant:acme:zh:ko".to_string(), "0".to_string()),
+        ]);
+        assert_eq!(pool_for_pair(&pools, "zh", "en"), Some(("zh:en", "3")));
+        assert_eq!(pool_for_pair(&pools, "ko", "en"), None);
+        assert_eq!(pool_for_pair(&pools, "auto", "en"), Some(("ja:en", "0")));
+        assert_eq!(pool_for_pair(&pools, "auto", "ja"), None);
+        assert_eq!(pool_for_pair(&pools, "auto", "ko"), None);
+    }
+
+    #[test]
+    fn drain_record_serializes_node_status() {
+        let record = NodeDrainRecord {
+            node_id: "node-1".to_string(),
+            status: NodeStatus::Draining,
+            stage: DrainStage::WaitingInFlight,
+            reason: "upgrade".to_string(),
+            requested_by: "admin".to_string(),
+            started_at_ms: 1,
+            updated_at_ms: 1,
+            in_flight_jobs: 2,
+            sessions_total: 0,
+            sessions_migrated: 0,
+            sessions_failed: 0,
+        };
+        let json = serde_json::to_value(&record).unwrap();
+        // begin() 的 Lua 脚本依赖 status 字段的字符串形式
+        assert_eq!(json["status"], "draining");
+        assert_eq!(json["stage"], "waiting_in_flight");
+    }
+}
@@ -1,8 +1,29 @@
+// 配对码（私有节点会话）
+//
+// 节点通过 /ws/node 发送 pairing_code_request，调度器签发短时效配对码并下发 pairing_code_issued；
+// 客户端在 session_init.pairing_code 中出示，校验通过后会话绑定该节点（Session.paired_node_id），
+// 该会话的所有任务只派发给配对节点，不走公共池。
+// 配对码存 Redis：{prefix}:pairing:code:{CODE} -> node_id（EX = ttl），一次性使用，多实例共享。
+
+use crate::core::config::PairingConfig;
+use crate::redis_runtime::RedisHandle;
+use chrono::{DateTime, Utc};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use chrono::{DateTime, Utc};
+
+/// 去掉易混淆字符（0/O、1/I/L）
+const CODE_ALPHABET: &[u8] = b"23456789ABCDEFGHJKMNPQRSTUVWXYZ";
+const ISSUE_MAX_ATTEMPTS: usize = 5;
+
+/// 原子取出并删除（一次性使用；兼容不支持 GETDEL 的 Redis）
+const TAKE_CODE_LUA: &str = r#"
+local node_id = redis.call('GET', KEYS[1])
+if node_id then
+    redis.call('DEL', KEYS[1])
+end
+return node_id
+"#;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PairingCode {
@@ -14,33 +35,102 @@ pub struct PairingCode {
 
 #[derive(Clone)]
 pub struct PairingService {
-    codes: Arc<RwLock<HashMap<String, PairingCode>>>,
+    redis: Arc<RedisHandle>,
+    key_prefix: String,
+    config: PairingConfig,
 }
 
 impl PairingService {
-    pub fn new() -> Self {
+    pub fn new(redis: Arc<RedisHandle>, key_prefix: String, config: PairingConfig) -> Self {
         Self {
-            codes: Arc::new(RwLock::new(HashMap::new())),
+            redis,
+            key_prefix,
+            config,
         }
     }
 
+    fn code_key(&self, code: &str) -> String {
+        format!("{}:pairing:code:{}", self.key_prefix, code)
+    }
 
+    /// 为节点签发配对码；ttl_seconds 不超过配置上限
+    pub async fn issue_pairing_code(
+        &self,
+        node_id: &str,
+        ttl_seconds: Option<u64>,
+    ) -> anyhow::Result<PairingCode> {
+        let ttl = clamp_ttl(ttl_seconds, self.config.code_ttl_seconds, self.config.max_code_ttl_seconds);
+        for _ in 0..ISSUE_MAX_ATTEMPTS {
+            let code = generate_code(self.config.code_length);
+            // NX：避免覆盖其他节点尚未使用的同名配对码
+            if self.redis.set_nx_ex_string(&self.code_key(&code), node_id, ttl).await? {
+                let created_at = Utc::now();
+                return Ok(PairingCode {
+                    code,
+                    node_id: node_id.to_string(),
+                    created_at,
+                    expires_at: created_at + chrono::Duration::seconds(ttl as i64),
+                });
+            }
+        }
+        Err(anyhow::anyhow!("Failed to allocate a unique pairing code"))
+    }
+
+    /// 校验并消费配对码，返回配对的 node_id（过期/不存在/已使用返回 None）
     pub async fn validate_pairing_code(&self, code: &str) -> Option<String> {
-        let mut codes = self.codes.write().await;
-        
-        if let Some(pairing_code) = codes.get(code) {
-            if pairing_code.expires_at > Utc::now() {
-                let node_id = pairing_code.node_id.clone();
-                codes.remove(code);
-                Some(node_id)
-            } else {
-                codes.remove(code);
+        let code = normalize_code(code);
+        if code.is_empty() {
+            return None;
+        }
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(TAKE_CODE_LUA).arg(1).arg(self.code_key(&code));
+        match self.redis.query::<Option<String>>(cmd).await {
+            Ok(node_id) => node_id,
+            Err(e) => {
+                tracing::warn!(error = %e, "校验配对码失败（Redis 不可用）");
                 None
             }
-        } else {
-            None
         }
     }
+}
+
+fn generate_code(length: usize) -> String {
+    let mut rng = rand::thread_rng();
+    (0..length.clamp(4, 16))
+        .map(|_| CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())] as char)
+        .collect()
+}
+
+/// 客户端输入容错：忽略空白与连字符，不区分大小写
+fn normalize_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
 
+fn clamp_ttl(requested: Option<u64>, default_ttl: u64, max_ttl: u64) -> u64 {
+    requested.unwrap_or(default_ttl).clamp(1, max_ttl.max(1))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_codes_use_unambiguous_alphabet() {
+        let code = generate_code(6);
+        assert_eq!(code.len(), 6);
+        assert!(code.bytes().all(|b| CODE_ALPHABET.contains(&b)));
+        assert_eq!(generate_code(1).len(), 4);
+        assert_eq!(normalize_code(&code.to_lowercase()), code);
+    }
+
+    #[test]
+    fn normalize_and_ttl_clamp() {
+        assert_eq!(normalize_code(" ab3-k9z "), "AB3K9Z");
+        assert_eq!(clamp_ttl(None, 300, 3600), 300);
+        assert_eq!(clamp_ttl(Some(7200), 300, 3600), 3600);
+        assert_eq!(clamp_ttl(Some(0), 300, 3600), 1);
+    }
+}
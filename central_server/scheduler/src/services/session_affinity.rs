@@ -20,6 +20,17 @@ pub struct SessionMigrationEvent {
     pub timestamp_ms: i64,
 }
 
+impl SessionMigrationEvent {
+    /// Orchestrator events pack details into `reason` as "reason|status=..|snapshot=..|durationMs=..".
+    /// Returns the base reason and the packed key/value pairs.
+    pub fn reason_parts(&self) -> (&str, Vec<(&str, &str)>) {
+        let mut parts = self.reason.split('|');
+        let base = parts.next().unwrap_or_default();
+        let fields = parts.filter_map(|p| p.split_once('=')).collect();
+        (base, fields)
+    }
+}
+
 pub struct SessionAffinityService {
     redis: Arc<RedisRuntime>,
 }
@@ -129,6 +140,18 @@ return redis.call('HGET', KEYS[1], 'assigned_node_id')
         Ok(())
     }
 
+    /// Migration history for a session, oldest first; only the most recent `limit` events are returned.
+    pub async fn list_migration_events(&self, session_id: &str, limit: usize) -> Result<Vec<SessionMigrationEvent>> {
+        let key = format!("{}{}", Self::session_key(session_id), MIGRATION_LOG_SUFFIX);
+        let mut cmd = redis::cmd("LRANGE");
+        cmd.arg(&key).arg(-(limit.max(1) as i64)).arg(-1);
+        let raw: Vec<String> = self.redis.redis_query(cmd).await?;
+        Ok(raw
+            .iter()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
     pub async fn record_migration_event(&self, event: SessionMigrationEvent) -> Result<()> {
         let key = format!("{}{}", Self::session_key(&event.session_id), MIGRATION_LOG_SUFFIX);
         let payload = serde_json::to_string(&event)?;
@@ -150,4 +173,24 @@ mod tests {
             "scheduler:session:abc"
         );
     }
+
+    #[test]
+    fn reason_parts_splits_orchestrator_details() {
+        let event = SessionMigrationEvent {
+            session_id: "s".to_string(),
+            from_node_id: Some("a".to_string()),
+            to_node_id: "b".to_string(),
+            reason: "node_drain|status=success|snapshot=session-migration-v1|durationMs=42".to_string(),
+            timestamp_ms: 0,
+        };
+        let (base, fields) = event.reason_parts();
+        assert_eq!(base, "node_drain");
+        assert_eq!(
+            fields,
+            vec![("status", "success"), ("snapshot", "session-migration-v1"), ("durationMs", "42")]
+        );
+
+        let plain = SessionMigrationEvent { reason: "failover".to_string(), ..event };
+        assert_eq!(plain.reason_parts(), ("failover", vec![]));
+    }
 }
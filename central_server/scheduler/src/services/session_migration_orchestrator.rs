@@ -1,4 +1,5 @@
 //! Scheduler-side session migration orchestrator — HTTP evacuate/import, binding after import only.
+//! Node endpoints: `NODE_MIGRATION_BASE_URL_<ID>` first, then the authenticated node's advertised `migration_endpoint`, then `NODE_MIGRATION_BASE_URL_DEFAULT`.
 
 use anyhow::{anyhow, Result};
 use reqwest::Client;
@@ -9,6 +10,7 @@ use std::time::{Duration, Instant};
 use tracing::{info, warn};
 
 use super::session_affinity::{SessionAffinityService, SessionMigrationEvent};
+use crate::redis_runtime::RedisRuntime;
 
 const MIGRATION_BACKOFF_MS: [u64; 3] = [2000, 5000, 15000];
 const MIGRATION_MAX_ATTEMPTS: usize = 3;
@@ -45,20 +47,29 @@ pub struct SessionMigrationOrchestratorResult {
 pub struct SessionMigrationOrchestrator {
     http: Client,
     affinity: Arc<SessionAffinityService>,
+    redis: Arc<RedisRuntime>,
 }
 
 impl SessionMigrationOrchestrator {
-    pub fn new(affinity: Arc<SessionAffinityService>) -> Self {
+    pub fn new(affinity: Arc<SessionAffinityService>, redis: Arc<RedisRuntime>) -> Self {
         Self {
             http: Client::builder()
                 .timeout(Duration::from_secs(15))
                 .build()
                 .expect("reqwest client"),
             affinity,
+            redis,
         }
     }
 
-    fn node_base_url(node_id: &str) -> Result<String> {
+    pub fn affinity(&self) -> &SessionAffinityService {
+        &self.affinity
+    }
+
+    /// 节点迁移地址：优先取调度器环境变量 NODE_MIGRATION_BASE_URL_<ID>（运维显式配置），
+    /// 其次是认证节点注册时声明的 migration_endpoint（节点 Hash 的 migration_base_url，注册时已校验），
+    /// 最后是 NODE_MIGRATION_BASE_URL_DEFAULT
+    async fn node_base_url(&self, node_id: &str) -> Result<String> {
         let key = format!(
             "NODE_MIGRATION_BASE_URL_{}",
             node_id.to_uppercase().replace('-', "_")
@@ -68,13 +79,24 @@ impl SessionMigrationOrchestrator {
                 return Ok(url.trim().trim_end_matches('/').to_string());
             }
         }
+
+        let mut cmd = redis::cmd("HGET");
+        cmd.arg(format!("lingua:v1:node:{node_id}")).arg("migration_base_url");
+        match self.redis.redis_query::<Option<String>>(cmd).await {
+            Ok(Some(url)) if !url.trim().is_empty() => {
+                return Ok(url.trim().trim_end_matches('/').to_string());
+            }
+            Ok(_) => {}
+            Err(e) => warn!(node_id = %node_id, error = %e, "Failed to read advertised migration endpoint"),
+        }
+
         if let Ok(default) = std::env::var("NODE_MIGRATION_BASE_URL_DEFAULT") {
             if !default.trim().is_empty() {
                 return Ok(default.trim().trim_end_matches('/').to_string());
             }
         }
         Err(anyhow!(
-            "missing node migration base URL for {node_id}; set {key} or NODE_MIGRATION_BASE_URL_DEFAULT"
+            "missing node migration base URL for {node_id}; neither {key} is set nor did the node advertise an accepted migration_endpoint, and NODE_MIGRATION_BASE_URL_DEFAULT is not set"
         ))
     }
 
@@ -122,8 +144,8 @@ impl SessionMigrationOrchestrator {
         reason: &str,
         started_at_ms: i64,
     ) -> Result<SessionMigrationOrchestratorResult> {
-        let source_base = Self::node_base_url(from_node_id)?;
-        let target_base = Self::node_base_url(to_node_id)?;
+        let source_base = self.node_base_url(from_node_id).await?;
+        let target_base = self.node_base_url(to_node_id).await?;
 
         self.record_event(
             session_id,
@@ -0,0 +1,256 @@
+// 会话恢复（WebSocket 断线重连）
+//
+// session_init_ack 下发 resume_token。连接异常断开（未收到 session_close）时会话不立即销毁，
+// 进入宽限期：Session/Actor/结果队列保留在原实例，快照与最近下发的结果写入 Redis：
+//   {prefix}:session_resume:{session:<id>}          (HASH：token、session 快照、断开时间)
+//   {prefix}:session_resume:{session:<id>}:replay   (LIST：待重放结果，每条一行 JSON)
+//
+// 客户端在宽限期内发送 session_resume（session_id + token + 最后确认的 utterance_index）：
+// - 原实例：直接把新连接挂回已有会话；
+// - 其他实例：按快照重建会话并接管 session owner，原实例宽限期结束时只做本地清理。
+// token 校验与删除在同一 Lua 脚本中完成，同一 token 只能恢复一次；恢复成功后轮换新 token。
+
+use crate::core::config::SessionResumeConfig;
+use crate::core::session::Session;
+use crate::messages::SessionMessage;
+use crate::redis_runtime::RedisHandle;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+#[derive(Clone)]
+pub struct SessionResumeService {
+    redis: Arc<RedisHandle>,
+    key_prefix: String,
+    config: SessionResumeConfig,
+    /// 本实例在线会话的 resume token：session_id -> token
+    tokens: Arc<RwLock<HashMap<String, String>>>,
+    /// 本实例上已断开、处于宽限期的会话
+    detached: Arc<RwLock<HashSet<String>>>,
+}
+
+impl SessionResumeService {
+    pub fn new(redis: Arc<RedisHandle>, key_prefix: String, config: SessionResumeConfig) -> Self {
+        Self {
+            redis,
+            key_prefix,
+            config,
+            tokens: Arc::new(RwLock::new(HashMap::new())),
+            detached: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+
+    fn record_key(&self, session_id: &str) -> String {
+        // hash tag: {session:<id>}，record 与 replay 落在同一 slot（Lua 脚本需要）
+        format!("{}:session_resume:{{session:{}}}", self.key_prefix, session_id)
+    }
+
+    fn replay_key(&self, session_id: &str) -> String {
+        format!("{}:session_resume:{{session:{}}}:replay", self.key_prefix, session_id)
+    }
+
+    pub fn grace_period(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.config.grace_period_seconds.max(1))
+    }
+
+    /// 为在线会话签发（或轮换）resume token
+    pub async fn issue_token(&self, session_id: &str) -> String {
+        let token = format!("rt_{}", uuid::Uuid::new_v4().simple());
+        self.tokens
+            .write()
+            .await
+            .insert(session_id.to_string(), token.clone());
+        token
+    }
+
+    /// 原连接尚未被感知断开（半开连接）时，凭本实例持有的 token 直接接管
+    pub async fn take_live_token(&self, session_id: &str, token: &str) -> bool {
+        let mut tokens = self.tokens.write().await;
+        if tokens.get(session_id).map(String::as_str) == Some(token) {
+            tokens.remove(session_id);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// 连接断开：写入快照与已下发结果，会话进入宽限期。
+    /// 会话没有 token（未签发或已关闭）时返回 false，调用方应直接清理。
+    pub async fn detach(&self, session: &Session, delivered: &[SessionMessage]) -> redis::RedisResult<bool> {
+        let Some(token) = self.tokens.write().await.remove(&session.session_id) else {
+            return Ok(false);
+        };
+        let ttl = self.config.grace_period_seconds.max(1);
+        let record_key = self.record_key(&session.session_id);
+        let replay_key = self.replay_key(&session.session_id);
+
+        let mut cmd = redis::cmd("HSET");
+        cmd.arg(&record_key)
+            .arg("token")
+            .arg(&token)
+            .arg("session")
+            .arg(serde_json::to_string(session).unwrap_or_default())
+            .arg("detached_at_ms")
+            .arg(chrono::Utc::now().timestamp_millis());
+        let _: i64 = self.redis.query(cmd).await?;
+        let mut cmd = redis::cmd("EXPIRE");
+        cmd.arg(&record_key).arg(ttl);
+        let _: i64 = self.redis.query(cmd).await?;
+
+        self.redis.del(&replay_key).await?;
+        for msg in delivered {
+            self.buffer(&session.session_id, msg).await?;
+        }
+
+        self.detached.write().await.insert(session.session_id.clone());
+        Ok(true)
+    }
+
+    /// 会话是否在本实例处于宽限期
+    pub async fn is_detached(&self, session_id: &str) -> bool {
+        self.detached.read().await.contains(session_id)
+    }
+
+    /// 本实例处于宽限期的会话（用于续约 session owner）
+    pub async fn detached_session_ids(&self) -> Vec<String> {
+        self.detached.read().await.iter().cloned().collect()
+    }
+
+    /// 宽限期内下发给该会话的结果先写入重放列表
+    pub async fn buffer(&self, session_id: &str, msg: &SessionMessage) -> redis::RedisResult<()> {
+        let script = r#"
+redis.call('RPUSH', KEYS[1], ARGV[1])
+redis.call('LTRIM', KEYS[1], -tonumber(ARGV[3]), -1)
+redis.call('EXPIRE', KEYS[1], ARGV[2])
+return 1
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(1)
+            .arg(self.replay_key(session_id))
+            .arg(serde_json::to_string(msg).unwrap_or_default())
+            .arg(self.config.grace_period_seconds.max(1))
+            .arg(self.config.replay_buffer_size.max(1));
+        let _: i64 = self.redis.query(cmd).await?;
+        Ok(())
+    }
+
+    /// 校验并消费 token，返回断开时的会话快照；token 不匹配或已过期返回 None
+    pub async fn claim(&self, session_id: &str, token: &str) -> redis::RedisResult<Option<Session>> {
+        let script = r#"
+if redis.call('HGET', KEYS[1], 'token') ~= ARGV[1] then
+  return false
+end
+local s = redis.call('HGET', KEYS[1], 'session')
+redis.call('DEL', KEYS[1])
+return s
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script).arg(1).arg(self.record_key(session_id)).arg(token);
+        let raw: Option<String> = self.redis.query(cmd).await?;
+        Ok(raw.and_then(|s| serde_json::from_str(&s).ok()))
+    }
+
+    /// 取出重放列表中 last_acked_index 之后的结果（按 utterance_index 排序）
+    ///
+    /// 读取与删除在同一脚本中完成，期间写入的结果不会丢失。
+    pub async fn take_replay(
+        &self,
+        session_id: &str,
+        last_acked_index: Option<u64>,
+    ) -> redis::RedisResult<Vec<SessionMessage>> {
+        let script = r#"
+local items = redis.call('LRANGE', KEYS[1], 0, -1)
+redis.call('DEL', KEYS[1])
+return items
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script).arg(1).arg(self.replay_key(session_id));
+        let raw: Vec<String> = self.redis.query(cmd).await?;
+        let messages = raw
+            .iter()
+            .filter_map(|line| serde_json::from_str::<SessionMessage>(line).ok())
+            .collect();
+        Ok(select_replay(messages, last_acked_index))
+    }
+
+    /// 宽限期结束或已恢复：移出本实例的 detached 集合，返回此前是否仍处于宽限期
+    pub async fn end_detach(&self, session_id: &str) -> bool {
+        self.detached.write().await.remove(session_id)
+    }
+
+    /// 会话正常结束：作废 token 与 Redis 中的恢复记录
+    pub async fn discard(&self, session_id: &str) {
+        self.tokens.write().await.remove(session_id);
+        self.detached.write().await.remove(session_id);
+        let _ = self.redis.del(&self.record_key(session_id)).await;
+        let _ = self.redis.del(&self.replay_key(session_id)).await;
+    }
+}
+
+/// 结果消息的 utterance_index（仅 TranslationResult / MissingResult 参与重放）
+pub fn replay_utterance_index(msg: &SessionMessage) -> Option<u64> {
+    match msg {
+        SessionMessage::TranslationResult { utterance_index, .. }
+        | SessionMessage::MissingResult { utterance_index, .. } => Some(*utterance_index),
+        _ => None,
+    }
+}
+
+/// 过滤出 last_acked_index 之后的结果；同一 index 保留最后一条
+pub fn select_replay(messages: Vec<SessionMessage>, last_acked_index: Option<u64>) -> Vec<SessionMessage> {
+    let mut by_index = BTreeMap::new();
+    for msg in messages {
+        let Some(index) = replay_utterance_index(&msg) else {
+            continue;
+        };
+        if last_acked_index.is_some_and(|acked| index <= acked) {
+            continue;
+        }
+        by_index.insert(index, msg);
+    }
+    by_index.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn missing(index: u64, reason: &str) -> SessionMessage {
+        SessionMessage::MissingResult {
+            session_id: "s-1".to_string(),
+            utterance_index: index,
+            reason: reason.to_string(),
+            created_at_ms: 0,
+            trace_id: None,
+        }
+    }
+
+    fn reason_of(msg: &SessionMessage) -> &str {
+        match msg {
+            SessionMessage::MissingResult { reason, .. } => reason,
+            _ => "",
+        }
+    }
+
+    #[test]
+    fn replay_skips_acked_and_keeps_latest_per_index() {
+        let messages = vec![
+            missing(3, "a"),
+            missing(1, "a"),
+            missing(2, "a"),
+            missing(3, "b"),
+            SessionMessage::SessionCloseAck { session_id: "s-1".to_string() },
+        ];
+        let replay = select_replay(messages, Some(1));
+        let indices: Vec<_> = replay.iter().filter_map(replay_utterance_index).collect();
+        assert_eq!(indices, vec![2, 3]);
+        assert_eq!(reason_of(&replay[1]), "b");
+    }
+
+    #[test]
+    fn replay_without_ack_returns_everything() {
+        let replay = select_replay(vec![missing(0, "a"), missing(1, "a")], None);
+        assert_eq!(replay.len(), 2);
+    }
+}
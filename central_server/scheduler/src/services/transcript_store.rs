@@ -0,0 +1,295 @@
+// 会话转写记录（可选持久化）
+//
+// 按租户配置或 session_init.persist_transcript 开启。开启后每条按序下发的 TranslationResult
+// 追加到 Redis，会话结束后仍可按 session_id 查询，保留期满自动过期，也可主动删除：
+//   {prefix}:transcripts:{session:<id>}:meta     (HASH：租户、语言、创建时间)
+//   {prefix}:transcripts:{session:<id>}:entries  (LIST：每条结果一行 JSON)
+//
+// 追加时 meta 不存在（未开启或已删除）则直接忽略，因此处理结果的实例无需持有会话本身。
+
+use crate::core::config::TranscriptConfig;
+use crate::messages::common::{NetworkTimings, ServiceTimings};
+use crate::messages::SessionMessage;
+use crate::redis_runtime::RedisHandle;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// 单条转写记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptEntry {
+    pub utterance_index: u64,
+    pub job_id: String,
+    pub src_lang: Option<String>,
+    pub tgt_lang: Option<String>,
+    pub text_asr: String,
+    pub text_translated: String,
+    pub tts_format: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_index: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asr_quality_level: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_codes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality_score: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rerun_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_timings: Option<ServiceTimings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_timings: Option<NetworkTimings>,
+    pub trace_id: String,
+    /// 写入时间（毫秒，UTC）
+    pub recorded_at_ms: i64,
+}
+
+impl TranscriptEntry {
+    /// 从下发给客户端的结果构造；非 TranslationResult 返回 None
+    pub fn from_result(result: &SessionMessage, src_lang: Option<&str>, tgt_lang: Option<&str>) -> Option<Self> {
+        let SessionMessage::TranslationResult {
+            utterance_index,
+            job_id,
+            text_asr,
+            text_translated,
+            tts_format,
+            trace_id,
+            group_id,
+            part_index,
+            service_timings,
+            network_timings,
+            asr_quality_level,
+            reason_codes,
+            quality_score,
+            rerun_count,
+            ..
+        } = result
+        else {
+            return None;
+        };
+        Some(Self {
+            utterance_index: *utterance_index,
+            job_id: job_id.clone(),
+            src_lang: src_lang.map(str::to_string),
+            tgt_lang: tgt_lang.map(str::to_string),
+            text_asr: text_asr.clone(),
+            text_translated: text_translated.clone(),
+            tts_format: tts_format.clone(),
+            group_id: group_id.clone(),
+            part_index: *part_index,
+            asr_quality_level: asr_quality_level.clone(),
+            reason_codes: reason_codes.clone(),
+            quality_score: *quality_score,
+            rerun_count: *rerun_count,
+            service_timings: service_timings.clone(),
+            network_timings: network_timings.clone(),
+            trace_id: trace_id.clone(),
+            recorded_at_ms: chrono::Utc::now().timestamp_millis(),
+        })
+    }
+}
+
+/// 会话转写元信息
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptMeta {
+    pub session_id: String,
+    pub tenant_id: Option<String>,
+    pub src_lang: String,
+    pub tgt_lang: String,
+    pub created_at_ms: i64,
+}
+
+#[derive(Clone)]
+pub struct TranscriptStore {
+    redis: Arc<RedisHandle>,
+    key_prefix: String,
+    config: TranscriptConfig,
+}
+
+impl TranscriptStore {
+    pub fn new(redis: Arc<RedisHandle>, key_prefix: String, config: TranscriptConfig) -> Self {
+        Self {
+            redis,
+            key_prefix,
+            config,
+        }
+    }
+
+    fn meta_key(&self, session_id: &str) -> String {
+        // hash tag: {session:<id>}，meta 与 entries 落在同一 slot（Lua 脚本需要）
+        format!("{}:transcripts:{{session:{}}}:meta", self.key_prefix, session_id)
+    }
+
+    fn entries_key(&self, session_id: &str) -> String {
+        format!("{}:transcripts:{{session:{}}}:entries", self.key_prefix, session_id)
+    }
+
+    fn retention_seconds(&self) -> u64 {
+        self.config.retention_days.max(1) * 86400
+    }
+
+    pub fn should_persist(&self, tenant_id: Option<&str>, requested: Option<bool>) -> bool {
+        should_persist(&self.config, tenant_id, requested)
+    }
+
+    /// 会话开启持久化：写入 meta（之后的结果才会被追加）
+    pub async fn open(
+        &self,
+        session_id: &str,
+        tenant_id: Option<&str>,
+        src_lang: &str,
+        tgt_lang: &str,
+    ) -> redis::RedisResult<()> {
+        let mut cmd = redis::cmd("HSET");
+        cmd.arg(self.meta_key(session_id))
+            .arg("tenant_id")
+            .arg(tenant_id.unwrap_or(""))
+            .arg("src_lang")
+            .arg(src_lang)
+            .arg("tgt_lang")
+            .arg(tgt_lang)
+            .arg("created_at_ms")
+            .arg(chrono::Utc::now().timestamp_millis());
+        let _: i64 = self.redis.query(cmd).await?;
+
+        let mut cmd = redis::cmd("EXPIRE");
+        cmd.arg(self.meta_key(session_id)).arg(self.retention_seconds());
+        let _: i64 = self.redis.query(cmd).await?;
+        Ok(())
+    }
+
+    /// 追加一条记录；返回是否写入（会话未开启持久化或已删除时为 false）
+    pub async fn append(&self, session_id: &str, entry: &TranscriptEntry) -> redis::RedisResult<bool> {
+        let script = r#"
+if redis.call('EXISTS', KEYS[1]) == 0 then
+  return 0
+end
+redis.call('RPUSH', KEYS[2], ARGV[1])
+local max = tonumber(ARGV[3])
+if max > 0 then
+  redis.call('LTRIM', KEYS[2], -max, -1)
+end
+redis.call('EXPIRE', KEYS[1], ARGV[2])
+redis.call('EXPIRE', KEYS[2], ARGV[2])
+return 1
+"#;
+        let payload = serde_json::to_string(entry).unwrap_or_default();
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(2)
+            .arg(self.meta_key(session_id))
+            .arg(self.entries_key(session_id))
+            .arg(payload)
+            .arg(self.retention_seconds())
+            .arg(self.config.max_entries_per_session);
+        let written: i64 = self.redis.query(cmd).await?;
+        Ok(written == 1)
+    }
+
+    /// 读取会话转写；不存在（未开启、已过期或已删除）时返回 None
+    pub async fn fetch(
+        &self,
+        session_id: &str,
+    ) -> redis::RedisResult<Option<(TranscriptMeta, Vec<TranscriptEntry>)>> {
+        let h = self.redis.hgetall(&self.meta_key(session_id)).await?;
+        if h.is_empty() {
+            return Ok(None);
+        }
+        let field = |k: &str| h.get(k).cloned().unwrap_or_default();
+        let meta = TranscriptMeta {
+            session_id: session_id.to_string(),
+            tenant_id: h.get("tenant_id").filter(|t| !t.is_empty()).cloned(),
+            src_lang: field("src_lang"),
+            tgt_lang: field("tgt_lang"),
+            created_at_ms: field("created_at_ms").parse().unwrap_or(0),
+        };
+
+        let mut cmd = redis::cmd("LRANGE");
+        cmd.arg(self.entries_key(session_id)).arg(0).arg(-1);
+        let raw: Vec<String> = self.redis.query(cmd).await?;
+        let entries = raw
+            .iter()
+            .filter_map(|line| serde_json::from_str::<TranscriptEntry>(line).ok())
+            .collect();
+        Ok(Some((meta, entries)))
+    }
+
+    /// 删除会话转写；会话仍在进行时后续结果也不再写入
+    pub async fn delete(&self, session_id: &str) -> redis::RedisResult<bool> {
+        let removed = self.redis.del(&self.meta_key(session_id)).await?;
+        self.redis.del(&self.entries_key(session_id)).await?;
+        Ok(removed > 0)
+    }
+}
+
+/// 该会话是否持久化：租户配置为 false 时强制关闭；否则以 session_init 为准，未指定时用租户/全局默认
+fn should_persist(config: &TranscriptConfig, tenant_id: Option<&str>, requested: Option<bool>) -> bool {
+    let tenant_setting = tenant_id.and_then(|t| config.tenants.get(t).copied());
+    if tenant_setting == Some(false) {
+        return false;
+    }
+    requested.or(tenant_setting).unwrap_or(config.default_enabled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translation_result() -> SessionMessage {
+        SessionMessage::TranslationResult {
+            session_id: "s1".to_string(),
+            utterance_index: 3,
+            job_id: "job-1".to_string(),
+            text_asr: "你好".to_string(),
+            text_translated: "hello".to_string(),
+            tts_audio: "AAAA".to_string(),
+            tts_format: "opus".to_string(),
+            extra: None,
+            trace_id: "trace-1".to_string(),
+            group_id: None,
+            part_index: None,
+            service_timings: None,
+            network_timings: None,
+            scheduler_sent_at_ms: None,
+            asr_quality_level: Some("good".to_string()),
+            reason_codes: None,
+            quality_score: Some(0.9),
+            rerun_count: None,
+            segments_meta: None,
+        }
+    }
+
+    #[test]
+    fn entry_keeps_text_and_quality_but_not_audio() {
+        let entry = TranscriptEntry::from_result(&translation_result(), Some("zh"), Some("en")).unwrap();
+        assert_eq!(entry.utterance_index, 3);
+        assert_eq!(entry.src_lang.as_deref(), Some("zh"));
+        assert_eq!(entry.asr_quality_level.as_deref(), Some("good"));
+
+        let json = serde_json::to_value(&entry).unwrap();
+        assert!(json.get("tts_audio").is_none());
+        assert!(json.get("group_id").is_none());
+        let back: TranscriptEntry = serde_json::from_value(json).unwrap();
+        assert_eq!(back.text_translated, "hello");
+
+        let missing = SessionMessage::ServerHeartbeat {
+            session_id: "s1".to_string(),
+            timestamp: 0,
+        };
+        assert!(TranscriptEntry::from_result(&missing, None, None).is_none());
+    }
+
+    #[test]
+    fn tenant_opt_out_overrides_session_flag() {
+        let mut config = TranscriptConfig::default();
+        config.tenants.insert("acme".to_string(), true);
+        config.tenants.insert("private".to_string(), false);
+
+        assert!(!should_persist(&config, None, None));
+        assert!(should_persist(&config, None, Some(true)));
+        assert!(should_persist(&config, Some("acme"), None));
+        assert!(!should_persist(&config, Some("acme"), Some(false)));
+        assert!(!should_persist(&config, Some("private"), Some(true)));
+    }
+}
@@ -0,0 +1,407 @@
+// 按租户的用量计量（计费用）
+//
+// JobResult 成功返回时累加：输入音频秒数、ASR 字符数、NMT 字符数、TTS 输出秒数、处理耗时。
+// 热路径只写内存；后台任务定期把增量 HINCRBYFLOAT 到 Redis 的小时汇总：
+//   {prefix}:usage:{tenant:<id>}:<YYYYMMDDHH>  (HASH)
+//   {prefix}:usage:tenants                     (SET，导出全部租户时使用)
+
+use crate::redis_runtime::RedisHandle;
+use chrono::{DateTime, Duration, TimeZone, Timelike, Utc};
+use lingua_audio_estimate::{estimate_audio_seconds, WAV_HEADER_BYTES};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+/// 单个租户在一个时间桶内的用量
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageRollup {
+    pub tenant_id: String,
+    /// 时间桶起点（UTC，整点或 0 点）
+    pub period_start: DateTime<Utc>,
+    pub jobs: u64,
+    pub audio_seconds_in: f64,
+    pub asr_chars: u64,
+    pub nmt_chars: u64,
+    pub tts_seconds_out: f64,
+    /// 各服务耗时之和（ServiceTimings.total_ms）
+    pub processing_ms: u64,
+}
+
+impl UsageRollup {
+    fn empty(tenant_id: &str, period_start: DateTime<Utc>) -> Self {
+        Self {
+            tenant_id: tenant_id.to_string(),
+            period_start,
+            ..Default::default()
+        }
+    }
+
+    fn add(&mut self, other: &UsageRollup) {
+        self.jobs += other.jobs;
+        self.audio_seconds_in += other.audio_seconds_in;
+        self.asr_chars += other.asr_chars;
+        self.nmt_chars += other.nmt_chars;
+        self.tts_seconds_out += other.tts_seconds_out;
+        self.processing_ms += other.processing_ms;
+    }
+
+    fn from_hash(tenant_id: &str, period_start: DateTime<Utc>, h: &HashMap<String, String>) -> Self {
+        let f = |k: &str| h.get(k).and_then(|v| v.parse::<f64>().ok()).unwrap_or(0.0);
+        Self {
+            tenant_id: tenant_id.to_string(),
+            period_start,
+            jobs: f("jobs") as u64,
+            audio_seconds_in: f("audio_seconds_in"),
+            asr_chars: f("asr_chars") as u64,
+            nmt_chars: f("nmt_chars") as u64,
+            tts_seconds_out: f("tts_seconds_out"),
+            processing_ms: f("processing_ms") as u64,
+        }
+    }
+
+    /// CSV 表头（与 `to_csv_row` 字段顺序一致）
+    pub const CSV_HEADER: &'static str =
+        "tenant_id,period_start,jobs,audio_seconds_in,asr_chars,nmt_chars,tts_seconds_out,processing_ms";
+
+    pub fn to_csv_row(&self) -> String {
+        format!(
+            "{},{},{},{:.3},{},{},{:.3},{}",
+            csv_escape(&self.tenant_id),
+            self.period_start.to_rfc3339(),
+            self.jobs,
+            self.audio_seconds_in,
+            self.asr_chars,
+            self.nmt_chars,
+            self.tts_seconds_out,
+            self.processing_ms,
+        )
+    }
+}
+
+/// 汇总粒度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageGranularity {
+    Hour,
+    Day,
+}
+
+impl UsageGranularity {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "hour" => Some(Self::Hour),
+            "day" => Some(Self::Day),
+            _ => None,
+        }
+    }
+
+    fn bucket_start(&self, t: DateTime<Utc>) -> DateTime<Utc> {
+        let hour = truncate_to_hour(t);
+        match self {
+            Self::Hour => hour,
+            Self::Day => hour - Duration::hours(hour.hour() as i64),
+        }
+    }
+}
+
+/// 一次查询最多覆盖的小时数（约 93 天）
+pub const MAX_QUERY_HOURS: i64 = 24 * 93;
+
+/// 尚未落盘的增量：(tenant_id, 小时起点) -> 用量
+type PendingRollups = HashMap<(String, DateTime<Utc>), UsageRollup>;
+
+#[derive(Clone)]
+pub struct UsageMeter {
+    redis: Arc<RedisHandle>,
+    key_prefix: String,
+    retention_seconds: u64,
+    pending: Arc<Mutex<PendingRollups>>,
+}
+
+impl UsageMeter {
+    pub fn new(redis: Arc<RedisHandle>, key_prefix: String, retention_days: u64) -> Self {
+        Self {
+            redis,
+            key_prefix,
+            retention_seconds: retention_days.max(1) * 24 * 3600,
+            pending: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn hour_key(&self, tenant_id: &str, hour: DateTime<Utc>) -> String {
+        // hash tag: {tenant:<id>}，同一租户的各小时 key 落在同一 slot
+        format!(
+            "{}:usage:{{tenant:{}}}:{}",
+            self.key_prefix,
+            tenant_id,
+            hour.format("%Y%m%d%H")
+        )
+    }
+
+    fn tenants_key(&self) -> String {
+        format!("{}:usage:tenants", self.key_prefix)
+    }
+
+    /// 记录一次用量（只写内存）
+    pub fn record(&self, tenant_id: &str, at: DateTime<Utc>, delta: &UsageRollup) {
+        let hour = truncate_to_hour(at);
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending
+            .entry((tenant_id.to_string(), hour))
+            .or_insert_with(|| UsageRollup::empty(tenant_id, hour))
+            .add(delta);
+    }
+
+    /// 把内存增量写入 Redis；失败的增量放回，下次重试
+    pub async fn flush(&self) {
+        let drained: Vec<UsageRollup> = {
+            let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            pending.drain().map(|(_, v)| v).collect()
+        };
+        for rollup in drained {
+            if let Err(e) = self.write_rollup(&rollup).await {
+                warn!(tenant_id = %rollup.tenant_id, error = %e, "用量汇总写入 Redis 失败，稍后重试");
+                self.record(&rollup.tenant_id, rollup.period_start, &rollup);
+            }
+        }
+    }
+
+    /// 先写幂等的租户索引再累加：累加成功后不会再因索引失败放回增量而重复计费
+    async fn write_rollup(&self, r: &UsageRollup) -> redis::RedisResult<()> {
+        let mut cmd = redis::cmd("SADD");
+        cmd.arg(self.tenants_key()).arg(&r.tenant_id);
+        let _: i64 = self.redis.query(cmd).await?;
+
+        let key = self.hour_key(&r.tenant_id, r.period_start);
+        let script = r#"
+redis.call('HINCRBY', KEYS[1], 'jobs', ARGV[1])
+redis.call('HINCRBYFLOAT', KEYS[1], 'audio_seconds_in', ARGV[2])
+redis.call('HINCRBY', KEYS[1], 'asr_chars', ARGV[3])
+redis.call('HINCRBY', KEYS[1], 'nmt_chars', ARGV[4])
+redis.call('HINCRBYFLOAT', KEYS[1], 'tts_seconds_out', ARGV[5])
+redis.call('HINCRBY', KEYS[1], 'processing_ms', ARGV[6])
+redis.call('EXPIRE', KEYS[1], ARGV[7])
+return 1
+"#;
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script)
+            .arg(1)
+            .arg(&key)
+            .arg(r.jobs)
+            .arg(r.audio_seconds_in)
+            .arg(r.asr_chars)
+            .arg(r.nmt_chars)
+            .arg(r.tts_seconds_out)
+            .arg(r.processing_ms)
+            .arg(self.retention_seconds);
+        let _: i64 = self.redis.query(cmd).await?;
+        Ok(())
+    }
+
+    /// 周期性落盘（进程退出前由 start_server 再落盘一次）
+    pub fn start_background_flush(&self, interval_seconds: u64) {
+        let meter = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_seconds.max(1)));
+            loop {
+                interval.tick().await;
+                meter.flush().await;
+                debug!("用量汇总已落盘");
+            }
+        });
+    }
+
+    /// 查询租户在 [from, to) 内的用量（含尚未落盘的增量），按粒度汇总，只返回非空时间桶
+    pub async fn query(
+        &self,
+        tenant_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        granularity: UsageGranularity,
+    ) -> redis::RedisResult<Vec<UsageRollup>> {
+        let mut buckets: BTreeMap<DateTime<Utc>, UsageRollup> = BTreeMap::new();
+        let pending: Vec<UsageRollup> = {
+            let pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            pending
+                .values()
+                .filter(|r| r.tenant_id == tenant_id)
+                .cloned()
+                .collect()
+        };
+
+        let mut hours = Vec::new();
+        let mut hour = truncate_to_hour(from);
+        while hour < to {
+            hours.push(hour);
+            hour += Duration::hours(1);
+        }
+        if hours.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // 同一租户的小时 key 共用 hash tag，一次 pipeline 读取
+        let mut pipe = redis::pipe();
+        for hour in &hours {
+            pipe.cmd("HGETALL").arg(self.hour_key(tenant_id, *hour));
+        }
+        let hashes: Vec<HashMap<String, String>> = self.redis.query_pipe(&pipe).await?;
+
+        for (hour, h) in hours.into_iter().zip(hashes) {
+            let mut rollup = UsageRollup::from_hash(tenant_id, hour, &h);
+            for p in pending.iter().filter(|p| p.period_start == hour) {
+                rollup.add(p);
+            }
+            if rollup.jobs > 0 {
+                let start = granularity.bucket_start(hour);
+                buckets
+                    .entry(start)
+                    .or_insert_with(|| UsageRollup::empty(tenant_id, start))
+                    .add(&rollup);
+            }
+        }
+        Ok(buckets.into_values().collect())
+    }
+
+    /// 有用量记录的全部租户
+    pub async fn list_tenants(&self) -> redis::RedisResult<Vec<String>> {
+        let mut tenants = self.redis.smembers_strings(&self.tenants_key()).await?;
+        {
+            let pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            tenants.extend(pending.keys().map(|(t, _)| t.clone()));
+        }
+        tenants.sort();
+        tenants.dedup();
+        Ok(tenants)
+    }
+}
+
+/// 一次成功的 JobResult 中与计费相关的字段
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JobUsageInput<'a> {
+    pub audio_base64: &'a str,
+    pub audio_format: &'a str,
+    pub sample_rate: u32,
+    pub text_asr: Option<&'a str>,
+    pub text_translated: Option<&'a str>,
+    pub tts_audio: Option<&'a str>,
+    pub tts_format: Option<&'a str>,
+    pub processing_ms: Option<u64>,
+}
+
+/// 根据一次成功的 JobResult 计算用量增量
+///
+/// 音频时长按格式估算（pcm16 精确；wav 读取头部 byte_rate；opus 等压缩格式与网关限流共用同一码率），
+/// base64 数据只按长度换算字节数，不解码。
+pub fn usage_delta_for_job_result(input: &JobUsageInput<'_>) -> UsageRollup {
+    let tts_seconds_out = input
+        .tts_audio
+        .filter(|a| !a.is_empty())
+        .map(|a| audio_seconds_from_base64(a, input.tts_format.unwrap_or("pcm16"), 16000))
+        .unwrap_or(0.0);
+    UsageRollup {
+        jobs: 1,
+        audio_seconds_in: audio_seconds_from_base64(
+            input.audio_base64,
+            input.audio_format,
+            input.sample_rate,
+        ),
+        asr_chars: input.text_asr.map(|t| t.trim().chars().count() as u64).unwrap_or(0),
+        nmt_chars: input.text_translated.map(|t| t.trim().chars().count() as u64).unwrap_or(0),
+        tts_seconds_out,
+        processing_ms: input.processing_ms.unwrap_or(0),
+        ..Default::default()
+    }
+}
+
+fn audio_seconds_from_base64(data: &str, format: &str, sample_rate: u32) -> f64 {
+    let padding = data.bytes().rev().take_while(|b| *b == b'=').count();
+    let byte_len = (data.len() / 4 * 3).saturating_sub(padding);
+    match format {
+        "wav" => {
+            // 只解码头部（前 48 个 base64 字符 = 36 字节）读取 byte_rate（偏移 28..32）
+            use base64::Engine as _;
+            let head = data.get(..48).unwrap_or(data);
+            let byte_rate = base64::engine::general_purpose::STANDARD
+                .decode(head)
+                .ok()
+                .filter(|h| h.len() >= 32)
+                .map(|h| u32::from_le_bytes([h[28], h[29], h[30], h[31]]))
+                .filter(|r| *r > 0)
+                .unwrap_or(sample_rate.max(1) * 2);
+            byte_len.saturating_sub(WAV_HEADER_BYTES) as f64 / byte_rate as f64
+        }
+        _ => estimate_audio_seconds(format, byte_len, sample_rate),
+    }
+}
+
+fn truncate_to_hour(t: DateTime<Utc>) -> DateTime<Utc> {
+    let ts = t.timestamp();
+    Utc.timestamp_opt(ts - ts.rem_euclid(3600), 0).single().unwrap_or(t)
+}
+
+fn csv_escape(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine as _;
+
+    #[test]
+    fn delta_counts_chars_and_audio_seconds() {
+        let pcm = base64::engine::general_purpose::STANDARD.encode(vec![0u8; 32000]);
+        let delta = usage_delta_for_job_result(&JobUsageInput {
+            audio_base64: &pcm,
+            audio_format: "pcm16",
+            sample_rate: 16000,
+            text_asr: Some(" 你好世界 "),
+            text_translated: Some("hello world"),
+            tts_audio: Some(&pcm),
+            tts_format: Some("pcm16"),
+            processing_ms: Some(850),
+        });
+        assert_eq!(delta.jobs, 1);
+        assert_eq!(delta.audio_seconds_in, 1.0);
+        assert_eq!(delta.asr_chars, 4);
+        assert_eq!(delta.nmt_chars, 11);
+        assert_eq!(delta.tts_seconds_out, 1.0);
+        assert_eq!(delta.processing_ms, 850);
+    }
+
+    #[test]
+    fn wav_duration_uses_header_byte_rate() {
+        // 24kHz 16bit 单声道：byte_rate = 48000
+        let mut wav = vec![0u8; 44 + 48000];
+        wav[28..32].copy_from_slice(&48000u32.to_le_bytes());
+        let b64 = base64::engine::general_purpose::STANDARD.encode(&wav);
+        assert!((audio_seconds_from_base64(&b64, "wav", 16000) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn opus_duration_matches_gateway_estimate() {
+        let b64 = base64::engine::general_purpose::STANDARD.encode(vec![0u8; 4000]);
+        assert_eq!(audio_seconds_from_base64(&b64, "opus", 16000), 1.0);
+    }
+
+    #[test]
+    fn day_granularity_truncates_to_midnight() {
+        let t = Utc.with_ymd_and_hms(2026, 3, 5, 17, 42, 10).unwrap();
+        assert_eq!(truncate_to_hour(t), Utc.with_ymd_and_hms(2026, 3, 5, 17, 0, 0).unwrap());
+        assert_eq!(
+            UsageGranularity::Day.bucket_start(t),
+            Utc.with_ymd_and_hms(2026, 3, 5, 0, 0, 0).unwrap()
+        );
+        let row = UsageRollup {
+            tenant_id: "acme, inc".to_string(),
+            period_start: truncate_to_hour(t),
+            jobs: 2,
+            ..Default::default()
+        };
+        assert!(row.to_csv_row().starts_with("\"acme, inc\",2026-03-05T17:00:00+00:00,2,"));
+    }
+}
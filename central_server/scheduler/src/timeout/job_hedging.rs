@@ -0,0 +1,144 @@
+use crate::core::AppState;
+use crate::core::dispatcher::Job;
+use crate::messages::NodeMessage;
+use crate::services::HedgedDispatch;
+use tracing::{debug, info, warn};
+
+/// 对冲派发：已派发但超过池内时限仍未开始处理的任务，以同一 attempt 派发到另一节点
+///
+/// 由超时扫描在 job_timeout_seconds 之前调用；每个 attempt 只评估一次，
+/// 占用顺序：选节点 → 预算 → reserve 槽位 → Job Hash 原子占用 → 下发 JobAssign（失败时逐级回滚）
+pub(crate) async fn maybe_hedge_job(
+    state: &AppState,
+    hedging: &HedgedDispatch,
+    job: &Job,
+    current_node_id: &str,
+    dispatched_at_ms: i64,
+    now_ms: i64,
+    reserved_ttl_seconds: u64,
+) {
+    if job.hedge_node_id.is_some() || !hedging.enabled_for(job) {
+        return;
+    }
+    let pool_key = HedgedDispatch::pool_key(job);
+    let deadline_ms = hedging.deadline_ms(&pool_key);
+    if now_ms - dispatched_at_ms < deadline_ms as i64 {
+        return;
+    }
+    let Some(rt) = state.redis_runtime.as_ref() else { return };
+
+    // 已收到 job_started / asr_partial（FSM 进入 RUNNING）或已结束：无需对冲
+    match rt.job_fsm_state(&job.job_id).await.as_deref() {
+        Some("RUNNING") | Some("FINISHED") | Some("RELEASED") => return,
+        _ => {}
+    }
+    if !hedging.mark_attempted(&job.job_id, job.dispatch_attempt_id, now_ms) {
+        return;
+    }
+
+    let required = match state.dispatcher.required_types_for_job(job).await {
+        Ok(v) => v,
+        Err(e) => {
+            warn!(trace_id = %job.trace_id, job_id = %job.job_id, error = %e, "对冲派发：计算 required_services 失败");
+            return;
+        }
+    };
+    // 与任务创建相同的池选择：租户任务只在租户私有池内（按租户策略回退公共池）选对冲节点
+    let Some(pool_service) = state.pool_service.as_ref() else { return };
+    let (pool_src, pool_tgt) = pool_langs(job);
+    let selected = crate::websocket::job_creator::select_pool_node(
+        state,
+        pool_service,
+        job.tenant_id.as_deref(),
+        pool_src,
+        pool_tgt,
+        None,
+        &job.session_id,
+        &required,
+        Some(current_node_id),
+    )
+    .await;
+    let hedge_node_id = match selected {
+        Ok(node_id) if node_id != current_node_id => node_id,
+        _ => {
+            crate::metrics::prometheus_metrics::on_hedged_dispatch("no_candidate");
+            debug!(job_id = %job.job_id, pool_key = %pool_key, "对冲派发：没有其他可用节点");
+            return;
+        }
+    };
+
+    // 预算在下发前占用（避免并发检查同时通过），之后任一步回滚都要归还
+    if !hedging.try_acquire_budget(now_ms) {
+        crate::metrics::prometheus_metrics::on_hedged_dispatch("budget_exhausted");
+        debug!(job_id = %job.job_id, pool_key = %pool_key, "对冲派发：预算已用尽");
+        return;
+    }
+
+    let attempt_id = job.dispatch_attempt_id;
+    match rt.reserve_node_slot(&hedge_node_id, &job.job_id, attempt_id, reserved_ttl_seconds).await {
+        Ok(true) => {}
+        _ => {
+            hedging.release_budget(now_ms);
+            debug!(job_id = %job.job_id, node_id = %hedge_node_id, "对冲派发：预留槽位失败");
+            return;
+        }
+    }
+
+    if !state.dispatcher.claim_job_hedge(job, &hedge_node_id, now_ms).await {
+        rt.release_node_slot(&hedge_node_id, &job.job_id, attempt_id).await;
+        hedging.release_budget(now_ms);
+        return;
+    }
+
+    let sent = match crate::websocket::create_job_assign_message(state, job, None, None, None).await {
+        Some(msg) => crate::redis_runtime::send_node_message_routed(state, &hedge_node_id, msg).await,
+        None => false,
+    };
+    if !sent {
+        // 下发失败：撤销对冲占用，主节点继续处理
+        state.dispatcher.resolve_job_hedge(&job.job_id, attempt_id, current_node_id).await;
+        rt.release_node_slot(&hedge_node_id, &job.job_id, attempt_id).await;
+        hedging.release_budget(now_ms);
+        crate::metrics::prometheus_metrics::on_hedged_dispatch("send_failed");
+        warn!(trace_id = %job.trace_id, job_id = %job.job_id, node_id = %hedge_node_id, "对冲派发：JobAssign 下发失败");
+        return;
+    }
+
+    crate::metrics::prometheus_metrics::on_hedged_dispatch("started");
+    info!(
+        trace_id = %job.trace_id,
+        job_id = %job.job_id,
+        session_id = %job.session_id,
+        utterance_index = job.utterance_index,
+        primary_node_id = %current_node_id,
+        hedge_node_id = %hedge_node_id,
+        attempt_id = attempt_id,
+        deadline_ms = deadline_ms,
+        elapsed_ms = now_ms - dispatched_at_ms,
+        "对冲派发：主节点未开始处理，已向第二节点下发"
+    );
+}
+
+/// 查池用的语言对：与任务创建一致，src_lang = "auto" 且有 lang_a/lang_b 时用 (lang_a, lang_b)
+fn pool_langs(job: &Job) -> (&str, &str) {
+    match (job.src_lang.as_str(), job.lang_a.as_deref(), job.lang_b.as_deref()) {
+        ("auto", Some(a), Some(b)) => (a, b),
+        _ => (job.src_lang.as_str(), job.tgt_lang.as_str()),
+    }
+}
+
+/// 超时重派前取消对冲节点（主节点由调用方取消）
+pub(crate) async fn cancel_hedge_on_failover(state: &AppState, job: &Job, send_cancel: bool) {
+    let Some(ref hedge_node_id) = job.hedge_node_id else { return };
+    if send_cancel {
+        let cancel_msg = NodeMessage::JobCancel {
+            job_id: job.job_id.clone(),
+            trace_id: Some(job.trace_id.clone()),
+            reason: Some("job_timeout".to_string()),
+        };
+        let _ = crate::redis_runtime::send_node_message_routed(state, hedge_node_id, cancel_msg).await;
+    }
+    if let Some(rt) = state.redis_runtime.as_ref() {
+        rt.release_node_slot(hedge_node_id, &job.job_id, job.dispatch_attempt_id).await;
+    }
+}
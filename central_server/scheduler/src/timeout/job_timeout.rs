@@ -63,11 +63,33 @@ pub fn start_job_timeout_manager(
                 let Some(ref current_node_id) = job.assigned_node_id else { continue };
                 let dispatched_at_ms = job.dispatched_at_ms.unwrap_or_else(|| job.created_at.timestamp_millis());
                 if now_ms - dispatched_at_ms <= dispatched_timeout_ms {
+                    // 未超时：按池内时延判断是否对冲派发
+                    if let Some(hedging) = state.hedged_dispatch.as_ref() {
+                        super::job_hedging::maybe_hedge_job(
+                            &state,
+                            hedging,
+                            &job,
+                            current_node_id,
+                            dispatched_at_ms,
+                            now_ms,
+                            reserved_ttl_seconds,
+                        )
+                        .await;
+                    }
                     continue;
                 }
 
                 // 记录 ACK 超时指标
                 crate::metrics::prometheus_metrics::on_ack_timeout(&job.job_id);
+
+                // 计入节点熔断（任务涉及的服务均记失败）
+                if let Some(breaker) = state.node_circuit_breaker.as_ref() {
+                    if let Ok(services) = state.dispatcher.required_types_for_job(&job).await {
+                        if let Err(e) = breaker.record_job_timeout(current_node_id, &services).await {
+                            warn!(job_id = %job.job_id, node_id = %current_node_id, error = %e, "记录节点熔断超时失败");
+                        }
+                    }
+                }
                 
                 warn!(
                     trace_id = %job.trace_id,
@@ -91,6 +113,11 @@ pub fn start_job_timeout_manager(
                     };
                     let _ = crate::redis_runtime::send_node_message_routed(&state, current_node_id, cancel_msg).await;
                 }
+                // 对冲节点同样超时：取消并释放（重派脚本会清除对冲字段）
+                super::job_hedging::cancel_hedge_on_failover(&state, &job, policy.send_cancel).await;
+                if let Some(hedging) = state.hedged_dispatch.as_ref() {
+                    hedging.forget_job(&job.job_id);
+                }
 
                 // 释放旧节点 reserved（幂等）- 统一使用Phase2 Redis实现
                 if let Some(rt) = state.redis_runtime.as_ref() {
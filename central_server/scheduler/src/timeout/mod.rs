@@ -1,3 +1,4 @@
+pub mod job_hedging;
 pub mod job_timeout;
 
 pub use job_timeout::start_job_timeout_manager;
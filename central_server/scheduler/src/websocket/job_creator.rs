@@ -55,7 +55,7 @@ pub(crate) async fn create_translation_jobs(
     audio_data: Vec<u8>,
     audio_format: String,
     sample_rate: u32,
-    _paired_node_id: Option<String>,
+    paired_node_id: Option<String>,
     mode: Option<String>,
     lang_a: Option<String>,
     lang_b: Option<String>,
@@ -119,6 +119,7 @@ pub(crate) async fn create_translation_jobs(
                 tenant_id,
                 request_id.clone(),
                 None, // 单会话模式
+                paired_node_id.as_deref(),
                 first_chunk_client_timestamp_ms,
                 padding_ms,
                 is_manual_cut,
@@ -188,6 +189,7 @@ pub(crate) async fn create_translation_jobs(
                 tenant_id.clone(),
                 request_id.clone(),
                 Some(target_session_ids.clone()),
+                paired_node_id.as_deref(),
                 first_chunk_client_timestamp_ms,
                 padding_ms,
                 is_manual_cut,
@@ -260,6 +262,7 @@ pub(crate) async fn create_translation_jobs(
             tenant_id,
             request_id,
             None,
+            paired_node_id.as_deref(),
             first_chunk_client_timestamp_ms,
             padding_ms,
             is_manual_cut,
@@ -291,6 +294,51 @@ fn should_bind_job_to_node(is_manual_cut: bool, is_max_duration_triggered: bool)
     is_max_duration_triggered && !is_manual_cut
 }
 
+/// 租户任务优先从租户私有池选择节点，私有池无可用节点时按租户策略回退公共池。
+/// `required_types` 用于节点熔断准入（熔断中的节点被跳过）；`exclude_node_id` 不会被选中（对冲派发）
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn select_pool_node(
+    state: &AppState,
+    pool_service: &crate::pool::PoolService,
+    tenant_id: Option<&str>,
+    src_lang: &str,
+    tgt_lang: &str,
+    job_id: Option<&str>,
+    session_id: &str,
+    required_types: &[crate::messages::ServiceType],
+    exclude_node_id: Option<&str>,
+) -> Result<String, anyhow::Error> {
+    let Some(tenant_id) = tenant_id.filter(|_| state.private_pools.enabled) else {
+        return pool_service
+            .select_node(src_lang, tgt_lang, job_id, Some(session_id), required_types, exclude_node_id)
+            .await;
+    };
+    match pool_service
+        .select_tenant_node(tenant_id, src_lang, tgt_lang, job_id, Some(session_id), required_types, exclude_node_id)
+        .await
+    {
+        Ok(node_id) => return Ok(node_id),
+        Err(e) if state.private_pools.allows_public_fallback(tenant_id) => {
+            info!(
+                session_id = %session_id,
+                tenant_id = %tenant_id,
+                reason = %e,
+                "【任务创建】租户私有池无可用节点，回退公共池"
+            );
+        }
+        Err(e) => {
+            return Err(anyhow::anyhow!(
+                "No private node available for tenant {} and public fallback is disabled: {}",
+                tenant_id,
+                e
+            ));
+        }
+    }
+    pool_service
+        .select_node(src_lang, tgt_lang, job_id, Some(session_id), required_types, exclude_node_id)
+        .await
+}
+
 /// 使用极简无锁调度服务创建任务
 /// turn_id: session 内 segment 标识（发往节点），不参与调度路由
 async fn create_job_with_minimal_scheduler(
@@ -316,6 +364,7 @@ async fn create_job_with_minimal_scheduler(
     tenant_id: Option<String>,
     request_id: String,
     target_session_ids: Option<Vec<String>>,
+    paired_node_id: Option<&str>,
     first_chunk_client_timestamp_ms: Option<i64>,
     padding_ms: Option<u64>,
     is_manual_cut: bool,
@@ -355,7 +404,39 @@ async fn create_job_with_minimal_scheduler(
 
     let pool_service = state.pool_service.as_ref()
         .ok_or_else(|| anyhow::anyhow!("PoolService not initialized"))?;
-    let node_id_str = pool_service.select_node(pool_src, pool_tgt, job_id_for_binding, Some(session_id)).await?;
+    // 配对会话：只派发给配对节点，不走公共池（私有节点 accept_public_jobs=false 同样可用）
+    let node_id_str = match paired_node_id {
+        Some(paired) => {
+            if !pool_service.is_node_dispatchable(paired).await? {
+                return Err(anyhow::anyhow!("Paired node {} is unavailable", paired));
+            }
+            info!(
+                session_id = %session_id,
+                utterance_index = utterance_index,
+                paired_node_id = %paired,
+                "【任务创建】配对会话，直接派发给配对节点"
+            );
+            paired.to_string()
+        }
+        None => {
+            let required_types =
+                state
+                    .dispatcher
+                    .get_required_types_for_features(&pipeline, features.as_ref(), &src_lang, &tgt_lang)?;
+            select_pool_node(
+                state,
+                pool_service,
+                tenant_id.as_deref(),
+                pool_src,
+                pool_tgt,
+                job_id_for_binding,
+                session_id,
+                &required_types,
+                None,
+            )
+            .await?
+        }
+    };
     
     let node_id = Some(node_id_str.clone());
 
@@ -428,6 +509,17 @@ async fn create_job_with_minimal_scheduler(
 
     // 注意：不再使用 request_binding，幂等性通过 JobIdempotencyManager 管理
 
+    // NMT 上下文：当前 Utterance Group 中与本 Job 源语言相同的 ASR 原文（不含译文；双向模式 src_lang = "auto" 不携带；
+    // 会议室模式按目标语言拆分 Job，不携带）
+    let context_text = if target_session_ids.is_none() {
+        state
+            .group_manager
+            .context_for_next_job(session_id, &src_lang, chrono::Utc::now().timestamp_millis() as u64)
+            .await
+    } else {
+        None
+    };
+
     let job = Job {
         job_id: job_id.clone(),
         request_id,
@@ -468,6 +560,9 @@ async fn create_job_with_minimal_scheduler(
         is_max_duration_triggered,
         turn_id: turn_id.map(String::from),
         expected_duration_ms: None, // 默认不设置预计时长
+        context_text,
+        hedge_node_id: None,
+        hedged_at_ms: None,
     };
 
     // 保存 Job 到 Redis（SSOT）
@@ -66,7 +66,7 @@ pub(crate) async fn create_job_assign_message(
     Some(NodeMessage::JobAssign {
         group_id,
         part_index,
-        context_text,
+        context_text: context_text.or_else(|| job.context_text.clone()),
         job_id: job.job_id.clone(),
         attempt_id: job.dispatch_attempt_id.max(1),
         session_id: job.session_id.clone(),
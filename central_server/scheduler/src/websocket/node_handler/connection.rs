@@ -1,4 +1,4 @@
-use super::message::handle_node_message;
+use super::message::{claimed_node_id, handle_node_message};
 use crate::core::AppState;
 use crate::messages::NodeMessage;
 use axum::extract::ws::{Message, WebSocket};
@@ -6,6 +6,18 @@ use futures_util::{SinkExt, StreamExt};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
+/// 启用节点认证时：认证（注册）完成前只接受 node_register / node_auth_response，
+/// 之后消息声明的 node_id 必须与本连接注册的 node_id 一致
+fn is_admitted(state: &AppState, node_id: Option<&str>, message: &NodeMessage) -> bool {
+    if state.node_auth.is_none() {
+        return true;
+    }
+    match message {
+        NodeMessage::NodeRegister { .. } | NodeMessage::NodeAuthResponse { .. } => true,
+        _ => node_id.is_some_and(|nid| claimed_node_id(message).is_none_or(|claimed| claimed == nid)),
+    }
+}
+
 // Node-side WebSocket handler
 pub async fn handle_node(socket: WebSocket, state: AppState) {
     info!("New node WebSocket connection");
@@ -35,6 +47,14 @@ pub async fn handle_node(socket: WebSocket, state: AppState) {
                 match serde_json::from_str::<NodeMessage>(&text) {
                     Ok(message) => {
                         info!("Successfully parsed node message, type: {:?}", std::mem::discriminant(&message));
+                        if !is_admitted(&state, node_id.as_deref(), &message) {
+                            warn!(
+                                node_id = ?node_id,
+                                claimed_node_id = ?claimed_node_id(&message),
+                                "Dropping node message from unauthenticated connection or with mismatched node_id"
+                            );
+                            continue;
+                        }
                         match handle_node_message(message, &state, &mut node_id, &tx).await {
                             Ok(()) => {}
                             Err(e) => {
@@ -61,8 +81,18 @@ pub async fn handle_node(socket: WebSocket, state: AppState) {
         }
     }
 
-    // Cleanup
-    if let Some(ref nid) = node_id {
+    // Cleanup（同一节点认证后的新连接已接管时跳过，避免把新连接一并下线）
+    let replaced = match node_id.as_deref() {
+        Some(nid) => state
+            .node_connections
+            .get(nid)
+            .await
+            .is_some_and(|current| !current.same_channel(&tx)),
+        None => false,
+    };
+    if replaced {
+        info!(node_id = ?node_id, "节点旧连接已被新连接接管，跳过下线清理");
+    } else if let Some(ref nid) = node_id {
         // 流程日志 1: 下线流程开始
         info!(
             step = "offline_start",
@@ -111,7 +141,7 @@ pub async fn handle_node(socket: WebSocket, state: AppState) {
         let _redis_runtime = state.redis_runtime.as_ref().map(|rt| rt.as_ref());
         
         // 流程日志 5: 注销 WebSocket 连接
-        state.node_connections.unregister(nid).await;
+        state.node_connections.unregister_if_current(nid, &tx).await;
         info!(
             step = "offline_connection_unregistered",
             node_id = %nid,
@@ -0,0 +1,80 @@
+use super::send_node_error;
+use crate::messages::{ErrorCode, NodeMessage};
+use crate::services::node_auth::{NodeAuthGrant, NodeSecretKind};
+use crate::services::NodeAuthService;
+use axum::extract::ws::Message;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// 启用节点认证时，node_register 先暂存并下发 challenge，应答校验通过后才真正注册
+pub(super) async fn handle_register_challenge(
+    auth: &NodeAuthService,
+    tx: &mpsc::UnboundedSender<Message>,
+    register: NodeMessage,
+) -> Result<(), anyhow::Error> {
+    let NodeMessage::NodeRegister { node_id: Some(nid), .. } = &register else {
+        send_node_error(tx, ErrorCode::NodeAuthFailed, "node_id is required when node authentication is enabled");
+        return Ok(());
+    };
+    let nid = nid.clone();
+    match auth.is_revoked(&nid).await {
+        Ok(false) => {}
+        Ok(true) => {
+            warn!(node_id = %nid, "Revoked node attempted to register");
+            send_node_error(tx, ErrorCode::NodeRevoked, "Node has been revoked");
+            return Ok(());
+        }
+        // 无法确认是否已吊销时拒绝注册，节点稍后重连再试
+        Err(e) => {
+            warn!(node_id = %nid, error = %e, "Failed to read node revocation list, rejecting register");
+            send_node_error(tx, ErrorCode::NodeAuthFailed, "Node revocation list unavailable, retry later");
+            return Ok(());
+        }
+    }
+
+    let (nonce, expires_in_ms) = auth.challenge(&nid, tx, register).await;
+    let challenge = NodeMessage::NodeAuthChallenge {
+        node_id: nid.clone(),
+        nonce,
+        expires_in_ms,
+    };
+    tx.send(Message::Text(serde_json::to_string(&challenge)?))
+        .map_err(|e| anyhow::anyhow!("Failed to send node_auth_challenge: {}", e))?;
+    info!(node_id = %nid, "Node auth challenge issued");
+    Ok(())
+}
+
+/// 校验 challenge 应答；通过时返回暂存的 node_register
+pub(super) async fn verify_response(
+    auth: &NodeAuthService,
+    tx: &mpsc::UnboundedSender<Message>,
+    nid: &str,
+    signature: &str,
+    kind: NodeSecretKind,
+) -> Option<(NodeMessage, NodeAuthGrant)> {
+    match auth.verify(nid, tx, signature, kind).await {
+        Ok(verified) => {
+            info!(node_id = %nid, secret_kind = ?kind, "Node authenticated");
+            Some(verified)
+        }
+        Err(e) => {
+            warn!(node_id = %nid, error = %e, "Node authentication failed");
+            send_node_error(tx, ErrorCode::NodeAuthFailed, &format!("Node authentication failed: {}", e));
+            None
+        }
+    }
+}
+
+/// 消息声明的 node_id（连接认证后只接受与已注册 node_id 一致的消息）
+pub(in crate::websocket::node_handler) fn claimed_node_id(message: &NodeMessage) -> Option<&str> {
+    match message {
+        NodeMessage::NodeHeartbeat { node_id, .. }
+        | NodeMessage::JobResult { node_id, .. }
+        | NodeMessage::JobAck { node_id, .. }
+        | NodeMessage::JobStarted { node_id, .. }
+        | NodeMessage::AsrPartial { node_id, .. }
+        | NodeMessage::NodeError { node_id, .. }
+        | NodeMessage::PairingCodeRequest { node_id, .. } => Some(node_id),
+        _ => None,
+    }
+}
@@ -33,7 +33,7 @@ pub(super) async fn handle_job_ack(
             warn!(trace_id = %trace_id, job_id = %job_id, node_id = %node_id, "Received JobAck for terminated Job, ignoring");
             return;
         }
-        if j.assigned_node_id.as_deref() != Some(&node_id) {
+        if j.assigned_node_id.as_deref() != Some(&node_id) && j.hedge_node_id.as_deref() != Some(&node_id) {
             warn!(trace_id = %trace_id, job_id = %job_id, node_id = %node_id, current_node_id = ?j.assigned_node_id, "Received JobAck from non-current node, ignoring");
             return;
         }
@@ -110,7 +110,7 @@ pub(super) async fn handle_job_started(
             warn!(trace_id = %trace_id, job_id = %job_id, node_id = %node_id, "Received JobStarted for terminated Job, ignoring");
             return;
         }
-        if j.assigned_node_id.as_deref() != Some(&node_id) {
+        if j.assigned_node_id.as_deref() != Some(&node_id) && j.hedge_node_id.as_deref() != Some(&node_id) {
             warn!(trace_id = %trace_id, job_id = %job_id, node_id = %node_id, current_node_id = ?j.assigned_node_id, "Received JobStarted from non-current node, ignoring");
             return;
         }
@@ -151,6 +151,23 @@ pub(super) async fn handle_job_started(
     if let Some(rt) = state.redis_runtime.as_ref() {
         let _ = rt.job_fsm_to_running(&job_id).await;
     }
+
+    // 对冲派发：记录「派发 → 开始处理」时延样本（对冲节点从发起对冲计时）
+    if let (Some(hedging), Some(j)) = (state.hedged_dispatch.as_ref(), job.as_ref()) {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let sent_at_ms = if j.hedge_node_id.as_deref() == Some(&node_id) {
+            j.hedged_at_ms
+        } else {
+            j.dispatched_at_ms
+        };
+        if let Some(sent_at_ms) = sent_at_ms {
+            hedging.record_started(
+                &crate::services::HedgedDispatch::pool_key(j),
+                (now_ms - sent_at_ms).max(0) as u64,
+                now_ms,
+            );
+        }
+    }
 }
 
 pub(super) async fn handle_asr_partial(
@@ -192,6 +209,12 @@ pub(super) async fn handle_asr_partial(
             if let Some(rt) = state.redis_runtime.as_ref() {
                 let _ = rt.job_fsm_to_running(&job_id).await;
             }
+        } else if j.hedge_node_id.as_deref() == Some(&node_id) {
+            // 对冲进行中：只转发主节点的部分结果，避免两路 partial 交错
+            if let Some(rt) = state.redis_runtime.as_ref() {
+                let _ = rt.job_fsm_to_running(&job_id).await;
+            }
+            return;
         }
     }
 
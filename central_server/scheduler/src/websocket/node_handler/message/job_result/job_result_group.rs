@@ -8,6 +8,7 @@ pub(crate) async fn process_group_for_job_result(
     session_id: &str,
     trace_id: &str,
     utterance_index: u64,
+    src_lang: Option<&str>,
     text_asr: &Option<String>,
     text_translated: &Option<String>,
 ) -> (Option<String>, Option<u64>) {
@@ -20,6 +21,7 @@ pub(crate) async fn process_group_for_job_result(
                 .filter(|t| !t.is_empty())
                 .map(|t| t.clone());
             
+            // 上下文在创建下一个 Job 时由 GroupManager::context_for_next_job 读取
             let (gid, _context, pidx) = state
                 .group_manager
                 .on_asr_final_and_nmt_done(
@@ -28,6 +30,7 @@ pub(crate) async fn process_group_for_job_result(
                     utterance_index,
                     text_asr.clone(),
                     translated_text,
+                    src_lang.map(String::from),
                     now_ms,
                 )
                 .await;
@@ -0,0 +1,84 @@
+use crate::core::AppState;
+use crate::core::dispatcher::Job;
+use crate::messages::NodeMessage;
+use tracing::info;
+
+/// 对冲派发结算结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HedgeSettlement {
+    /// 继续正常处理该结果
+    Proceed,
+    /// 对冲中一方失败、另一方仍在处理：忽略该失败结果
+    Suppressed,
+}
+
+/// 对冲派发结算（仅当前 attempt 的结果调用）
+/// - 成功：先到者胜出，取消并释放另一节点
+/// - 失败：另一节点仍在处理时忽略本次失败，等待另一方结果
+pub(crate) async fn settle_hedged_job_result(
+    state: &AppState,
+    job: &Option<Job>,
+    node_id: &str,
+    attempt_id: u32,
+    success: bool,
+    session_id: &str,
+    trace_id: &str,
+) -> HedgeSettlement {
+    let Some(job) = job.as_ref() else { return HedgeSettlement::Proceed };
+    if let Some(hedging) = state.hedged_dispatch.as_ref() {
+        hedging.forget_job(&job.job_id);
+    }
+    let Some(other_node_id) = job.hedge_counterpart(node_id) else {
+        return HedgeSettlement::Proceed;
+    };
+    let won_by_hedge = job.hedge_node_id.as_deref() == Some(node_id);
+
+    if success {
+        if !state.dispatcher.resolve_job_hedge(&job.job_id, attempt_id, node_id).await {
+            return HedgeSettlement::Proceed;
+        }
+        let cancel_msg = NodeMessage::JobCancel {
+            job_id: job.job_id.clone(),
+            trace_id: Some(trace_id.to_string()),
+            reason: Some("hedge_lost".to_string()),
+        };
+        let _ = crate::redis_runtime::send_node_message_routed(state, other_node_id, cancel_msg).await;
+        if let Some(rt) = state.redis_runtime.as_ref() {
+            rt.release_node_slot(other_node_id, &job.job_id, attempt_id).await;
+        }
+        crate::metrics::prometheus_metrics::on_hedged_dispatch(if won_by_hedge { "won_by_hedge" } else { "won_by_primary" });
+        info!(
+            trace_id = %trace_id,
+            job_id = %job.job_id,
+            winner_node_id = %node_id,
+            loser_node_id = %other_node_id,
+            won_by_hedge = won_by_hedge,
+            "对冲派发：结果已胜出，取消另一节点"
+        );
+        return HedgeSettlement::Proceed;
+    }
+
+    if !state.dispatcher.resolve_job_hedge(&job.job_id, attempt_id, other_node_id).await {
+        // 对冲已被另一方结果结束：若本节点仍是当前节点（另一方已先失败），按正常失败处理
+        let still_current = state
+            .dispatcher
+            .get_job(&job.job_id)
+            .await
+            .is_some_and(|j| j.assigned_node_id.as_deref() == Some(node_id));
+        return if still_current { HedgeSettlement::Proceed } else { HedgeSettlement::Suppressed };
+    }
+    if let Some(rt) = state.redis_runtime.as_ref() {
+        rt.release_node_slot(node_id, &job.job_id, attempt_id).await;
+    }
+    // 去重记录已写入本次失败结果，移除后另一方的结果才能正常处理
+    state.job_result_deduplicator.forget(session_id, &job.job_id).await;
+    crate::metrics::prometheus_metrics::on_hedged_dispatch("attempt_failed");
+    info!(
+        trace_id = %trace_id,
+        job_id = %job.job_id,
+        failed_node_id = %node_id,
+        remaining_node_id = %other_node_id,
+        "对冲派发：一方失败，等待另一节点结果"
+    );
+    HedgeSettlement::Suppressed
+}
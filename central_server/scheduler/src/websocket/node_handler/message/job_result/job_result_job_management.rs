@@ -26,7 +26,7 @@ pub(crate) async fn check_should_process_job(
                 "Received result for terminated Job, will still add to result queue for utterance_index continuity"
             );
             false  // 不处理 Job 相关操作（释放 slot、更新状态等），但仍添加到队列
-        } else if j.assigned_node_id.as_deref() != Some(node_id) {
+        } else if j.assigned_node_id.as_deref() != Some(node_id) && j.hedge_node_id.as_deref() != Some(node_id) {
             warn!(
                 trace_id = %trace_id,
                 job_id = %job_id,
@@ -1,5 +1,9 @@
-use crate::messages::common::ExtraResult;
+use crate::core::AppState;
+use crate::core::dispatcher::Job;
+use crate::messages::JobError;
+use crate::messages::common::{ExtraResult, ServiceTimings};
 use crate::metrics::metrics;
+use crate::services::usage_metering::{usage_delta_for_job_result, JobUsageInput};
 
 /// 记录 ASR 相关指标
 pub(crate) fn record_asr_metrics(
@@ -25,3 +29,65 @@ pub(crate) fn record_asr_metrics(
     }
 }
 
+
+/// 按租户记录用量（仅对带 tenant_id 的 Job 计量）
+pub(crate) fn record_tenant_usage(
+    state: &AppState,
+    job: &Option<Job>,
+    text_asr: &Option<String>,
+    text_translated: &Option<String>,
+    tts_audio: &Option<String>,
+    tts_format: &Option<String>,
+    service_timings: &Option<ServiceTimings>,
+) {
+    let (Some(meter), Some(job)) = (state.usage_meter.as_ref(), job.as_ref()) else {
+        return;
+    };
+    let Some(tenant_id) = job.tenant_id.as_deref() else {
+        return;
+    };
+    let delta = usage_delta_for_job_result(&JobUsageInput {
+        audio_base64: &job.audio_base64,
+        audio_format: &job.audio_format,
+        sample_rate: job.sample_rate,
+        text_asr: text_asr.as_deref(),
+        text_translated: text_translated.as_deref(),
+        tts_audio: tts_audio.as_deref(),
+        tts_format: tts_format.as_deref(),
+        processing_ms: service_timings.as_ref().and_then(|t| t.total_ms),
+    });
+    meter.record(tenant_id, chrono::Utc::now(), &delta);
+}
+
+/// 将任务结果计入节点熔断器（只统计当前 attempt 的结果，后台写入不阻塞结果下发）
+pub(crate) fn record_node_breaker_outcome(
+    state: &AppState,
+    job: &Option<Job>,
+    node_id: &str,
+    success: bool,
+    job_error: &Option<JobError>,
+    asr_quality_level: &Option<String>,
+) {
+    let (Some(breaker), Some(job)) = (state.node_circuit_breaker.clone(), job.clone()) else {
+        return;
+    };
+    let dispatcher = state.dispatcher.clone();
+    let node_id = node_id.to_string();
+    let error_code = job_error.as_ref().map(|e| e.code.clone());
+    let asr_quality_level = asr_quality_level.clone();
+    tokio::spawn(async move {
+        let services = match dispatcher.required_types_for_job(&job).await {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::debug!(job_id = %job.job_id, error = %e, "计算任务服务类型失败，跳过熔断统计");
+                return;
+            }
+        };
+        if let Err(e) = breaker
+            .record_job_result(&node_id, &services, success, error_code.as_deref(), asr_quality_level.as_deref())
+            .await
+        {
+            tracing::warn!(node_id = %node_id, job_id = %job.job_id, error = %e, "记录节点熔断结果失败");
+        }
+    });
+}
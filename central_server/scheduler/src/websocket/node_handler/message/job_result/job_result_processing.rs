@@ -7,13 +7,14 @@ use super::job_result_routing::forward_job_result_if_needed;
 use super::job_result_job_management::{check_should_process_job, process_job_operations};
 use super::job_result_group::process_group_for_job_result;
 use super::job_result_events::send_ui_events_for_job_result;
-use super::job_result_metrics::record_asr_metrics;
+use super::job_result_metrics::{record_asr_metrics, record_node_breaker_outcome, record_tenant_usage};
 use super::job_result_creation::{
     calculate_elapsed_ms, create_service_timings, create_network_timings,
     create_translation_result, log_translation_result,
 };
 use super::job_result_sending::send_results_to_clients;
 use super::job_result_error::handle_job_result_error;
+use super::job_result_hedge::{settle_hedged_job_result, HedgeSettlement};
 
 pub(crate) async fn handle_job_result(
     state: &AppState,
@@ -107,6 +108,17 @@ pub(crate) async fn handle_job_result(
         &trace_id,
     ).await;
 
+    // 节点熔断统计（空结果核销同样说明节点正常响应）
+    if should_process_job {
+        record_node_breaker_outcome(state, &job, &node_id, success, &job_error, &asr_quality_level);
+
+        // 对冲派发：先成功者胜出；一方失败而另一方仍在处理时忽略该失败
+        let settlement = settle_hedged_job_result(state, &job, &node_id, attempt_id, success, &session_id, &trace_id).await;
+        if settlement == HedgeSettlement::Suppressed {
+            return;
+        }
+    }
+
     // 检查空结果核销：NO_TEXT_ASSIGNED（空容器）或 ASR_EMPTY（ASR 结果为空，静音/无效音频等）
     // 注意：reason 已经在上面定义过了，这里直接使用
     if is_empty_ack {
@@ -151,6 +163,7 @@ pub(crate) async fn handle_job_result(
         &session_id,
         &trace_id,
         utterance_index,
+        job.as_ref().map(|j| j.src_lang.as_str()),
         &text_asr,
         &text_translated,
     ).await;
@@ -181,6 +194,18 @@ pub(crate) async fn handle_job_result(
             &asr_quality_level,
             rerun_count,
         );
+        // 计费只记首次有效结果：重复 / 过期（should_process_job = false）的结果不计量
+        if should_process_job {
+            record_tenant_usage(
+                state,
+                &job,
+                &text_asr,
+                &text_translated,
+                &tts_audio,
+                &tts_format,
+                &service_timings,
+            );
+        }
 
         // 创建 TranslationResult 消息
         let result = create_translation_result(
@@ -1,6 +1,7 @@
 use crate::core::AppState;
 use crate::core::dispatcher::Job;
 use crate::messages::SessionMessage;
+use crate::services::transcript_store::TranscriptEntry;
 use tracing::{info, warn};
 
 /// 发送结果到客户端（支持房间模式和单会话模式）
@@ -33,6 +34,8 @@ pub(crate) async fn send_results_to_clients(
         if should_send_missing {
             continue; // 跳过原始结果，因为已经发送了 MissingResult
         }
+
+        record_transcript(state, session_id, job, &result, trace_id).await;
         
         // Check if Job is in target_session_ids (room mode)
         if let Some(ref job_info) = job {
@@ -62,6 +65,35 @@ pub(crate) async fn send_results_to_clients(
     }
 }
 
+/// 会话开启了转写持久化时追加记录（未开启时 store 内部直接忽略）
+async fn record_transcript(
+    state: &AppState,
+    session_id: &str,
+    job: &Option<Job>,
+    result: &SessionMessage,
+    trace_id: &str,
+) {
+    let Some(store) = state.transcript_store.as_ref() else {
+        return;
+    };
+    let (src_lang, tgt_lang) = match job {
+        Some(j) => (Some(j.src_lang.as_str()), Some(j.tgt_lang.as_str())),
+        None => (None, None),
+    };
+    let Some(entry) = TranscriptEntry::from_result(result, src_lang, tgt_lang) else {
+        return;
+    };
+    if let Err(e) = store.append(session_id, &entry).await {
+        warn!(
+            trace_id = %trace_id,
+            session_id = %session_id,
+            utterance_index = entry.utterance_index,
+            error = %e,
+            "Failed to append transcript entry"
+        );
+    }
+}
+
 /// 检查并处理空结果
 async fn check_and_handle_empty_result(
     state: &AppState,
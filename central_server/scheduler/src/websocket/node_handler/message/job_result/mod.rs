@@ -8,6 +8,7 @@ mod job_result_creation;
 mod job_result_sending;
 mod job_result_error;
 mod job_result_processing;
+mod job_result_hedge;
 
 pub(crate) use job_result_processing::handle_job_result;
 
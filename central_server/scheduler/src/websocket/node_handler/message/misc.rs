@@ -1,5 +1,9 @@
-use crate::messages::NodeMessage;
-use tracing::{error, warn};
+use super::send_node_error;
+use crate::core::AppState;
+use crate::messages::{ErrorCode, NodeMessage};
+use axum::extract::ws::Message;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
 
 pub(super) async fn handle_node_error(node_id: &str, code: &str, message: &str, _details: Option<serde_json::Value>) {
     error!("鑺傜偣 {} 鎶ュ憡閿欒: {} - {}", node_id, code, message);
@@ -11,3 +15,53 @@ pub(super) async fn handle_unhandled(message: NodeMessage) {
 }
 
 
+
+/// 节点主动发起的控制命令（目前仅 drain：升级前请求排空）
+pub(super) async fn handle_node_control(state: &AppState, node_id: Option<&str>, command: &str, reason: Option<String>) {
+    let Some(node_id) = node_id else {
+        warn!(command = %command, "node_control received before node_register, ignored");
+        return;
+    };
+    match command {
+        "drain" => {
+            let reason = reason.unwrap_or_else(|| "node requested".to_string());
+            match crate::services::node_drain::start_node_drain(state, node_id, &reason, "node").await {
+                Ok(_) => info!(node_id = %node_id, reason = %reason, "Node-initiated drain started"),
+                Err(e) => warn!(node_id = %node_id, error = %e, "Node-initiated drain rejected"),
+            }
+        }
+        other => warn!(node_id = %node_id, command = %other, "Unknown node_control command"),
+    }
+}
+
+/// 节点申请配对码：只接受本连接已注册的 node_id
+pub(super) async fn handle_pairing_code_request(
+    state: &AppState,
+    node_id: Option<&str>,
+    tx: &mpsc::UnboundedSender<Message>,
+    requested_node_id: &str,
+    ttl_seconds: Option<u64>,
+) -> Result<(), anyhow::Error> {
+    if node_id != Some(requested_node_id) {
+        warn!(node_id = ?node_id, requested_node_id = %requested_node_id, "pairing_code_request from unregistered connection, ignored");
+        send_node_error(tx, ErrorCode::InvalidPairingCode, "pairing_code_request requires a registered node");
+        return Ok(());
+    }
+    match state.pairing_service.issue_pairing_code(requested_node_id, ttl_seconds).await {
+        Ok(pairing) => {
+            let issued = NodeMessage::PairingCodeIssued {
+                node_id: pairing.node_id,
+                code: pairing.code,
+                expires_at_ms: pairing.expires_at.timestamp_millis(),
+            };
+            tx.send(Message::Text(serde_json::to_string(&issued)?))
+                .map_err(|e| anyhow::anyhow!("Failed to send pairing_code_issued: {}", e))?;
+            info!(node_id = %requested_node_id, "Pairing code issued");
+        }
+        Err(e) => {
+            warn!(node_id = %requested_node_id, error = %e, "Failed to issue pairing code");
+            send_node_error(tx, ErrorCode::InvalidPairingCode, "Failed to issue pairing code");
+        }
+    }
+    Ok(())
+}
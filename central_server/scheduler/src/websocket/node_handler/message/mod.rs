@@ -1,13 +1,29 @@
 use crate::core::AppState;
-use crate::messages::NodeMessage;
+use crate::messages::{ErrorCode, NodeMessage};
+use crate::services::node_auth::{NodeAuthGrant, NodeSecretKind};
 use axum::extract::ws::Message;
 use tokio::sync::mpsc;
 
+mod auth;
 mod job_progress;
 mod job_result;
 mod misc;
 mod register;
 
+pub(super) use auth::claimed_node_id;
+
+/// 向节点发送 error 消息
+pub(super) fn send_node_error(tx: &mpsc::UnboundedSender<Message>, code: ErrorCode, message: &str) {
+    let error_msg = NodeMessage::Error {
+        code: code.to_string(),
+        message: message.to_string(),
+        details: None,
+    };
+    if let Ok(json) = serde_json::to_string(&error_msg) {
+        let _ = tx.send(Message::Text(json));
+    }
+}
+
 /// Phase 2: When node connects to A and session connects to B, node->scheduler result messages arrive at A
 /// But session, result_queue / job context is on B (session owner)
 /// Therefore, forward these NodeMessage to session owner, let owner instance execute "final business processing"
@@ -25,37 +41,30 @@ pub(super) async fn handle_node_message(
     tx: &mpsc::UnboundedSender<Message>,
 ) -> Result<(), anyhow::Error> {
     match message {
-        NodeMessage::NodeRegister {
-            node_id: provided_node_id,
-            version,
-            capability_schema_version,
-            platform,
-            hardware,
-            installed_models,
-            installed_services,
-            features_supported,
-            advanced_features: _,
-            accept_public_jobs,
-            capability_by_type,
-            language_capabilities,
+        msg @ NodeMessage::NodeRegister { .. } => match state.node_auth.as_ref() {
+            Some(auth) => auth::handle_register_challenge(auth, tx, msg).await,
+            None => register_node(state, node_id, tx, msg, None).await,
+        },
+
+        NodeMessage::NodeAuthResponse {
+            node_id: nid,
+            signature,
+            enrollment_expires_at,
+            node_credential,
         } => {
-            register::handle_node_register(
-                state,
-                node_id,
-                tx,
-                provided_node_id,
-                version,
-                capability_schema_version,
-                platform,
-                hardware,
-                installed_models,
-                installed_services,
-                features_supported,
-                accept_public_jobs,
-                capability_by_type,
-                language_capabilities,
-            )
-            .await
+            let Some(auth) = state.node_auth.as_ref() else {
+                send_node_error(tx, ErrorCode::UnsupportedFeature, "Node authentication is disabled");
+                return Ok(());
+            };
+            let kind = match (enrollment_expires_at, node_credential) {
+                (_, true) => NodeSecretKind::Credential,
+                (Some(expires_at), false) => NodeSecretKind::Enrollment { expires_at },
+                (None, false) => NodeSecretKind::PreShared,
+            };
+            match auth::verify_response(auth, tx, &nid, &signature, kind).await {
+                Some((register, grant)) => register_node(state, node_id, tx, register, Some(grant)).await,
+                None => Ok(()),
+            }
         }
 
         NodeMessage::NodeHeartbeat {
@@ -196,9 +205,67 @@ pub(super) async fn handle_node_message(
             Ok(())
         }
 
+        NodeMessage::NodeControl { command, reason } => {
+            misc::handle_node_control(state, node_id.as_deref(), &command, reason).await;
+            Ok(())
+        }
+
+        NodeMessage::PairingCodeRequest { node_id: nid, ttl_seconds } => {
+            misc::handle_pairing_code_request(state, node_id.as_deref(), tx, &nid, ttl_seconds).await
+        }
+
         other => {
             misc::handle_unhandled(other).await;
             Ok(())
         }
     }
 }
+
+/// grant 为 None 表示未启用节点认证
+async fn register_node(
+    state: &AppState,
+    node_id: &mut Option<String>,
+    tx: &mpsc::UnboundedSender<Message>,
+    message: NodeMessage,
+    grant: Option<NodeAuthGrant>,
+) -> Result<(), anyhow::Error> {
+    let NodeMessage::NodeRegister {
+        node_id: provided_node_id,
+        version,
+        capability_schema_version,
+        platform,
+        hardware,
+        installed_models,
+        installed_services,
+        features_supported,
+        advanced_features: _,
+        accept_public_jobs,
+        owner_tenant_id,
+        capability_by_type,
+        language_capabilities,
+        migration_endpoint,
+    } = message
+    else {
+        return Ok(());
+    };
+    register::handle_node_register(
+        state,
+        node_id,
+        tx,
+        provided_node_id,
+        version,
+        capability_schema_version,
+        platform,
+        hardware,
+        installed_models,
+        installed_services,
+        features_supported,
+        accept_public_jobs,
+        owner_tenant_id,
+        capability_by_type,
+        language_capabilities,
+        migration_endpoint,
+        grant,
+    )
+    .await
+}
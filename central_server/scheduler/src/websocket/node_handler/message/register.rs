@@ -1,6 +1,7 @@
 use crate::core::AppState;
-use crate::messages::{CapabilityByType, FeatureFlags, HardwareInfo, InstalledModel, InstalledService, ResourceUsage, NodeMessage};
+use crate::messages::{ErrorCode, CapabilityByType, FeatureFlags, HardwareInfo, InstalledModel, InstalledService, ResourceUsage, NodeMessage};
 use crate::services::minimal_scheduler::RegisterNodeRequest;
+use crate::services::node_auth::NodeAuthGrant;
 use axum::extract::ws::Message;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
@@ -20,6 +21,92 @@ fn extract_langs_optional(
     (asr, semantic, tts)
 }
 
+/// 校验节点声明的迁移地址：只接受认证节点声明、且通过 node_auth.migration_endpoint_hosts 检查的 http(s) 地址，
+/// 去掉末尾斜杠；否则忽略（回退到调度器静态配置）
+fn normalize_migration_endpoint(
+    state: &AppState,
+    node_id: &str,
+    endpoint: Option<String>,
+    authenticated: bool,
+) -> String {
+    let Some(endpoint) = endpoint else {
+        return String::new();
+    };
+    let endpoint = endpoint.trim().trim_end_matches('/');
+    if endpoint.is_empty() {
+        return String::new();
+    }
+    let Some(auth) = state.node_auth.as_ref().filter(|_| authenticated) else {
+        warn!(node_id = %node_id, endpoint = %endpoint, "忽略未认证节点声明的迁移地址");
+        return String::new();
+    };
+    if auth.allows_migration_endpoint(endpoint) {
+        endpoint.to_string()
+    } else {
+        warn!(node_id = %node_id, endpoint = %endpoint, "忽略不允许的节点迁移地址（见 node_auth.migration_endpoint_hosts）");
+        String::new()
+    }
+}
+
+/// 归属租户用于拼接租户池 key（tenant:{tenant_id}:{pair}），含分隔符或空白的值忽略
+fn normalize_owner_tenant_id(node_id: &str, owner_tenant_id: Option<String>) -> String {
+    let Some(tenant_id) = owner_tenant_id else {
+        return String::new();
+    };
+    let tenant_id = tenant_id.trim();
+    if tenant_id.contains(':') || tenant_id.chars().any(char::is_whitespace) {
+        warn!(node_id = %node_id, owner_tenant_id = %tenant_id, "忽略无效的节点归属租户（不能包含 ':' 或空白）");
+        return String::new();
+    }
+    tenant_id.to_string()
+}
+
+/// 确定节点归属租户（入参均已规范化）：凭证绑定了租户时以凭证为准，
+/// 否则以 private_pools.node_owner_tenants 配置为准（未声明时采用配置值）；声明值不符时返回 Err
+fn resolve_owner_tenant_id(
+    node_id: &str,
+    declared: String,
+    bound: Option<&str>,
+    configured: Option<&str>,
+) -> Result<String, String> {
+    let expected = match bound {
+        Some(bound) => bound,
+        None if declared.is_empty() => return Ok(configured.unwrap_or_default().to_string()),
+        None => configured.unwrap_or_default(),
+    };
+    if declared == expected {
+        Ok(declared)
+    } else {
+        Err(format!("owner_tenant_id {} is not authorized for node {}", declared, node_id))
+    }
+}
+
+/// node_id 是否被其他在线连接占用，返回冲突原因。
+/// authenticated 时本实例上的旧连接视为断线残留：关闭旧连接并由新连接接管（旧连接的下线清理会跳过）
+async fn node_id_conflict(
+    state: &AppState,
+    node_id: &str,
+    tx: &mpsc::UnboundedSender<Message>,
+    authenticated: bool,
+) -> Option<String> {
+    if let Some(existing) = state.node_connections.get(node_id).await {
+        if !existing.same_channel(tx) && !existing.is_closed() {
+            if !authenticated {
+                return Some(format!("Node id {} is already connected", node_id));
+            }
+            warn!(node_id = %node_id, "认证通过的注册接管同一 node_id 的旧连接");
+            let _ = existing.send(Message::Close(None));
+        }
+    }
+    let rt = state.redis_runtime.as_ref()?;
+    match rt.resolve_node_owner(node_id).await {
+        Some(owner) if owner != rt.instance_id => {
+            Some(format!("Node id {} is already connected to another scheduler instance", node_id))
+        }
+        _ => None,
+    }
+}
+
 /// 节点注册处理（极简版）
 pub(super) async fn handle_node_register(
     state: &AppState,
@@ -33,9 +120,12 @@ pub(super) async fn handle_node_register(
     _installed_models: Vec<InstalledModel>,
     _installed_services: Option<Vec<InstalledService>>,
     _features_supported: FeatureFlags,
-    _accept_public_jobs: bool,
+    accept_public_jobs: bool,
+    owner_tenant_id: Option<String>,
     _capability_by_type: Vec<CapabilityByType>,
     language_capabilities: Option<crate::messages::common::NodeLanguageCapabilities>,
+    migration_endpoint: Option<String>,
+    grant: Option<NodeAuthGrant>,
 ) -> Result<(), anyhow::Error> {
     // 流程日志 1: 注册流程开始
     info!(
@@ -50,6 +140,39 @@ pub(super) async fn handle_node_register(
     let final_node_id = provided_node_id.unwrap_or_else(|| {
         format!("node-{}", uuid::Uuid::new_v4().to_string()[..8].to_uppercase())
     });
+
+    // node_id 已被其他在线连接占用（本实例或其他存活实例）时拒绝，防止抢占他人任务；
+    // 节点收到 NODE_ID_CONFLICT 后按退避重连，待旧连接下线后即可注册
+    if let Some(reason) = node_id_conflict(state, &final_node_id, tx, grant.is_some()).await {
+        warn!(
+            step = "register_conflict",
+            node_id = %final_node_id,
+            reason = %reason,
+            "【节点管理流程】节点 ID 冲突，拒绝注册"
+        );
+        super::send_node_error(tx, ErrorCode::NodeIdConflict, &reason);
+        return Ok(());
+    }
+
+    // 归属租户决定节点进入哪个租户私有池，不能只信节点自报
+    let owner_tenant_id = match resolve_owner_tenant_id(
+        &final_node_id,
+        normalize_owner_tenant_id(&final_node_id, owner_tenant_id),
+        grant.as_ref().and_then(|g| g.owner_tenant_id.as_deref()),
+        state.private_pools.node_owner_tenants.get(&final_node_id).map(String::as_str),
+    ) {
+        Ok(owner_tenant_id) => owner_tenant_id,
+        Err(reason) => {
+            warn!(
+                step = "register_owner_mismatch",
+                node_id = %final_node_id,
+                reason = %reason,
+                "【节点管理流程】节点归属租户未经授权，拒绝注册"
+            );
+            super::send_node_error(tx, ErrorCode::NodeOwnerTenantMismatch, &reason);
+            return Ok(());
+        }
+    };
     *node_id = Some(final_node_id.clone());
     
     // 流程日志 2: 节点 ID 确定
@@ -78,7 +201,17 @@ pub(super) async fn handle_node_register(
         asr_langs_json,
         semantic_langs_json,
         tts_langs_json,
+        migration_base_url: normalize_migration_endpoint(state, &final_node_id, migration_endpoint, grant.is_some()),
+        accept_public_jobs,
+        owner_tenant_id,
     };
+    info!(
+        step = "register_scope",
+        node_id = %final_node_id,
+        accept_public_jobs = req.accept_public_jobs,
+        owner_tenant_id = %req.owner_tenant_id,
+        "【节点管理流程】节点池作用域（公共/私有）"
+    );
 
     // 流程日志 4: 准备写入 Redis
     info!(
@@ -111,6 +244,13 @@ pub(super) async fn handle_node_register(
     // 注册节点的 WebSocket 连接（用于发送任务）
     // 注意：连接注册必须在节点注册成功后执行，否则任务无法发送
     state.node_connections.register(final_node_id.clone(), tx.clone()).await;
+    // 立即写入 node owner，其他实例据此判断 node_id 冲突（之后由后台任务续约）
+    if let Some(rt) = state.redis_runtime.as_ref() {
+        rt.set_node_owner(&final_node_id).await;
+    }
+    if let Some(drain) = state.node_drain.as_ref() {
+        drain.clear_if_offline(&final_node_id).await;
+    }
     
     // 流程日志 6: WebSocket 连接已注册
     info!(
@@ -125,6 +265,7 @@ pub(super) async fn handle_node_register(
         node_id: final_node_id.clone(),
         message: "Node registered successfully".to_string(),
         status: "registering".to_string(), // 初始状态为 registering
+        node_credential: grant.and_then(|g| g.node_credential),
     };
     
     let ack_json = serde_json::to_string(&ack)
@@ -159,7 +300,7 @@ pub(super) async fn handle_node_heartbeat(
     _capability_by_type: Vec<CapabilityByType>,
     _rerun_metrics: Option<crate::messages::common::RerunMetrics>,
     _asr_metrics: Option<crate::messages::common::ASRMetrics>,
-    _processing_metrics: Option<crate::messages::common::ProcessingMetrics>,
+    processing_metrics: Option<crate::messages::common::ProcessingMetrics>,
     language_capabilities: Option<crate::messages::common::NodeLanguageCapabilities>,
 ) {
     info!(step = "heartbeat_start", node_id = %node_id, "【节点管理流程】收到节点心跳");
@@ -169,6 +310,14 @@ pub(super) async fn handle_node_heartbeat(
     let semantic_json = serde_json::to_string(&semantic_langs).unwrap_or_else(|_| "[]".to_string());
     let tts_json = serde_json::to_string(&tts_langs).unwrap_or_else(|_| "[]".to_string());
 
+    // 排空完成后节点已下线：不再刷新节点数据与池，直到重新注册
+    if let Some(drain) = state.node_drain.as_ref() {
+        if drain.is_offline(node_id).await {
+            debug!(node_id = %node_id, "节点已排空下线，忽略心跳");
+            return;
+        }
+    }
+
     if let (Some(scheduler), Some(pool_service)) = (state.minimal_scheduler.as_ref(), state.pool_service.as_ref()) {
         let need_pool = !asr_langs.is_empty() && !semantic_langs.is_empty();
         if need_pool {
@@ -194,6 +343,12 @@ pub(super) async fn handle_node_heartbeat(
                         elapsed_ms = t0.elapsed().as_millis(),
                         "【节点管理流程】Redis 心跳成功（TTL 已刷新，节点池已分配）"
                     );
+                    // 心跳周期内无任务时不上报对应服务，沿用上次记录
+                    if let Some(metrics) = processing_metrics.as_ref().filter(|m| !m.service_efficiencies.is_empty()) {
+                        if let Err(e) = pool_service.record_service_efficiencies(node_id, &metrics.service_efficiencies).await {
+                            tracing::warn!(node_id = %node_id, error = %e, "记录节点处理效率失败");
+                        }
+                    }
                 }
                 Err(e) => {
                     tracing::warn!(
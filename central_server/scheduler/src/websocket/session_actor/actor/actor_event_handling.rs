@@ -20,6 +20,10 @@ impl SessionActor {
             SessionEvent::CloseSession => {
                 self.handle_close().await?;
             }
+            SessionEvent::ConnectionReplaced { message_tx } => {
+                debug!(session_id = %self.session_id, "Session connection replaced after resume");
+                self.message_tx = message_tx;
+            }
         }
         Ok(())
     }
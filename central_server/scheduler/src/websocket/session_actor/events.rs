@@ -26,6 +26,10 @@ pub enum SessionEvent {
     },
     /// 关闭会话
     CloseSession,
+    /// 断线重连后替换下行连接（session_resume）
+    ConnectionReplaced {
+        message_tx: MessageSender,
+    },
 }
 
 /// 用于向 WebSocket 发送消息的回调
@@ -3,6 +3,7 @@
 use crate::core::AppState;
 use crate::messages::{SessionMessage, ErrorCode};
 use crate::websocket::{send_error, session_message_handler};
+use crate::websocket::session_actor::SessionEvent;
 use axum::extract::ws::{Message, WebSocket};
 use futures_util::{SinkExt, StreamExt};
 use tokio::sync::mpsc;
@@ -46,6 +47,8 @@ pub async fn handle_session(socket: WebSocket, state: AppState) {
     });
     
     let mut session_id: Option<String> = None;
+    // session_init 协商出的 Binary Frame 模式（未协商时拒绝二进制帧）
+    let mut binary_frame_negotiated = false;
     
     // Receive message loop with send task failure detection
     // 使用 Box::pin 来固定 future，以便在 select! 中使用
@@ -82,8 +85,18 @@ pub async fn handle_session(socket: WebSocket, state: AppState) {
                         
                         match serde_json::from_str::<SessionMessage>(&text) {
                             Ok(message) => {
+                                let init_supports_binary = match &message {
+                                    SessionMessage::SessionInit { supports_binary_frame, .. }
+                                    | SessionMessage::SessionResume { supports_binary_frame, .. } => Some(*supports_binary_frame),
+                                    _ => None,
+                                };
                                 match session_message_handler::handle_session_message(message, &state, &mut session_id, &tx).await {
-                                    Ok(()) => {}
+                                    Ok(()) => {
+                                        if let Some(supports) = init_supports_binary {
+                                            binary_frame_negotiated = session_id.is_some()
+                                                && session_message_handler::negotiate_binary_frame(&state, supports);
+                                        }
+                                    }
                                     Err(e) => {
                                         error!("Failed to handle session message: {}", e);
                                         send_error(&tx, ErrorCode::InternalError, &format!("Failed to process message: {}", e)).await;
@@ -96,6 +109,20 @@ pub async fn handle_session(socket: WebSocket, state: AppState) {
                             }
                         }
                     }
+                    Some(Ok(Message::Binary(data))) => {
+                        if let Err(e) = session_message_handler::handle_binary_frame(
+                            &state,
+                            &tx,
+                            session_id.as_deref(),
+                            binary_frame_negotiated,
+                            &data,
+                        )
+                        .await
+                        {
+                            error!("Failed to handle binary frame: {}", e);
+                            send_error(&tx, ErrorCode::InternalError, &format!("Failed to process binary frame: {}", e)).await;
+                        }
+                    }
                     Some(Ok(Message::Close(_))) => {
                         info!(
                             session_id = session_id.as_deref().unwrap_or("unknown"),
@@ -127,43 +154,12 @@ pub async fn handle_session(socket: WebSocket, state: AppState) {
     
     // Cleanup: 无论是否检测到发送失败，都执行清理
     if let Some(ref sess_id) = session_id {
-        info!(
-            session_id = %sess_id,
-            "开始清理会话资源"
-        );
-        
-        // Phase 2: 清除会话所有者
-        if let Some(rt) = state.redis_runtime.as_ref() {
-            rt.clear_session_owner(sess_id).await;
+        if session_taken_over(&state, sess_id, &tx).await {
+            // 会话已被新连接恢复（session_resume），旧连接不再清理任何资源
+            info!(session_id = %sess_id, "旧连接断开，会话已由新连接接管");
+        } else if !detach_for_resume(&state, sess_id).await {
+            cleanup_session(&state, sess_id).await;
         }
-        
-        // 立即从连接管理器中移除（防止后续消息误发送）
-        state.session_connections.unregister(sess_id).await;
-        info!(
-            session_id = %sess_id,
-            "已从连接管理器移除会话"
-        );
-        
-        // RF-4 增强：在删除结果队列前，flush 所有待发送的结果
-        // 注意：此时 WebSocket 连接可能已经关闭，但尝试发送结果（best-effort）
-        let pending_results = state.result_queue.remove_session(sess_id).await;
-        if !pending_results.is_empty() {
-            warn!(
-                session_id = %sess_id,
-                pending_count = pending_results.len(),
-                "会话关闭时发现待发送的结果（WebSocket 连接可能已断开）"
-            );
-            // 注意：此时连接已从 session_connections 中移除，无法再发送
-            // 这些结果将丢失，但这是预期的行为（连接已断开）
-        }
-        
-        // 清理会话管理器
-        state.session_manager.remove_session(sess_id).await;
-        
-        info!(
-            session_id = %sess_id,
-            "会话资源清理完成"
-        );
     } else {
         warn!("会话 ID 为空，跳过清理");
     }
@@ -176,3 +172,120 @@ pub async fn handle_session(socket: WebSocket, state: AppState) {
         debug!("已中止发送任务");
     }
 }
+
+/// 本连接已不是会话的当前连接（同一 session_id 已注册了新的 sender）
+async fn session_taken_over(state: &AppState, sess_id: &str, tx: &mpsc::UnboundedSender<Message>) -> bool {
+    matches!(state.session_connections.get(sess_id).await, Some(current) if !current.same_channel(tx))
+}
+
+/// 连接异常断开时让会话进入断线宽限期；返回 false 表示不可恢复，需要立即清理
+async fn detach_for_resume(state: &AppState, sess_id: &str) -> bool {
+    let Some(resume) = state.session_resume.as_ref() else {
+        return false;
+    };
+    // SessionClose 已移除会话时不可恢复
+    let Some(session) = state.session_manager.get_session(sess_id).await else {
+        return false;
+    };
+    state.session_connections.unregister(sess_id).await;
+    let delivered = state.result_queue.delivered_history(sess_id).await;
+    match resume.detach(&session, &delivered).await {
+        Ok(true) => {}
+        Ok(false) => return false,
+        Err(e) => {
+            warn!(session_id = %sess_id, error = %e, "会话进入断线宽限期失败，直接清理");
+            return false;
+        }
+    }
+
+    let grace = resume.grace_period();
+    info!(
+        session_id = %sess_id,
+        grace_secs = grace.as_secs(),
+        "连接断开，会话进入断线宽限期（等待 session_resume）"
+    );
+    let state = state.clone();
+    let sess_id = sess_id.to_string();
+    tokio::spawn(async move {
+        tokio::time::sleep(grace).await;
+        expire_detached_session(&state, &sess_id).await;
+    });
+    true
+}
+
+/// 宽限期结束：未恢复则清理；已被其他实例恢复则只清理本地状态
+async fn expire_detached_session(state: &AppState, sess_id: &str) {
+    let Some(resume) = state.session_resume.as_ref() else {
+        return;
+    };
+    if !resume.end_detach(sess_id).await {
+        // 已在本实例恢复
+        return;
+    }
+
+    if let Some(actor_handle) = state.session_manager.get_actor_handle(sess_id).await {
+        let _ = actor_handle.send(SessionEvent::CloseSession);
+        state.session_manager.remove_actor(sess_id).await;
+    }
+
+    let taken_over = match state.redis_runtime.as_ref() {
+        Some(rt) => rt
+            .resolve_session_owner(sess_id)
+            .await
+            .is_some_and(|owner| owner != rt.instance_id),
+        None => false,
+    };
+    if taken_over {
+        state.result_queue.remove_session(sess_id).await;
+        state.session_manager.remove_session(sess_id).await;
+        state.tenant_quota.forget(sess_id).await;
+        info!(session_id = %sess_id, "会话已在其他实例恢复，清理本地状态");
+    } else {
+        resume.discard(sess_id).await;
+        info!(session_id = %sess_id, "断线宽限期结束，会话未恢复");
+        cleanup_session(state, sess_id).await;
+    }
+}
+
+/// 释放会话资源（连接断开且不可恢复，或宽限期结束）
+async fn cleanup_session(state: &AppState, sess_id: &str) {
+    info!(
+        session_id = %sess_id,
+        "开始清理会话资源"
+    );
+    
+    // Phase 2: 清除会话所有者
+    if let Some(rt) = state.redis_runtime.as_ref() {
+        rt.clear_session_owner(sess_id).await;
+    }
+    
+    // 立即从连接管理器中移除（防止后续消息误发送）
+    state.session_connections.unregister(sess_id).await;
+    info!(
+        session_id = %sess_id,
+        "已从连接管理器移除会话"
+    );
+    
+    // RF-4 增强：在删除结果队列前，flush 所有待发送的结果
+    // 注意：此时 WebSocket 连接可能已经关闭，但尝试发送结果（best-effort）
+    let pending_results = state.result_queue.remove_session(sess_id).await;
+    if !pending_results.is_empty() {
+        warn!(
+            session_id = %sess_id,
+            pending_count = pending_results.len(),
+            "会话关闭时发现待发送的结果（WebSocket 连接可能已断开）"
+        );
+        // 注意：此时连接已从 session_connections 中移除，无法再发送
+        // 这些结果将丢失，但这是预期的行为（连接已断开）
+    }
+    
+    // 清理会话管理器
+    state.session_manager.remove_session(sess_id).await;
+    // 释放租户会话名额（SessionClose 已释放时为空操作）
+    state.tenant_quota.release(sess_id).await;
+    
+    info!(
+        session_id = %sess_id,
+        "会话资源清理完成"
+    );
+}
@@ -1,9 +1,12 @@
 use base64::{engine::general_purpose, Engine as _};
 use crate::core::AppState;
+use crate::messages::binary_frame::{restore_timestamp_ms, BinaryFrame};
+use crate::messages::ErrorCode;
+use crate::websocket::send_error;
 use crate::websocket::session_actor::SessionEvent;
 use axum::extract::ws::Message;
 use tokio::sync::mpsc;
-use tracing::debug;
+use tracing::{debug, warn};
 
 
 pub(super) async fn handle_audio_chunk(
@@ -13,6 +16,80 @@ pub(super) async fn handle_audio_chunk(
     is_final: bool,
     payload: Option<String>,
     client_timestamp_ms: Option<i64>,
+) -> Result<(), anyhow::Error> {
+    // 解码音频数据
+    let chunk = if let Some(payload_str) = payload {
+        general_purpose::STANDARD
+            .decode(&payload_str)
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    forward_audio_chunk(state, sess_id, chunk, is_final, client_timestamp_ms).await
+}
+
+/// Binary Frame 上行音频（仅在 session_init 协商成功后接受）
+///
+/// AUDIO_CHUNK 与 FINAL 帧最终与 JSON audio_chunk 走同一条 AudioChunkReceived 路径；
+/// FINAL 等价于 payload 为空、is_final = true 的 audio_chunk。
+pub(crate) async fn handle_binary_frame(
+    state: &AppState,
+    tx: &mpsc::UnboundedSender<Message>,
+    session_id: Option<&str>,
+    negotiated: bool,
+    data: &[u8],
+) -> Result<(), anyhow::Error> {
+    if !negotiated {
+        warn!(
+            session_id = session_id.unwrap_or("unknown"),
+            "Binary frame received before negotiation"
+        );
+        send_error(tx, ErrorCode::InvalidMessage, "Binary frames were not negotiated for this session").await;
+        return Ok(());
+    }
+    if data.len() > state.binary_frame.max_frame_bytes {
+        send_error(
+            tx,
+            ErrorCode::InvalidMessage,
+            &format!("Binary frame too large: {} bytes (max {})", data.len(), state.binary_frame.max_frame_bytes),
+        )
+        .await;
+        return Ok(());
+    }
+    let frame = match BinaryFrame::decode(data) {
+        Ok(frame) => frame,
+        Err(e) => {
+            warn!(session_id = session_id.unwrap_or("unknown"), error = %e, "Invalid binary frame");
+            send_error(tx, ErrorCode::InvalidMessage, &format!("Invalid binary frame: {}", e)).await;
+            return Ok(());
+        }
+    };
+    if session_id != Some(frame.session_id()) {
+        send_error(tx, ErrorCode::InvalidSession, "Binary frame session_id does not match this connection").await;
+        return Ok(());
+    }
+
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    match frame {
+        BinaryFrame::AudioChunk { session_id, timestamp_low32, is_final, audio, .. } => {
+            let client_ts = restore_timestamp_ms(timestamp_low32, now_ms);
+            forward_audio_chunk(state, session_id, audio, is_final, Some(client_ts)).await
+        }
+        BinaryFrame::Final { session_id, timestamp_low32, .. } => {
+            let client_ts = restore_timestamp_ms(timestamp_low32, now_ms);
+            forward_audio_chunk(state, session_id, Vec::new(), true, Some(client_ts)).await
+        }
+    }
+}
+
+/// 投递音频块到 Session Actor（JSON 与 Binary Frame 共用）
+async fn forward_audio_chunk(
+    state: &AppState,
+    sess_id: String,
+    chunk: Vec<u8>,
+    is_final: bool,
+    client_timestamp_ms: Option<i64>,
 ) -> Result<(), anyhow::Error> {
     // 验证会话
     let _session = state
@@ -28,15 +105,6 @@ pub(super) async fn handle_audio_chunk(
         .await
         .ok_or_else(|| anyhow::anyhow!("Session Actor not found: {}", sess_id))?;
 
-    // 解码音频数据
-    let chunk = if let Some(payload_str) = payload {
-        general_purpose::STANDARD
-            .decode(&payload_str)
-            .unwrap_or_default()
-    } else {
-        Vec::new()
-    };
-
     let now_ms = chrono::Utc::now().timestamp_millis();
 
     // 发送音频块事件到 Actor
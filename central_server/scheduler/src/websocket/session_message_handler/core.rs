@@ -7,6 +7,38 @@ use axum::extract::ws::Message;
 use tokio::sync::mpsc;
 use tracing::{info, warn};
 
+/// 创建并启动 Session Actor（session_init 与跨实例 session_resume 共用）
+pub(super) async fn spawn_session_actor(
+    state: &AppState,
+    session_id: &str,
+    tx: &mpsc::UnboundedSender<Message>,
+    utterance_index: u64,
+) {
+    let pause_ms = state.web_task_segmentation.pause_ms;
+    let max_duration_ms = state.web_task_segmentation.max_duration_ms;
+    let edge_config = state.web_task_segmentation.edge_stabilization.clone();
+    let (actor, actor_handle) = SessionActor::new(
+        session_id.to_string(),
+        state.clone(),
+        tx.clone(),
+        utterance_index,
+        pause_ms,
+        max_duration_ms,
+        edge_config,
+    );
+    
+    // Register actor handle
+    state
+        .session_manager
+        .register_actor(session_id.to_string(), actor_handle)
+        .await;
+    
+    // Spawn actor task
+    tokio::spawn(async move {
+        actor.run().await;
+    });
+}
+
 pub(super) async fn handle_session_init(
     state: &AppState,
     session_id: &mut Option<String>,
@@ -19,17 +51,26 @@ pub(super) async fn handle_session_init(
     features: Option<crate::messages::FeatureFlags>,
     pairing_code: Option<String>,
     tenant_id: Option<String>,
+    tenant_max_sessions: Option<usize>,
     mode: Option<String>,
     lang_a: Option<String>,
     lang_b: Option<String>,
     auto_langs: Option<Vec<String>>,
     trace_id: Option<String>,
+    persist_transcript: Option<bool>,
+    supports_binary_frame: Option<bool>,
 ) -> Result<(), anyhow::Error> {
-    // Handle pairing code
-    let paired_node_id = if let Some(code) = pairing_code {
-        state.pairing_service.validate_pairing_code(&code).await
-    } else {
-        None
+    // Handle pairing code：出示了配对码但无效（过期/已使用/不存在）时拒绝，避免私有会话误走公共池
+    let paired_node_id = match pairing_code.filter(|code| !code.trim().is_empty()) {
+        Some(code) => match state.pairing_service.validate_pairing_code(&code).await {
+            Some(node_id) => Some(node_id),
+            None => {
+                warn!("Invalid or expired pairing code, session_init rejected");
+                send_error(tx, ErrorCode::InvalidPairingCode, "Invalid or expired pairing code").await;
+                return Ok(());
+            }
+        },
+        None => None,
     };
 
     // Create session (pass trace_id)
@@ -42,7 +83,7 @@ pub(super) async fn handle_session_init(
             tgt_lang,
             dialect.clone(),
             features.clone(),
-            tenant_id,
+            tenant_id.clone(),
             mode.clone(),
             lang_a.clone(),
             lang_b.clone(),
@@ -55,6 +96,33 @@ pub(super) async fn handle_session_init(
         )
         .await;
 
+    // 租户并发会话配额：超限则撤销刚创建的会话并拒绝
+    if let Some(ref tid) = tenant_id {
+        if let Err(exceeded) = state
+            .tenant_quota
+            .try_acquire(tid, &session.session_id, tenant_max_sessions)
+            .await {
+            state.session_manager.remove_session(&session.session_id).await;
+            warn!(
+                trace_id = %session.trace_id,
+                tenant_id = %tid,
+                max_sessions = exceeded.max_sessions,
+                active_sessions = exceeded.active_sessions,
+                "Tenant session limit exceeded, session_init rejected"
+            );
+            send_error(
+                tx,
+                ErrorCode::TenantSessionLimitExceeded,
+                &format!(
+                    "Tenant {} has reached its concurrent session limit ({})",
+                    tid, exceeded.max_sessions
+                ),
+            )
+            .await;
+            return Ok(());
+        }
+    }
+
     // If pairing successful, update session
     if let Some(ref node_id) = paired_node_id {
         state
@@ -81,6 +149,23 @@ pub(super) async fn handle_session_init(
         }
     }
 
+    // 会话转写持久化：写入 meta 后，后续按序下发的结果才会被追加
+    if let Some(store) = state.transcript_store.as_ref() {
+        if store.should_persist(tenant_id.as_deref(), persist_transcript) {
+            if let Err(e) = store
+                .open(&session.session_id, tenant_id.as_deref(), &session.src_lang, &session.tgt_lang)
+                .await
+            {
+                warn!(
+                    trace_id = %session.trace_id,
+                    session_id = %session.session_id,
+                    error = %e,
+                    "Failed to enable transcript persistence"
+                );
+            }
+        }
+    }
+
     // Initialize result queue
     state
         .result_queue
@@ -88,29 +173,13 @@ pub(super) async fn handle_session_init(
         .await;
 
     // Create and start Session Actor
-    let pause_ms = state.web_task_segmentation.pause_ms;
-    let max_duration_ms = state.web_task_segmentation.max_duration_ms;
-    let edge_config = state.web_task_segmentation.edge_stabilization.clone();
-    let (actor, actor_handle) = SessionActor::new(
-        session.session_id.clone(),
-        state.clone(),
-        tx.clone(),
-        session.utterance_index,
-        pause_ms,
-        max_duration_ms,
-        edge_config,
-    );
-    
-    // Register actor handle
-    state
-        .session_manager
-        .register_actor(session.session_id.clone(), actor_handle.clone())
-        .await;
-    
-    // Spawn actor task
-    tokio::spawn(async move {
-        actor.run().await;
-    });
+    spawn_session_actor(state, &session.session_id, tx, session.utterance_index).await;
+
+    // 断线重连凭证
+    let resume_token = match state.session_resume.as_ref() {
+        Some(resume) => Some(resume.issue_token(&session.session_id).await),
+        None => None,
+    };
 
     // Send acknowledgment message (include trace_id and protocol negotiation)
     let ack = SessionMessage::SessionInitAck {
@@ -120,11 +189,12 @@ pub(super) async fn handle_session_init(
         trace_id: session.trace_id.clone(),
         // 协议协商结果：从 session 配置中获取
         protocol_version: Some("1.0".to_string()),
-        use_binary_frame: Some(false), // Phase 2: 暂时不使用 Binary Frame
+        use_binary_frame: Some(super::negotiate_binary_frame(state, supports_binary_frame)),
         negotiated_codec: session.audio_format.clone(),
         negotiated_audio_format: session.audio_format.clone(), // 兼容字段
         negotiated_sample_rate: session.sample_rate,
         negotiated_channel_count: Some(1), // 单声道
+        resume_token,
     };
 
     send_message(tx, &ack).await?;
@@ -274,14 +344,8 @@ pub(super) async fn handle_session_close(
                 room_code: room_code.clone(),
                 members: members.clone(),
             };
-            // Broadcast to all members in room
-            for member in members {
-                if member.session_id != sess_id {
-                    if let Some(member_tx) = state.session_connections.get(&member.session_id).await {
-                        let _ = send_message(&member_tx, &members_msg).await;
-                    }
-                }
-            }
+            // Broadcast to all members in room (including members on other instances)
+            crate::redis_runtime::broadcast_to_room_members(state, &members, &members_msg, Some(&sess_id)).await;
         }
     }
 
@@ -315,6 +379,11 @@ pub(super) async fn handle_session_close(
     // Cleanup session
     state.session_connections.unregister(&sess_id).await;
     state.session_manager.remove_session(&sess_id).await;
+    state.tenant_quota.release(&sess_id).await;
+    // 主动关闭的会话不可恢复
+    if let Some(resume) = state.session_resume.as_ref() {
+        resume.discard(&sess_id).await;
+    }
     // Schema compat: Clear v1:sessions:bind (default off)
     if let Some(rt) = state.redis_runtime.as_ref() {
         rt.schema_clear_session_bind(&sess_id).await;
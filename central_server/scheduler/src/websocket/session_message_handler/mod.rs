@@ -2,6 +2,7 @@
 
 mod audio;
 mod core;
+mod resume;
 mod room;
 mod utterance;
 mod webrtc;
@@ -12,6 +13,13 @@ use axum::extract::ws::Message;
 use tokio::sync::mpsc;
 use tracing::warn;
 
+pub(crate) use audio::handle_binary_frame;
+
+/// Binary Frame 协商：服务端启用且客户端声明支持时才使用
+pub(crate) fn negotiate_binary_frame(state: &AppState, supports_binary_frame: Option<bool>) -> bool {
+    state.binary_frame.enabled && supports_binary_frame == Some(true)
+}
+
 /// Handle session messages
 pub(crate) async fn handle_session_message(
     message: SessionMessage,
@@ -29,6 +37,7 @@ pub(crate) async fn handle_session_message(
             features,
             pairing_code,
             tenant_id,
+            tenant_max_sessions,
             mode,
             lang_a,
             lang_b,
@@ -36,6 +45,8 @@ pub(crate) async fn handle_session_message(
             enable_streaming_asr: _,
             partial_update_interval_ms: _,
             trace_id,
+            persist_transcript,
+            supports_binary_frame,
         } => {
             core::handle_session_init(
                 state,
@@ -49,11 +60,32 @@ pub(crate) async fn handle_session_message(
                 features,
                 pairing_code,
                 tenant_id,
+                tenant_max_sessions,
                 mode,
                 lang_a,
                 lang_b,
                 auto_langs,
                 trace_id,
+                persist_transcript,
+                supports_binary_frame,
+            )
+            .await?;
+        }
+
+        SessionMessage::SessionResume {
+            session_id: sess_id,
+            resume_token,
+            last_acked_utterance_index,
+            supports_binary_frame,
+        } => {
+            resume::handle_session_resume(
+                state,
+                session_id,
+                tx,
+                sess_id,
+                resume_token,
+                last_acked_utterance_index,
+                supports_binary_frame,
             )
             .await?;
         }
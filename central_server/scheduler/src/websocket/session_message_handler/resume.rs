@@ -0,0 +1,123 @@
+use crate::core::session::Session;
+use crate::core::AppState;
+use crate::messages::{ErrorCode, SessionMessage};
+use crate::services::session_resume::select_replay;
+use crate::websocket::session_actor::SessionEvent;
+use crate::websocket::{send_error, send_message};
+use axum::extract::ws::Message;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// 断线重连：把新连接挂回宽限期内（或原连接尚未被感知断开）的会话，并重放未确认的结果
+pub(super) async fn handle_session_resume(
+    state: &AppState,
+    session_id: &mut Option<String>,
+    tx: &mpsc::UnboundedSender<Message>,
+    sess_id: String,
+    resume_token: String,
+    last_acked_utterance_index: Option<u64>,
+    supports_binary_frame: Option<bool>,
+) -> Result<(), anyhow::Error> {
+    let Some(resume) = state.session_resume.as_ref() else {
+        send_error(tx, ErrorCode::UnsupportedFeature, "Session resume is disabled").await;
+        return Ok(());
+    };
+    if session_id.is_some() {
+        send_error(tx, ErrorCode::InvalidMessage, "Connection is already bound to a session").await;
+        return Ok(());
+    }
+
+    // 宽限期记录在 Redis（任意实例可恢复）；找不到时再看本实例是否仍持有在线 token
+    let snapshot = resume.claim(&sess_id, &resume_token).await?;
+    let live_takeover = snapshot.is_none() && resume.take_live_token(&sess_id, &resume_token).await;
+    if snapshot.is_none() && !live_takeover {
+        warn!(session_id = %sess_id, "Session resume rejected: invalid token or grace period expired");
+        send_error(tx, ErrorCode::InvalidSession, "Resume token is invalid or the session has expired").await;
+        return Ok(());
+    }
+    resume.end_detach(&sess_id).await;
+
+    let session = match (state.session_manager.get_session(&sess_id).await, snapshot) {
+        (Some(session), _) => session,
+        // 会话在其他实例断开：按快照在本实例重建
+        (None, Some(snapshot)) => adopt_session(state, snapshot, last_acked_utterance_index).await,
+        (None, None) => {
+            send_error(tx, ErrorCode::InvalidSession, "Session no longer exists").await;
+            return Ok(());
+        }
+    };
+
+    // 挂接新连接并接管 session owner（之后的结果路由到本实例）
+    state
+        .session_connections
+        .register(sess_id.clone(), tx.clone())
+        .await;
+    if let Some(rt) = state.redis_runtime.as_ref() {
+        rt.set_session_owner(&sess_id).await;
+    }
+    let actor_attached = match state.session_manager.get_actor_handle(&sess_id).await {
+        Some(handle) => handle
+            .send(SessionEvent::ConnectionReplaced { message_tx: tx.clone() })
+            .is_ok(),
+        None => false,
+    };
+    if !actor_attached {
+        super::core::spawn_session_actor(state, &sess_id, tx, session.utterance_index).await;
+    }
+    *session_id = Some(sess_id.clone());
+
+    let replay = if live_takeover {
+        select_replay(
+            state.result_queue.delivered_history(&sess_id).await,
+            last_acked_utterance_index,
+        )
+    } else {
+        resume
+            .take_replay(&sess_id, last_acked_utterance_index)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(session_id = %sess_id, error = %e, "Failed to load session replay buffer");
+                Vec::new()
+            })
+    };
+
+    let ack = SessionMessage::SessionResumeAck {
+        session_id: sess_id.clone(),
+        trace_id: session.trace_id.clone(),
+        resume_token: resume.issue_token(&sess_id).await,
+        next_utterance_index: session.utterance_index,
+        replayed_count: replay.len(),
+        use_binary_frame: super::negotiate_binary_frame(state, supports_binary_frame),
+    };
+    send_message(tx, &ack).await?;
+    for msg in &replay {
+        send_message(tx, msg).await?;
+    }
+
+    info!(
+        trace_id = %session.trace_id,
+        session_id = %sess_id,
+        live_takeover = live_takeover,
+        actor_reused = actor_attached,
+        replayed = replay.len(),
+        "Session resumed"
+    );
+    Ok(())
+}
+
+/// 接管其他实例断开的会话：恢复 Session、结果队列与租户名额
+async fn adopt_session(state: &AppState, mut session: Session, last_acked_utterance_index: Option<u64>) -> Session {
+    // 原实例在断开后可能又切出了 utterance，以客户端确认位置为下限
+    if let Some(acked) = last_acked_utterance_index {
+        session.utterance_index = session.utterance_index.max(acked + 1);
+    }
+    state.session_manager.restore_session(session.clone()).await;
+    state
+        .result_queue
+        .initialize_session(session.session_id.clone())
+        .await;
+    if let Some(tenant_id) = session.tenant_id.as_deref() {
+        state.tenant_quota.adopt(tenant_id, &session.session_id).await;
+    }
+    session
+}
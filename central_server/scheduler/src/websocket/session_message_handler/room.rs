@@ -19,10 +19,21 @@ pub(super) async fn handle_room_create(
     let sess_id = require_session_id(session_id)?;
 
     // 创建房间（创建者自动成为第一个成员）
-    let (room_code, room_id) = state
+    let (room_code, room_id) = match state
         .room_manager
         .create_room(sess_id.clone(), display_name, preferred_lang)
-        .await;
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            let error_msg = SessionMessage::RoomError {
+                code: "INTERNAL_ERROR".to_string(),
+                message: Some(e.to_string()),
+            };
+            send_message(tx, &error_msg).await?;
+            return Ok(());
+        }
+    };
 
     // 获取成员列表（包含创建者）
     if let Some(members) = state.room_manager.get_room_members(&room_code).await {
@@ -79,14 +90,8 @@ pub(super) async fn handle_room_join(
                 };
                 send_message(tx, &members_msg).await?;
 
-                // Broadcast member list update to other members in room
-                for member in members {
-                    if member.session_id != *sess_id {
-                        if let Some(member_tx) = state.session_connections.get(&member.session_id).await {
-                            let _ = send_message(&member_tx, &members_msg).await;
-                        }
-                    }
-                }
+                // Broadcast member list update to other members in room (including members on other instances)
+                crate::redis_runtime::broadcast_to_room_members(state, &members, &members_msg, Some(sess_id)).await;
             }
 
             info!(session_id = %sess_id, room_code = %room_code, "Member joined room");
@@ -95,6 +100,7 @@ pub(super) async fn handle_room_join(
             let error_code = match e {
                 crate::managers::room_manager::RoomError::RoomNotFound => "ROOM_NOT_FOUND",
                 crate::managers::room_manager::RoomError::AlreadyInRoom => "ALREADY_IN_ROOM",
+                crate::managers::room_manager::RoomError::StorageUnavailable => "INTERNAL_ERROR",
             };
             let error_msg = SessionMessage::RoomError {
                 code: error_code.to_string(),
@@ -125,11 +131,7 @@ pub(super) async fn handle_room_leave(
                         members: members.clone(),
                     };
                     // 向房间内所有成员广�?
-                    for member in members {
-                        if let Some(member_tx) = state.session_connections.get(&member.session_id).await {
-                            let _ = send_message(&member_tx, &members_msg).await;
-                        }
-                    }
+                    crate::redis_runtime::broadcast_to_room_members(state, &members, &members_msg, None).await;
                 }
             }
             info!(room_code = %room_code, "Member left room");
@@ -173,11 +175,7 @@ pub(super) async fn handle_room_raw_voice_preference(
                     members: members.clone(),
                 };
                 // 向房间内所有成员广�?
-                for member in members {
-                    if let Some(member_tx) = state.session_connections.get(&member.session_id).await {
-                        let _ = send_message(&member_tx, &members_msg).await;
-                    }
-                }
+                crate::redis_runtime::broadcast_to_room_members(state, &members, &members_msg, None).await;
             }
             info!(
                 room_code = %room_code,
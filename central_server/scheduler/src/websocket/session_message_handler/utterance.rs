@@ -132,7 +132,7 @@ pub(super) async fn handle_utterance(
             }
             
             // 原子占用成功，可以安全派发
-            // Note: In current implementation, JobAssign doesn't have ASR result yet, so group_id, part_index, context_text are None
+            // Note: JobAssign 尚无本句 ASR 结果，group_id/part_index 为 None；context_text 取自 Job（创建时的 Group 上下文）
             // 记录派发开始时间（用于计算 dispatch_latency）
             let dispatch_start = std::time::Instant::now();
             if let Some(job_assign_msg) = create_job_assign_message(state, &job, None, None, None).await {
@@ -1,12 +1,21 @@
 use crate::core::AppState;
 use crate::messages::SessionMessage;
-use crate::websocket::send_message;
+use axum::extract::ws::Message as WsMessage;
 use tracing::{info, warn};
 
 fn require_session_id(session_id: &Option<String>) -> anyhow::Result<&String> {
     session_id.as_ref().ok_or_else(|| anyhow::anyhow!("Session not initialized"))
 }
 
+/// 转发信令到房间成员：本实例持有的会话直接发送，其余经 Streams 投递到持有该会话的实例
+async fn relay_to_member(state: &AppState, to: &str, msg: SessionMessage) -> anyhow::Result<bool> {
+    let json = serde_json::to_string(&msg)?;
+    if state.session_connections.send(to, WsMessage::Text(json)).await {
+        return Ok(true);
+    }
+    Ok(state.redis_runtime.is_some() && crate::redis_runtime::send_session_message_routed(state, to, msg).await)
+}
+
 pub(super) async fn handle_webrtc_offer(
     state: &AppState,
     session_id: &Option<String>,
@@ -28,16 +37,15 @@ pub(super) async fn handle_webrtc_offer(
     }
 
     // Forward offer to target member
-    if let Some(target_tx) = state.session_connections.get(&to).await {
-        let offer_msg = SessionMessage::WebRTCOffer {
-            room_code: room_code.clone(),
-            to: sess_id.clone(), // Reverse direction: to becomes from
-            sdp: sdp.clone(),
-        };
-        send_message(&target_tx, &offer_msg).await?;
+    let offer_msg = SessionMessage::WebRTCOffer {
+        room_code: room_code.clone(),
+        to: sess_id.clone(), // Reverse direction: to becomes from
+        sdp,
+    };
+    if relay_to_member(state, &to, offer_msg).await? {
         info!(room_code = %room_code, from = %sess_id, to = %to, "WebRTC Offer forwarded");
     } else {
-        warn!(room_code = %room_code, to = %to, "WebRTC Offer forward failed: target member offline");
+        warn!(room_code = %room_code, to = %to, "WebRTC Offer forward failed: target member unreachable");
     }
     Ok(())
 }
@@ -63,16 +71,15 @@ pub(super) async fn handle_webrtc_answer(
     }
 
     // Forward answer to target member
-    if let Some(target_tx) = state.session_connections.get(&to).await {
-        let answer_msg = SessionMessage::WebRTCAnswer {
-            room_code: room_code.clone(),
-            to: sess_id.clone(), // Reverse direction: to becomes from
-            sdp: sdp.clone(),
-        };
-        send_message(&target_tx, &answer_msg).await?;
+    let answer_msg = SessionMessage::WebRTCAnswer {
+        room_code: room_code.clone(),
+        to: sess_id.clone(), // Reverse direction: to becomes from
+        sdp,
+    };
+    if relay_to_member(state, &to, answer_msg).await? {
         info!(room_code = %room_code, from = %sess_id, to = %to, "WebRTC Answer forwarded");
     } else {
-        warn!(room_code = %room_code, to = %to, "WebRTC Answer forward failed: target member offline");
+        warn!(room_code = %room_code, to = %to, "WebRTC Answer forward failed: target member unreachable");
     }
     Ok(())
 }
@@ -98,15 +105,13 @@ pub(super) async fn handle_webrtc_ice(
     }
 
     // Forward ICE candidate to target member
-    if let Some(target_tx) = state.session_connections.get(&to).await {
-        let ice_msg = SessionMessage::WebRTCIce {
-            room_code: room_code.clone(),
-            to: sess_id.clone(), // Reverse direction: to becomes from
-            candidate: candidate.clone(),
-        };
-        send_message(&target_tx, &ice_msg).await?;
-    } else {
-        warn!(room_code = %room_code, to = %to, "WebRTC ICE forward failed: target member offline");
+    let ice_msg = SessionMessage::WebRTCIce {
+        room_code: room_code.clone(),
+        to: sess_id.clone(), // Reverse direction: to becomes from
+        candidate,
+    };
+    if !relay_to_member(state, &to, ice_msg).await? {
+        warn!(room_code = %room_code, to = %to, "WebRTC ICE forward failed: target member unreachable");
     }
 
     Ok(())
@@ -0,0 +1,33 @@
+// 音频时长估算（限流/计量使用，不解码音频）
+
+/// 压缩格式（opus 等）按 32kbps 估算的字节率
+pub const COMPRESSED_AUDIO_BYTES_PER_SECOND: f64 = 4000.0;
+
+/// WAV 标准头部长度
+pub const WAV_HEADER_BYTES: usize = 44;
+
+/// 按格式估算音频秒数
+///
+/// - pcm16：按 16bit 单声道精确计算
+/// - wav：扣除 44 字节头后按 pcm16 计算
+/// - 其他压缩格式（opus 等）：按 32kbps 估算
+pub fn estimate_audio_seconds(audio_format: &str, byte_len: usize, sample_rate: u32) -> f64 {
+    let sample_rate = sample_rate.max(1) as f64;
+    match audio_format {
+        "pcm16" | "pcm" => byte_len as f64 / 2.0 / sample_rate,
+        "wav" => byte_len.saturating_sub(WAV_HEADER_BYTES) as f64 / 2.0 / sample_rate,
+        _ => byte_len as f64 / COMPRESSED_AUDIO_BYTES_PER_SECOND,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pcm16_is_exact() {
+        assert_eq!(estimate_audio_seconds("pcm16", 32_000, 16_000), 1.0);
+        assert_eq!(estimate_audio_seconds("wav", 32_044, 16_000), 1.0);
+        assert_eq!(estimate_audio_seconds("opus", 4_000, 48_000), 1.0);
+    }
+}
@@ -12,6 +12,7 @@ import {
 } from '../../../../shared/protocols/messages';
 import { InferenceService } from '../inference/inference-service';
 import logger from '../logger';
+import { loadNodeConfig } from '../node-config';
 import { HardwareInfoHandler } from './node-agent-hardware';
 import { LanguageCapabilityDetector } from './node-agent-language-capability';
 
@@ -83,6 +84,8 @@ export class RegistrationHandler {
       const featuresSupported = this.inferenceService.getFeaturesSupported();
       logger.debug({ features: featuresSupported }, 'Features supported retrieved');
 
+      const schedulerConfig = loadNodeConfig().scheduler;
+
       // 对齐协议规范：node_register 消息格式
       const message: NodeRegisterMessage = {
         type: 'node_register',
@@ -97,8 +100,10 @@ export class RegistrationHandler {
         installed_services: installedServicesAll.length > 0 ? installedServicesAll : undefined,
         capability_by_type: capabilityByType,
         features_supported: featuresSupported,
-        accept_public_jobs: true, // TODO: 从配置读取
+        accept_public_jobs: schedulerConfig?.acceptPublicJobs ?? true,
+        owner_tenant_id: schedulerConfig?.ownerTenantId || undefined,
         language_capabilities: languageCapabilities,
+        migration_endpoint: schedulerConfig?.migrationEndpoint || undefined,
       };
 
       const messageStr = JSON.stringify(message);
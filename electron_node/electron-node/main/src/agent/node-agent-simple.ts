@@ -4,14 +4,21 @@
  */
 
 import WebSocket from 'ws';
+import { createHmac } from 'crypto';
 import { InferenceService, JobResult } from '../inference/inference-service';
 import {
   NodeRegisterAckMessage,
+  NodeAuthChallengeMessage,
+  NodeAuthResponseMessage,
+  ErrorMessage,
+  NodeControlMessage,
+  PairingCodeRequestMessage,
+  PairingCodeIssuedMessage,
   JobAssignMessage,
   JobCancelMessage,
   InstalledService,
 } from '../../../../shared/protocols/messages';
-import { loadNodeConfig, getSchedulerUrl, type NodeConfig } from '../node-config';
+import { loadNodeConfig, saveNodeConfig, getSchedulerUrl, type NodeConfig } from '../node-config';
 import logger from '../logger';
 import { ServicesHandlerSimple } from './node-agent-services-simple';
 import { HeartbeatHandler } from './node-agent-heartbeat';
@@ -37,6 +44,8 @@ export { buildResultsToSend };
 const RECONNECT_INITIAL_MS = 5000;
 /** 重连退避：最大间隔（毫秒） */
 const RECONNECT_MAX_MS = 60000;
+/** 调度器拒绝注册后需断开重连的错误码（node_id 被旧连接占用、吊销名单暂不可读等） */
+const RECONNECT_ON_ERROR_CODES = new Set(['NODE_ID_CONFLICT', 'NODE_AUTH_FAILED']);
 
 export class NodeAgent {
   private ws: WebSocket | null = null;
@@ -46,7 +55,7 @@ export class NodeAgent {
   private modelManager: any; // ModelManager 实例
   private nodeConfig: NodeConfig;
 
-  /** 下次重连间隔（指数退避），连接成功后重置 */
+  /** 下次重连间隔（指数退避），注册成功后重置 */
   private reconnectDelayMs = RECONNECT_INITIAL_MS;
   /** 重连定时器，stop 时清除 */
   private reconnectTimer: ReturnType<typeof setTimeout> | null = null;
@@ -54,6 +63,12 @@ export class NodeAgent {
   private suppressReconnectOnClose = false;
   /** capability-state-changed 只注册一次，避免重连时重复挂载 */
   private capabilityListenerAttached = false;
+  /** 等待调度器下发的配对码（同一时间只保留一个请求） */
+  private pendingPairing: {
+    resolve: (issued: PairingCodeIssuedMessage) => void;
+    reject: (error: Error) => void;
+    timer: ReturnType<typeof setTimeout>;
+  } | null = null;
   private readonly onCapabilityStateChanged = (): void => {
     logger.debug({}, 'Model state changed, triggering immediate heartbeat');
     this.heartbeatHandler.triggerImmediateHeartbeat();
@@ -89,6 +104,7 @@ export class NodeAgent {
   ) {
     this.nodeConfig = loadNodeConfig();
     this.schedulerUrl = getSchedulerUrl();
+    this.nodeId = this.nodeConfig.scheduler?.nodeId || null;
 
     this.inferenceService = inferenceService;
     this.modelManager = modelManager || (inferenceService as any).modelManager;
@@ -193,7 +209,6 @@ export class NodeAgent {
       this.ws = new WebSocket(this.schedulerUrl);
 
       this.ws.on('open', () => {
-        this.reconnectDelayMs = RECONNECT_INITIAL_MS;
         logger.info(
           { schedulerUrl: this.schedulerUrl, nodeId: this.nodeId },
           'Connected to scheduler server'
@@ -287,7 +302,11 @@ export class NodeAgent {
       switch (message.type) {
         case 'node_register_ack': {
           const ack = message as NodeRegisterAckMessage;
+          this.reconnectDelayMs = RECONNECT_INITIAL_MS;
           this.nodeId = ack.node_id;
+          if (ack.node_credential) {
+            this.saveNodeCredential(ack.node_credential);
+          }
           this.inferenceService.setNodeId(this.nodeId);
           this.heartbeatHandler.updateConnection(this.ws, this.nodeId);
           this.registrationHandler.updateConnection(this.ws, this.nodeId);
@@ -303,6 +322,39 @@ export class NodeAgent {
           break;
         }
 
+        case 'node_auth_challenge': {
+          this.respondAuthChallenge(message as NodeAuthChallengeMessage);
+          break;
+        }
+
+        case 'node_control': {
+          // drain：调度器已将本节点移出池，在途任务继续完成；drain_complete：会话已迁移、节点已下线，可安全停机升级
+          const control = message as NodeControlMessage;
+          logger.info({ nodeId: this.nodeId, command: control.command, reason: control.reason }, 'Received node_control from scheduler');
+          break;
+        }
+
+        case 'pairing_code_issued': {
+          const issued = message as PairingCodeIssuedMessage;
+          logger.info({ nodeId: issued.node_id, expiresAtMs: issued.expires_at_ms }, 'Pairing code issued by scheduler');
+          this.settlePairing(issued);
+          break;
+        }
+
+        case 'error': {
+          const err = message as ErrorMessage;
+          logger.error({ code: err.code, message: err.message }, 'Scheduler rejected node request');
+          if (err.code === 'INVALID_PAIRING_CODE') {
+            this.settlePairing(new Error(err.message));
+          }
+          if (RECONNECT_ON_ERROR_CODES.has(err.code) && this.ws) {
+            // 未注册成功：断开后由 close 回调按退避重连（旧连接下线后即可注册）
+            logger.warn({ code: err.code, nodeId: this.nodeId, nextRetryMs: this.reconnectDelayMs }, 'Registration rejected, reconnecting with backoff');
+            this.ws.close();
+          }
+          break;
+        }
+
         case 'job_assign': {
           const job = message as JobAssignMessage;
           logger.info(
@@ -335,6 +387,79 @@ export class NodeAgent {
     }
   }
 
+  /** 向调度器申请配对码（需已注册）；客户端在 session_init.pairing_code 中出示后，该会话只派发给本节点 */
+  requestPairingCode(ttlSeconds?: number, timeoutMs = 10000): Promise<PairingCodeIssuedMessage> {
+    if (!this.nodeId || !this.ws || this.ws.readyState !== WebSocket.OPEN) {
+      return Promise.reject(new Error('Node is not registered with scheduler'));
+    }
+    this.settlePairing(new Error('Superseded by a new pairing code request'));
+    const request: PairingCodeRequestMessage = {
+      type: 'pairing_code_request',
+      node_id: this.nodeId,
+      ttl_seconds: ttlSeconds,
+    };
+    return new Promise((resolve, reject) => {
+      const timer = setTimeout(() => this.settlePairing(new Error('Pairing code request timed out')), timeoutMs);
+      this.pendingPairing = { resolve, reject, timer };
+      this.ws!.send(JSON.stringify(request));
+    });
+  }
+
+  private settlePairing(result: PairingCodeIssuedMessage | Error): void {
+    const pending = this.pendingPairing;
+    if (!pending) {
+      return;
+    }
+    this.pendingPairing = null;
+    clearTimeout(pending.timer);
+    if (result instanceof Error) {
+      pending.reject(result);
+    } else {
+      pending.resolve(result);
+    }
+  }
+
+  /** 节点认证：优先用长期节点凭证，其次 enrollment token（首次接入）、预共享密钥对 challenge 签名 */
+  private respondAuthChallenge(challenge: NodeAuthChallengeMessage): void {
+    const { nodeSecret, enrollmentToken, nodeCredential } = this.nodeConfig.scheduler ?? {};
+    const secret = nodeCredential || enrollmentToken || nodeSecret;
+    if (!secret || !this.ws || this.ws.readyState !== WebSocket.OPEN) {
+      logger.error({ nodeId: challenge.node_id }, 'Node auth challenge received but no nodeCredential/enrollmentToken/nodeSecret configured');
+      return;
+    }
+    const response: NodeAuthResponseMessage = {
+      type: 'node_auth_response',
+      node_id: challenge.node_id,
+      signature: createHmac('sha256', secret).update(`${challenge.nonce}.${challenge.node_id}`).digest('hex'),
+    };
+    if (nodeCredential) {
+      response.node_credential = true;
+    } else if (enrollmentToken) {
+      response.enrollment_expires_at = Number(enrollmentToken.split('.')[0]);
+    }
+    this.ws.send(JSON.stringify(response));
+    logger.info(
+      { nodeId: challenge.node_id, credential: !!nodeCredential, enrollment: !nodeCredential && !!enrollmentToken },
+      'Node auth response sent'
+    );
+  }
+
+  /** 保存调度器下发的长期节点凭证（enrollment token 过期后仍可认证） */
+  private saveNodeCredential(nodeCredential: string): void {
+    if (this.nodeConfig.scheduler?.nodeCredential === nodeCredential) {
+      return;
+    }
+    this.nodeConfig.scheduler = { ...this.nodeConfig.scheduler, nodeCredential };
+    try {
+      const persisted = loadNodeConfig();
+      persisted.scheduler = { ...persisted.scheduler, nodeCredential };
+      saveNodeConfig(persisted);
+      logger.info({ nodeId: this.nodeId }, 'Node credential saved');
+    } catch (error) {
+      logger.error({ error, nodeId: this.nodeId }, 'Failed to save node credential');
+    }
+  }
+
   private async handleJob(job: JobAssignMessage): Promise<void> {
     if (!this.ws || this.ws.readyState !== WebSocket.OPEN || !this.nodeId) {
       logger.warn(
@@ -1,6 +1,6 @@
 /**
- * TranslationStage：节点端不传 context_text
- * 约定：节点端一律传 undefined，由 NMT 服务自行处理上下文。
+ * TranslationStage：节点端不自行拼接 context_text
+ * 约定：仅透传 JobAssign.context_text（调度端 Group 上下文），否则为 undefined。
  */
 
 import { TranslationStage } from './translation-stage';
@@ -77,6 +77,21 @@ describe('TranslationStage - context_text（节点端不传）', () => {
     const task = (mockTaskRouter.routeNMTTask as jest.Mock).mock.calls[0][0];
     expect(task.context_text).toBeUndefined();
   });
+
+  it('JobAssign 携带调度端 Group 上下文时，原样透传给 NMT', async () => {
+    mockAggregatorManager = {
+      getLastCommittedText: jest.fn().mockReturnValue('上一句原文。'),
+      setLastTranslatedText: jest.fn(),
+    } as any;
+    const groupContext = '他昨天来了';
+
+    const stage = new TranslationStage(mockTaskRouter, mockAggregatorManager, {});
+    await stage.process({ ...createJob(1), context_text: groupContext }, '他今天也来');
+
+    const task = (mockTaskRouter.routeNMTTask as jest.Mock).mock.calls[0][0];
+    expect(task.text).toBe('他今天也来');
+    expect(task.context_text).toBe(groupContext);
+  });
 });
 
 describe('TranslationStage - 模拟语义修复输出，验证 NMT 处理流程', () => {
@@ -108,8 +108,10 @@ export class TranslationStage {
       };
     }
 
-    // 节点端不传 context_text，由 NMT 服务自行处理上下文；避免节点端拼接/截断导致译文空或合并错误。
-    const contextText: string | undefined = undefined;
+    // 节点端不自行拼接 context_text（避免拼接/截断导致译文空或合并错误）；
+    // 仅透传调度端随 JobAssign 下发的 Utterance Group 上下文（此前的源语言原文，NMT 会与当前句一同按源语言编码），
+    // 供 NMT 处理代词与术语一致性。
+    const contextText: string | undefined = job.context_text || undefined;
     const cacheKey = generateCacheKey(
       job.src_lang,
       job.tgt_lang,
@@ -24,7 +24,23 @@ export interface NodeConfig {
   servicePreferences: ServicePreferences;
   /** 上次退出时的运行快照（不覆盖 servicePreferences） */
   serviceLastRuntimeState?: ServiceLastRuntimeState;
-  scheduler?: { url?: string };
+  scheduler?: {
+    url?: string;
+    /** 固定 node_id（调度器开启节点认证时必填） */
+    nodeId?: string;
+    /** 预共享密钥（与 enrollmentToken 二选一） */
+    nodeSecret?: string;
+    /** 调度器签发的 enrollment token（"<expires_at>.<sig>"），只用于首次接入 */
+    enrollmentToken?: string;
+    /** 长期节点凭证（首次用 enrollment token 认证成功后由调度器下发并自动保存） */
+    nodeCredential?: string;
+    /** 会话迁移接口地址（http(s)://host:port），注册时上报给调度器 */
+    migrationEndpoint?: string;
+    /** false：私有节点，不进入公共池（默认 true） */
+    acceptPublicJobs?: boolean;
+    /** 私有节点所属租户（该租户的任务优先派发到本节点）；须与 enrollment token 绑定的租户或调度器配置一致，否则注册被拒绝 */
+    ownerTenantId?: string;
+  };
   modelHub?: { url?: string };
   services?: {
     baseUrl?: string;
@@ -47,7 +47,8 @@ export class PipelineOrchestratorASRHandler {
    * 构建S1 prompt（如果启用）
    */
   buildPrompt(job: JobAssignMessage): string | undefined {
-    let contextText = (job as any).context_text;  // 保留原有的context_text
+    // job.context_text 为调度端 Utterance Group 的 NMT 上下文（此前的源语言原文），不作为 ASR prompt
+    let contextText: string | undefined = undefined;
     if (this.enableS1PromptBias && this.aggregatorManager && this.promptBuilder && job.session_id) {
       try {
         const state = this.aggregatorManager.getOrCreateState(job.session_id, 'offline');
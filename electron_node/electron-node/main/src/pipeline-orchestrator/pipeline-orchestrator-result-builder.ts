@@ -65,9 +65,9 @@ export class PipelineOrchestratorResultBuilder {
       tts_audio: '',  // TTS 也由 PostProcess 处理
       tts_format: 'pcm16',
       extra: {
-        emotion: undefined,
-        speech_rate: undefined,
-        voice_style: undefined,
+        emotion: asrResult.emotion,
+        speech_rate: asrResult.speech_rate,
+        voice_style: asrResult.voice_style,
         language_probability: asrResult.language_probability,  // 新增：检测到的语言的概率
         language_probabilities: asrResult.language_probabilities,  // 新增：所有语言的概率信息
       },
@@ -96,9 +96,9 @@ export class PipelineOrchestratorResultBuilder {
       tts_audio: '',
       tts_format: 'pcm16',
       extra: {
-        emotion: undefined,
-        speech_rate: undefined,
-        voice_style: undefined,
+        emotion: asrResult?.emotion,
+        speech_rate: asrResult?.speech_rate,
+        voice_style: asrResult?.voice_style,
         language_probability: asrResult?.language_probability,
         language_probabilities: asrResult?.language_probabilities,
       },
@@ -118,9 +118,9 @@ export class PipelineOrchestratorResultBuilder {
       tts_audio: '',
       tts_format: 'pcm16',
       extra: {
-        emotion: undefined,
-        speech_rate: undefined,
-        voice_style: undefined,
+        emotion: asrResult.emotion,
+        speech_rate: asrResult.speech_rate,
+        voice_style: asrResult.voice_style,
         language_probability: asrResult.language_probability,
         language_probabilities: asrResult.language_probabilities,
       },
@@ -25,6 +25,9 @@ export function buildCoreResultExtra(job: JobAssignMessage, ctx: JobContext): Re
     ...(ctx.fwDetectorResult ? { fw_detector: ctx.fwDetectorResult } : {}),
     ...buildSessionResultExtra(job, ctx),
     ...(ctx.asrResult?.tone ? { utterance_tone: ctx.asrResult.tone } : {}),
+    ...(ctx.asrResult?.emotion ? { emotion: ctx.asrResult.emotion } : {}),
+    ...(ctx.asrResult?.voice_style ? { voice_style: ctx.asrResult.voice_style } : {}),
+    ...(ctx.asrResult?.speech_rate != null ? { speech_rate: ctx.asrResult.speech_rate } : {}),
     ...(ctx.lexiconManifestReady ? { lexicon_manifest_ready: ctx.lexiconManifestReady } : {}),
     ...(ctx.duplicateSanitizeTrace ? { duplicate_sanitize: ctx.duplicateSanitizeTrace } : {}),
   };
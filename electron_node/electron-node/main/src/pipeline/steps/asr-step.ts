@@ -52,7 +52,7 @@ export async function runAsrStep(
   );
 
   // 构建 prompt（如果启用）
-  const contextText = asrHandler.buildPrompt(job) || (job as any).context_text;
+  const contextText = asrHandler.buildPrompt(job);
 
   // 处理音频：聚合和格式转换
   const audioProcessResult = await audioProcessor.processAudio(job);
@@ -1,5 +1,5 @@
 import { describe, it, expect } from '@jest/globals';
-import { mapCtcUtteranceResponse } from './asr-response-mapper';
+import { mapCtcUtteranceResponse, pickAsrAffect } from './asr-response-mapper';
 
 describe('mapCtcUtteranceResponse', () => {
   it('maps nbest and kenlm when present', () => {
@@ -67,3 +67,15 @@ describe('mapCtcUtteranceResponse', () => {
     expect(nbest!.length).toBe(2);
   });
 });
+
+describe('pickAsrAffect', () => {
+  it('copies typed affect fields and drops malformed ones', () => {
+    expect(pickAsrAffect({ emotion: 'happy', voice_style: 'calm', speech_rate: 4.2 })).toEqual({
+      emotion: 'happy',
+      voice_style: 'calm',
+      speech_rate: 4.2,
+    });
+    expect(pickAsrAffect({ emotion: '', speech_rate: 'fast' })).toEqual({});
+    expect(pickAsrAffect(null)).toEqual({});
+  });
+});
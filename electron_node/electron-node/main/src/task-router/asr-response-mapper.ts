@@ -7,6 +7,7 @@
  */
 
 import { AsrKenlmMeta, AsrNBestItem } from './asr-evidence-types';
+import type { ASRResult } from './types';
 
 export type CtcUtteranceEvidence = {
   nbest?: AsrNBestItem[];
@@ -184,3 +185,22 @@ export function mapCtcUtteranceResponse(data: unknown): CtcUtteranceEvidence {
     ...(kenlmMeta ? { kenlmMeta } : {}),
   };
 }
+
+/**
+ * Pick emotion / voice style / speech rate from an ASR response body (any backend).
+ * Only well-typed fields are copied; absent or malformed fields are omitted.
+ */
+export function pickAsrAffect(data: unknown): Pick<ASRResult, 'emotion' | 'voice_style' | 'speech_rate'> {
+  const record = asRecord(data);
+  if (!record) {
+    return {};
+  }
+  const emotion = readString(record.emotion);
+  const voiceStyle = readString(record.voice_style);
+  const speechRate = readNumber(record.speech_rate);
+  return {
+    ...(emotion ? { emotion } : {}),
+    ...(voiceStyle ? { voice_style: voiceStyle } : {}),
+    ...(speechRate !== undefined ? { speech_rate: speechRate } : {}),
+  };
+}
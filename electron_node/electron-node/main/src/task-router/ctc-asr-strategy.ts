@@ -6,7 +6,7 @@
 import { AxiosInstance } from 'axios';
 import logger from '../logger';
 import { ASRTask, ASRResult, ServiceEndpoint } from './types';
-import { mapCtcUtteranceResponse } from './asr-response-mapper';
+import { mapCtcUtteranceResponse, pickAsrAffect } from './asr-response-mapper';
 import { postASRUtteranceRequest } from './task-router-asr-http';
 import { ASRMetricsHandler } from './task-router-asr-metrics';
 
@@ -69,6 +69,7 @@ export async function executeCTCASR(
     is_final: true,
     ...(evidence.nbest ? { nbest: evidence.nbest } : {}),
     ...(evidence.kenlmMeta ? { kenlmMeta: evidence.kenlmMeta } : {}),
+    ...pickAsrAffect(data),
   };
   if (data.text_zh != null || data.text_en != null) {
     (asrResult as any).text_zh = data.text_zh ?? '';
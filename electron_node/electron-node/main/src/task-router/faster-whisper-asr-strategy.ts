@@ -7,6 +7,7 @@ import { AxiosInstance } from 'axios';
 import logger from '../logger';
 import { ASRTask, ASRResult, ServiceEndpoint } from './types';
 import { detectBadSegment } from './bad-segment-detector';
+import { pickAsrAffect } from './asr-response-mapper';
 import { postASRUtteranceRequest } from './task-router-asr-http';
 import { ASRRerunHandler } from './task-router-asr-rerun';
 import { ASRMetricsHandler } from './task-router-asr-metrics';
@@ -89,6 +90,7 @@ export async function executeFasterWhisperASR(
     is_final: true,
     ...(response.data.diagnostics ? { diagnostics: response.data.diagnostics } : {}),
     ...(response.data.tone ? { tone: response.data.tone } : {}),
+    ...pickAsrAffect(response.data),
   };
 
   const audioDurationMs = response.data.duration
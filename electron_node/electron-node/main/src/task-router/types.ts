@@ -116,6 +116,10 @@ export interface ASRResult {
   diagnostics?: Record<string, unknown>;
   /** Phase3 ToneModule — acoustic tone slices from FW Worker. */
   tone?: UtteranceAcousticTonePayload;
+  /** ASR 服务启用情感 / 语音风格 / 语速识别时返回（写入 JobResult.extra） */
+  emotion?: string;
+  voice_style?: string;
+  speech_rate?: number;  // 音节/秒
 }
 
 /**
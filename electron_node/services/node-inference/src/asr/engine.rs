@@ -7,6 +7,7 @@ use std::sync::Arc;
 use tracing::info;
 use whisper_rs::{WhisperContext, WhisperContextParameters};
 
+use super::stream::ASRStreamState;
 use super::whisper_run;
 
 /// ASR 部分结果
@@ -22,10 +23,8 @@ pub struct ASREngine {
     ctx: Arc<WhisperContext>,
     model_path: PathBuf,
     language: Option<String>,
-    audio_buffer: Arc<tokio::sync::Mutex<Vec<f32>>>,
-    streaming_enabled: Arc<tokio::sync::Mutex<bool>>,
-    partial_update_interval_ms: Arc<tokio::sync::Mutex<u64>>,
-    last_partial_update_ms: Arc<tokio::sync::Mutex<u64>>,
+    /// 引擎级流式缓冲（单会话调用方使用；多会话请使用 *_with_state 方法）
+    stream_state: Arc<tokio::sync::Mutex<ASRStreamState>>,
 }
 
 impl ASREngine {
@@ -73,10 +72,7 @@ impl ASREngine {
             ctx: Arc::new(ctx),
             model_path: model_path.to_path_buf(),
             language: None,
-            audio_buffer: Arc::new(tokio::sync::Mutex::new(Vec::new())),
-            streaming_enabled: Arc::new(tokio::sync::Mutex::new(false)),
-            partial_update_interval_ms: Arc::new(tokio::sync::Mutex::new(1000)),
-            last_partial_update_ms: Arc::new(tokio::sync::Mutex::new(0)),
+            stream_state: Arc::new(tokio::sync::Mutex::new(ASRStreamState::new())),
         })
     }
 
@@ -142,8 +138,7 @@ impl ASREngine {
     }
 
     pub async fn enable_streaming(&self, partial_update_interval_ms: u64) {
-        *self.streaming_enabled.lock().await = true;
-        *self.partial_update_interval_ms.lock().await = partial_update_interval_ms;
+        self.stream_state.lock().await.enable(partial_update_interval_ms);
         info!(
             "ASR streaming enabled with interval: {}ms",
             partial_update_interval_ms
@@ -151,49 +146,54 @@ impl ASREngine {
     }
 
     pub async fn disable_streaming(&self) {
-        *self.streaming_enabled.lock().await = false;
-        self.clear_buffer().await;
+        self.stream_state.lock().await.disable();
     }
 
     pub async fn is_streaming_enabled(&self) -> bool {
-        *self.streaming_enabled.lock().await
+        self.stream_state.lock().await.is_enabled()
     }
 
     pub async fn accumulate_audio(&self, audio_data: &[f32]) {
-        let mut buffer = self.audio_buffer.lock().await;
-        buffer.extend_from_slice(audio_data);
+        self.stream_state.lock().await.accumulate(audio_data);
     }
 
     pub async fn clear_buffer(&self) {
-        let mut buffer = self.audio_buffer.lock().await;
-        buffer.clear();
-        *self.last_partial_update_ms.lock().await = 0;
+        self.stream_state.lock().await.clear();
     }
 
     pub async fn get_partial_result(&self, current_timestamp_ms: u64, lang: &str) -> Result<Option<ASRPartialResult>> {
-        let streaming_enabled = *self.streaming_enabled.lock().await;
-        if !streaming_enabled {
-            return Ok(None);
+        let audio_data = self.stream_state.lock().await.take_partial_audio(current_timestamp_ms);
+        match audio_data {
+            Some(audio_data) => self.run_partial(audio_data, lang).await,
+            None => Ok(None),
         }
+    }
 
-        let partial_interval = *self.partial_update_interval_ms.lock().await;
-        let last_update = *self.last_partial_update_ms.lock().await;
+    pub async fn get_final_result(&self, lang: &str) -> Result<String> {
+        let audio_data = self.stream_state.lock().await.take_final_audio();
+        self.run_final(audio_data, lang).await
+    }
 
-        if current_timestamp_ms < last_update + partial_interval {
-            return Ok(None);
+    /// 使用调用方持有的会话级缓冲获取部分结果（多会话并发时使用）
+    pub async fn get_partial_result_with_state(
+        &self,
+        state: &mut ASRStreamState,
+        current_timestamp_ms: u64,
+        lang: &str,
+    ) -> Result<Option<ASRPartialResult>> {
+        match state.take_partial_audio(current_timestamp_ms) {
+            Some(audio_data) => self.run_partial(audio_data, lang).await,
+            None => Ok(None),
         }
+    }
 
-        *self.last_partial_update_ms.lock().await = current_timestamp_ms;
-
-        let audio_data = {
-            let buffer = self.audio_buffer.lock().await;
-            buffer.clone()
-        };
-
-        if audio_data.is_empty() {
-            return Ok(None);
-        }
+    /// 使用调用方持有的会话级缓冲获取最终结果（多会话并发时使用）
+    pub async fn get_final_result_with_state(&self, state: &mut ASRStreamState, lang: &str) -> Result<String> {
+        let audio_data = state.take_final_audio();
+        self.run_final(audio_data, lang).await
+    }
 
+    async fn run_partial(&self, audio_data: Vec<f32>, lang: &str) -> Result<Option<ASRPartialResult>> {
         let language = if lang.is_empty() {
             self.language.clone()
         } else {
@@ -235,15 +235,7 @@ impl ASREngine {
         }
     }
 
-    pub async fn get_final_result(&self, lang: &str) -> Result<String> {
-        let audio_data = {
-            let mut buffer = self.audio_buffer.lock().await;
-            let data = buffer.clone();
-            buffer.clear();
-            *self.last_partial_update_ms.lock().await = 0;
-            data
-        };
-
+    async fn run_final(&self, audio_data: Vec<f32>, lang: &str) -> Result<String> {
         if audio_data.is_empty() {
             return Ok(String::new());
         }
@@ -1,6 +1,8 @@
 //! ASR 模块
 
 mod engine;
+mod stream;
 mod whisper_run;
 
 pub use engine::{ASREngine, ASRPartialResult};
+pub use stream::ASRStreamState;
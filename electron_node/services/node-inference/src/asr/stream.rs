@@ -0,0 +1,75 @@
+//! 流式 ASR 缓冲状态（每个请求一份，避免并发请求的音频互相串入）
+
+/// 流式 ASR 缓冲状态
+#[derive(Debug)]
+pub struct ASRStreamState {
+    audio_buffer: Vec<f32>,
+    streaming_enabled: bool,
+    partial_update_interval_ms: u64,
+    last_partial_update_ms: u64,
+}
+
+impl Default for ASRStreamState {
+    fn default() -> Self {
+        Self {
+            audio_buffer: Vec::new(),
+            streaming_enabled: false,
+            partial_update_interval_ms: 1000,
+            last_partial_update_ms: 0,
+        }
+    }
+}
+
+impl ASRStreamState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn enable(&mut self, partial_update_interval_ms: u64) {
+        self.streaming_enabled = true;
+        self.partial_update_interval_ms = partial_update_interval_ms;
+    }
+
+    pub fn disable(&mut self) {
+        self.streaming_enabled = false;
+        self.clear();
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.streaming_enabled
+    }
+
+    pub fn accumulate(&mut self, audio_data: &[f32]) {
+        self.audio_buffer.extend_from_slice(audio_data);
+    }
+
+    pub fn clear(&mut self) {
+        self.audio_buffer.clear();
+        self.last_partial_update_ms = 0;
+    }
+
+    pub fn buffered_samples(&self) -> usize {
+        self.audio_buffer.len()
+    }
+
+    /// 到达部分结果更新间隔时返回当前缓冲音频的副本
+    pub(crate) fn take_partial_audio(&mut self, current_timestamp_ms: u64) -> Option<Vec<f32>> {
+        if !self.streaming_enabled {
+            return None;
+        }
+        if current_timestamp_ms < self.last_partial_update_ms + self.partial_update_interval_ms {
+            return None;
+        }
+        self.last_partial_update_ms = current_timestamp_ms;
+        if self.audio_buffer.is_empty() {
+            return None;
+        }
+        Some(self.audio_buffer.clone())
+    }
+
+    /// 取出全部缓冲音频用于最终结果，并清空缓冲
+    pub(crate) fn take_final_audio(&mut self) -> Vec<f32> {
+        self.last_partial_update_ms = 0;
+        std::mem::take(&mut self.audio_buffer)
+    }
+}
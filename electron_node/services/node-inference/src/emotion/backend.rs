@@ -0,0 +1,89 @@
+//! 情感识别后端接口与 HTTP 后端
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::types::{voice_style_for_emotion, EmotionResult};
+
+/// 情感识别后端
+#[async_trait]
+pub trait EmotionBackend: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// 对 16kHz 单声道 f32 音频做情感分类
+    async fn classify(&self, audio: &[f32]) -> Result<EmotionResult>;
+}
+
+/// HTTP 情感识别服务响应
+#[derive(Debug, Deserialize)]
+struct EmotionHttpResponse {
+    emotion: String,
+    #[serde(default)]
+    confidence: f32,
+    #[serde(default)]
+    voice_style: Option<String>,
+    #[serde(default)]
+    scores: Option<HashMap<String, f32>>,
+}
+
+/// HTTP 情感识别后端（POST {endpoint}/classify，请求体 {"audio": [f32], "sample_rate": 16000}）
+pub struct HttpEmotionBackend {
+    client: Client,
+    endpoint: String,
+}
+
+impl HttpEmotionBackend {
+    pub fn new(endpoint: String, timeout_ms: u64) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_millis(timeout_ms))
+            .build()
+            .map_err(|e| anyhow!("Failed to create HTTP client: {}", e))?;
+        Ok(Self { client, endpoint })
+    }
+}
+
+#[async_trait]
+impl EmotionBackend for HttpEmotionBackend {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    async fn classify(&self, audio: &[f32]) -> Result<EmotionResult> {
+        let request_body = serde_json::json!({
+            "audio": audio,
+            "sample_rate": 16000,
+        });
+        let response = self.client
+            .post(format!("{}/classify", self.endpoint.trim_end_matches('/')))
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| anyhow!("Emotion HTTP request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Emotion HTTP request failed with status {}: {}", status, error_text));
+        }
+
+        let result: EmotionHttpResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse emotion response: {}", e))?;
+
+        let mut scores: Vec<(String, f32)> = result.scores.unwrap_or_default().into_iter().collect();
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(EmotionResult {
+            voice_style: result
+                .voice_style
+                .unwrap_or_else(|| voice_style_for_emotion(&result.emotion).to_string()),
+            emotion: result.emotion,
+            confidence: result.confidence,
+            scores,
+        })
+    }
+}
@@ -0,0 +1,153 @@
+//! 情感 / 语音风格识别模块
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::modules::InferenceModule;
+
+use super::backend::{EmotionBackend, HttpEmotionBackend};
+use super::onnx::OnnxEmotionBackend;
+use super::types::{EmotionResult, NEUTRAL_VOICE_STYLE};
+
+/// 音频过短时不做情感识别（16kHz 下 0.5 秒）
+const MIN_AUDIO_SAMPLES: usize = 8000;
+
+/// 情感识别后端类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmotionBackendKind {
+    /// 本地 ONNX 模型（CPU）
+    Onnx,
+    /// 外部 HTTP 服务
+    Http,
+}
+
+/// 情感识别配置
+#[derive(Debug, Clone)]
+pub struct EmotionDetectorConfig {
+    pub backend: EmotionBackendKind,
+    /// ONNX 模型文件路径
+    pub model_path: PathBuf,
+    /// HTTP 服务端点（例如：http://127.0.0.1:5008）
+    pub service_url: String,
+    /// HTTP 请求超时时间（毫秒）
+    pub timeout_ms: u64,
+    /// 低于该置信度时语音风格回退为 neutral
+    pub min_confidence: f32,
+}
+
+impl EmotionDetectorConfig {
+    /// 从环境变量读取配置
+    ///
+    /// EMOTION_BACKEND（onnx | http）、EMOTION_MODEL_PATH、EMOTION_SERVICE_URL、
+    /// EMOTION_TIMEOUT_MS、EMOTION_MIN_CONFIDENCE
+    pub fn from_env(model_dir: PathBuf) -> Self {
+        let backend = match std::env::var("EMOTION_BACKEND").ok().as_deref() {
+            Some("http") => EmotionBackendKind::Http,
+            _ => EmotionBackendKind::Onnx,
+        };
+        let model_path = std::env::var("EMOTION_MODEL_PATH")
+            .ok()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                let path = model_dir.join("emotion.onnx");
+                if path.exists() { path } else { model_dir.join("model.onnx") }
+            });
+
+        Self {
+            backend,
+            model_path,
+            service_url: std::env::var("EMOTION_SERVICE_URL")
+                .unwrap_or_else(|_| "http://127.0.0.1:5008".to_string()),
+            timeout_ms: std::env::var("EMOTION_TIMEOUT_MS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(3000),
+            min_confidence: std::env::var("EMOTION_MIN_CONFIDENCE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0.4),
+        }
+    }
+}
+
+/// 情感识别模块（同时输出可用于选择 TTS 风格的语音风格）
+pub struct EmotionDetector {
+    enabled: bool,
+    model_loaded: bool,
+    config: EmotionDetectorConfig,
+    backend: Option<Arc<dyn EmotionBackend>>,
+}
+
+#[async_trait]
+impl InferenceModule for EmotionDetector {
+    fn name(&self) -> &str {
+        "emotion_detection"
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled && self.model_loaded
+    }
+
+    async fn enable(&mut self) -> Result<()> {
+        if !self.model_loaded {
+            let backend: Arc<dyn EmotionBackend> = match self.config.backend {
+                EmotionBackendKind::Onnx => Arc::new(OnnxEmotionBackend::load(&self.config.model_path)?),
+                EmotionBackendKind::Http => Arc::new(HttpEmotionBackend::new(
+                    self.config.service_url.clone(),
+                    self.config.timeout_ms,
+                )?),
+            };
+            info!("Emotion detection backend: {}", backend.name());
+            self.backend = Some(backend);
+            self.model_loaded = true;
+        }
+        self.enabled = true;
+        Ok(())
+    }
+
+    async fn disable(&mut self) -> Result<()> {
+        self.enabled = false;
+        Ok(())
+    }
+}
+
+impl EmotionDetector {
+    /// # Arguments
+    /// * `model_dir` - 情感模型目录（未设置 EMOTION_MODEL_PATH 时在此查找 emotion.onnx / model.onnx）
+    pub fn new(model_dir: PathBuf) -> Self {
+        Self::with_config(EmotionDetectorConfig::from_env(model_dir))
+    }
+
+    pub fn with_config(config: EmotionDetectorConfig) -> Self {
+        Self {
+            enabled: false,
+            model_loaded: false,
+            config,
+            backend: None,
+        }
+    }
+
+    /// 识别情感与语音风格
+    ///
+    /// # Arguments
+    /// * `audio` - 音频数据（16kHz 单声道，f32）
+    pub async fn detect(&self, audio: &[f32]) -> Result<EmotionResult> {
+        if !self.is_enabled() {
+            return Err(anyhow!("Emotion detection module is not enabled"));
+        }
+        if audio.len() < MIN_AUDIO_SAMPLES {
+            return Err(anyhow!("Audio too short for emotion detection: {} samples", audio.len()));
+        }
+        let backend = self.backend.as_ref()
+            .ok_or_else(|| anyhow!("Emotion detection backend not loaded"))?;
+
+        let mut result = backend.classify(audio).await?;
+        if result.confidence < self.config.min_confidence {
+            result.voice_style = NEUTRAL_VOICE_STYLE.to_string();
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,14 @@
+//! 情感 / 语音风格识别模块
+//!
+//! 后端：本地 ONNX 模型（CPU，默认）或外部 HTTP 服务（EMOTION_BACKEND=http）。
+//! 输出情感标签与语音风格，语音风格可用于选择 TTS 音色。
+
+mod backend;
+mod detector;
+mod onnx;
+mod types;
+
+pub use backend::{EmotionBackend, HttpEmotionBackend};
+pub use detector::{EmotionBackendKind, EmotionDetector, EmotionDetectorConfig};
+pub use onnx::OnnxEmotionBackend;
+pub use types::{softmax, voice_style_for_emotion, EmotionResult, DEFAULT_EMOTION_LABELS, NEUTRAL_VOICE_STYLE};
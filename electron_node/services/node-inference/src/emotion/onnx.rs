@@ -0,0 +1,108 @@
+//! ONNX 情感识别后端（CPU 推理）
+//!
+//! 模型输入为 16kHz 单声道波形 [1, N]，输出为各情感标签的 logits [1, K]。
+//! 标签顺序读取模型同目录下的 labels.txt（每行一个），不存在时使用默认标签。
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use ort::{session::Session, value::Tensor, Error as OrtError};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::info;
+
+use super::backend::EmotionBackend;
+use super::types::{softmax, EmotionResult, DEFAULT_EMOTION_LABELS};
+
+/// 单次推理最多使用的音频长度（16kHz 下 10 秒），控制 CPU 耗时
+const MAX_INPUT_SAMPLES: usize = 16000 * 10;
+
+/// ONNX 情感识别后端
+pub struct OnnxEmotionBackend {
+    session: Arc<Mutex<Session>>,
+    labels: Vec<String>,
+}
+
+impl OnnxEmotionBackend {
+    pub fn load(model_path: &Path) -> Result<Self> {
+        if !model_path.exists() {
+            return Err(anyhow!("Emotion model file not found: {}", model_path.display()));
+        }
+
+        info!("Loading emotion model from: {}", model_path.display());
+
+        let model_data = std::fs::read(model_path)
+            .map_err(|e| anyhow!("Failed to read model file: {}", e))?;
+
+        // 情感识别模型较小，固定使用 CPU，避免与 ASR 争用 GPU
+        let session = Session::builder()
+            .map_err(|e: OrtError| anyhow!("Failed to create session builder: {}", e))?
+            .commit_from_memory(&model_data)
+            .map_err(|e: OrtError| anyhow!("Failed to load model: {}", e))?;
+
+        let labels = load_labels(model_path);
+        info!("Emotion model loaded successfully ({} labels)", labels.len());
+
+        Ok(Self {
+            session: Arc::new(Mutex::new(session)),
+            labels,
+        })
+    }
+}
+
+#[async_trait]
+impl EmotionBackend for OnnxEmotionBackend {
+    fn name(&self) -> &str {
+        "onnx"
+    }
+
+    async fn classify(&self, audio: &[f32]) -> Result<EmotionResult> {
+        let session = self.session.clone();
+        let input = audio[..audio.len().min(MAX_INPUT_SAMPLES)].to_vec();
+        let logits = tokio::task::spawn_blocking(move || run_session(&session, input))
+            .await
+            .map_err(|e| anyhow!("Emotion inference task failed: {}", e))??;
+
+        EmotionResult::from_scores(&self.labels, &softmax(&logits))
+            .ok_or_else(|| anyhow!("Emotion model returned empty output"))
+    }
+}
+
+fn run_session(session: &Mutex<Session>, audio: Vec<f32>) -> Result<Vec<f32>> {
+    let audio_shape = vec![1usize, audio.len()];
+    let audio_tensor = Tensor::from_array((audio_shape, audio))
+        .map_err(|e| anyhow!("Failed to create audio input: {}", e))?;
+
+    let mut session_guard = session.lock()
+        .map_err(|e| anyhow!("Failed to lock session: {}", e))?;
+
+    let outputs = session_guard
+        .run(ort::inputs![audio_tensor])
+        .map_err(|e| anyhow!("ONNX inference failed: {}", e))?;
+
+    let (_shape, logits): (&ort::tensor::Shape, &[f32]) = outputs[0]
+        .try_extract_tensor()
+        .map_err(|e| anyhow!("Failed to extract output: {}", e))?;
+
+    Ok(logits.to_vec())
+}
+
+/// 读取模型同目录下的 labels.txt
+fn load_labels(model_path: &Path) -> Vec<String> {
+    let labels_path = model_path.with_file_name("labels.txt");
+    let labels: Vec<String> = std::fs::read_to_string(&labels_path)
+        .map(|content| {
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if labels.is_empty() {
+        DEFAULT_EMOTION_LABELS.iter().map(|s| s.to_string()).collect()
+    } else {
+        labels
+    }
+}
@@ -0,0 +1,103 @@
+//! 情感识别结果与语音风格映射
+
+/// 模型未附带标签文件时使用的默认情感标签（与常见语音情感模型输出顺序一致）
+pub const DEFAULT_EMOTION_LABELS: [&str; 7] = [
+    "neutral", "happy", "sad", "angry", "fearful", "disgusted", "surprised",
+];
+
+/// 置信度不足时使用的语音风格
+pub const NEUTRAL_VOICE_STYLE: &str = "neutral";
+
+/// 情感识别结果
+#[derive(Debug, Clone)]
+pub struct EmotionResult {
+    /// 情感标签（如 happy、sad）
+    pub emotion: String,
+    /// 置信度（0.0-1.0）
+    pub confidence: f32,
+    /// 语音风格（用于选择 TTS 风格，如 cheerful、gentle）
+    pub voice_style: String,
+    /// 各标签概率（按概率降序）
+    pub scores: Vec<(String, f32)>,
+}
+
+impl EmotionResult {
+    /// 由各标签概率生成结果（取最大概率标签）
+    pub fn from_scores(labels: &[String], probabilities: &[f32]) -> Option<Self> {
+        let mut scores: Vec<(String, f32)> = probabilities
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| {
+                let label = labels.get(i).cloned().unwrap_or_else(|| format!("label_{}", i));
+                (label, p)
+            })
+            .collect();
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let (emotion, confidence) = scores.first().cloned()?;
+        Some(Self {
+            voice_style: voice_style_for_emotion(&emotion).to_string(),
+            emotion,
+            confidence,
+            scores,
+        })
+    }
+}
+
+/// 情感标签对应的语音风格
+pub fn voice_style_for_emotion(emotion: &str) -> &'static str {
+    match emotion.to_ascii_lowercase().as_str() {
+        "happy" | "joy" | "hap" => "cheerful",
+        "surprised" | "surprise" | "sur" => "excited",
+        "sad" => "sad",
+        "angry" | "anger" | "ang" | "disgusted" | "disgust" | "dis" => "serious",
+        "fearful" | "fear" | "fea" => "gentle",
+        _ => NEUTRAL_VOICE_STYLE,
+    }
+}
+
+/// logits 转概率
+pub fn softmax(logits: &[f32]) -> Vec<f32> {
+    let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    if !max.is_finite() {
+        return vec![0.0; logits.len()];
+    }
+    let exps: Vec<f32> = logits.iter().map(|&x| (x - max).exp()).collect();
+    let sum: f32 = exps.iter().sum();
+    exps.into_iter().map(|e| e / sum).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_scores_picks_top_label_and_style() {
+        let labels: Vec<String> = DEFAULT_EMOTION_LABELS.iter().map(|s| s.to_string()).collect();
+        let probabilities = softmax(&[0.1, 3.0, 0.2, 0.0, 0.0, 0.0, 0.5]);
+        assert!((probabilities.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+
+        let result = EmotionResult::from_scores(&labels, &probabilities).unwrap();
+        assert_eq!(result.emotion, "happy");
+        assert_eq!(result.voice_style, "cheerful");
+        assert!(result.confidence > 0.5);
+        assert_eq!(result.scores.len(), 7);
+        assert_eq!(result.scores[1].0, "surprised");
+    }
+
+    #[test]
+    fn test_from_scores_handles_missing_labels() {
+        let result = EmotionResult::from_scores(&[], &[0.2, 0.8]).unwrap();
+        assert_eq!(result.emotion, "label_1");
+        assert_eq!(result.voice_style, NEUTRAL_VOICE_STYLE);
+        assert!(EmotionResult::from_scores(&[], &[]).is_none());
+    }
+
+    #[test]
+    fn test_voice_style_mapping() {
+        assert_eq!(voice_style_for_emotion("Sad"), "sad");
+        assert_eq!(voice_style_for_emotion("ang"), "serious");
+        assert_eq!(voice_style_for_emotion("fearful"), "gentle");
+        assert_eq!(voice_style_for_emotion("neutral"), "neutral");
+        assert_eq!(voice_style_for_emotion("unknown"), "neutral");
+    }
+}
@@ -43,6 +43,32 @@ pub struct HttpInferenceRequest {
     pub trace_id: Option<String>,
     /// 上下文文本（可选，用于 NMT 翻译质量提升）
     pub context_text: Option<String>,
+    /// 会话 ID（会话级推理状态隔离键）
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// 轮次 ID（未提供 session_id 时作为隔离键）
+    #[serde(default)]
+    pub turn_id: Option<String>,
+    /// 合成语速（音节/秒，可选；仅 HTTP 接口提供，未指定时匹配说话者语速）
+    #[serde(default)]
+    pub target_speech_rate: Option<f32>,
+}
+
+/// 会话结束请求
+#[derive(Debug, Deserialize)]
+pub struct HttpEndSessionRequest {
+    pub session_id: String,
+}
+
+/// 会话结束响应
+#[derive(Debug, Serialize)]
+pub struct HttpEndSessionResponse {
+    pub success: bool,
+    pub session_id: String,
+    /// 节点上是否存在该会话的推理状态
+    pub existed: bool,
+    /// 释放后仍保留推理状态的会话数
+    pub active_sessions: usize,
 }
 
 /// 推理响应（HTTP 格式）
@@ -78,6 +104,7 @@ pub async fn start_server(
         .route("/health", get(health_check))
         .route("/v1/inference", post(handle_inference))
         .route("/v1/inference/stream", get(handle_inference_stream_ws))
+        .route("/v1/session/end", post(handle_end_session))
         .with_state(state);
 
     let addr = SocketAddr::from(([127, 0, 0, 1], port));
@@ -139,6 +166,9 @@ async fn handle_inference(
         partial_update_interval_ms: None,
         trace_id: request.trace_id, // Added: propagate trace_id
         context_text: request.context_text, // Added: propagate context_text
+        session_id: request.session_id,
+        turn_id: request.turn_id,
+        target_speech_rate: request.target_speech_rate,
     };
 
     // 调用推理服务
@@ -162,6 +192,9 @@ async fn handle_inference(
             if let Some(emotion) = result.emotion {
                 extra.insert("emotion".to_string(), serde_json::Value::String(emotion));
             }
+            if let Some(voice_style) = result.voice_style {
+                extra.insert("voice_style".to_string(), serde_json::Value::String(voice_style));
+            }
 
             Ok(Json(HttpInferenceResponse {
                 success: true,
@@ -193,6 +226,21 @@ async fn handle_inference(
     }
 }
 
+/// 会话结束：释放该会话的上下文、流式 ASR、VAD 与说话者状态
+async fn handle_end_session(
+    State(state): State<ServerState>,
+    Json(request): Json<HttpEndSessionRequest>,
+) -> Json<HttpEndSessionResponse> {
+    let service = state.inference_service.read().await;
+    let existed = service.end_session(&request.session_id);
+    Json(HttpEndSessionResponse {
+        success: true,
+        session_id: request.session_id,
+        existed,
+        active_sessions: service.active_session_count(),
+    })
+}
+
 /// 处理流式推理请求（WebSocket）
 async fn handle_inference_stream_ws(
     ws: WebSocketUpgrade,
@@ -307,6 +355,9 @@ async fn handle_inference_stream(
                             partial_update_interval_ms: request.partial_update_interval_ms,
                             trace_id: request.trace_id, // Added: propagate trace_id
                             context_text: request.context_text.clone(), // Added: propagate context_text
+                            session_id: request.session_id.clone(),
+                            turn_id: request.turn_id.clone(),
+                            target_speech_rate: request.target_speech_rate,
                         };
 
                         // 调用推理服务
@@ -330,6 +381,7 @@ async fn handle_inference_stream(
                                         "speaker_id": result.speaker_id,
                                         "speech_rate": result.speech_rate,
                                         "emotion": result.emotion,
+                                        "voice_style": result.voice_style,
                                     },
                                 });
                                 
@@ -2,6 +2,7 @@
 
 mod process;
 mod service;
+mod session_state;
 mod types;
 
 pub use service::InferenceService;
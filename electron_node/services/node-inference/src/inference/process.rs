@@ -3,9 +3,11 @@
 use anyhow::Result;
 use tracing::{debug, info, warn};
 
+use crate::asr::ASRStreamState;
 use crate::modules::InferenceModule;
 use crate::pipeline::PipelineContext;
 
+use super::session_state::session_key;
 use super::types::{InferenceRequest, InferenceResult, PartialResultCallback};
 use super::InferenceService;
 
@@ -31,8 +33,21 @@ pub(super) async fn run_process(
         if features.speech_rate_control {
             let _ = service.enable_module("speech_rate_control").await;
         }
+        // 语音风格由情感检测模块一并给出
+        if features.emotion_detection || features.voice_style_detection {
+            let _ = service.enable_module("emotion_detection").await;
+        }
     }
 
+    // 会话级状态：只在读写状态时短暂加锁，模型推理期间不持锁（未带 session_id / turn_id 的请求共用默认会话，不能互相阻塞）
+    let session_key = session_key(&request);
+    let session_handle = service.sessions.acquire(session_key, || service.new_session_state());
+    let (context_audio, speaker_state) = {
+        let session = session_handle.lock().await;
+        (session.context_buffer.clone(), session.speaker.clone())
+    };
+    debug!(trace_id = %trace_id, session_key = %session_key, "使用会话级推理状态");
+
     let mut ctx = PipelineContext::from_audio(request.audio_data.clone());
     let features = request.features.as_ref();
 
@@ -94,7 +109,7 @@ pub(super) async fn run_process(
     debug!(trace_id = %trace_id, src_lang = %src_lang, "开始 ASR 语音识别");
 
     let audio_f32_with_context = {
-        let context = service.context_buffer.lock().await;
+        let context = &context_audio;
         if !context.is_empty() {
             let mut audio_with_context = context.clone();
             audio_with_context.extend_from_slice(&audio_f32);
@@ -120,9 +135,15 @@ pub(super) async fn run_process(
         }
     };
 
+    // VAD 语音段（用于语速识别的有声时长）
+    let mut speech_segments: Vec<(usize, usize)> = Vec::new();
     let audio_f32_processed = {
-        match service.vad_engine.detect_speech(&audio_f32_with_context) {
+        let vad_result = service
+            .vad_engine
+            .detect_speech_with_state(&audio_f32_with_context, &mut session_handle.lock().await.vad);
+        match vad_result {
             Ok(segments) => {
+                speech_segments = segments.clone();
                 if segments.is_empty() {
                     warn!(
                         trace_id = %trace_id,
@@ -169,19 +190,22 @@ pub(super) async fn run_process(
 
     let transcript = if request.enable_streaming_asr.unwrap_or(false) {
         let interval_ms = request.partial_update_interval_ms.unwrap_or(1000);
-        service.asr_engine.enable_streaming(interval_ms).await;
+        // 每个请求开始时都会清空缓冲，流式 ASR 状态按请求持有
+        let mut stream_state = ASRStreamState::new();
+        let stream = &mut stream_state;
+        stream.enable(interval_ms);
 
         let chunk_size = 8000;
         let mut current_timestamp_ms = 0u64;
         let sample_rate = 16000u32;
         let chunk_duration_ms = (chunk_size * 1000) / sample_rate;
 
-        service.asr_engine.clear_buffer().await;
+        stream.clear();
 
         for chunk in audio_f32_processed.chunks(chunk_size as usize) {
-            service.asr_engine.accumulate_audio(chunk).await;
+            stream.accumulate(chunk);
 
-            if let Some(partial) = service.asr_engine.get_partial_result(current_timestamp_ms, &src_lang).await? {
+            if let Some(partial) = service.asr_engine.get_partial_result_with_state(stream, current_timestamp_ms, &src_lang).await? {
                 if let Some(ref callback) = partial_callback {
                     callback(partial.clone());
                 }
@@ -190,8 +214,8 @@ pub(super) async fn run_process(
             current_timestamp_ms += chunk_duration_ms as u64;
         }
 
-        let final_text = service.asr_engine.get_final_result(&src_lang).await?;
-        service.asr_engine.disable_streaming().await;
+        let final_text = service.asr_engine.get_final_result_with_state(stream, &src_lang).await?;
+        stream.disable();
         final_text
     } else {
         service.asr_engine.transcribe_f32(&audio_f32_processed, &src_lang).await?
@@ -218,7 +242,7 @@ pub(super) async fn run_process(
         if let Some(ref m) = service.speaker_identifier {
             let module = m.read().await;
             if InferenceModule::is_enabled(&*module) {
-                match module.identify(&audio_f32_processed).await {
+                match module.identify_with_state(&audio_f32_processed, &speaker_state).await {
                     Ok(result) => {
                         ctx.set_speaker_id(result.speaker_id.clone());
                         if let Some(ref embedding) = result.voice_embedding {
@@ -235,13 +259,49 @@ pub(super) async fn run_process(
         }
     }
 
+    let detect_emotion = features.map(|f| f.emotion_detection).unwrap_or(false);
+    let detect_voice_style = features.map(|f| f.voice_style_detection).unwrap_or(false);
+    if detect_emotion || detect_voice_style {
+        if let Some(ref m) = service.emotion_detector {
+            let module = m.read().await;
+            if InferenceModule::is_enabled(&*module) {
+                match module.detect(&audio_f32_processed).await {
+                    Ok(result) => {
+                        info!(
+                            trace_id = %trace_id,
+                            emotion = %result.emotion,
+                            confidence = result.confidence,
+                            voice_style = %result.voice_style,
+                            "情感识别完成"
+                        );
+                        if detect_emotion {
+                            ctx.set_emotion(result.emotion);
+                        }
+                        if detect_voice_style {
+                            ctx.set_persona_style(result.voice_style);
+                        }
+                    }
+                    Err(e) => {
+                        warn!(trace_id = %trace_id, error = %e, "情感识别失败");
+                    }
+                }
+            }
+        }
+    }
+
     if features.map(|f| f.speech_rate_detection).unwrap_or(false) {
-        let duration = request.audio_data.len() as f32 / 16000.0 / 2.0;
+        let voiced_sec = crate::speech_rate::voiced_duration_sec(&speech_segments, audio_f32_with_context.len(), 16000);
         if let Some(ref m) = service.speech_rate_detector {
             let module = m.read().await;
             if InferenceModule::is_enabled(&*module) {
-                if let Ok(rate) = module.detect(&request.audio_data, duration).await {
-                    ctx.set_speech_rate(rate);
+                match module.detect(&transcript, &src_lang, voiced_sec).await {
+                    Ok(rate) => {
+                        info!(trace_id = %trace_id, speech_rate = rate, voiced_sec = voiced_sec, "语速识别完成（音节/秒）");
+                        ctx.set_speech_rate(rate);
+                    }
+                    Err(e) => {
+                        debug!(trace_id = %trace_id, error = %e, "语速识别跳过");
+                    }
                 }
             }
         }
@@ -261,6 +321,7 @@ pub(super) async fn run_process(
             speaker_id: None,
             speech_rate: None,
             emotion: None,
+            voice_style: None,
         });
     }
 
@@ -278,6 +339,7 @@ pub(super) async fn run_process(
             speaker_id: None,
             speech_rate: None,
             emotion: None,
+            voice_style: None,
         });
     }
 
@@ -286,9 +348,11 @@ pub(super) async fn run_process(
         const SAMPLE_RATE: u32 = 16000;
         let context_samples = (CONTEXT_DURATION_SEC * SAMPLE_RATE as f32) as usize;
 
-        let mut context = service.context_buffer.lock().await;
+        let mut session_guard = session_handle.lock().await;
+        let session = &mut *session_guard;
+        let context = &mut session.context_buffer;
 
-        match service.vad_engine.detect_speech(&audio_f32) {
+        match service.vad_engine.detect_speech_with_state(&audio_f32, &mut session.vad) {
             Ok(segments) => {
                 if !segments.is_empty() {
                     let (last_start, last_end) = segments.last().unwrap();
@@ -375,6 +439,8 @@ pub(super) async fn run_process(
     info!(trace_id = %trace_id, translation_len = translation.len(), "机器翻译完成");
 
     debug!(trace_id = %trace_id, tgt_lang = %tgt_lang, "开始语音合成");
+    // 情感识别得出的语音风格用于选择 Piper 语音（见 TTS_STYLE_VOICES）
+    let voice_style = ctx.persona_style.as_deref();
     let use_voice_cloning = features.map(|f| f.voice_cloning).unwrap_or(false);
     let mut audio = if use_voice_cloning {
         if let Some(ref speaker_id) = ctx.speaker_id {
@@ -388,34 +454,53 @@ pub(super) async fn run_process(
                         }
                         Err(e) => {
                             warn!(trace_id = %trace_id, error = %e, "YourTTS 音色克隆失败，降级到 Piper TTS");
-                            service.tts_engine.synthesize(&translation, &tgt_lang).await?
+                            service.tts_engine.synthesize_with_style(&translation, &tgt_lang, voice_style).await?
                         }
                     }
                 } else {
                     warn!(trace_id = %trace_id, "Voice cloning module not enabled, using Piper TTS");
-                    service.tts_engine.synthesize(&translation, &tgt_lang).await?
+                    service.tts_engine.synthesize_with_style(&translation, &tgt_lang, voice_style).await?
                 }
             } else {
                 warn!(trace_id = %trace_id, "VoiceCloner not initialized, using Piper TTS");
-                service.tts_engine.synthesize(&translation, &tgt_lang).await?
+                service.tts_engine.synthesize_with_style(&translation, &tgt_lang, voice_style).await?
             }
         } else {
             warn!(trace_id = %trace_id, "No speaker_id available, using Piper TTS");
-            service.tts_engine.synthesize(&translation, &tgt_lang).await?
+            service.tts_engine.synthesize_with_style(&translation, &tgt_lang, voice_style).await?
         }
     } else {
-        service.tts_engine.synthesize(&translation, &tgt_lang).await?
+        service.tts_engine.synthesize_with_style(&translation, &tgt_lang, voice_style).await?
     };
     info!(trace_id = %trace_id, audio_len = audio.len(), "语音合成完成");
 
     if features.map(|f| f.speech_rate_control).unwrap_or(false) {
-        if let Some(rate) = ctx.speech_rate {
-            if let Some(ref controller) = service.speech_rate_controller {
-                let module = controller.read().await;
-                if InferenceModule::is_enabled(&*module) {
-                    let target_rate = 1.0;
-                    if let Ok(adjusted) = module.adjust_audio(&audio, target_rate, rate) {
-                        audio = adjusted;
+        // 目标语速：HTTP 调用方指定优先，否则按说话者相对语速换算到目标语言
+        let target_rate = request.target_speech_rate.or_else(|| {
+            ctx.speech_rate
+                .map(|rate| crate::speech_rate::match_pace(rate, &src_lang, &tgt_lang))
+        });
+        if let (Some(target_rate), Some(controller)) = (target_rate, &service.speech_rate_controller) {
+            let module = controller.read().await;
+            if InferenceModule::is_enabled(&*module) {
+                match module.measure_rate(&audio, &translation, &tgt_lang) {
+                    Some(current_rate) => match module.adjust_audio(&audio, target_rate, current_rate) {
+                        Ok(adjusted) => {
+                            info!(
+                                trace_id = %trace_id,
+                                target_rate = target_rate,
+                                tts_rate = current_rate,
+                                audio_len = adjusted.len(),
+                                "语速控制完成"
+                            );
+                            audio = adjusted;
+                        }
+                        Err(e) => {
+                            warn!(trace_id = %trace_id, error = %e, "语速控制失败，使用原始合成音频");
+                        }
+                    },
+                    None => {
+                        debug!(trace_id = %trace_id, "无法测量合成音频语速，跳过语速控制");
                     }
                 }
             }
@@ -432,5 +517,6 @@ pub(super) async fn run_process(
         speaker_id: ctx.speaker_id,
         speech_rate: ctx.speech_rate,
         emotion: ctx.emotion,
+        voice_style: ctx.persona_style,
     })
 }
@@ -6,6 +6,7 @@ use std::sync::Arc;
 use tracing::info;
 
 use crate::asr;
+use crate::emotion;
 use crate::language_detector;
 use crate::modules::{InferenceModule, ModuleManager};
 use crate::nmt;
@@ -14,6 +15,7 @@ use crate::speech_rate;
 use crate::tts;
 use crate::vad;
 
+use super::session_state::{InferenceSessionState, SessionStateStore, DEFAULT_SESSION_KEY};
 use super::types::{InferenceRequest, InferenceResult, PartialResultCallback};
 
 /// 推理服务（字段 pub(crate) 供 process 子模块访问，不改变对外 API）
@@ -27,8 +29,9 @@ pub struct InferenceService {
     pub(crate) voice_cloner: Option<Arc<tokio::sync::RwLock<speaker::VoiceCloner>>>,
     pub(crate) speech_rate_detector: Option<Arc<tokio::sync::RwLock<speech_rate::SpeechRateDetector>>>,
     pub(crate) speech_rate_controller: Option<Arc<tokio::sync::RwLock<speech_rate::SpeechRateController>>>,
+    pub(crate) emotion_detector: Option<Arc<tokio::sync::RwLock<emotion::EmotionDetector>>>,
     pub(crate) module_manager: ModuleManager,
-    pub(crate) context_buffer: Arc<tokio::sync::Mutex<Vec<f32>>>,
+    pub(crate) sessions: SessionStateStore,
 }
 
 impl InferenceService {
@@ -48,6 +51,9 @@ impl InferenceService {
 
         let speaker_identifier = Some(Arc::new(tokio::sync::RwLock::new(speaker::SpeakerIdentifier::new())));
         let voice_cloner = Some(Arc::new(tokio::sync::RwLock::new(speaker::VoiceCloner::new())));
+        let speech_rate_detector = Some(Arc::new(tokio::sync::RwLock::new(speech_rate::SpeechRateDetector::new())));
+        let speech_rate_controller = Some(Arc::new(tokio::sync::RwLock::new(speech_rate::SpeechRateController::new())));
+        let emotion_detector = Some(Arc::new(tokio::sync::RwLock::new(emotion::EmotionDetector::new(models_dir.join("emotion")))));
 
         Ok(Self {
             asr_engine,
@@ -57,10 +63,11 @@ impl InferenceService {
             language_detector,
             speaker_identifier,
             voice_cloner,
-            speech_rate_detector: None,
-            speech_rate_controller: None,
+            speech_rate_detector,
+            speech_rate_controller,
+            emotion_detector,
             module_manager,
-            context_buffer: Arc::new(tokio::sync::Mutex::new(Vec::new())),
+            sessions: SessionStateStore::from_env(),
         })
     }
 
@@ -101,7 +108,12 @@ impl InferenceService {
                 }
             }
             "emotion_detection" => {
-                return Err(anyhow::anyhow!("Module {} not yet implemented.", module_name));
+                if let Some(ref m) = self.emotion_detector {
+                    let mut module = m.write().await;
+                    InferenceModule::enable(&mut *module).await?;
+                } else {
+                    return Err(anyhow::anyhow!("Module {} not initialized. Please initialize it first.", module_name));
+                }
             }
             "persona_adaptation" => {
                 return Err(anyhow::anyhow!("Module {} not yet implemented.", module_name));
@@ -138,20 +150,37 @@ impl InferenceService {
                     InferenceModule::disable(&mut *module).await?;
                 }
             }
+            "emotion_detection" => {
+                if let Some(ref m) = self.emotion_detector {
+                    let mut module = m.write().await;
+                    InferenceModule::disable(&mut *module).await?;
+                }
+            }
             _ => return Err(anyhow::anyhow!("Unknown module: {}", module_name)),
         }
         self.module_manager.disable_module(module_name).await?;
         Ok(())
     }
 
+    /// 清空默认会话（请求未携带 session_id / turn_id）的上下文缓冲区和VAD状态
     pub async fn clear_context_buffer(&self) {
-        let mut context = self.context_buffer.lock().await;
-        let previous_size = context.len();
-        context.clear();
-        if let Err(e) = self.vad_engine.reset_state() {
-            tracing::warn!("重置VAD状态失败: {}", e);
-        }
+        self.clear_session_context(DEFAULT_SESSION_KEY).await;
+    }
+
+    /// 清空指定会话的上下文缓冲区和VAD状态（会话本身保留）
+    pub async fn clear_session_context(&self, session_id: &str) {
+        let previous_size = match self.sessions.get(session_id) {
+            Some(state) => {
+                let mut state = state.lock().await;
+                let previous_size = state.context_buffer.len();
+                state.context_buffer.clear();
+                state.vad = self.vad_engine.new_session_state();
+                previous_size
+            }
+            None => 0,
+        };
         info!(
+            session_id = %session_id,
             previous_context_samples = previous_size,
             previous_context_duration_sec = (previous_size as f32 / 16000.0),
             "🗑️ 上下文缓冲区和VAD状态已清空"
@@ -159,8 +188,30 @@ impl InferenceService {
     }
 
     pub async fn get_context_buffer_size(&self) -> usize {
-        let context = self.context_buffer.lock().await;
-        context.len()
+        self.get_session_context_size(DEFAULT_SESSION_KEY).await
+    }
+
+    pub async fn get_session_context_size(&self, session_id: &str) -> usize {
+        match self.sessions.get(session_id) {
+            Some(state) => state.lock().await.context_buffer.len(),
+            None => 0,
+        }
+    }
+
+    /// 会话结束：释放该会话的全部推理状态；返回会话之前是否存在
+    pub fn end_session(&self, session_id: &str) -> bool {
+        let existed = self.sessions.remove(session_id);
+        info!(session_id = %session_id, existed = existed, "会话结束，已释放会话推理状态");
+        existed
+    }
+
+    /// 当前保留推理状态的会话数
+    pub fn active_session_count(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub(crate) fn new_session_state(&self) -> InferenceSessionState {
+        InferenceSessionState::new(self.vad_engine.new_session_state())
     }
 
     pub async fn process(&self, request: InferenceRequest, partial_callback: Option<PartialResultCallback>) -> Result<InferenceResult> {
@@ -0,0 +1,209 @@
+//! 会话级推理状态（上下文音频、VAD 状态、说话者状态）
+//!
+//! 同一节点并发服务多个会话时，各会话状态按 session_id（无则 turn_id）隔离；
+//! 请求只在读写状态时短暂持有会话锁，同一会话的并发请求不会在推理期间互相阻塞；
+//! 超过容量按最近最少使用淘汰，空闲超时的会话在下次访问时清理，会话结束时由调用方显式释放。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+use crate::speaker::SpeakerSessionState;
+use crate::vad::VADSessionState;
+
+use super::types::InferenceRequest;
+
+/// 未携带 session_id / turn_id 的请求共用的会话
+pub(crate) const DEFAULT_SESSION_KEY: &str = "__default__";
+
+const DEFAULT_SESSION_CAPACITY: usize = 256;
+const DEFAULT_SESSION_IDLE_TTL_SECS: u64 = 600;
+
+/// 单个会话的推理状态
+pub(crate) struct InferenceSessionState {
+    /// 上一个 utterance 尾部音频（前置到下一个 utterance）
+    pub(crate) context_buffer: Vec<f32>,
+    pub(crate) vad: VADSessionState,
+    pub(crate) speaker: SpeakerSessionState,
+}
+
+impl InferenceSessionState {
+    pub(crate) fn new(vad: VADSessionState) -> Self {
+        Self {
+            context_buffer: Vec::new(),
+            vad,
+            speaker: SpeakerSessionState::new(),
+        }
+    }
+}
+
+struct SessionEntry {
+    state: Arc<tokio::sync::Mutex<InferenceSessionState>>,
+    last_used: Instant,
+}
+
+/// 会话状态表
+pub(crate) struct SessionStateStore {
+    entries: Mutex<HashMap<String, SessionEntry>>,
+    capacity: usize,
+    idle_ttl: Duration,
+}
+
+impl SessionStateStore {
+    pub(crate) fn new(capacity: usize, idle_ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            capacity: capacity.max(1),
+            idle_ttl,
+        }
+    }
+
+    /// 从环境变量读取容量与空闲超时
+    pub(crate) fn from_env() -> Self {
+        let capacity = std::env::var("INFERENCE_SESSION_CAPACITY")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_SESSION_CAPACITY);
+        let idle_ttl_secs = std::env::var("INFERENCE_SESSION_IDLE_TTL_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_SESSION_IDLE_TTL_SECS);
+        Self::new(capacity, Duration::from_secs(idle_ttl_secs))
+    }
+
+    /// 获取会话状态，不存在时用 init 创建
+    pub(crate) fn acquire(
+        &self,
+        key: &str,
+        init: impl FnOnce() -> InferenceSessionState,
+    ) -> Arc<tokio::sync::Mutex<InferenceSessionState>> {
+        self.acquire_at(key, Instant::now(), init)
+    }
+
+    fn acquire_at(
+        &self,
+        key: &str,
+        now: Instant,
+        init: impl FnOnce() -> InferenceSessionState,
+    ) -> Arc<tokio::sync::Mutex<InferenceSessionState>> {
+        let mut entries = self.entries.lock().unwrap();
+
+        let idle_ttl = self.idle_ttl;
+        let before = entries.len();
+        entries.retain(|k, e| k == key || now.saturating_duration_since(e.last_used) < idle_ttl);
+        if entries.len() < before {
+            info!(evicted = before - entries.len(), "清理空闲超时的会话推理状态");
+        }
+
+        if let Some(entry) = entries.get_mut(key) {
+            entry.last_used = now;
+            return entry.state.clone();
+        }
+
+        while entries.len() >= self.capacity {
+            let Some(lru_key) = entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(k, _)| k.clone())
+            else {
+                break;
+            };
+            entries.remove(&lru_key);
+            info!(session_key = %lru_key, capacity = self.capacity, "会话推理状态超过容量，淘汰最久未使用的会话");
+        }
+
+        let state = Arc::new(tokio::sync::Mutex::new(init()));
+        entries.insert(key.to_string(), SessionEntry { state: state.clone(), last_used: now });
+        state
+    }
+
+    /// 获取已存在的会话状态（不创建、不刷新使用时间）
+    pub(crate) fn get(&self, key: &str) -> Option<Arc<tokio::sync::Mutex<InferenceSessionState>>> {
+        self.entries.lock().unwrap().get(key).map(|e| e.state.clone())
+    }
+
+    /// 移除会话状态；返回该会话之前是否存在
+    pub(crate) fn remove(&self, key: &str) -> bool {
+        self.entries.lock().unwrap().remove(key).is_some()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+}
+
+/// 请求所属会话的状态键：session_id，其次 turn_id，都没有时使用默认会话
+pub(crate) fn session_key(request: &InferenceRequest) -> &str {
+    request
+        .session_id
+        .as_deref()
+        .filter(|s| !s.is_empty())
+        .or_else(|| request.turn_id.as_deref().filter(|s| !s.is_empty()))
+        .unwrap_or(DEFAULT_SESSION_KEY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vad::VADConfig;
+
+    fn new_state() -> InferenceSessionState {
+        InferenceSessionState::new(VADSessionState::new(&VADConfig::default()))
+    }
+
+    #[tokio::test]
+    async fn test_sessions_are_isolated_and_lru_evicted() {
+        let store = SessionStateStore::new(2, Duration::from_secs(600));
+        let t0 = Instant::now();
+
+        let a = store.acquire_at("a", t0, new_state);
+        a.lock().await.context_buffer = vec![0.5; 10];
+        let b = store.acquire_at("b", t0 + Duration::from_secs(1), new_state);
+        assert!(b.lock().await.context_buffer.is_empty());
+
+        // 访问 a 后，b 成为最久未使用的会话
+        let a_again = store.acquire_at("a", t0 + Duration::from_secs(2), new_state);
+        assert_eq!(a_again.lock().await.context_buffer.len(), 10);
+        store.acquire_at("c", t0 + Duration::from_secs(3), new_state);
+
+        assert_eq!(store.len(), 2);
+        assert!(store.get("a").is_some());
+        assert!(store.get("b").is_none());
+        assert!(store.get("c").is_some());
+    }
+
+    #[test]
+    fn test_idle_sessions_expire_and_end_session_removes() {
+        let store = SessionStateStore::new(8, Duration::from_secs(60));
+        let t0 = Instant::now();
+
+        store.acquire_at("idle", t0, new_state);
+        store.acquire_at("active", t0 + Duration::from_secs(30), new_state);
+        store.acquire_at("active", t0 + Duration::from_secs(61), new_state);
+        assert!(store.get("idle").is_none());
+
+        assert!(store.remove("active"));
+        assert!(!store.remove("active"));
+        assert_eq!(store.len(), 0);
+    }
+
+    #[test]
+    fn test_session_key_prefers_session_id() {
+        let mut request: InferenceRequest = serde_json::from_value(serde_json::json!({
+            "job_id": "job-1",
+            "src_lang": "zh",
+            "tgt_lang": "en",
+            "audio_data": [],
+            "features": null,
+        }))
+        .unwrap();
+        assert_eq!(session_key(&request), DEFAULT_SESSION_KEY);
+
+        request.turn_id = Some("turn-1".to_string());
+        assert_eq!(session_key(&request), "turn-1");
+
+        request.session_id = Some("s-1".to_string());
+        assert_eq!(session_key(&request), "s-1");
+    }
+}
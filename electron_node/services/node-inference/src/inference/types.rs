@@ -31,6 +31,16 @@ pub struct InferenceRequest {
     pub trace_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context_text: Option<String>,
+    /// 会话 ID（会话级上下文 / 流式 ASR / VAD / 说话者状态按此隔离）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// 轮次 ID（未提供 session_id 时作为会话状态的隔离键）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_id: Option<String>,
+    /// 调用方指定的合成语速（音节/秒，需开启 speech_rate_control）；未指定时匹配说话者语速。
+    /// 仅本服务 HTTP 接口（/v1/inference、/v1/inference/stream）可设置，调度链路（session_init / JobAssign）不携带
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_speech_rate: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,4 +51,5 @@ pub struct InferenceResult {
     pub speaker_id: Option<String>,
     pub speech_rate: Option<f32>,
     pub emotion: Option<String>,
+    pub voice_style: Option<String>,
 }
@@ -13,6 +13,7 @@ pub mod speaker;
 pub mod speaker_embedding_client;
 pub mod faster_whisper_vad_client;
 pub mod speech_rate;
+pub mod emotion;
 pub mod language_detector;
 pub mod text_filter;
 pub mod audio_codec;
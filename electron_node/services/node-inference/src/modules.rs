@@ -308,7 +308,7 @@ lazy_static! {
             ],
             dependencies: vec!["asr".to_string()],
             conflicts: vec![],
-            outputs: vec!["emotion".to_string()],
+            outputs: vec!["emotion".to_string(), "persona_style".to_string()],
         });
         
         // 可选模块：音色识别
@@ -394,5 +394,8 @@ pub struct FeatureSet {
     pub speech_rate_control: bool,
     pub emotion_detection: bool,
     pub persona_adaptation: bool,
+    /// 语音风格识别（由情感检测模块给出，写入 PipelineContext.persona_style 用于选择 TTS 语音）
+    #[serde(default)]
+    pub voice_style_detection: bool,
 }
 
@@ -31,7 +31,7 @@ pub struct PipelineContext {
     /// 音色识别结果（可选输出）
     pub speaker_id: Option<String>,
     
-    /// 语速识别结果（可选输出，单位：字/秒或词/秒）
+    /// 语速识别结果（可选输出，单位：音节/秒）
     pub speech_rate: Option<f32>,
     
     /// 情感分析结果（可选输出）
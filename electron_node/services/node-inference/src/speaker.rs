@@ -34,6 +34,15 @@ pub struct SpeakerIdentifier {
     embedding_client: Option<Arc<SpeakerEmbeddingClient>>,
     /// 相似度阈值（0.0-1.0），超过此值认为是同一说话者
     similarity_threshold: f32,
+    /// 未指定会话时使用的说话者状态
+    default_state: SpeakerSessionState,
+    /// 识别模式：单人模式或多人模式
+    mode: Arc<tokio::sync::RwLock<SpeakerIdentificationMode>>,
+}
+
+/// 会话级说话者状态（说话者编号只在同一会话内有意义，不同会话互不影响）
+#[derive(Clone)]
+pub struct SpeakerSessionState {
     /// 已有说话者的 embedding 库
     /// Key: speaker_id, Value: embedding vector
     speaker_embeddings: Arc<tokio::sync::RwLock<HashMap<String, Vec<f32>>>>,
@@ -41,8 +50,41 @@ pub struct SpeakerIdentifier {
     next_speaker_id: Arc<tokio::sync::RwLock<u32>>,
     /// 单人模式下的固定 speaker_id
     single_user_speaker_id: Arc<tokio::sync::RwLock<Option<String>>>,
-    /// 识别模式：单人模式或多人模式
-    mode: Arc<tokio::sync::RwLock<SpeakerIdentificationMode>>,
+}
+
+impl Default for SpeakerSessionState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpeakerSessionState {
+    pub fn new() -> Self {
+        Self {
+            speaker_embeddings: Arc::new(tokio::sync::RwLock::new(HashMap::new())),
+            next_speaker_id: Arc::new(tokio::sync::RwLock::new(1)),
+            single_user_speaker_id: Arc::new(tokio::sync::RwLock::new(None)),
+        }
+    }
+
+    /// 重置状态
+    pub async fn reset(&self) {
+        let mut embeddings = self.speaker_embeddings.write().await;
+        let mut counter = self.next_speaker_id.write().await;
+        let mut single_id = self.single_user_speaker_id.write().await;
+
+        embeddings.clear();
+        *counter = 1;
+        *single_id = None;
+    }
+
+    /// 生成新的说话者 ID
+    async fn generate_speaker_id(&self) -> String {
+        let mut counter = self.next_speaker_id.write().await;
+        let id = format!("speaker_{}", *counter);
+        *counter += 1;
+        id
+    }
 }
 
 /// 说话者识别模式
@@ -89,9 +131,7 @@ impl SpeakerIdentifier {
             model_loaded: false,
             embedding_client: None,
             similarity_threshold: 0.7,
-            speaker_embeddings: Arc::new(tokio::sync::RwLock::new(HashMap::new())),
-            next_speaker_id: Arc::new(tokio::sync::RwLock::new(1)),
-            single_user_speaker_id: Arc::new(tokio::sync::RwLock::new(None)),
+            default_state: SpeakerSessionState::new(),
             mode: Arc::new(tokio::sync::RwLock::new(SpeakerIdentificationMode::SingleUser)),
         }
     }
@@ -104,6 +144,15 @@ impl SpeakerIdentifier {
     /// # Returns
     /// 返回说话者识别结果
     pub async fn identify(&self, audio_data: &[f32]) -> Result<SpeakerIdentificationResult> {
+        self.identify_with_state(audio_data, &self.default_state).await
+    }
+
+    /// 在指定会话的说话者状态下识别说话者
+    pub async fn identify_with_state(
+        &self,
+        audio_data: &[f32],
+        state: &SpeakerSessionState,
+    ) -> Result<SpeakerIdentificationResult> {
         if !self.is_enabled() {
             return Err(anyhow::anyhow!("Speaker identification module is not enabled"));
         }
@@ -115,10 +164,10 @@ impl SpeakerIdentifier {
 
         match mode {
             SpeakerIdentificationMode::SingleUser => {
-                self.identify_single_user_mode(client, audio_data).await
+                self.identify_single_user_mode(client, audio_data, state).await
             }
             SpeakerIdentificationMode::MultiUser => {
-                self.identify_multi_user_mode(client, audio_data).await
+                self.identify_multi_user_mode(client, audio_data, state).await
             }
         }
     }
@@ -128,10 +177,11 @@ impl SpeakerIdentifier {
         &self,
         client: &SpeakerEmbeddingClient,
         audio_data: &[f32],
+        state: &SpeakerSessionState,
     ) -> Result<SpeakerIdentificationResult> {
         // 1. 获取或创建固定的 speaker_id
         let speaker_id = {
-            let mut single_id = self.single_user_speaker_id.write().await;
+            let mut single_id = state.single_user_speaker_id.write().await;
             if single_id.is_none() {
                 *single_id = Some("single_user".to_string());
             }
@@ -143,7 +193,7 @@ impl SpeakerIdentifier {
 
         // 3. 更新或保存 embedding
         if let Some(ref embedding) = extract_result.embedding {
-            let mut embeddings = self.speaker_embeddings.write().await;
+            let mut embeddings = state.speaker_embeddings.write().await;
             if let Some(existing_emb) = embeddings.get_mut(&speaker_id) {
                 // 使用加权平均更新 embedding（持续优化音色）
                 for (i, new_val) in embedding.iter().enumerate() {
@@ -171,6 +221,7 @@ impl SpeakerIdentifier {
         &self,
         client: &SpeakerEmbeddingClient,
         audio_data: &[f32],
+        state: &SpeakerSessionState,
     ) -> Result<SpeakerIdentificationResult> {
         // 1. 提取 embedding
         let extract_result = client.extract_embedding(audio_data).await?;
@@ -199,11 +250,11 @@ impl SpeakerIdentifier {
         })?;
 
         let (speaker_id, is_new_speaker, confidence) = {
-            let embeddings = self.speaker_embeddings.read().await;
+            let embeddings = state.speaker_embeddings.read().await;
             
             if embeddings.is_empty() {
                 // 第一个说话者
-                let new_id = self.generate_speaker_id().await;
+                let new_id = state.generate_speaker_id().await;
                 (new_id, true, 1.0)
             } else {
                 // 查找最相似的说话者
@@ -227,11 +278,11 @@ impl SpeakerIdentifier {
                         (best_id, false, best_sim)
                     } else {
                         // 新说话者
-                        let new_id = self.generate_speaker_id().await;
+                        let new_id = state.generate_speaker_id().await;
                         (new_id, true, 1.0 - best_sim)
                     }
                 } else {
-                    let new_id = self.generate_speaker_id().await;
+                    let new_id = state.generate_speaker_id().await;
                     (new_id, true, 1.0)
                 }
             }
@@ -239,7 +290,7 @@ impl SpeakerIdentifier {
 
         // 4. 如果是新说话者，保存 embedding
         if is_new_speaker {
-            let mut embeddings = self.speaker_embeddings.write().await;
+            let mut embeddings = state.speaker_embeddings.write().await;
             embeddings.insert(speaker_id.clone(), embedding.clone());
         }
 
@@ -252,14 +303,6 @@ impl SpeakerIdentifier {
         })
     }
 
-    /// 生成新的说话者 ID
-    async fn generate_speaker_id(&self) -> String {
-        let mut counter = self.next_speaker_id.write().await;
-        let id = format!("speaker_{}", *counter);
-        *counter += 1;
-        id
-    }
-
     /// 计算两个 embedding 的余弦相似度
     fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
         if a.len() != b.len() {
@@ -285,14 +328,7 @@ impl SpeakerIdentifier {
 
     /// 重置识别器状态
     pub async fn reset(&self) -> Result<()> {
-        let mut embeddings = self.speaker_embeddings.write().await;
-        let mut counter = self.next_speaker_id.write().await;
-        let mut single_id = self.single_user_speaker_id.write().await;
-        
-        embeddings.clear();
-        *counter = 1;
-        *single_id = None;
-        
+        self.default_state.reset().await;
         Ok(())
     }
 }
@@ -1,115 +0,0 @@
-use anyhow::Result;
-use async_trait::async_trait;
-use crate::modules::InferenceModule;
-
-/// 语速识别模块
-pub struct SpeechRateDetector {
-    enabled: bool,
-    model_loaded: bool,
-}
-
-#[async_trait]
-impl InferenceModule for SpeechRateDetector {
-    fn name(&self) -> &str {
-        "speech_rate_detection"
-    }
-
-    fn is_enabled(&self) -> bool {
-        self.enabled && self.model_loaded
-    }
-
-    async fn enable(&mut self) -> Result<()> {
-        if !self.model_loaded {
-            // TODO: 加载模型（如果需要）
-            self.model_loaded = true;
-        }
-        self.enabled = true;
-        Ok(())
-    }
-
-    async fn disable(&mut self) -> Result<()> {
-        self.enabled = false;
-        Ok(())
-    }
-}
-
-impl SpeechRateDetector {
-    pub fn new() -> Self {
-        Self {
-            enabled: false,
-            model_loaded: false,
-        }
-    }
-
-    pub async fn detect(&self, _audio_data: &[u8], duration_seconds: f32) -> Result<f32> {
-        if !self.is_enabled() {
-            return Err(anyhow::anyhow!("Speech rate detection module is not enabled"));
-        }
-
-        // TODO: 实现语速识别逻辑
-        // 1. 分析音频时长和语音段
-        // 2. 计算语速（字/秒 或 音节/秒）
-        // 3. 返回语速值
-
-        // 简化实现：基于音频时长估算
-        let estimated_words = duration_seconds * 2.0; // 假设平均语速
-        Ok(estimated_words / duration_seconds)
-    }
-}
-
-/// 语速控制模块
-pub struct SpeechRateController {
-    enabled: bool,
-    model_loaded: bool,
-}
-
-#[async_trait]
-impl InferenceModule for SpeechRateController {
-    fn name(&self) -> &str {
-        "speech_rate_control"
-    }
-
-    fn is_enabled(&self) -> bool {
-        self.enabled && self.model_loaded
-    }
-
-    async fn enable(&mut self) -> Result<()> {
-        if !self.model_loaded {
-            // TODO: 加载模型（如果需要）
-            self.model_loaded = true;
-        }
-        self.enabled = true;
-        Ok(())
-    }
-
-    async fn disable(&mut self) -> Result<()> {
-        self.enabled = false;
-        Ok(())
-    }
-}
-
-impl SpeechRateController {
-    pub fn new() -> Self {
-        Self {
-            enabled: false,
-            model_loaded: false,
-        }
-    }
-
-    pub fn adjust_audio(&self, audio_data: &[u8], target_rate: f32, current_rate: f32) -> Result<Vec<u8>> {
-        if !self.is_enabled() {
-            return Err(anyhow::anyhow!("Speech rate control module is not enabled"));
-        }
-
-        // TODO: 实现语速调整逻辑
-        // 1. 计算速度比例
-        // 2. 使用音频处理库调整播放速度
-        // 3. 保持音调不变（可选）
-
-        let _speed_ratio = target_rate / current_rate;
-        // 简化实现：返回原始音频（实际需要音频处理）
-        // TODO: 使用 speed_ratio 调整音频速度
-        Ok(audio_data.to_vec())
-    }
-}
-
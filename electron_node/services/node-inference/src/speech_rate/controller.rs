@@ -0,0 +1,249 @@
+//! 语速控制：对 TTS 输出（WAV 或 16kHz PCM16）做变速不变调
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use hound::{WavReader, WavWriter, SampleFormat};
+use std::io::Cursor;
+use tracing::warn;
+
+use crate::modules::InferenceModule;
+
+use super::detector::speech_rate;
+use super::wsola;
+
+/// 变速倍率范围（超出后音质明显下降）
+pub const MIN_SPEED: f32 = 0.5;
+pub const MAX_SPEED: f32 = 2.0;
+/// 倍率偏离 1.0 小于该值时不处理
+const SPEED_TOLERANCE: f32 = 0.05;
+/// 裸 PCM16（无 WAV 头）的采样率
+const RAW_PCM_SAMPLE_RATE: u32 = 16000;
+/// 有声帧判定：帧长（毫秒）与相对最大帧能量的阈值
+const ACTIVITY_FRAME_MS: usize = 20;
+const ACTIVITY_RELATIVE_THRESHOLD: f32 = 0.1;
+
+/// 语速控制模块
+pub struct SpeechRateController {
+    enabled: bool,
+    model_loaded: bool,
+}
+
+#[async_trait]
+impl InferenceModule for SpeechRateController {
+    fn name(&self) -> &str {
+        "speech_rate_control"
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled && self.model_loaded
+    }
+
+    async fn enable(&mut self) -> Result<()> {
+        if !self.model_loaded {
+            // 纯信号处理，无需加载模型
+            self.model_loaded = true;
+        }
+        self.enabled = true;
+        Ok(())
+    }
+
+    async fn disable(&mut self) -> Result<()> {
+        self.enabled = false;
+        Ok(())
+    }
+}
+
+impl SpeechRateController {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            model_loaded: false,
+        }
+    }
+
+    /// 测量合成音频的语速（音节/秒）：文本音节数 / 有声帧时长
+    pub fn measure_rate(&self, audio_data: &[u8], text: &str, lang: &str) -> Option<f32> {
+        let audio = decode(audio_data).ok()?;
+        let mono: Vec<f32> = audio.channel(0);
+        speech_rate(text, lang, active_duration_sec(&mono, audio.sample_rate))
+    }
+
+    /// 把音频语速从 current_rate 调整到 target_rate（倍率限制在 [MIN_SPEED, MAX_SPEED]），保持音调
+    ///
+    /// 输入为 WAV 时输出同规格 WAV，否则按 16kHz 单声道 PCM16 处理
+    pub fn adjust_audio(&self, audio_data: &[u8], target_rate: f32, current_rate: f32) -> Result<Vec<u8>> {
+        if !self.is_enabled() {
+            return Err(anyhow::anyhow!("Speech rate control module is not enabled"));
+        }
+        if !(target_rate > 0.0 && current_rate > 0.0) {
+            return Err(anyhow!("Invalid speech rate: target={}, current={}", target_rate, current_rate));
+        }
+
+        let speed = (target_rate / current_rate).clamp(MIN_SPEED, MAX_SPEED);
+        if (speed - 1.0).abs() < SPEED_TOLERANCE {
+            return Ok(audio_data.to_vec());
+        }
+
+        let audio = decode(audio_data)?;
+        let channels: Vec<Vec<f32>> = (0..audio.channels)
+            .map(|c| wsola::time_stretch(&audio.channel(c), audio.sample_rate, speed))
+            .collect();
+        let frames = channels.iter().map(Vec::len).min().unwrap_or(0);
+        let mut samples = Vec::with_capacity(frames * channels.len());
+        for i in 0..frames {
+            for channel in &channels {
+                samples.push((channel[i].clamp(-1.0, 1.0) * 32767.0) as i16);
+            }
+        }
+
+        let stretched = Pcm16Audio { samples, ..audio };
+        encode(&stretched)
+    }
+}
+
+/// 解码后的 PCM16 音频（交错多声道）
+struct Pcm16Audio {
+    samples: Vec<i16>,
+    sample_rate: u32,
+    channels: usize,
+    is_wav: bool,
+}
+
+impl Pcm16Audio {
+    fn channel(&self, index: usize) -> Vec<f32> {
+        self.samples
+            .iter()
+            .skip(index)
+            .step_by(self.channels.max(1))
+            .map(|&s| s as f32 / 32768.0)
+            .collect()
+    }
+}
+
+fn decode(audio_data: &[u8]) -> Result<Pcm16Audio> {
+    if audio_data.starts_with(b"RIFF") {
+        let reader = WavReader::new(Cursor::new(audio_data))
+            .map_err(|e| anyhow!("Failed to read WAV: {}", e))?;
+        let spec = reader.spec();
+        if spec.sample_format != SampleFormat::Int || spec.bits_per_sample != 16 {
+            return Err(anyhow!(
+                "Unsupported WAV format: {:?} {} bits",
+                spec.sample_format,
+                spec.bits_per_sample
+            ));
+        }
+        let samples = reader
+            .into_samples::<i16>()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| anyhow!("Failed to read WAV samples: {}", e))?;
+        Ok(Pcm16Audio {
+            samples,
+            sample_rate: spec.sample_rate,
+            channels: spec.channels as usize,
+            is_wav: true,
+        })
+    } else {
+        if audio_data.len() % 2 != 0 {
+            warn!("PCM16 audio has odd byte length {}, dropping last byte", audio_data.len());
+        }
+        Ok(Pcm16Audio {
+            samples: audio_data
+                .chunks_exact(2)
+                .map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]]))
+                .collect(),
+            sample_rate: RAW_PCM_SAMPLE_RATE,
+            channels: 1,
+            is_wav: false,
+        })
+    }
+}
+
+fn encode(audio: &Pcm16Audio) -> Result<Vec<u8>> {
+    if !audio.is_wav {
+        return Ok(audio.samples.iter().flat_map(|s| s.to_le_bytes()).collect());
+    }
+
+    let spec = hound::WavSpec {
+        channels: audio.channels as u16,
+        sample_rate: audio.sample_rate,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut buffer = Vec::new();
+    {
+        let mut writer = WavWriter::new(Cursor::new(&mut buffer), spec)
+            .map_err(|e| anyhow!("Failed to create WAV writer: {}", e))?;
+        for &sample in &audio.samples {
+            writer.write_sample(sample)
+                .map_err(|e| anyhow!("Failed to write WAV sample: {}", e))?;
+        }
+        writer.finalize()
+            .map_err(|e| anyhow!("Failed to finalize WAV: {}", e))?;
+    }
+    Ok(buffer)
+}
+
+/// 有声帧总时长（秒）：帧能量超过最大帧能量一定比例的帧计入
+fn active_duration_sec(samples: &[f32], sample_rate: u32) -> f32 {
+    let frame_len = (sample_rate as usize * ACTIVITY_FRAME_MS / 1000).max(1);
+    let energies: Vec<f32> = samples
+        .chunks(frame_len)
+        .map(|frame| (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt())
+        .collect();
+    let max_energy = energies.iter().copied().fold(0.0f32, f32::max);
+    if max_energy <= 1e-4 {
+        return 0.0;
+    }
+    let threshold = max_energy * ACTIVITY_RELATIVE_THRESHOLD;
+    let active_frames = energies.iter().filter(|&&e| e >= threshold).count();
+    (active_frames * frame_len) as f32 / sample_rate as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone_wav(duration_sec: f32, sample_rate: u32) -> Vec<u8> {
+        let samples = (duration_sec * sample_rate as f32) as usize;
+        let audio = Pcm16Audio {
+            samples: (0..samples)
+                .map(|i| ((2.0 * std::f32::consts::PI * 200.0 * i as f32 / sample_rate as f32).sin() * 16000.0) as i16)
+                .collect(),
+            sample_rate,
+            channels: 1,
+            is_wav: true,
+        };
+        encode(&audio).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_adjust_audio_stretches_wav_and_keeps_spec() {
+        let mut controller = SpeechRateController::new();
+        InferenceModule::enable(&mut controller).await.unwrap();
+
+        let input = tone_wav(2.0, 22050);
+        let output = controller.adjust_audio(&input, 6.0, 4.0).unwrap();
+        let decoded = decode(&output).unwrap();
+        assert!(decoded.is_wav);
+        assert_eq!(decoded.sample_rate, 22050);
+        let expected = 2.0 * 22050.0 / 1.5;
+        assert!((decoded.samples.len() as f32 - expected).abs() / expected < 0.05);
+
+        // 倍率被限制在 MAX_SPEED
+        let output = controller.adjust_audio(&input, 100.0, 1.0).unwrap();
+        let decoded = decode(&output).unwrap();
+        assert!((decoded.samples.len() as f32 - 22050.0).abs() / 22050.0 < 0.05);
+    }
+
+    #[test]
+    fn test_measure_rate_on_raw_pcm() {
+        let controller = SpeechRateController::new();
+        // 1 秒有声 + 1 秒静音
+        let mut pcm: Vec<u8> = (0..16000)
+            .flat_map(|i| (((i as f32 * 0.1).sin() * 12000.0) as i16).to_le_bytes())
+            .collect();
+        pcm.extend(std::iter::repeat(0u8).take(32000));
+        let rate = controller.measure_rate(&pcm, "你好世界", "zh").unwrap();
+        assert!((rate - 4.0).abs() < 0.2, "rate={}", rate);
+    }
+}
@@ -0,0 +1,217 @@
+//! 语速识别：VAD 有声时长 + 转写文本音节数
+//!
+//! 语速单位统一为「音节/秒」：中日韩按字计（一字一音节），拼音文字按元音组估算音节。
+
+use anyhow::Result;
+use async_trait::async_trait;
+use crate::modules::InferenceModule;
+
+/// 有声时长过短时不计算语速（结果不稳定）
+const MIN_VOICED_DURATION_SEC: f32 = 0.3;
+
+/// 语速识别模块
+pub struct SpeechRateDetector {
+    enabled: bool,
+    model_loaded: bool,
+}
+
+#[async_trait]
+impl InferenceModule for SpeechRateDetector {
+    fn name(&self) -> &str {
+        "speech_rate_detection"
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled && self.model_loaded
+    }
+
+    async fn enable(&mut self) -> Result<()> {
+        if !self.model_loaded {
+            // 基于规则计算，无需加载模型
+            self.model_loaded = true;
+        }
+        self.enabled = true;
+        Ok(())
+    }
+
+    async fn disable(&mut self) -> Result<()> {
+        self.enabled = false;
+        Ok(())
+    }
+}
+
+impl SpeechRateDetector {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            model_loaded: false,
+        }
+    }
+
+    /// 计算语速（音节/秒）
+    ///
+    /// # Arguments
+    /// * `transcript` - ASR 转写文本
+    /// * `lang` - 转写文本语言
+    /// * `voiced_duration_sec` - VAD 语音段总时长（秒）
+    pub async fn detect(&self, transcript: &str, lang: &str, voiced_duration_sec: f32) -> Result<f32> {
+        if !self.is_enabled() {
+            return Err(anyhow::anyhow!("Speech rate detection module is not enabled"));
+        }
+        speech_rate(transcript, lang, voiced_duration_sec)
+            .ok_or_else(|| anyhow::anyhow!("Not enough speech to estimate speech rate"))
+    }
+}
+
+/// 文本音节数 / 有声时长；文本为空或时长过短时返回 None
+pub fn speech_rate(text: &str, lang: &str, voiced_duration_sec: f32) -> Option<f32> {
+    if voiced_duration_sec < MIN_VOICED_DURATION_SEC {
+        return None;
+    }
+    let syllables = count_syllables(text, lang);
+    if syllables == 0 {
+        return None;
+    }
+    Some(syllables as f32 / voiced_duration_sec)
+}
+
+/// 各语言常态语速（音节/秒），用于跨语言换算相对语速
+fn typical_rate(lang: &str) -> f32 {
+    match base_lang(lang).as_str() {
+        "zh" => 4.5,
+        "ja" => 7.0,
+        "ko" => 6.0,
+        "en" => 5.0,
+        "de" => 5.0,
+        "fr" => 6.0,
+        "it" => 6.5,
+        "es" => 7.0,
+        "ru" => 5.5,
+        _ => 5.0,
+    }
+}
+
+/// 把源语言语速换算为目标语言中「同样快慢」的语速
+pub fn match_pace(src_rate: f32, src_lang: &str, tgt_lang: &str) -> f32 {
+    src_rate / typical_rate(src_lang) * typical_rate(tgt_lang)
+}
+
+/// VAD 语音段总时长（秒）；没有语音段时使用整段音频时长
+pub fn voiced_duration_sec(segments: &[(usize, usize)], total_samples: usize, sample_rate: u32) -> f32 {
+    let voiced_samples: usize = segments.iter().map(|(start, end)| end.saturating_sub(*start)).sum();
+    let samples = if voiced_samples == 0 { total_samples } else { voiced_samples };
+    samples as f32 / sample_rate as f32
+}
+
+/// 估算文本音节数
+pub fn count_syllables(text: &str, lang: &str) -> usize {
+    let lang = base_lang(lang);
+    let mut count = 0;
+    let mut word = String::new();
+    for ch in text.chars() {
+        if is_cjk_syllable(ch) || ch.is_ascii_digit() {
+            count += count_word_syllables(&word, &lang);
+            word.clear();
+            count += 1;
+        } else if ch.is_alphabetic() {
+            word.extend(ch.to_lowercase());
+        } else if ch == '\'' || ch == '’' {
+            // 缩写（don't）不拆词
+        } else {
+            count += count_word_syllables(&word, &lang);
+            word.clear();
+        }
+    }
+    count + count_word_syllables(&word, &lang)
+}
+
+/// 去掉地区后缀的小写语言代码（zh-CN -> zh）
+fn base_lang(lang: &str) -> String {
+    lang.split(['-', '_']).next().unwrap_or("").to_ascii_lowercase()
+}
+
+/// 汉字、假名、韩文音节各计一个音节
+fn is_cjk_syllable(ch: char) -> bool {
+    matches!(ch,
+        '\u{4E00}'..='\u{9FFF}'   // CJK 统一表意文字
+        | '\u{3400}'..='\u{4DBF}' // CJK 扩展 A
+        | '\u{F900}'..='\u{FAFF}' // CJK 兼容表意文字
+        | '\u{3041}'..='\u{3096}' // 平假名
+        | '\u{30A1}'..='\u{30FA}' // 片假名
+        | '\u{AC00}'..='\u{D7A3}' // 韩文音节
+    )
+}
+
+/// 拼音文字单词按元音组计音节（至少 1 个）
+fn count_word_syllables(word: &str, lang: &str) -> usize {
+    if word.is_empty() {
+        return 0;
+    }
+    let chars: Vec<char> = word.chars().collect();
+    let mut groups = 0;
+    let mut prev_vowel = false;
+    for &ch in &chars {
+        let vowel = is_vowel(ch);
+        if vowel && !prev_vowel {
+            groups += 1;
+        }
+        prev_vowel = vowel;
+    }
+    // 英语词尾不发音的 e（make、time），-le 结尾除外（table）
+    if lang == "en" && groups > 1 && chars.len() > 2 {
+        let n = chars.len();
+        if chars[n - 1] == 'e' && !is_vowel(chars[n - 2]) && chars[n - 2] != 'l' {
+            groups -= 1;
+        }
+    }
+    groups.max(1)
+}
+
+fn is_vowel(ch: char) -> bool {
+    matches!(ch,
+        'a' | 'e' | 'i' | 'o' | 'u' | 'y'
+        | 'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'æ'
+        | 'è' | 'é' | 'ê' | 'ë'
+        | 'ì' | 'í' | 'î' | 'ï'
+        | 'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'œ'
+        | 'ù' | 'ú' | 'û' | 'ü' | 'ý' | 'ÿ'
+        | 'а' | 'е' | 'ё' | 'и' | 'о' | 'у' | 'ы' | 'э' | 'ю' | 'я'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_syllables_by_script() {
+        assert_eq!(count_syllables("你好，世界！", "zh"), 4);
+        assert_eq!(count_syllables("こんにちは", "ja"), 5);
+        assert_eq!(count_syllables("안녕하세요", "ko"), 5);
+        assert_eq!(count_syllables("Hello world", "en"), 3);
+        assert_eq!(count_syllables("Make a table", "en"), 4);
+        assert_eq!(count_syllables("I don't know", "en"), 3);
+        assert_eq!(count_syllables("我有3个apple", "zh"), 6);
+        assert_eq!(count_syllables("", "en"), 0);
+    }
+
+    #[test]
+    fn test_speech_rate_uses_voiced_duration() {
+        let segments = [(0, 16000), (32000, 48000)];
+        let voiced = voiced_duration_sec(&segments, 64000, 16000);
+        assert!((voiced - 2.0).abs() < 1e-6);
+        assert!((voiced_duration_sec(&[], 32000, 16000) - 2.0).abs() < 1e-6);
+
+        let rate = speech_rate("你好世界我们", "zh", voiced).unwrap();
+        assert!((rate - 3.0).abs() < 1e-6);
+        assert!(speech_rate("你好", "zh", 0.1).is_none());
+        assert!(speech_rate("", "zh", 2.0).is_none());
+    }
+
+    #[test]
+    fn test_match_pace_across_languages() {
+        assert!((match_pace(4.5, "zh", "en") - 5.0).abs() < 1e-6);
+        assert!((match_pace(9.0, "zh-CN", "en-US") - 10.0).abs() < 1e-6);
+        assert!((match_pace(5.0, "en", "en") - 5.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,12 @@
+//! 语速识别与语速控制模块
+//!
+//! 识别：VAD 语音段时长 + 转写文本音节数（按语言估算），单位为音节/秒。
+//! 控制：对 TTS 输出做 WSOLA 变速不变调，使合成语速匹配说话者语速或客户端指定语速。
+
+mod controller;
+mod detector;
+mod wsola;
+
+pub use controller::{SpeechRateController, MAX_SPEED, MIN_SPEED};
+pub use detector::{count_syllables, match_pace, speech_rate, voiced_duration_sec, SpeechRateDetector};
+pub use wsola::time_stretch;
@@ -0,0 +1,134 @@
+//! WSOLA（波形相似叠加）变速不变调
+//!
+//! 以固定合成步长叠加加窗帧，分析步长 = 合成步长 × 速度；每帧在容差范围内搜索与上一帧
+//! 自然延续最相似的位置，避免相位不连续导致的颤音。
+
+use std::f32::consts::PI;
+
+/// 帧长（毫秒）
+const FRAME_MS: usize = 30;
+/// 对齐搜索容差（毫秒）
+const TOLERANCE_MS: usize = 10;
+
+/// 对 f32 单声道音频做变速不变调；speed > 1 加快（时长变短），speed < 1 放慢
+pub fn time_stretch(input: &[f32], sample_rate: u32, speed: f32) -> Vec<f32> {
+    let frame_len = ((sample_rate as usize * FRAME_MS) / 1000).max(4) & !1;
+    let hop = frame_len / 2;
+    let tolerance = (sample_rate as usize * TOLERANCE_MS) / 1000;
+
+    if !speed.is_finite() || speed <= 0.0 || (speed - 1.0).abs() < 0.01 || input.len() < frame_len * 2 {
+        return input.to_vec();
+    }
+
+    let window: Vec<f32> = (0..frame_len)
+        .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / frame_len as f32).cos())
+        .collect();
+
+    let out_len = (input.len() as f64 / speed as f64).round() as usize;
+    let mut output = vec![0.0f32; out_len + frame_len];
+    let mut norm = vec![0.0f32; out_len + frame_len];
+    let mut written_end = 0;
+    let mut prev_pos: Option<usize> = None;
+
+    let mut out_pos = 0;
+    while out_pos < out_len {
+        let nominal = (out_pos as f64 * speed as f64).round() as usize;
+        let pos = match prev_pos {
+            None => nominal,
+            Some(prev) => {
+                // 上一帧在输入中的自然延续，作为对齐模板
+                let template_start = prev + hop;
+                if template_start + hop > input.len() {
+                    break;
+                }
+                best_aligned_position(input, &input[template_start..template_start + hop], nominal, tolerance, frame_len)
+            }
+        };
+        if pos + frame_len > input.len() {
+            break;
+        }
+
+        for i in 0..frame_len {
+            output[out_pos + i] += input[pos + i] * window[i];
+            norm[out_pos + i] += window[i];
+        }
+        written_end = out_pos + frame_len;
+        prev_pos = Some(pos);
+        out_pos += hop;
+    }
+
+    for (sample, weight) in output.iter_mut().zip(norm.iter()) {
+        if *weight > 1e-3 {
+            *sample /= *weight;
+        }
+    }
+    output.truncate(out_len.min(written_end));
+    output
+}
+
+/// 在 nominal ± tolerance 内寻找与模板互相关最大的起点
+fn best_aligned_position(input: &[f32], template: &[f32], nominal: usize, tolerance: usize, frame_len: usize) -> usize {
+    let lo = nominal.saturating_sub(tolerance);
+    let hi = (nominal + tolerance).min(input.len().saturating_sub(frame_len));
+    if lo > hi {
+        return nominal;
+    }
+
+    let mut best = lo;
+    let mut best_score = f32::MIN;
+    for candidate in lo..=hi {
+        let score: f32 = template
+            .iter()
+            .zip(&input[candidate..candidate + template.len()])
+            .map(|(a, b)| a * b)
+            .sum();
+        if score > best_score {
+            best_score = score;
+            best = candidate;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(duration_sec: f32, frequency: f32, sample_rate: u32) -> Vec<f32> {
+        let samples = (duration_sec * sample_rate as f32) as usize;
+        (0..samples)
+            .map(|i| (2.0 * PI * frequency * i as f32 / sample_rate as f32).sin() * 0.5)
+            .collect()
+    }
+
+    /// 过零率估算频率
+    fn estimate_frequency(samples: &[f32], sample_rate: u32) -> f32 {
+        let crossings = samples.windows(2).filter(|w| w[0] < 0.0 && w[1] >= 0.0).count();
+        crossings as f32 * sample_rate as f32 / samples.len() as f32
+    }
+
+    #[test]
+    fn test_time_stretch_changes_duration_keeps_pitch() {
+        let input = sine(2.0, 220.0, 16000);
+
+        for speed in [0.5f32, 0.8, 1.25, 2.0] {
+            let output = time_stretch(&input, 16000, speed);
+            let expected = input.len() as f32 / speed;
+            assert!(
+                (output.len() as f32 - expected).abs() / expected < 0.05,
+                "speed={} len={} expected={}", speed, output.len(), expected
+            );
+            // 去掉首尾帧后估算音高
+            let body = &output[800..output.len() - 800];
+            let frequency = estimate_frequency(body, 16000);
+            assert!((frequency - 220.0).abs() < 10.0, "speed={} frequency={}", speed, frequency);
+        }
+    }
+
+    #[test]
+    fn test_time_stretch_passthrough() {
+        let input = sine(0.5, 220.0, 16000);
+        assert_eq!(time_stretch(&input, 16000, 1.0), input);
+        assert_eq!(time_stretch(&input[..100], 16000, 2.0), input[..100].to_vec());
+    }
+}
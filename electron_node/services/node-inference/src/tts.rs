@@ -4,6 +4,7 @@
 
 use anyhow::{Result, anyhow};
 use serde::Serialize;
+use std::collections::HashMap;
 use std::time::Duration;
 use reqwest::Client;
 use tracing::{info, error};
@@ -17,6 +18,8 @@ pub struct PiperHttpConfig {
     pub default_voice: String,
     /// 请求超时时间（毫秒）
     pub timeout_ms: u64,
+    /// 语音风格到语音名称的映射（键为 `风格` 或 `语言:风格`，例如 `zh:cheerful`）
+    pub style_voices: HashMap<String, String>,
 }
 
 impl Default for PiperHttpConfig {
@@ -25,6 +28,7 @@ impl Default for PiperHttpConfig {
             endpoint: "http://127.0.0.1:5006/tts".to_string(),
             default_voice: "zh_CN-huayan-medium".to_string(),
             timeout_ms: 8000,
+            style_voices: HashMap::new(),
         }
     }
 }
@@ -64,6 +68,9 @@ impl TTSEngine {
                     .ok()
                     .and_then(|s| s.parse().ok())
                     .unwrap_or(8000),
+                style_voices: std::env::var("TTS_STYLE_VOICES")
+                    .map(|s| parse_style_voices(&s))
+                    .unwrap_or_default(),
             }
         });
 
@@ -88,15 +95,27 @@ impl TTSEngine {
     /// # Returns
     /// 返回 WAV 格式的音频数据
     pub async fn synthesize(&self, text: &str, lang: &str) -> Result<Vec<u8>> {
+        self.synthesize_with_style(text, lang, None).await
+    }
+
+    /// 按语音风格选择语音进行合成（风格未配置对应语音时使用语言默认语音）
+    /// 
+    /// # Arguments
+    /// * `text` - 要合成的文本
+    /// * `lang` - 语言代码（如 "zh", "en"）
+    /// * `style` - 语音风格（如 "cheerful", "sad"），来自情感识别
+    pub async fn synthesize_with_style(&self, text: &str, lang: &str, style: Option<&str>) -> Result<Vec<u8>> {
         use std::time::Instant;
         let tts_start = Instant::now();
         
-        info!("Piper TTS request started: text='{}' (lang={})", 
+        info!("Piper TTS request started: text='{}' (lang={}, style={:?})", 
             if text.len() > 50 { &text[..50] } else { text },
-            lang);
+            lang, style);
         
         // 确定使用的语音
-        let voice = self.determine_voice(lang);
+        let voice = style
+            .and_then(|style| self.style_voice(lang, style))
+            .unwrap_or_else(|| self.determine_voice(lang));
         
         // 构造请求体
         let http_request = PiperHttpRequest {
@@ -162,6 +181,16 @@ impl TTSEngine {
         Ok(audio_data)
     }
 
+    /// 查找语音风格对应的语音（`语言:风格` 优先于 `风格`）
+    fn style_voice(&self, lang: &str, style: &str) -> Option<String> {
+        let base_lang = lang.split(['-', '_']).next().unwrap_or("").to_lowercase();
+        let style = style.to_lowercase();
+        self.config.style_voices
+            .get(&format!("{}:{}", base_lang, style))
+            .or_else(|| self.config.style_voices.get(&style))
+            .cloned()
+    }
+
     /// 根据语言确定使用的语音
     fn determine_voice(&self, lang: &str) -> String {
         let lang_lower = lang.to_lowercase();
@@ -179,3 +208,17 @@ impl TTSEngine {
     }
 }
 
+/// 解析 TTS_STYLE_VOICES（逗号分隔的 `风格=语音` 或 `语言:风格=语音`）
+fn parse_style_voices(value: &str) -> HashMap<String, String> {
+    value
+        .split(',')
+        .filter_map(|entry| {
+            let (key, voice) = entry.split_once('=')?;
+            let (key, voice) = (key.trim(), voice.trim());
+            if key.is_empty() || voice.is_empty() {
+                return None;
+            }
+            Some((key.to_lowercase(), voice.to_string()))
+        })
+        .collect()
+}
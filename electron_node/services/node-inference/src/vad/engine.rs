@@ -14,6 +14,7 @@ use ndarray::{Array1, Array2, Array3, CowArray, Ix2, Ix3};
 use tracing::info;
 
 use super::config::{VADConfig, AdaptiveState};
+use super::session::VADSessionState;
 
 /// Silero VAD 引擎
 pub struct VADEngine {
@@ -115,6 +116,22 @@ impl VADEngine {
     /// # Returns
     /// 返回语音段的起止位置列表（样本索引）
     pub fn detect_speech(&self, audio_data: &[f32]) -> Result<Vec<(usize, usize)>> {
+        let mut state_guard = self.hidden_state.lock()
+            .map_err(|e| anyhow!("Failed to lock hidden state: {}", e))?;
+        self.detect_speech_inner(audio_data, &mut *state_guard)
+    }
+
+    /// 使用会话级状态检测语音活动（多会话并发时使用，隐藏状态不在会话之间共享）
+    pub fn detect_speech_with_state(&self, audio_data: &[f32], state: &mut VADSessionState) -> Result<Vec<(usize, usize)>> {
+        self.detect_speech_inner(audio_data, &mut state.hidden_state)
+    }
+
+    /// 创建新的会话级状态
+    pub fn new_session_state(&self) -> VADSessionState {
+        VADSessionState::new(&self.config)
+    }
+
+    fn detect_speech_inner(&self, audio_data: &[f32], hidden_state: &mut Option<Array2<f32>>) -> Result<Vec<(usize, usize)>> {
         let mut segments = Vec::new();
         let mut current_segment_start: Option<usize> = None;
 
@@ -123,7 +140,7 @@ impl VADEngine {
                 break;
             }
 
-            let speech_prob = self.detect_voice_activity_frame(frame)?;
+            let speech_prob = self.detect_voice_activity_frame(frame, hidden_state)?;
 
             if speech_prob > self.config.silence_threshold {
                 let sample_start = frame_idx * self.config.frame_size;
@@ -147,7 +164,7 @@ impl VADEngine {
     }
 
     /// 检测单帧的语音活动概率
-    fn detect_voice_activity_frame(&self, audio_frame: &[f32]) -> Result<f32> {
+    fn detect_voice_activity_frame(&self, audio_frame: &[f32], hidden_state: &mut Option<Array2<f32>>) -> Result<f32> {
         if audio_frame.len() != self.config.frame_size {
             return Err(anyhow!(
                 "Audio frame length {} does not match frame size {}",
@@ -164,15 +181,12 @@ impl VADEngine {
             .map_err(|e| anyhow!("Failed to create input array: {}", e))?;
 
         let state_array = {
-            let mut state_guard = self.hidden_state.lock()
-                .map_err(|e| anyhow!("Failed to lock hidden state: {}", e))?;
-
-            if let Some(ref state_2d) = *state_guard {
+            if let Some(ref state_2d) = *hidden_state {
                 state_2d.clone().into_shape((2, 1, 128))
                     .map_err(|e| anyhow!("Failed to reshape state: {}", e))?
             } else {
                 let new_state = Array3::<f32>::zeros((2, 1, 128));
-                *state_guard = Some(new_state.clone().into_shape((2, 128))
+                *hidden_state = Some(new_state.clone().into_shape((2, 128))
                     .map_err(|e| anyhow!("Failed to reshape new state: {}", e))?);
                 new_state
             }
@@ -245,9 +259,7 @@ impl VADEngine {
             let new_state_2d = new_state_3d.into_shape((2, 128))
                 .map_err(|e| anyhow!("Failed to reshape state for storage: {}", e))?;
 
-            let mut state_guard = self.hidden_state.lock()
-                .map_err(|e| anyhow!("Failed to lock hidden state: {}", e))?;
-            *state_guard = Some(new_state_2d);
+            *hidden_state = Some(new_state_2d);
         }
 
         let view = output_array;
@@ -294,8 +306,22 @@ impl VADEngine {
 
     /// 更新语速（用于自适应调整）
     pub fn update_speech_rate(&self, text: &str, audio_duration_ms: u64) {
+        if let Some(speech_rate) = self.reasonable_speech_rate(text, audio_duration_ms) {
+            let mut state = self.adaptive_state.lock().unwrap();
+            state.update_speech_rate(speech_rate, &self.config);
+        }
+    }
+
+    /// 更新会话级语速（用于自适应调整）
+    pub fn update_speech_rate_with_state(&self, text: &str, audio_duration_ms: u64, state: &mut VADSessionState) {
+        if let Some(speech_rate) = self.reasonable_speech_rate(text, audio_duration_ms) {
+            state.adaptive_state.update_speech_rate(speech_rate, &self.config);
+        }
+    }
+
+    fn reasonable_speech_rate(&self, text: &str, audio_duration_ms: u64) -> Option<f32> {
         if !self.config.adaptive_enabled || audio_duration_ms == 0 {
-            return;
+            return None;
         }
 
         let text_length = text.chars().count() as f32;
@@ -306,11 +332,9 @@ impl VADEngine {
         const MAX_REASONABLE_RATE: f32 = 50.0;
 
         if speech_rate < MIN_REASONABLE_RATE || speech_rate > MAX_REASONABLE_RATE {
-            return;
+            return None;
         }
-
-        let mut state = self.adaptive_state.lock().unwrap();
-        state.update_speech_rate(speech_rate, &self.config);
+        Some(speech_rate)
     }
 
     /// 获取调整后的阈值
@@ -323,6 +347,14 @@ impl VADEngine {
         state.get_adjusted_duration(&self.config)
     }
 
+    /// 获取会话级调整后的阈值
+    pub fn get_adjusted_duration_ms_with_state(&self, state: &VADSessionState) -> u64 {
+        if !self.config.adaptive_enabled {
+            return self.config.min_silence_duration_ms;
+        }
+        state.adaptive_state.get_adjusted_duration(&self.config)
+    }
+
     /// 重置状态（用于新的音频流）
     pub fn reset_state(&self) -> Result<()> {
         let mut state_guard = self.hidden_state.lock()
@@ -5,6 +5,8 @@
 
 mod config;
 mod engine;
+mod session;
 
 pub use config::VADConfig;
 pub use engine::VADEngine;
+pub use session::VADSessionState;
@@ -0,0 +1,23 @@
+//! Silero VAD 会话级状态（隐藏状态与语速自适应状态，每个会话一份）
+
+use ndarray::Array2;
+
+use super::config::{VADConfig, AdaptiveState};
+
+/// VAD 会话级状态
+pub struct VADSessionState {
+    /// 隐藏状态（VAD 模型在同一会话的帧之间传递）
+    pub(super) hidden_state: Option<Array2<f32>>,
+    /// 自适应状态
+    pub(super) adaptive_state: AdaptiveState,
+}
+
+impl VADSessionState {
+    pub fn new(config: &VADConfig) -> Self {
+        let base_threshold = (config.base_threshold_min_ms + config.base_threshold_max_ms) / 2;
+        Self {
+            hidden_state: None,
+            adaptive_state: AdaptiveState::new(base_threshold),
+        }
+    }
+}
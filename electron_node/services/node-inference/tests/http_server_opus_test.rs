@@ -36,6 +36,9 @@ fn test_http_request_with_opus_format() {
         partial_update_interval_ms: None,
         trace_id: Some("test-trace-1".to_string()),
         context_text: None,
+        session_id: None,
+        turn_id: None,
+        target_speech_rate: None,
     };
     
     // 验证请求格式
@@ -78,6 +81,9 @@ fn test_http_request_with_opus_format_unsupported() {
         partial_update_interval_ms: None,
         trace_id: None,
         context_text: None,
+        session_id: None,
+        turn_id: None,
+        target_speech_rate: None,
     };
     
     let audio_data_raw = general_purpose::STANDARD.decode(&request.audio).unwrap();
@@ -119,6 +125,9 @@ fn test_http_request_default_format() {
         partial_update_interval_ms: None,
         trace_id: None,
         context_text: None,
+        session_id: None,
+        turn_id: None,
+        target_speech_rate: None,
     };
     
     // 应该使用默认值
@@ -161,6 +170,9 @@ fn test_audio_format_case_insensitive() {
             partial_update_interval_ms: None,
             trace_id: None,
             context_text: None,
+            session_id: None,
+            turn_id: None,
+            target_speech_rate: None,
         };
         
         let audio_data_raw = general_purpose::STANDARD.decode(&request.audio).unwrap();
@@ -201,6 +213,9 @@ fn test_sample_rate_handling() {
             partial_update_interval_ms: None,
             trace_id: None,
             context_text: None,
+            session_id: None,
+            turn_id: None,
+            target_speech_rate: None,
         };
         
         let audio_data_raw = general_purpose::STANDARD.decode(&request.audio).unwrap();
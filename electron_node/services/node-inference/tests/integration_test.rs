@@ -88,6 +88,9 @@ async fn test_inference_service_full_pipeline() {
         partial_update_interval_ms: None,
         trace_id: Some("test-trace-1".to_string()),
         context_text: None,
+        session_id: None,
+        turn_id: None,
+        target_speech_rate: None,
     };
 
     // 运行推理（可能会失败，因为需要实际模型）
@@ -49,6 +49,9 @@ async fn test_inference_service_with_partial_callback() {
         partial_update_interval_ms: Some(500), // 500ms 更新间隔
         trace_id: Some("test-trace-1".to_string()),
         context_text: None,
+        session_id: None,
+        turn_id: None,
+        target_speech_rate: None,
     };
 
     // 注意：由于需要实际的模型和 WhisperContext，这个测试可能需要调整
@@ -73,6 +76,9 @@ fn test_inference_request_with_streaming_config() {
         partial_update_interval_ms: Some(1000),
         trace_id: Some("test-trace-2".to_string()),
         context_text: None,
+        session_id: None,
+        turn_id: None,
+        target_speech_rate: None,
     };
 
     assert_eq!(request.enable_streaming_asr, Some(true));
@@ -97,6 +103,9 @@ fn test_inference_request_without_streaming() {
         partial_update_interval_ms: None,
         trace_id: Some("test-trace-3".to_string()),
         context_text: None,
+        session_id: None,
+        turn_id: None,
+        target_speech_rate: None,
     };
 
     assert_eq!(request.enable_streaming_asr, None);
@@ -51,6 +51,7 @@ async fn test_tts_engine_custom_config() {
         endpoint: "http://127.0.0.1:5006/tts".to_string(),
         default_voice: "zh_CN-huayan-medium".to_string(),
         timeout_ms: 10000,
+        ..Default::default()
     };
     
     let engine = TTSEngine::new(Some(config)).expect("Failed to create TTS engine");
@@ -320,15 +320,41 @@ export interface NodeRegisterMessage {
   /** 按 ServiceType 聚合的能力图 */
   capability_by_type: CapabilityByType[];
   features_supported: FeatureFlags;
+  /** false：私有节点，不进入公共池 */
   accept_public_jobs: boolean;
+  /** 私有节点所属租户（可选） */
+  owner_tenant_id?: string;
   /** 语言能力信息（新增，可选，向后兼容） */
   language_capabilities?: NodeLanguageCapabilities;
+  /** 会话迁移接口地址（可选，调度器迁移会话时优先使用） */
+  migration_endpoint?: string;
 }
 
 export interface NodeRegisterAckMessage {
   type: 'node_register_ack';
   node_id: string;
   message: string;
+  /** 以 enrollment token 认证成功时签发的长期节点凭证（节点保存后代替 token 使用） */
+  node_credential?: string;
+}
+
+/** 节点认证：调度器对 node_register 下发的 challenge */
+export interface NodeAuthChallengeMessage {
+  type: 'node_auth_challenge';
+  node_id: string;
+  nonce: string;
+  expires_in_ms: number;
+}
+
+/** 节点认证应答：signature = hex(HMAC-SHA256(secret, `${nonce}.${node_id}`)) */
+export interface NodeAuthResponseMessage {
+  type: 'node_auth_response';
+  node_id: string;
+  signature: string;
+  /** 使用 enrollment token 作为密钥时携带其过期时间（秒） */
+  enrollment_expires_at?: number;
+  /** 使用调度器签发的长期节点凭证作为密钥 */
+  node_credential?: boolean;
 }
 
 /** Gate-B: Rerun 指标 */
@@ -429,6 +455,8 @@ export interface JobAssignMessage {
   lid?: { candidates: [string, string] };
   /** 面对面模式：房间 ID，用于 Router 状态按房间维护 */
   room_id?: string;
+  /** 调度端 Utterance Group 上下文（此前的源语言 ASR 原文，按 max_context_length 截取，仅供 NMT 使用） */
+  context_text?: string;
 }
 
 export interface JobCancelMessage {
@@ -493,6 +521,22 @@ export interface NodeControlMessage {
   reason?: string;
 }
 
+/** 节点申请配对码（私有会话：客户端在 session_init.pairing_code 中出示） */
+export interface PairingCodeRequestMessage {
+  type: 'pairing_code_request';
+  node_id: string;
+  /** 有效期（秒），不指定时使用调度器默认值 */
+  ttl_seconds?: number;
+}
+
+export interface PairingCodeIssuedMessage {
+  type: 'pairing_code_issued';
+  node_id: string;
+  code: string;
+  /** 过期时间（毫秒时间戳） */
+  expires_at_ms: number;
+}
+
 // ===== 消息联合类型 =====
 
 export type SessionSideIncomingMessage =
@@ -513,16 +557,20 @@ export type SessionSideOutgoingMessage =
 
 export type NodeSideIncomingMessage =
   | NodeRegisterAckMessage
+  | NodeAuthChallengeMessage
   | JobAssignMessage
   | JobCancelMessage
   | NodeControlMessage
+  | PairingCodeIssuedMessage
   | ErrorMessage;
 
 export type NodeSideOutgoingMessage =
   | NodeRegisterMessage
+  | NodeAuthResponseMessage
   | NodeHeartbeatMessage
   | JobResultMessage
-  | NodeErrorMessage;
+  | NodeErrorMessage
+  | PairingCodeRequestMessage;
 
 export type AnyMessage =
   | SessionInitMessage
@@ -544,4 +592,6 @@ export type AnyMessage =
   | JobCancelMessage
   | JobResultMessage
   | NodeErrorMessage
-  | NodeControlMessage;
+  | NodeControlMessage
+  | PairingCodeRequestMessage
+  | PairingCodeIssuedMessage;